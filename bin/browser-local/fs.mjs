@@ -69,8 +69,11 @@ export async function isDirectory({ path }) {
   }
 }
 
-export async function createFile({ path, content }) {
-  await fsWriteFile(resolvePath(path), content ?? "", "utf8");
+export async function createFile({ path, content, exclusive }) {
+  await fsWriteFile(resolvePath(path), content ?? "", {
+    encoding: "utf8",
+    flag: exclusive ? "wx" : "w",
+  });
 }
 
 export async function createDirectory({ path }) {
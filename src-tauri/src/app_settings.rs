@@ -0,0 +1,126 @@
+// ABOUTME: Read-only access to the renderer-owned `app` settings blob in settings.json.
+// ABOUTME: Also converts stored setting values for the string and typed get_setting commands.
+
+use serde_json::Value;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+pub(crate) const SETTINGS_STORE: &str = "settings.json";
+const APP_SETTINGS_KEY: &str = "app";
+
+/// Look up `key` in the `app` settings object.
+///
+/// The renderer persists this object as a JSON string, so both the string
+/// and an already-structured object are accepted.
+pub(crate) fn app_setting<R: Runtime>(app: &AppHandle<R>, key: &str) -> Option<Value> {
+    let store = app.store(SETTINGS_STORE).ok()?;
+    let raw = store.get(APP_SETTINGS_KEY)?;
+    setting_from_blob(&raw, key)
+}
+
+pub(crate) fn app_setting_string<R: Runtime>(app: &AppHandle<R>, key: &str) -> Option<String> {
+    app_setting(app, key)?
+        .as_str()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+pub(crate) fn app_setting_u64<R: Runtime>(app: &AppHandle<R>, key: &str) -> Option<u64> {
+    app_setting(app, key)?.as_u64()
+}
+
+/// Render a stored value for the string `get_setting` API. Values written
+/// through `set_setting_json` come back as their JSON text rather than
+/// disappearing.
+pub(crate) fn setting_as_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Null => None,
+        other => Some(other.to_string()),
+    }
+}
+
+/// Read a stored value as a bool, accepting the legacy `"true"`/`"false"`
+/// strings older code wrote through `set_setting`.
+pub(crate) fn setting_as_bool(value: &Value) -> Option<bool> {
+    match value {
+        Value::Bool(b) => Some(*b),
+        Value::String(s) => match s.trim() {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Read a stored value as a number, accepting stringified numbers.
+pub(crate) fn setting_as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse::<f64>().ok().filter(|n| n.is_finite()),
+        _ => None,
+    }
+}
+
+fn setting_from_blob(raw: &Value, key: &str) -> Option<Value> {
+    match raw {
+        Value::String(encoded) => serde_json::from_str::<Value>(encoded)
+            .ok()?
+            .get(key)
+            .cloned(),
+        Value::Object(map) => map.get(key).cloned(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reads_keys_from_string_encoded_blob() {
+        let raw = json!(r#"{"oauthCallbackPortMin":49200,"name":"seren"}"#);
+        assert_eq!(
+            setting_from_blob(&raw, "oauthCallbackPortMin"),
+            Some(json!(49200))
+        );
+        assert_eq!(setting_from_blob(&raw, "missing"), None);
+    }
+
+    #[test]
+    fn reads_keys_from_object_blob() {
+        let raw = json!({ "enabled": true });
+        assert_eq!(setting_from_blob(&raw, "enabled"), Some(json!(true)));
+    }
+
+    #[test]
+    fn typed_reads_accept_legacy_strings() {
+        assert_eq!(setting_as_bool(&json!(false)), Some(false));
+        assert_eq!(setting_as_bool(&json!("false")), Some(false));
+        assert_eq!(setting_as_bool(&json!("yes")), None);
+        assert_eq!(setting_as_number(&json!(1.5)), Some(1.5));
+        assert_eq!(setting_as_number(&json!(" 42 ")), Some(42.0));
+        assert_eq!(setting_as_number(&json!("NaN")), None);
+        assert_eq!(setting_as_number(&json!(true)), None);
+    }
+
+    #[test]
+    fn string_reads_render_json_values() {
+        assert_eq!(setting_as_string(&json!("dark")), Some("dark".into()));
+        assert_eq!(setting_as_string(&json!(false)), Some("false".into()));
+        assert_eq!(
+            setting_as_string(&json!({ "a": [1, 2] })),
+            Some(r#"{"a":[1,2]}"#.into())
+        );
+        assert_eq!(setting_as_string(&json!(null)), None);
+    }
+
+    #[test]
+    fn malformed_blob_yields_none() {
+        assert_eq!(setting_from_blob(&json!("{not json"), "key"), None);
+        assert_eq!(setting_from_blob(&json!(42), "key"), None);
+    }
+}
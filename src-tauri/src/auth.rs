@@ -1,7 +1,9 @@
 // ABOUTME: Authentication utilities for Rust-side HTTP callers.
 // ABOUTME: Provides token refresh and authenticated request helpers.
 
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
 use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tauri::Emitter;
 use tauri_plugin_store::StoreExt;
 use tokio::sync::Mutex as TokioMutex;
@@ -11,6 +13,19 @@ const TOKEN_KEY: &str = "token";
 const REFRESH_TOKEN_KEY: &str = "refresh_token";
 const GATEWAY_BASE_URL: &str = "https://api.serendb.com";
 
+/// Refresh this long before the access token's `exp` so in-flight requests
+/// rarely see a 401.
+const PROACTIVE_REFRESH_LEAD: Duration = Duration::from_secs(90);
+/// Upper bound on one scheduler sleep, so a sign-in or token swap made
+/// elsewhere is picked up without waiting for a far-future expiry.
+const MAX_REFRESH_CHECK_INTERVAL: Duration = Duration::from_secs(300);
+/// Minimum gap between scheduler passes. Keeps a short-lived token or a
+/// failing refresh endpoint from spinning the loop.
+const MIN_REFRESH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+/// Consecutive failed proactive refreshes of one access token after which
+/// the scheduler stops trying until a different token is stored.
+const MAX_PROACTIVE_REFRESH_FAILURES: u32 = 5;
+
 /// Global mutex to prevent concurrent refresh attempts from multiple workers.
 static REFRESH_LOCK: OnceLock<TokioMutex<()>> = OnceLock::new();
 
@@ -205,12 +220,158 @@ pub async fn refresh_access_token(app: &tauri::AppHandle) -> Result<String, Stri
     Ok(new_access_token.to_string())
 }
 
+/// Read the `exp` claim (seconds since the epoch) from a JWT access token.
+///
+/// The signature is not verified — this only schedules a refresh, and the
+/// Gateway remains the authority on validity. Returns `None` for opaque
+/// tokens or JWTs without `exp`.
+fn jwt_expiry(token: &str) -> Option<u64> {
+    let mut segments = token.split('.');
+    let (_header, payload, _signature) = (segments.next()?, segments.next()?, segments.next()?);
+    if segments.next().is_some() {
+        return None;
+    }
+    let decoded = URL_SAFE_NO_PAD.decode(payload.trim_end_matches('=')).ok()?;
+    let claims: serde_json::Value = serde_json::from_slice(&decoded).ok()?;
+    claims.get("exp")?.as_u64()
+}
+
+/// How long to wait before refreshing a token that expires at `expires_at`.
+fn proactive_refresh_delay(expires_at: u64, now: u64) -> Duration {
+    let refresh_at = expires_at.saturating_sub(PROACTIVE_REFRESH_LEAD.as_secs());
+    Duration::from_secs(refresh_at.saturating_sub(now)).min(MAX_REFRESH_CHECK_INTERVAL)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+/// Wait after the `failures`-th consecutive failed proactive refresh:
+/// doubles from the minimum interval up to the maximum.
+fn refresh_retry_delay(failures: u32) -> Duration {
+    MIN_REFRESH_CHECK_INTERVAL
+        .saturating_mul(1 << failures.saturating_sub(1).min(16))
+        .min(MAX_REFRESH_CHECK_INTERVAL)
+}
+
+/// Refresh the access token shortly before it expires.
+///
+/// Runs for the lifetime of the app. When the stored token is not a JWT or
+/// has no `exp`, nothing is refreshed ahead of time and
+/// [`authenticated_request`] keeps handling expiry reactively on 401.
+/// A successful refresh emits `auth:token-refreshed` (from
+/// [`refresh_access_token`]). Failures back off, and after
+/// `MAX_PROACTIVE_REFRESH_FAILURES` in a row the token is left alone until
+/// a sign-in stores a new one.
+pub fn schedule_token_refresh(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut failures = 0u32;
+        let mut failed_token: Option<String> = None;
+        loop {
+            let token = get_access_token(&app).ok();
+            if failures > 0 && token != failed_token {
+                // A sign-in replaced the token that kept failing.
+                failures = 0;
+            }
+            if failures >= MAX_PROACTIVE_REFRESH_FAILURES {
+                tokio::time::sleep(MAX_REFRESH_CHECK_INTERVAL).await;
+                continue;
+            }
+
+            let Some(expires_at) = token.as_deref().and_then(jwt_expiry) else {
+                tokio::time::sleep(MAX_REFRESH_CHECK_INTERVAL).await;
+                continue;
+            };
+
+            let delay = proactive_refresh_delay(expires_at, unix_now());
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+                continue;
+            }
+
+            match refresh_access_token(&app).await {
+                Ok(_) => {
+                    failures = 0;
+                    log::debug!("[auth] Proactively refreshed access token");
+                    tokio::time::sleep(MIN_REFRESH_CHECK_INTERVAL).await;
+                }
+                Err(error) => {
+                    failures += 1;
+                    failed_token = token;
+                    if failures >= MAX_PROACTIVE_REFRESH_FAILURES {
+                        log::warn!(
+                            "[auth] Proactive token refresh failed {} times, pausing until the next sign-in: {}",
+                            failures,
+                            error
+                        );
+                    } else {
+                        log::debug!("[auth] Proactive token refresh skipped: {}", error);
+                    }
+                    tokio::time::sleep(refresh_retry_delay(failures)).await;
+                }
+            }
+        }
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
-        RefreshLockDecision, RefreshUnauthorizedDecision, decide_after_refresh_lock,
-        decide_after_refresh_unauthorized,
+        MAX_REFRESH_CHECK_INTERVAL, MIN_REFRESH_CHECK_INTERVAL, RefreshLockDecision,
+        RefreshUnauthorizedDecision, decide_after_refresh_lock, decide_after_refresh_unauthorized,
+        jwt_expiry, proactive_refresh_delay, refresh_retry_delay,
     };
+    use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+    use std::time::Duration;
+
+    fn jwt_with_claims(claims: &str) -> String {
+        format!(
+            "{}.{}.signature",
+            URL_SAFE_NO_PAD.encode(r#"{"alg":"HS256","typ":"JWT"}"#),
+            URL_SAFE_NO_PAD.encode(claims)
+        )
+    }
+
+    #[test]
+    fn jwt_expiry_reads_exp_claim() {
+        let token = jwt_with_claims(r#"{"sub":"user","exp":1900000000}"#);
+        assert_eq!(jwt_expiry(&token), Some(1_900_000_000));
+    }
+
+    #[test]
+    fn jwt_expiry_is_none_for_opaque_or_expless_tokens() {
+        assert_eq!(jwt_expiry("seren_opaque_token"), None);
+        assert_eq!(jwt_expiry(&jwt_with_claims(r#"{"sub":"user"}"#)), None);
+        assert_eq!(jwt_expiry("a.!!!.c"), None);
+        assert_eq!(jwt_expiry("a.b.c.d"), None);
+    }
+
+    #[test]
+    fn refresh_delay_leads_expiry_and_is_capped() {
+        let now = 1_000_000;
+        assert_eq!(
+            proactive_refresh_delay(now + 150, now),
+            Duration::from_secs(60)
+        );
+        assert_eq!(proactive_refresh_delay(now + 60, now), Duration::ZERO);
+        assert_eq!(proactive_refresh_delay(now - 10, now), Duration::ZERO);
+        assert_eq!(
+            proactive_refresh_delay(now + 86_400, now),
+            MAX_REFRESH_CHECK_INTERVAL
+        );
+    }
+
+    #[test]
+    fn refresh_retry_delay_backs_off_up_to_the_cap() {
+        assert_eq!(refresh_retry_delay(1), MIN_REFRESH_CHECK_INTERVAL);
+        assert_eq!(refresh_retry_delay(2), MIN_REFRESH_CHECK_INTERVAL * 2);
+        assert_eq!(refresh_retry_delay(3), MIN_REFRESH_CHECK_INTERVAL * 4);
+        assert_eq!(refresh_retry_delay(10), MAX_REFRESH_CHECK_INTERVAL);
+        assert_eq!(refresh_retry_delay(u32::MAX), MAX_REFRESH_CHECK_INTERVAL);
+    }
 
     #[test]
     fn lock_decision_reuses_access_token_after_refresh_token_rotation() {
@@ -7,8 +7,11 @@ use crate::services::conversation_index::{self, IndexableMessage, open_index_db}
 use crate::services::database::{
     DbPool, PersistedMessage, WalCheckpointMode, checkpoint_wal, enqueue_sync_tombstone, init_db,
     mark_sync_upsert, save_message_record, stamp_existing_privileged_messages,
+    truncate_messages_after, update_message_content,
 };
 use crate::commands::memory::MemoryState;
+use crate::orchestrator::service::OrchestratorState;
+use crate::orchestrator::types::{ImageAttachment, RoutingDecision, UserCapabilities, WorkerType};
 use rusqlite::{Connection, OptionalExtension, Transaction, TransactionBehavior, params};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -297,6 +300,10 @@ pub(crate) fn delete_conversation_records(
             "DELETE FROM orchestration_plans WHERE conversation_id = ?1",
             params![id],
         )?;
+        tx.execute(
+            "DELETE FROM orchestration_checkpoints WHERE conversation_id = ?1",
+            params![id],
+        )?;
         tx.execute(
             "DELETE FROM input_history WHERE conversation_id = ?1",
             params![id],
@@ -334,6 +341,26 @@ pub(crate) fn delete_conversation_records(
     Ok(deleted)
 }
 
+/// Trim, drop empties, and dedupe tags while keeping their first-seen order.
+fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags
+        .iter()
+        .map(|tag| tag.trim())
+        .filter(|tag| !tag.is_empty())
+    {
+        if !normalized.iter().any(|existing| existing == tag) {
+            normalized.push(tag.to_string());
+        }
+    }
+    normalized
+}
+
+fn parse_tags(raw: Option<String>) -> Vec<String> {
+    raw.and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
 fn normalize_project_root(path: &str) -> Option<String> {
     let trimmed = path.trim();
     if trimmed.is_empty() {
@@ -449,6 +476,11 @@ pub struct UnifiedConversationRow {
     #[serde(default)]
     pub privileged: bool,
     pub counsel_direction: Option<String>,
+    /// Set while the conversation sits in the trash; only surfaced when the
+    /// list is requested with `include_trashed`.
+    pub trashed_at: Option<i64>,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -463,6 +495,25 @@ pub struct StoredMessage {
     pub provider: Option<String>,
 }
 
+/// Direction a message page walks through a conversation.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageOrder {
+    /// Start at the latest message and walk back in time (scroll-up loading).
+    #[default]
+    NewestFirst,
+    /// Start at the first message and walk forward in time.
+    OldestFirst,
+}
+
+/// One page of a conversation's messages. `next_cursor` is the id to pass back
+/// as `cursor` to fetch the following page, or `None` when the thread is exhausted.
+#[derive(Serialize, Debug, Clone)]
+pub struct MessagePage {
+    pub messages: Vec<StoredMessage>,
+    pub next_cursor: Option<String>,
+}
+
 // ============================================================================
 // Conversation Commands
 // ============================================================================
@@ -526,13 +577,17 @@ pub async fn create_conversation(
 /// agent-side `agent_cwd` equals the raw or canonicalized form — that
 /// preserves the prior asymmetric behavior of the two separate commands.
 /// Chat-kind rows with a NULL `project_root` are always included so the
-/// default sidebar bucket keeps surfacing them.
+/// default sidebar bucket keeps surfacing them. Trashed rows are hidden
+/// unless `include_trashed` is set. With `filter_tags`, only conversations
+/// carrying at least one of the tags are returned.
 #[tauri::command]
 pub async fn list_conversations(
     app: AppHandle,
     kind: Option<String>,
     project_root: Option<String>,
     limit: Option<i32>,
+    include_trashed: Option<bool>,
+    filter_tags: Option<Vec<String>>,
 ) -> Result<Vec<UnifiedConversationRow>, String> {
     if let Some(ref k) = kind {
         if k != "chat" && k != "agent" {
@@ -554,48 +609,93 @@ pub async fn list_conversations(
     // SQLite treats `LIMIT -1` as no limit, matching the old behavior of
     // the unlimited chat read.
     let effective_limit = limit.unwrap_or(-1);
+    let include_trashed = include_trashed.unwrap_or(false);
+    let filter_tags = filter_tags
+        .map(|tags| serde_json::to_string(&normalize_tags(&tags)))
+        .transpose()
+        .map_err(|err| err.to_string())?;
 
     run_db(app, move |conn| {
-        let sql = format!(
-            "WITH derived AS (
-                SELECT c.id, c.title, c.created_at, c.is_archived,
-                       c.project_root, c.selected_provider, c.selected_model,
-                       c.employee_id, c.agent_type, c.agent_session_id,
-                       c.agent_cwd, c.agent_model_id, c.agent_permission_mode,
-                       c.agent_metadata, c.project_id, c.privileged,
-                       c.counsel_direction, psr.provider AS runtime_provider,
-                       {case} AS derived_kind
-                FROM conversations c
-                LEFT JOIN provider_session_runtime psr ON psr.thread_id = c.id
+        query_conversation_rows(
+            conn,
+            kind.as_deref(),
+            raw.as_deref(),
+            normalized.as_deref(),
+            effective_limit,
+            include_trashed,
+            filter_tags.as_deref(),
+        )
+    })
+    .await
+}
+
+fn query_conversation_rows(
+    conn: &Connection,
+    kind: Option<&str>,
+    raw: Option<&str>,
+    normalized: Option<&str>,
+    effective_limit: i32,
+    include_trashed: bool,
+    filter_tags: Option<&str>,
+) -> rusqlite::Result<Vec<UnifiedConversationRow>> {
+    let sql = format!(
+        "WITH derived AS (
+            SELECT c.id, c.title, c.created_at, c.is_archived,
+                   c.project_root, c.selected_provider, c.selected_model,
+                   c.employee_id, c.agent_type, c.agent_session_id,
+                   c.agent_cwd, c.agent_model_id, c.agent_permission_mode,
+                   c.agent_metadata, c.project_id, c.privileged,
+                   c.counsel_direction, c.trashed_at, c.tags,
+                   psr.provider AS runtime_provider,
+                   {case} AS derived_kind
+            FROM conversations c
+            LEFT JOIN provider_session_runtime psr ON psr.thread_id = c.id
+        )
+        SELECT id, title, created_at, derived_kind, project_root, is_archived,
+               CASE WHEN derived_kind = 'chat'
+                    THEN COALESCE(selected_provider, runtime_provider)
+                    ELSE selected_provider END AS selected_provider,
+               selected_model, employee_id,
+               CASE WHEN derived_kind = 'agent'
+                    THEN COALESCE(agent_type, runtime_provider)
+                    ELSE agent_type END AS agent_type,
+               agent_session_id, agent_cwd, agent_model_id,
+               agent_permission_mode, agent_metadata, project_id,
+               privileged, counsel_direction, trashed_at, tags
+        FROM derived
+        WHERE is_archived = 0
+          AND (?5 OR trashed_at IS NULL)
+          AND (
+            ?6 IS NULL
+            OR EXISTS (
+              SELECT 1 FROM json_each(derived.tags) tag
+              WHERE tag.value IN (SELECT value FROM json_each(?6))
             )
-            SELECT id, title, created_at, derived_kind, project_root, is_archived,
-                   CASE WHEN derived_kind = 'chat'
-                        THEN COALESCE(selected_provider, runtime_provider)
-                        ELSE selected_provider END AS selected_provider,
-                   selected_model, employee_id,
-                   CASE WHEN derived_kind = 'agent'
-                        THEN COALESCE(agent_type, runtime_provider)
-                        ELSE agent_type END AS agent_type,
-                   agent_session_id, agent_cwd, agent_model_id,
-                   agent_permission_mode, agent_metadata, project_id,
-                   privileged, counsel_direction
-            FROM derived
-            WHERE is_archived = 0
-              AND (?1 IS NULL OR derived_kind = ?1)
-              AND (
-                (?2 IS NULL AND ?3 IS NULL)
-                OR project_root = ?2 OR project_id = ?2 OR agent_cwd = ?2
-                OR project_root = ?3 OR project_id = ?3 OR agent_cwd = ?3
-                OR (derived_kind = 'chat' AND project_root IS NULL)
-              )
-            ORDER BY created_at DESC
-            LIMIT ?4",
-            case = DERIVED_KIND_CASE_SQL,
-        );
-        let mut stmt = conn.prepare(&sql)?;
+          )
+          AND (?1 IS NULL OR derived_kind = ?1)
+          AND (
+            (?2 IS NULL AND ?3 IS NULL)
+            OR project_root = ?2 OR project_id = ?2 OR agent_cwd = ?2
+            OR project_root = ?3 OR project_id = ?3 OR agent_cwd = ?3
+            OR (derived_kind = 'chat' AND project_root IS NULL)
+          )
+        ORDER BY created_at DESC
+        LIMIT ?4",
+        case = DERIVED_KIND_CASE_SQL,
+    );
+    let mut stmt = conn.prepare(&sql)?;
 
-        let rows = stmt
-            .query_map(params![kind, raw, normalized, effective_limit], |row| {
+    let rows = stmt
+        .query_map(
+            params![
+                kind,
+                raw,
+                normalized,
+                effective_limit,
+                include_trashed,
+                filter_tags
+            ],
+            |row| {
                 Ok(UnifiedConversationRow {
                     id: row.get(0)?,
                     title: row.get(1)?,
@@ -615,15 +715,50 @@ pub async fn list_conversations(
                     project_id: row.get(15)?,
                     privileged: row.get::<_, i32>(16)? != 0,
                     counsel_direction: row.get(17)?,
+                    trashed_at: row.get(18)?,
+                    tags: parse_tags(row.get(19)?),
                 })
-            })?
-            .collect::<Result<Vec<_>, _>>()?;
+            },
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(rows)
+    Ok(rows)
+}
+
+/// Replace a conversation's tags. Tags are trimmed and deduplicated; an empty
+/// list clears them. Returns the stored tags.
+#[tauri::command]
+pub async fn set_conversation_tags(
+    app: AppHandle,
+    id: String,
+    tags: Vec<String>,
+) -> Result<Vec<String>, String> {
+    run_db(app, move |conn| {
+        set_conversation_tags_in_db(conn, &id, &tags)
     })
     .await
 }
 
+fn set_conversation_tags_in_db(
+    conn: &Connection,
+    id: &str,
+    tags: &[String],
+) -> rusqlite::Result<Vec<String>> {
+    let tags = normalize_tags(tags);
+    let encoded = serde_json::to_string(&tags)
+        .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+    let changed = conn.execute(
+        "UPDATE conversations SET tags = ?1 WHERE id = ?2",
+        params![encoded, id],
+    )?;
+    if changed == 0 {
+        return Err(rusqlite::Error::QueryReturnedNoRows);
+    }
+    // Tags are local-only: history sync does not carry them, so the row is
+    // not marked for upload.
+    Ok(tags)
+}
+
 #[tauri::command]
 pub async fn get_conversation(app: AppHandle, id: String) -> Result<Option<Conversation>, String> {
     run_db(app, move |conn| {
@@ -761,20 +896,112 @@ pub async fn archive_conversation(app: AppHandle, id: String) -> Result<(), Stri
     Ok(())
 }
 
+const MS_PER_DAY: i64 = 86_400_000;
+
+fn trash_conversation_in_db(conn: &Connection, id: &str, now_ms: i64) -> rusqlite::Result<bool> {
+    let changed = conn.execute(
+        "UPDATE conversations SET trashed_at = ?2 WHERE id = ?1 AND trashed_at IS NULL",
+        params![id, now_ms],
+    )?;
+    Ok(changed > 0)
+}
+
+/// Clear the trash marker. Returns the conversation's archived flag so the
+/// search index can be restored to the right visibility, or `None` when the
+/// conversation was not in the trash.
+fn restore_conversation_in_db(conn: &Connection, id: &str) -> rusqlite::Result<Option<bool>> {
+    let changed = conn.execute(
+        "UPDATE conversations SET trashed_at = NULL WHERE id = ?1 AND trashed_at IS NOT NULL",
+        params![id],
+    )?;
+    if changed == 0 {
+        return Ok(None);
+    }
+    conn.query_row(
+        "SELECT is_archived FROM conversations WHERE id = ?1",
+        params![id],
+        |row| Ok(row.get::<_, i32>(0)? != 0),
+    )
+    .map(Some)
+}
+
+fn trashed_conversation_ids_before(
+    conn: &Connection,
+    cutoff_ms: i64,
+) -> rusqlite::Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT id FROM conversations WHERE trashed_at IS NOT NULL AND trashed_at <= ?1",
+    )?;
+    let ids = stmt
+        .query_map(params![cutoff_ms], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(ids)
+}
+
+/// Move a conversation to the trash. Its data stays intact until
+/// `purge_trashed` removes it; `restore_conversation` brings it back.
 #[tauri::command]
 pub async fn delete_conversation(app: AppHandle, id: String) -> Result<(), String> {
     let index_id = id.clone();
-    let transcript_targets = run_db(app.clone(), move |conn| {
-        let targets = collect_agent_transcript_targets(conn, std::slice::from_ref(&id))?;
-        delete_conversation_records(conn, &[id])?;
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64;
+    let trashed = run_db(app.clone(), move |conn| {
+        trash_conversation_in_db(conn, &id, now_ms)
+    })
+    .await?;
+    // The index has no trash state; hiding it as archived keeps trashed
+    // threads out of default search results.
+    if trashed {
+        refresh_conversation_index_meta_best_effort(app, index_id, None, Some(true)).await;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn restore_conversation(app: AppHandle, id: String) -> Result<(), String> {
+    let index_id = id.clone();
+    let restored = run_db(app.clone(), move |conn| {
+        restore_conversation_in_db(conn, &id)
+    })
+    .await?;
+    let Some(is_archived) = restored else {
+        return Err("conversation is not in the trash".to_string());
+    };
+    refresh_conversation_index_meta_best_effort(app, index_id, None, Some(is_archived)).await;
+    Ok(())
+}
+
+/// Permanently delete conversations that have been in the trash for at least
+/// `older_than_days` days (0 empties the trash). Returns the number removed.
+#[tauri::command]
+pub async fn purge_trashed(app: AppHandle, older_than_days: u32) -> Result<i64, String> {
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64;
+    let cutoff_ms = now_ms - i64::from(older_than_days) * MS_PER_DAY;
+    let (deleted, conversation_ids, transcript_targets) = run_db(app.clone(), move |conn| {
+        let conversation_ids = trashed_conversation_ids_before(conn, cutoff_ms)?;
+        if conversation_ids.is_empty() {
+            return Ok((0, conversation_ids, Vec::new()));
+        }
+        let targets = collect_agent_transcript_targets(conn, &conversation_ids)?;
+        let deleted = delete_conversation_records(conn, &conversation_ids)?;
         vacuum_database(conn)?;
-        Ok(targets)
+        Ok((deleted as i64, conversation_ids, targets))
     })
     .await?;
-    delete_conversation_index_best_effort(&app, &index_id);
+    if conversation_ids.is_empty() {
+        return Ok(0);
+    }
+    for conversation_id in &conversation_ids {
+        delete_conversation_index_best_effort(&app, conversation_id);
+    }
     vacuum_conversation_index_best_effort(&app);
     delete_agent_transcripts_best_effort(&transcript_targets);
-    Ok(())
+    Ok(deleted)
 }
 
 #[tauri::command]
@@ -977,6 +1204,7 @@ fn list_legacy_happy_restoration_candidates_in_db(
          LEFT JOIN happy_provider_session_lifecycle hpsl
            ON hpsl.provider_session_id = c.id
          WHERE c.is_archived = 0
+           AND c.trashed_at IS NULL
            AND hpsl.provider_session_id IS NULL
            AND ({case}) = 'agent'
            AND json_valid(c.agent_metadata)
@@ -1161,6 +1389,7 @@ fn lookup_happy_restoration_candidate_in_db(
          FROM conversations c
          LEFT JOIN provider_session_runtime psr ON psr.thread_id = c.id
          WHERE c.id = ?1
+           AND c.trashed_at IS NULL
          LIMIT 1",
         case = DERIVED_KIND_CASE_SQL,
     );
@@ -2188,6 +2417,340 @@ pub async fn get_messages(
     .await
 }
 
+/// Page through a conversation keyed on `(timestamp, id)` so messages sharing a
+/// timestamp are never skipped or repeated across pages. The cursor message is
+/// excluded from the page it anchors.
+fn query_message_page(
+    conn: &Connection,
+    conversation_id: &str,
+    cursor: Option<&str>,
+    limit: usize,
+    order: MessageOrder,
+) -> rusqlite::Result<MessagePage> {
+    let limit = limit.max(1);
+    let anchor: Option<(i64, String)> = match cursor {
+        Some(cursor_id) => Some(conn.query_row(
+            "SELECT timestamp, id FROM messages WHERE id = ?1 AND conversation_id = ?2",
+            params![cursor_id, conversation_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?),
+        None => None,
+    };
+
+    let (comparison, direction) = match order {
+        MessageOrder::NewestFirst => ("<", "DESC"),
+        MessageOrder::OldestFirst => (">", "ASC"),
+    };
+    let anchor_clause = if anchor.is_some() {
+        format!("AND (timestamp, id) {comparison} (?2, ?3)")
+    } else {
+        String::new()
+    };
+    let sql = format!(
+        "SELECT id, conversation_id, role, content, model, timestamp, metadata, provider
+         FROM messages
+         WHERE conversation_id = ?1 {anchor_clause}
+         ORDER BY timestamp {direction}, id {direction}
+         LIMIT {}",
+        limit + 1
+    );
+
+    let mut stmt = conn.prepare(&sql)?;
+    let map_row = |row: &rusqlite::Row| {
+        Ok(StoredMessage {
+            id: row.get(0)?,
+            conversation_id: row.get(1)?,
+            role: row.get(2)?,
+            content: row.get(3)?,
+            model: row.get(4)?,
+            timestamp: row.get(5)?,
+            metadata: row.get(6)?,
+            provider: row.get(7)?,
+        })
+    };
+    let mut messages = match &anchor {
+        Some((timestamp, id)) => stmt
+            .query_map(params![conversation_id, timestamp, id], map_row)?
+            .collect::<Result<Vec<_>, _>>()?,
+        None => stmt
+            .query_map(params![conversation_id], map_row)?
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+
+    let next_cursor = if messages.len() > limit {
+        messages.truncate(limit);
+        messages.last().map(|message| message.id.clone())
+    } else {
+        None
+    };
+    Ok(MessagePage {
+        messages,
+        next_cursor,
+    })
+}
+
+/// Paginated variant of `get_messages` for long threads. Pass the previous
+/// page's `next_cursor` as `cursor` to continue in the same `order`.
+#[tauri::command]
+pub async fn get_messages_page(
+    app: AppHandle,
+    conversation_id: String,
+    cursor: Option<String>,
+    limit: usize,
+    order: Option<MessageOrder>,
+) -> Result<MessagePage, String> {
+    run_db(app, move |conn| {
+        query_message_page(
+            conn,
+            &conversation_id,
+            cursor.as_deref(),
+            limit,
+            order.unwrap_or_default(),
+        )
+    })
+    .await
+}
+
+/// All live messages in a conversation, oldest first.
+fn load_conversation_messages(
+    conn: &Connection,
+    conversation_id: &str,
+) -> rusqlite::Result<Vec<StoredMessage>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, conversation_id, role, content, model, timestamp, metadata, provider
+         FROM messages
+         WHERE conversation_id = ?1 AND deleted_at IS NULL
+         ORDER BY timestamp ASC, id ASC",
+    )?;
+    stmt.query_map(params![conversation_id], |row| {
+        Ok(StoredMessage {
+            id: row.get(0)?,
+            conversation_id: row.get(1)?,
+            role: row.get(2)?,
+            content: row.get(3)?,
+            model: row.get(4)?,
+            timestamp: row.get(5)?,
+            metadata: row.get(6)?,
+            provider: row.get(7)?,
+        })
+    })?
+    .collect()
+}
+
+fn message_conversation_id(conn: &Connection, message_id: &str) -> rusqlite::Result<String> {
+    conn.query_row(
+        "SELECT conversation_id FROM messages WHERE id = ?1",
+        params![message_id],
+        |row| row.get(0),
+    )
+}
+
+/// Replace the text of an earlier message, e.g. to fix a typo before
+/// re-running from it with `truncate_after`. Returns the conversation's
+/// messages in chronological order.
+#[tauri::command]
+pub async fn edit_message(
+    app: AppHandle,
+    message_id: String,
+    new_content: String,
+) -> Result<Vec<StoredMessage>, String> {
+    let (messages, indexable) = run_db(app.clone(), move |conn| {
+        let conversation_id = message_conversation_id(conn, &message_id)?;
+        update_message_content(conn, &message_id, &new_content)?;
+        let messages = load_conversation_messages(conn, &conversation_id)?;
+        let meta = load_indexable_message_meta(conn, &conversation_id)?;
+        let indexable = messages
+            .iter()
+            .find(|message| message.id == message_id)
+            .zip(meta)
+            .map(
+                |(message, (kind, title, agent_type, project_root, is_archived, is_privileged))| {
+                    IndexableMessage {
+                        message_id: message.id.clone(),
+                        conversation_id: conversation_id.clone(),
+                        kind,
+                        role: message.role.clone(),
+                        title,
+                        agent_type,
+                        project_root,
+                        is_archived,
+                        is_privileged,
+                        timestamp: message.timestamp,
+                        content: message.content.clone(),
+                    }
+                },
+            );
+        Ok((messages, indexable))
+    })
+    .await?;
+    if let Some(message) = indexable {
+        index_message_best_effort(&app, &message);
+    }
+    Ok(messages)
+}
+
+/// Remove every message after `message_id` so a fresh `orchestrate` can run
+/// from that point. Returns the remaining messages in chronological order.
+#[tauri::command]
+pub async fn truncate_after(
+    app: AppHandle,
+    message_id: String,
+) -> Result<Vec<StoredMessage>, String> {
+    let (messages, removed) = run_db(app.clone(), move |conn| {
+        let conversation_id = message_conversation_id(conn, &message_id)?;
+        let removed = truncate_messages_after(conn, &message_id)?;
+        Ok((load_conversation_messages(conn, &conversation_id)?, removed))
+    })
+    .await?;
+    delete_message_index_best_effort(&app, &removed);
+    Ok(messages)
+}
+
+fn delete_message_index_best_effort(app: &AppHandle, removed: &[String]) {
+    if removed.is_empty() {
+        return;
+    }
+    match open_index_db(app) {
+        Ok(index) => {
+            for removed_id in removed {
+                if let Err(err) = conversation_index::delete_message_chunks(&index, removed_id) {
+                    log::warn!(
+                        "[ConversationIndex] Failed to delete index for message {}: {}",
+                        removed_id,
+                        err
+                    );
+                }
+            }
+        }
+        Err(err) => log::warn!("[ConversationIndex] Failed to open index: {}", err),
+    }
+}
+
+/// The last user turn of a conversation and the history that preceded it.
+#[derive(Debug, PartialEq)]
+struct RegenerationPoint {
+    user_message_id: String,
+    prompt: String,
+    history: Vec<serde_json::Value>,
+}
+
+/// Find the last user message. Everything after it (the assistant reply and
+/// any tool messages) is what a regeneration replaces.
+fn regeneration_point(messages: &[StoredMessage]) -> Option<RegenerationPoint> {
+    let index = messages
+        .iter()
+        .rposition(|message| message.role == "user")?;
+    let history = messages[..index]
+        .iter()
+        .filter(|message| matches!(message.role.as_str(), "user" | "assistant" | "system"))
+        .filter(|message| !message.content.trim().is_empty())
+        .map(|message| serde_json::json!({ "role": message.role, "content": message.content }))
+        .collect();
+    Some(RegenerationPoint {
+        user_message_id: messages[index].id.clone(),
+        prompt: messages[index].content.clone(),
+        history,
+    })
+}
+
+/// Drop the last assistant response (and trailing tool messages) and run the
+/// prior user turn through `orchestrate` again, optionally on
+/// `override_model`. `images` are the user turn's original attachments, which
+/// the database does not store. The reply streams as `assistant_message_id`
+/// like a normal turn. Returns the routing decision used.
+#[tauri::command]
+pub async fn regenerate_last(
+    app: AppHandle,
+    state: State<'_, OrchestratorState>,
+    conversation_id: String,
+    override_model: Option<String>,
+    assistant_message_id: String,
+    capabilities: UserCapabilities,
+    images: Option<Vec<ImageAttachment>>,
+) -> Result<RoutingDecision, String> {
+    let lookup_id = conversation_id.clone();
+    let point = run_db(app.clone(), move |conn| {
+        Ok(regeneration_point(&load_conversation_messages(
+            conn, &lookup_id,
+        )?))
+    })
+    .await?
+    .ok_or_else(|| "No user message to regenerate from".to_string())?;
+
+    // Take a turn slot and validate the route before anything is removed, so
+    // a rejected run leaves the conversation untouched.
+    let turn = crate::orchestrator::service::reserve_turn(&app, &state)?;
+    let mut decision =
+        crate::orchestrator::service::classify_only(&app, &state, &point.prompt, &capabilities);
+    if let Some(model) = override_model.filter(|model| !model.trim().is_empty()) {
+        // A model override only makes sense for a chat-model worker.
+        decision.worker_type = WorkerType::ChatModel;
+        decision.model_id = model;
+    }
+    crate::orchestrator::service::validate_routing_override(&decision, &capabilities)?;
+
+    let user_message_id = point.user_message_id.clone();
+    let removed = run_db(app.clone(), move |conn| {
+        truncate_messages_after(conn, &user_message_id)
+    })
+    .await?;
+    delete_message_index_best_effort(&app, &removed);
+
+    crate::orchestrator::service::orchestrate_in_slot(
+        turn,
+        app,
+        &state,
+        conversation_id,
+        assistant_message_id,
+        point.prompt,
+        point.history,
+        capabilities,
+        images.unwrap_or_default(),
+        Some(decision.clone()),
+        None,
+    )
+    .await?;
+    Ok(decision)
+}
+
+/// Delete a conversation's messages and its orchestration checkpoints, which
+/// hold a copy of the prompt and message history.
+fn clear_conversation_history_in_db(
+    conn: &Connection,
+    conversation_id: &str,
+) -> rusqlite::Result<()> {
+    let mut event_stmt =
+        conn.prepare("SELECT id FROM message_events WHERE conversation_id = ?1")?;
+    let event_ids = event_stmt
+        .query_map(params![conversation_id], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    drop(event_stmt);
+    let mut message_stmt = conn.prepare("SELECT id FROM messages WHERE conversation_id = ?1")?;
+    let message_ids = message_stmt
+        .query_map(params![conversation_id], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    drop(message_stmt);
+    for event_id in &event_ids {
+        enqueue_sync_tombstone(conn, "message_events", event_id)?;
+    }
+    for message_id in &message_ids {
+        enqueue_sync_tombstone(conn, "messages", message_id)?;
+    }
+    conn.execute(
+        "DELETE FROM message_events WHERE conversation_id = ?1",
+        params![conversation_id],
+    )?;
+    conn.execute(
+        "DELETE FROM messages WHERE conversation_id = ?1",
+        params![conversation_id],
+    )?;
+    conn.execute(
+        "DELETE FROM orchestration_checkpoints WHERE conversation_id = ?1",
+        params![conversation_id],
+    )?;
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn clear_conversation_history(
     app: AppHandle,
@@ -2195,70 +2758,47 @@ pub async fn clear_conversation_history(
 ) -> Result<(), String> {
     let index_id = conversation_id.clone();
     run_db(app.clone(), move |conn| {
-        let mut event_stmt =
-            conn.prepare("SELECT id FROM message_events WHERE conversation_id = ?1")?;
-        let event_ids = event_stmt
-            .query_map(params![conversation_id], |row| row.get::<_, String>(0))?
-            .collect::<rusqlite::Result<Vec<_>>>()?;
-        drop(event_stmt);
-        let mut message_stmt =
-            conn.prepare("SELECT id FROM messages WHERE conversation_id = ?1")?;
-        let message_ids = message_stmt
-            .query_map(params![conversation_id], |row| row.get::<_, String>(0))?
-            .collect::<rusqlite::Result<Vec<_>>>()?;
-        drop(message_stmt);
-        for event_id in &event_ids {
-            enqueue_sync_tombstone(conn, "message_events", event_id)?;
-        }
-        for message_id in &message_ids {
-            enqueue_sync_tombstone(conn, "messages", message_id)?;
-        }
-        conn.execute(
-            "DELETE FROM message_events WHERE conversation_id = ?1",
-            params![conversation_id],
-        )?;
-        conn.execute(
-            "DELETE FROM messages WHERE conversation_id = ?1",
-            params![conversation_id],
-        )?;
-        Ok(())
+        clear_conversation_history_in_db(conn, &conversation_id)
     })
     .await?;
     delete_conversation_index_best_effort(&app, &index_id);
     Ok(())
 }
 
+/// Delete every conversation, message and orchestration checkpoint.
+fn clear_all_history_in_db(conn: &Connection) -> rusqlite::Result<()> {
+    let conversation_ids = conn
+        .prepare("SELECT id FROM conversations")?
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for id in &conversation_ids {
+        enqueue_sync_tombstone(conn, "thread_drafts", id)?;
+        enqueue_sync_tombstone(conn, "conversations", id)?;
+    }
+    let event_ids = conn
+        .prepare("SELECT id FROM message_events")?
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for id in &event_ids {
+        enqueue_sync_tombstone(conn, "message_events", id)?;
+    }
+    let message_ids = conn
+        .prepare("SELECT id FROM messages")?
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for id in &message_ids {
+        enqueue_sync_tombstone(conn, "messages", id)?;
+    }
+    conn.execute("DELETE FROM message_events", [])?;
+    conn.execute("DELETE FROM messages", [])?;
+    conn.execute("DELETE FROM orchestration_checkpoints", [])?;
+    conn.execute("DELETE FROM conversations", [])?;
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn clear_all_history(app: AppHandle) -> Result<(), String> {
-    run_db(app.clone(), move |conn| {
-        let conversation_ids = conn
-            .prepare("SELECT id FROM conversations")?
-            .query_map([], |row| row.get::<_, String>(0))?
-            .collect::<rusqlite::Result<Vec<_>>>()?;
-        for id in &conversation_ids {
-            enqueue_sync_tombstone(conn, "thread_drafts", id)?;
-            enqueue_sync_tombstone(conn, "conversations", id)?;
-        }
-        let event_ids = conn
-            .prepare("SELECT id FROM message_events")?
-            .query_map([], |row| row.get::<_, String>(0))?
-            .collect::<rusqlite::Result<Vec<_>>>()?;
-        for id in &event_ids {
-            enqueue_sync_tombstone(conn, "message_events", id)?;
-        }
-        let message_ids = conn
-            .prepare("SELECT id FROM messages")?
-            .query_map([], |row| row.get::<_, String>(0))?
-            .collect::<rusqlite::Result<Vec<_>>>()?;
-        for id in &message_ids {
-            enqueue_sync_tombstone(conn, "messages", id)?;
-        }
-        conn.execute("DELETE FROM message_events", [])?;
-        conn.execute("DELETE FROM messages", [])?;
-        conn.execute("DELETE FROM conversations", [])?;
-        Ok(())
-    })
-    .await?;
+    run_db(app.clone(), clear_all_history_in_db).await?;
     clear_conversation_index_best_effort(&app);
     Ok(())
 }
@@ -2429,15 +2969,18 @@ where
 mod tests {
     use super::{
         AgentArchiveOrigin, AgentConversation, AgentTranscriptTarget, DERIVED_KIND_CASE_SQL,
-        ExpectedHappyRestoration, HappyRestorationCandidate, HappyRestorationLookup,
-        archive_agent_conversation_in_db, archive_happy_provider_session_in_db,
+        ExpectedHappyRestoration, HappyRestorationCandidate, HappyRestorationLookup, MessageOrder,
+        StoredMessage, archive_agent_conversation_in_db, archive_happy_provider_session_in_db,
         claim_happy_provider_session_owner_in_db,
-        claim_happy_provider_session_owner_with_provenance_in_db, collect_agent_transcript_targets,
+        claim_happy_provider_session_owner_with_provenance_in_db, clear_all_history_in_db,
+        clear_conversation_history_in_db, collect_agent_transcript_targets,
         delete_conversation_records, emit_happy_archive_event, emit_happy_provider_archive_event,
         is_happy_provider_session_archived_in_db, list_legacy_happy_restoration_candidates_in_db,
         lookup_agent_conversation_owner_in_db, lookup_happy_restoration_candidate_in_db,
         lookup_happy_session_id_by_conversation_in_db, migrate_happy_restoration_relay_in_db,
-        remove_agent_transcripts, set_agent_conversation_session_id_in_db,
+        query_conversation_rows, query_message_page, regeneration_point, remove_agent_transcripts,
+        restore_conversation_in_db, set_agent_conversation_session_id_in_db,
+        set_conversation_tags_in_db, trash_conversation_in_db, trashed_conversation_ids_before,
         upsert_agent_conversation_in_db, vacuum_database,
     };
     use crate::services::database::{configure_connection, setup_schema};
@@ -2452,6 +2995,180 @@ mod tests {
         conn
     }
 
+    fn seed_messages(conn: &Connection, conversation_id: &str, timestamps: &[(&str, i64)]) {
+        conn.execute(
+            "INSERT INTO conversations (id, title, created_at) VALUES (?1, 't', 0)",
+            params![conversation_id],
+        )
+        .unwrap();
+        for (id, timestamp) in timestamps {
+            conn.execute(
+                "INSERT INTO messages (id, conversation_id, role, content, timestamp)
+                 VALUES (?1, ?2, 'user', ?1, ?3)",
+                params![id, conversation_id, timestamp],
+            )
+            .unwrap();
+        }
+    }
+
+    fn page_ids(page: &super::MessagePage) -> Vec<&str> {
+        page.messages.iter().map(|m| m.id.as_str()).collect()
+    }
+
+    #[test]
+    fn message_pages_walk_back_from_newest_without_gaps() {
+        let conn = open();
+        // "c" and "d" share a timestamp so the id tiebreak must keep them apart.
+        seed_messages(
+            &conn,
+            "convo",
+            &[("a", 1), ("b", 2), ("c", 3), ("d", 3), ("e", 4)],
+        );
+        seed_messages(&conn, "other", &[("z", 5)]);
+
+        let first = query_message_page(&conn, "convo", None, 2, MessageOrder::NewestFirst).unwrap();
+        assert_eq!(page_ids(&first), vec!["e", "d"]);
+        assert_eq!(first.next_cursor.as_deref(), Some("d"));
+
+        let second = query_message_page(
+            &conn,
+            "convo",
+            first.next_cursor.as_deref(),
+            2,
+            MessageOrder::NewestFirst,
+        )
+        .unwrap();
+        assert_eq!(page_ids(&second), vec!["c", "b"]);
+
+        let last = query_message_page(
+            &conn,
+            "convo",
+            second.next_cursor.as_deref(),
+            2,
+            MessageOrder::NewestFirst,
+        )
+        .unwrap();
+        assert_eq!(page_ids(&last), vec!["a"]);
+        assert_eq!(last.next_cursor, None);
+    }
+
+    #[test]
+    fn message_pages_walk_forward_from_oldest() {
+        let conn = open();
+        seed_messages(&conn, "convo", &[("a", 1), ("b", 2), ("c", 3)]);
+
+        let first = query_message_page(&conn, "convo", None, 2, MessageOrder::OldestFirst).unwrap();
+        assert_eq!(page_ids(&first), vec!["a", "b"]);
+
+        let rest = query_message_page(
+            &conn,
+            "convo",
+            first.next_cursor.as_deref(),
+            2,
+            MessageOrder::OldestFirst,
+        )
+        .unwrap();
+        assert_eq!(page_ids(&rest), vec!["c"]);
+        assert_eq!(rest.next_cursor, None);
+    }
+
+    #[test]
+    fn message_page_rejects_cursor_from_another_conversation() {
+        let conn = open();
+        seed_messages(&conn, "convo", &[("a", 1)]);
+        seed_messages(&conn, "other", &[("z", 2)]);
+
+        assert!(
+            query_message_page(&conn, "convo", Some("z"), 10, MessageOrder::NewestFirst).is_err()
+        );
+    }
+
+    #[test]
+    fn conversation_tags_are_normalized_and_filterable() {
+        let conn = open();
+        seed_messages(&conn, "work", &[]);
+        seed_messages(&conn, "home", &[]);
+        seed_messages(&conn, "untagged", &[]);
+
+        let stored = set_conversation_tags_in_db(
+            &conn,
+            "work",
+            &[
+                " client ".to_string(),
+                "urgent".to_string(),
+                "client".to_string(),
+                "".to_string(),
+            ],
+        )
+        .unwrap();
+        assert_eq!(stored, vec!["client", "urgent"]);
+        set_conversation_tags_in_db(&conn, "home", &["personal".to_string()]).unwrap();
+        assert!(set_conversation_tags_in_db(&conn, "missing", &[]).is_err());
+
+        let ids = |filter: Option<&str>| -> Vec<(String, Vec<String>)> {
+            let mut rows: Vec<_> =
+                query_conversation_rows(&conn, None, None, None, -1, false, filter)
+                    .unwrap()
+                    .into_iter()
+                    .map(|row| (row.id, row.tags))
+                    .collect();
+            rows.sort();
+            rows
+        };
+        assert_eq!(ids(None).len(), 3);
+        assert_eq!(
+            ids(Some(r#"["urgent","personal"]"#)),
+            vec![
+                ("home".to_string(), vec!["personal".to_string()]),
+                (
+                    "work".to_string(),
+                    vec!["client".to_string(), "urgent".to_string()]
+                ),
+            ]
+        );
+        assert!(ids(Some(r#"["nope"]"#)).is_empty());
+
+        set_conversation_tags_in_db(&conn, "work", &[]).unwrap();
+        assert!(ids(Some(r#"["client"]"#)).is_empty());
+    }
+
+    #[test]
+    fn trash_restore_and_purge_cutoff() {
+        let conn = open();
+        conn.execute(
+            "INSERT INTO conversations (id, title, created_at, is_archived)
+             VALUES ('old', 't', 0, 1), ('recent', 't', 0, 0), ('kept', 't', 0, 0)",
+            [],
+        )
+        .unwrap();
+
+        assert!(trash_conversation_in_db(&conn, "old", 1_000).unwrap());
+        assert!(trash_conversation_in_db(&conn, "recent", 5_000).unwrap());
+        // Trashing twice keeps the original timestamp so retention isn't reset.
+        assert!(!trash_conversation_in_db(&conn, "old", 9_000).unwrap());
+
+        assert_eq!(
+            trashed_conversation_ids_before(&conn, 2_000).unwrap(),
+            vec!["old".to_string()]
+        );
+
+        // Restoring reports the archived flag the index should go back to.
+        assert_eq!(
+            restore_conversation_in_db(&conn, "old").unwrap(),
+            Some(true)
+        );
+        assert_eq!(restore_conversation_in_db(&conn, "kept").unwrap(), None);
+        assert!(
+            trashed_conversation_ids_before(&conn, 2_000)
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            trashed_conversation_ids_before(&conn, 10_000).unwrap(),
+            vec!["recent".to_string()]
+        );
+    }
+
     #[test]
     fn deleting_agent_conversation_removes_its_cli_transcripts() {
         let conn = open();
@@ -2660,6 +3377,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn clearing_history_deletes_orchestration_checkpoints() {
+        let conn = open();
+        let checkpoints = |conn: &Connection| -> Vec<String> {
+            conn.prepare("SELECT id FROM orchestration_checkpoints ORDER BY id")
+                .unwrap()
+                .query_map([], |row| row.get(0))
+                .unwrap()
+                .collect::<rusqlite::Result<Vec<_>>>()
+                .unwrap()
+        };
+        for (conversation_id, checkpoint_id) in [("c1", "o1"), ("c2", "o2")] {
+            seed_messages(&conn, conversation_id, &[(checkpoint_id, 1)]);
+            conn.execute(
+                "INSERT INTO orchestration_checkpoints
+                 (id, conversation_id, prompt, routing, capabilities, created_at, updated_at)
+                 VALUES (?1, ?2, 'secret prompt', '{}', '{}', 1, 1)",
+                params![format!("{checkpoint_id}-checkpoint"), conversation_id],
+            )
+            .unwrap();
+        }
+
+        clear_conversation_history_in_db(&conn, "c1").unwrap();
+        assert_eq!(checkpoints(&conn), vec!["o2-checkpoint".to_string()]);
+
+        clear_all_history_in_db(&conn).unwrap();
+        assert!(checkpoints(&conn).is_empty());
+    }
+
     fn insert_happy_restoration_candidate(conn: &Connection, id: &str, happy_session_id: &str) {
         conn.execute(
             "INSERT INTO conversations (
@@ -2679,6 +3425,24 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn happy_restoration_lookup_skips_trashed_conversations() {
+        let conn = open();
+        insert_happy_restoration_candidate(&conn, "provider-local-id", "relay-id");
+        assert!(trash_conversation_in_db(&conn, "provider-local-id", 2000).unwrap());
+
+        assert_eq!(
+            lookup_happy_restoration_candidate_in_db(&conn, "provider-local-id", "relay-id")
+                .unwrap(),
+            HappyRestorationLookup::NotHappyOrigin,
+        );
+        assert!(
+            list_legacy_happy_restoration_candidates_in_db(&conn)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
     #[test]
     fn happy_restoration_lookup_returns_exact_saved_resume_fields() {
         let conn = open();
@@ -3370,8 +4134,8 @@ mod tests {
         );
     }
 
-    /// Mirror of the production `list_conversations` SQL so tests can
-    /// drive every filter (derived kind, project_root, limit) without an
+    /// Run the production `list_conversations` query so tests can drive
+    /// every filter (derived kind, project_root, limit) without an
     /// AppHandle. Returns the (id, kind, agent_type) triplet for each
     /// row in created_at DESC.
     ///
@@ -3392,54 +4156,21 @@ mod tests {
         normalized_project_root: Option<&str>,
         limit: Option<i32>,
     ) -> Vec<(String, String, Option<String>, Option<String>)> {
-        // Keep this SQL in lock-step with the production
-        // `list_conversations` query so the test exercises the same
-        // filter shape (kind + project_root + limit), not a relaxed
-        // subset.
-        let sql = format!(
-            "WITH derived AS (
-                SELECT c.id, c.created_at, c.kind, c.agent_type,
-                       c.selected_provider, c.is_archived,
-                       c.project_root, c.project_id, c.agent_cwd,
-                       psr.provider AS runtime_provider,
-                       {case} AS derived_kind
-                FROM conversations c
-                LEFT JOIN provider_session_runtime psr ON psr.thread_id = c.id
-            )
-            SELECT id, derived_kind,
-                   CASE WHEN derived_kind = 'agent'
-                        THEN COALESCE(agent_type, runtime_provider)
-                        ELSE agent_type END AS agent_type,
-                   CASE WHEN derived_kind = 'chat'
-                        THEN COALESCE(selected_provider, runtime_provider)
-                        ELSE selected_provider END AS selected_provider
-            FROM derived
-            WHERE is_archived = 0
-              AND (?1 IS NULL OR derived_kind = ?1)
-              AND (
-                (?2 IS NULL AND ?3 IS NULL)
-                OR project_root = ?2 OR project_id = ?2 OR agent_cwd = ?2
-                OR project_root = ?3 OR project_id = ?3 OR agent_cwd = ?3
-                OR (derived_kind = 'chat' AND project_root IS NULL)
-              )
-            ORDER BY created_at DESC
-            LIMIT ?4",
-            case = DERIVED_KIND_CASE_SQL,
-        );
-        let effective_limit = limit.unwrap_or(-1);
-        let mut stmt = conn.prepare(&sql).unwrap();
-        stmt.query_map(
-            params![
-                kind,
-                raw_project_root,
-                normalized_project_root,
-                effective_limit
-            ],
-            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        // Run the production query so tests exercise the exact filter shape,
+        // including the trashed and tag filters.
+        query_conversation_rows(
+            conn,
+            kind,
+            raw_project_root,
+            normalized_project_root,
+            limit.unwrap_or(-1),
+            false,
+            None,
         )
         .unwrap()
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap()
+        .into_iter()
+        .map(|row| (row.id, row.kind, row.agent_type, row.selected_provider))
+        .collect()
     }
 
     #[test]
@@ -3691,4 +4422,42 @@ mod tests {
         let chat_ids: Vec<&str> = chat_only.iter().map(|r| r.0.as_str()).collect();
         assert_eq!(chat_ids, vec!["chat-live"]);
     }
+
+    fn message(id: &str, role: &str, content: &str) -> StoredMessage {
+        StoredMessage {
+            id: id.to_string(),
+            conversation_id: Some("c1".to_string()),
+            role: role.to_string(),
+            content: content.to_string(),
+            model: None,
+            timestamp: 0,
+            metadata: None,
+            provider: None,
+        }
+    }
+
+    #[test]
+    fn regeneration_point_reruns_the_last_user_turn() {
+        let messages = vec![
+            message("u1", "user", "First question"),
+            message("a1", "assistant", "First answer"),
+            message("u2", "user", "Second question"),
+            message("a2", "assistant", ""),
+            message("t1", "tool", "{\"ok\":true}"),
+            message("a3", "assistant", "Second answer"),
+        ];
+
+        let point = regeneration_point(&messages).unwrap();
+        assert_eq!(point.user_message_id, "u2");
+        assert_eq!(point.prompt, "Second question");
+        assert_eq!(
+            point.history,
+            vec![
+                serde_json::json!({ "role": "user", "content": "First question" }),
+                serde_json::json!({ "role": "assistant", "content": "First answer" }),
+            ]
+        );
+
+        assert!(regeneration_point(&[message("a1", "assistant", "Hi")]).is_none());
+    }
 }
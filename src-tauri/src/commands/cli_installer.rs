@@ -1,9 +1,18 @@
 // ABOUTME: CLI availability checks and guarded installation handoffs.
 // ABOUTME: Avoids executing downloaded scripts; unverified tools require official manual setup.
 
+use std::collections::BTreeMap;
+use std::path::Path;
 use std::process::Command;
+use std::sync::{LazyLock, Mutex};
 use tauri::{AppHandle, Emitter};
 
+/// Agent CLI versions that probed successfully, keyed by binary name. A CLI
+/// that is missing or fails is probed again on the next request, so one
+/// installed after launch shows up.
+static AGENT_CLI_VERSIONS: LazyLock<Mutex<BTreeMap<String, String>>> =
+    LazyLock::new(|| Mutex::new(BTreeMap::new()));
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum CliTool {
@@ -12,16 +21,20 @@ pub enum CliTool {
     Gemini,
 }
 
-/// Check if a CLI tool is installed and in PATH
-#[tauri::command]
-pub async fn check_cli_installed(tool: CliTool) -> Result<bool, String> {
-    let bin_name = match tool {
-        CliTool::Claude => "claude",
-        CliTool::Codex => "codex",
-        CliTool::Gemini => "gemini",
-    };
+impl CliTool {
+    fn bin_name(&self) -> &'static str {
+        match self {
+            CliTool::Claude => "claude",
+            CliTool::Codex => "codex",
+            CliTool::Gemini => "gemini",
+        }
+    }
+}
 
-    // Try to run --version command
+/// Locate a CLI tool on PATH with `which`/`where`, returning the first path it
+/// prints. `None` when the lookup fails or the tool is absent.
+pub(crate) fn resolve_cli(tool: &CliTool) -> Option<String> {
+    let bin_name = tool.bin_name();
     let result = if cfg!(target_os = "windows") {
         let mut c = Command::new("where");
         c.arg(bin_name);
@@ -36,14 +49,57 @@ pub async fn check_cli_installed(tool: CliTool) -> Result<bool, String> {
     };
 
     match result {
-        Ok(output) => Ok(output.status.success()),
+        Ok(output) if output.status.success() => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            Some(stdout.lines().next().unwrap_or_default().trim().to_string())
+        }
+        Ok(_) => None,
         Err(e) => {
             log::debug!("[CliInstaller] Failed to check {}: {}", bin_name, e);
-            Ok(false)
+            None
         }
     }
 }
 
+/// `--version` of the Claude and Codex CLIs, keyed by binary name. `None`
+/// when a CLI is missing or fails to report. Successful probes are cached,
+/// since each probe spawns a process; failures are retried next time.
+pub(crate) fn agent_cli_versions() -> BTreeMap<String, Option<String>> {
+    [CliTool::Claude, CliTool::Codex]
+        .iter()
+        .map(|tool| {
+            let name = tool.bin_name().to_string();
+            let cached = AGENT_CLI_VERSIONS
+                .lock()
+                .ok()
+                .and_then(|versions| versions.get(&name).cloned());
+            let version = cached.or_else(|| {
+                let version = resolve_cli(tool).and_then(|path| {
+                    crate::embedded_runtime::probe_cli_version(Path::new(&path))
+                        .map_err(|e| log::debug!("[CliInstaller] {}", e))
+                        .ok()
+                })?;
+                let version = first_line(&version);
+                if let Ok(mut versions) = AGENT_CLI_VERSIONS.lock() {
+                    versions.insert(name.clone(), version.clone());
+                }
+                Some(version)
+            });
+            (name, version)
+        })
+        .collect()
+}
+
+fn first_line(output: &str) -> String {
+    output.lines().next().unwrap_or_default().trim().to_string()
+}
+
+/// Check if a CLI tool is installed and in PATH
+#[tauri::command]
+pub async fn check_cli_installed(tool: CliTool) -> Result<bool, String> {
+    Ok(resolve_cli(&tool).is_some())
+}
+
 fn manual_install_url(tool: &CliTool) -> &'static str {
     match tool {
         CliTool::Claude => "https://code.claude.com/docs/en/installation",
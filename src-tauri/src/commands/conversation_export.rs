@@ -0,0 +1,326 @@
+// ABOUTME: Tauri command that serializes a stored conversation to Markdown or JSON.
+// ABOUTME: Reads chat.db via run_db, renders tool/diff blocks, and masks credentials.
+
+use crate::commands::chat::{StoredMessage, run_db};
+use crate::skills::format_iso8601_utc;
+use crate::support::redact_secrets;
+use rusqlite::{Connection, params};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::AppHandle;
+
+/// Bumped whenever the JSON export shape changes so importers can branch.
+const EXPORT_VERSION: u32 = 1;
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Markdown,
+    Json,
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct ExportedConversation {
+    id: String,
+    title: String,
+    kind: String,
+    agent_type: Option<String>,
+    created_at: i64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct ConversationExport {
+    version: u32,
+    conversation: ExportedConversation,
+    messages: Vec<StoredMessage>,
+}
+
+/// Load a conversation and its live (non-tombstoned) messages in chronological
+/// order, with credentials masked in every free-text field.
+fn load_export(conn: &Connection, conversation_id: &str) -> rusqlite::Result<ConversationExport> {
+    let conversation = conn.query_row(
+        "SELECT id, title, kind, agent_type, created_at FROM conversations WHERE id = ?1",
+        params![conversation_id],
+        |row| {
+            Ok(ExportedConversation {
+                id: row.get(0)?,
+                title: redact_secrets(&row.get::<_, String>(1)?),
+                kind: row.get(2)?,
+                agent_type: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        },
+    )?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, conversation_id, role, content, model, timestamp, metadata, provider
+         FROM messages
+         WHERE conversation_id = ?1 AND deleted_at IS NULL
+         ORDER BY timestamp ASC, id ASC",
+    )?;
+    let messages = stmt
+        .query_map(params![conversation_id], |row| {
+            Ok(StoredMessage {
+                id: row.get(0)?,
+                conversation_id: row.get(1)?,
+                role: row.get(2)?,
+                content: redact_secrets(&row.get::<_, String>(3)?),
+                model: row.get(4)?,
+                timestamp: row.get(5)?,
+                metadata: row
+                    .get::<_, Option<String>>(6)?
+                    .map(|metadata| redact_secrets(&metadata)),
+                provider: row.get(7)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ConversationExport {
+        version: EXPORT_VERSION,
+        conversation,
+        messages,
+    })
+}
+
+fn format_timestamp(timestamp_ms: i64) -> String {
+    let timestamp_ms = timestamp_ms.max(0) as u64;
+    format_iso8601_utc(timestamp_ms / 1000, (timestamp_ms % 1000) as u32)
+}
+
+/// Wrap `body` in a code fence longer than any backtick run inside it, so
+/// fences in the original content never terminate the block early.
+fn fenced(body: &str, lang: &str) -> String {
+    let longest_run = body.split(|ch| ch != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    format!("{fence}{lang}\n{}\n{fence}\n", body.trim_end_matches('\n'))
+}
+
+fn role_heading(role: &str) -> String {
+    let mut chars = role.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => "Message".to_string(),
+    }
+}
+
+fn render_tool_block(tool_call: &Value) -> String {
+    let field = |key: &str| tool_call.get(key).and_then(Value::as_str);
+    let title = field("title")
+        .or_else(|| field("name"))
+        .or_else(|| field("kind"))
+        .unwrap_or("tool");
+    let mut out = match field("status") {
+        Some(status) => format!("<details>\n<summary>Tool: {title} ({status})</summary>\n\n"),
+        None => format!("<details>\n<summary>Tool: {title}</summary>\n\n"),
+    };
+    if let Some(parameters) = tool_call.get("parameters").filter(|value| !value.is_null()) {
+        let pretty = serde_json::to_string_pretty(parameters).unwrap_or_default();
+        out.push_str("Input:\n\n");
+        out.push_str(&fenced(&pretty, "json"));
+        out.push('\n');
+    }
+    if let Some(result) = field("result") {
+        out.push_str("Result:\n\n");
+        out.push_str(&fenced(result, ""));
+        out.push('\n');
+    }
+    if let Some(error) = field("error") {
+        out.push_str("Error:\n\n");
+        out.push_str(&fenced(error, ""));
+        out.push('\n');
+    }
+    out.push_str("</details>\n");
+    out
+}
+
+fn render_diff_block(diff: &Value) -> String {
+    let field = |key: &str| diff.get(key).and_then(Value::as_str).unwrap_or_default();
+    let mut body = String::new();
+    for line in field("oldText").lines() {
+        body.push_str(&format!("-{line}\n"));
+    }
+    for line in field("newText").lines() {
+        body.push_str(&format!("+{line}\n"));
+    }
+    format!(
+        "<details>\n<summary>Diff: {}</summary>\n\n{}\n</details>\n",
+        field("path"),
+        fenced(&body, "diff")
+    )
+}
+
+/// Render one message. Tool and diff rows persist their payload in
+/// `metadata.block_type` (see `serializeAgentMessageMetadata`) and become
+/// collapsible sections; everything else is emitted verbatim so code fences in
+/// the original text survive.
+fn render_message(message: &StoredMessage) -> String {
+    let block: Option<Value> = message
+        .metadata
+        .as_deref()
+        .and_then(|metadata| serde_json::from_str(metadata).ok());
+    let block_type = block
+        .as_ref()
+        .and_then(|value| value.get("block_type"))
+        .and_then(Value::as_str);
+    let when = format_timestamp(message.timestamp);
+
+    match (block_type, block.as_ref()) {
+        (Some("tool"), Some(value)) if value.get("tool_call").is_some() => {
+            format!("{}\n", render_tool_block(&value["tool_call"]))
+        }
+        (Some("diff"), Some(value)) if value.get("diff").is_some() => {
+            format!("{}\n", render_diff_block(&value["diff"]))
+        }
+        _ => {
+            let heading = match &message.model {
+                Some(model) => format!("### {} ({model}) · {when}", role_heading(&message.role)),
+                None => format!("### {} · {when}", role_heading(&message.role)),
+            };
+            format!("{heading}\n\n{}\n\n", message.content.trim_end())
+        }
+    }
+}
+
+fn render_markdown(export: &ConversationExport) -> String {
+    let conversation = &export.conversation;
+    let mut out = format!("# {}\n\n", conversation.title);
+    out.push_str(&format!(
+        "_{} conversation · started {}_\n\n---\n\n",
+        conversation.kind,
+        format_timestamp(conversation.created_at)
+    ));
+    for message in &export.messages {
+        out.push_str(&render_message(message));
+    }
+    out
+}
+
+fn render_export(export: &ConversationExport, format: ExportFormat) -> Result<String, String> {
+    match format {
+        ExportFormat::Markdown => Ok(render_markdown(export)),
+        ExportFormat::Json => serde_json::to_string_pretty(export).map_err(|err| err.to_string()),
+    }
+}
+
+/// Serialize a full conversation for sharing or archiving. JSON is a faithful
+/// dump (raw `metadata` strings included) suitable for re-import; Markdown is
+/// for reading. Credentials are masked in both.
+#[tauri::command]
+pub async fn export_conversation(
+    app: AppHandle,
+    conversation_id: String,
+    format: ExportFormat,
+) -> Result<String, String> {
+    let export = run_db(app, move |conn| load_export(conn, &conversation_id)).await?;
+    render_export(&export, format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::database::setup_schema;
+
+    fn open() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        setup_schema(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO conversations (id, title, created_at, kind)
+             VALUES ('c1', 'Release notes', 0, 'agent')",
+            [],
+        )
+        .unwrap();
+        conn
+    }
+
+    fn insert(
+        conn: &Connection,
+        id: &str,
+        role: &str,
+        content: &str,
+        ts: i64,
+        metadata: Option<&str>,
+    ) {
+        conn.execute(
+            "INSERT INTO messages (id, conversation_id, role, content, timestamp, metadata)
+             VALUES (?1, 'c1', ?2, ?3, ?4, ?5)",
+            params![id, role, content, ts, metadata],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn markdown_preserves_code_fences_and_collapses_tools() {
+        let conn = open();
+        insert(
+            &conn,
+            "m1",
+            "user",
+            "Run this:\n```sh\nls\n```",
+            1_000,
+            None,
+        );
+        insert(
+            &conn,
+            "m2",
+            "assistant",
+            "",
+            2_000,
+            Some(
+                r#"{"v":1,"block_type":"tool","tool_call":{"title":"Bash","status":"completed","parameters":{"command":"ls"},"result":"a.txt"}}"#,
+            ),
+        );
+        insert(&conn, "m3", "assistant", "Done.", 3_000, None);
+
+        let markdown = render_markdown(&load_export(&conn, "c1").unwrap());
+        assert!(markdown.starts_with("# Release notes\n"));
+        assert!(markdown.contains("### User · 1970-01-01T00:00:01.000Z"));
+        assert!(markdown.contains("```sh\nls\n```"));
+        assert!(markdown.contains("<summary>Tool: Bash (completed)</summary>"));
+        assert!(markdown.contains("\"command\": \"ls\""));
+        assert!(markdown.find("Run this").unwrap() < markdown.find("Done.").unwrap());
+    }
+
+    #[test]
+    fn fence_outgrows_backticks_in_body() {
+        assert_eq!(fenced("plain", ""), "```\nplain\n```\n");
+        assert_eq!(
+            fenced("has ``` inside", "md"),
+            "````md\nhas ``` inside\n````\n"
+        );
+    }
+
+    #[test]
+    fn json_export_round_trips_and_masks_credentials() {
+        let conn = open();
+        insert(
+            &conn,
+            "m1",
+            "user",
+            "my key is sk_live_abcdef123456",
+            1_000,
+            None,
+        );
+        insert(&conn, "m2", "assistant", "ok", 2_000, Some(r#"{"v":1}"#));
+        conn.execute(
+            "INSERT INTO messages (id, conversation_id, role, content, timestamp, deleted_at)
+             VALUES ('gone', 'c1', 'user', 'tombstoned', 1500, 1)",
+            [],
+        )
+        .unwrap();
+
+        let json = render_export(&load_export(&conn, "c1").unwrap(), ExportFormat::Json).unwrap();
+        let parsed: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["version"], 1);
+        assert_eq!(parsed["conversation"]["kind"], "agent");
+        let messages = parsed["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0]["content"], "my key is [REDACTED_KEY]");
+        assert_eq!(messages[1]["metadata"], r#"{"v":1}"#);
+    }
+
+    #[test]
+    fn unknown_conversation_is_an_error() {
+        let conn = open();
+        assert!(load_export(&conn, "missing").is_err());
+    }
+}
@@ -4,7 +4,7 @@
 use crate::commands::chat::run_db;
 use crate::commands::provider_runtime::DERIVED_KIND_CASE_SQL;
 use crate::services::conversation_index::{
-    self, ConversationHit, IndexableMessage, SearchFilters, open_index_db,
+    self, ConversationHit, IndexableMessage, MessageSearchHit, SearchFilters, open_index_db,
 };
 use rusqlite::{OptionalExtension, params};
 use serde::Serialize;
@@ -47,6 +47,25 @@ pub fn search_conversations_fts(
     .map_err(|err| err.to_string())
 }
 
+/// Message-level exact search with highlighted snippets. Narrow to one thread
+/// or one role through `filters.conversation_id` / `filters.role`.
+#[tauri::command]
+pub fn search_messages(
+    app: AppHandle,
+    query: String,
+    filters: Option<SearchFilters>,
+    limit: Option<usize>,
+) -> Result<Vec<MessageSearchHit>, String> {
+    let conn = open_index_db(&app).map_err(|err| err.to_string())?;
+    conversation_index::search_messages(
+        &conn,
+        &query,
+        &filters.unwrap_or_default(),
+        limit.unwrap_or(20),
+    )
+    .map_err(|err| err.to_string())
+}
+
 #[tauri::command]
 pub fn search_conversations(
     app: AppHandle,
@@ -0,0 +1,139 @@
+// ABOUTME: One-shot health report for support: embedded runtime, agent CLIs, Gateway.
+// ABOUTME: Each check is reported as {name, ok, detail} alongside the build info.
+
+use crate::commands::cli_installer::{CliTool, resolve_cli};
+use crate::commands::gateway_http::GATEWAY_BASE_URL;
+use crate::embedded_runtime::{EmbeddedRuntimePaths, discover_embedded_runtime};
+use crate::{BuildInfo, get_build_info};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::AppHandle;
+
+const GATEWAY_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct DiagnosticCheck {
+    pub name: String,
+    pub ok: bool,
+    pub detail: String,
+}
+
+#[derive(Serialize)]
+pub struct DiagnosticsReport {
+    ok: bool,
+    build: BuildInfo,
+    checks: Vec<DiagnosticCheck>,
+}
+
+fn presence_check(name: &str, path: Option<&PathBuf>, missing: &str) -> DiagnosticCheck {
+    DiagnosticCheck {
+        name: name.to_string(),
+        ok: path.is_some(),
+        detail: path
+            .map(|path| path.to_string_lossy().to_string())
+            .unwrap_or_else(|| missing.to_string()),
+    }
+}
+
+fn runtime_checks(paths: &EmbeddedRuntimePaths) -> Vec<DiagnosticCheck> {
+    vec![
+        presence_check(
+            "embedded_node",
+            paths.node_dir.as_ref(),
+            "embedded-runtime node directory was not found",
+        ),
+        presence_check(
+            "embedded_git",
+            paths.git_dir.as_ref(),
+            "embedded-runtime git directory was not found",
+        ),
+    ]
+}
+
+fn cli_check(name: &str, resolved: Option<String>) -> DiagnosticCheck {
+    DiagnosticCheck {
+        name: name.to_string(),
+        ok: resolved.is_some(),
+        detail: resolved.unwrap_or_else(|| "not found on PATH".to_string()),
+    }
+}
+
+async fn gateway_check() -> DiagnosticCheck {
+    let url = format!("{GATEWAY_BASE_URL}/health");
+    let result = match reqwest::Client::builder()
+        .timeout(GATEWAY_PROBE_TIMEOUT)
+        .build()
+    {
+        Ok(client) => client.get(&url).send().await.map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+    let (ok, detail) = match result {
+        Ok(response) => (
+            response.status().is_success(),
+            format!("{url} responded {}", response.status()),
+        ),
+        Err(e) => (false, format!("{url} unreachable: {e}")),
+    };
+    DiagnosticCheck {
+        name: "gateway".to_string(),
+        ok,
+        detail,
+    }
+}
+
+/// Collect a structured "what's broken" snapshot for support. Checks never
+/// fail the command; a failing check is reported with `ok: false`.
+#[tauri::command]
+pub async fn diagnostics(app: AppHandle) -> Result<DiagnosticsReport, String> {
+    let mut checks = runtime_checks(&discover_embedded_runtime(&app));
+
+    let (claude, codex) = tauri::async_runtime::spawn_blocking(|| {
+        (resolve_cli(&CliTool::Claude), resolve_cli(&CliTool::Codex))
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    checks.push(cli_check("claude_cli", claude));
+    checks.push(cli_check("codex_cli", codex));
+    checks.push(gateway_check().await);
+
+    Ok(DiagnosticsReport {
+        ok: checks.iter().all(|check| check.ok),
+        build: get_build_info(app).await,
+        checks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runtime_checks_report_missing_directories() {
+        let paths = EmbeddedRuntimePaths {
+            node_dir: Some(PathBuf::from("/opt/seren/node")),
+            git_dir: None,
+            bin_dir: None,
+            python_dir: None,
+        };
+
+        let checks = runtime_checks(&paths);
+        assert_eq!(checks[0].name, "embedded_node");
+        assert!(checks[0].ok);
+        assert_eq!(checks[0].detail, "/opt/seren/node");
+        assert_eq!(checks[1].name, "embedded_git");
+        assert!(!checks[1].ok);
+        assert!(checks[1].detail.contains("not found"));
+    }
+
+    #[test]
+    fn cli_check_reports_resolved_path() {
+        let found = cli_check("claude_cli", Some("/usr/local/bin/claude".to_string()));
+        assert!(found.ok);
+        assert_eq!(found.detail, "/usr/local/bin/claude");
+
+        let missing = cli_check("codex_cli", None);
+        assert!(!missing.ok);
+        assert_eq!(missing.detail, "not found on PATH");
+    }
+}
@@ -15,7 +15,7 @@ use tokio::sync::{Mutex, oneshot};
 use url::Url;
 
 const GATEWAY_HTTP_EVENT: &str = "gateway-http://event";
-const GATEWAY_BASE_URL: &str = "https://api.serendb.com";
+pub(crate) const GATEWAY_BASE_URL: &str = "https://api.serendb.com";
 const GATEWAY_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
 
 pub struct GatewayHttpState {
@@ -1,7 +1,9 @@
 // ABOUTME: Tauri commands for semantic codebase indexing.
 // ABOUTME: Exposes vector store operations to the frontend for code search.
 
-use crate::services::indexer::{self, ChunkedFile, DiscoveredFile};
+use crate::services::indexer::{
+    self, ChunkedFile, DiscoveredFile, EmbeddingThroughput, IndexingEstimate,
+};
 use crate::services::vector_store::{self, EMBEDDING_DIM, IndexStats, SearchResult};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -121,6 +123,13 @@ pub fn delete_file_index(
     vector_store::delete_file_chunks(&conn, &file_path).map_err(|e| e.to_string())
 }
 
+/// Flush the project's index to disk for durability before the app exits.
+#[tauri::command]
+pub fn flush_index(app: AppHandle, project_path: String) -> Result<(), String> {
+    let conn = vector_store::open_vector_db(&app, &project_path).map_err(|e| e.to_string())?;
+    vector_store::flush_vector_db(&conn).map_err(|e| e.to_string())
+}
+
 /// Check if a file needs re-indexing.
 #[tauri::command]
 pub fn file_needs_reindex(
@@ -136,13 +145,15 @@ pub fn file_needs_reindex(
     vector_store::file_needs_reindex(&conn, &file_path, &file_hash).map_err(|e| e.to_string())
 }
 
-/// Search for similar code chunks.
+/// Search for similar code chunks. When the query text is passed too, each
+/// result carries a snippet of the chunk around the query terms.
 #[tauri::command]
 pub fn search_codebase(
     app: AppHandle,
     project_path: String,
     query_embedding: Vec<f32>,
     limit: usize,
+    query: Option<String>,
 ) -> Result<Vec<SearchResult>, String> {
     if query_embedding.len() != EMBEDDING_DIM {
         return Err(format!(
@@ -153,7 +164,14 @@ pub fn search_codebase(
     }
 
     let conn = vector_store::open_vector_db(&app, &project_path).map_err(|e| e.to_string())?;
-    vector_store::search_similar(&conn, &query_embedding, limit).map_err(|e| e.to_string())
+    let mut results =
+        vector_store::search_similar(&conn, &query_embedding, limit).map_err(|e| e.to_string())?;
+    if let Some(query) = query.filter(|q| !q.trim().is_empty()) {
+        for result in &mut results {
+            result.snippet = Some(vector_store::build_snippet(&result.chunk, &query));
+        }
+    }
+    Ok(results)
 }
 
 /// Get the embedding dimension constant.
@@ -174,10 +192,14 @@ pub fn chunk_file(file: DiscoveredFile) -> Result<ChunkedFile, String> {
     indexer::chunk_file(&file)
 }
 
-/// Estimate indexing work (chunk count and tokens) for discovered files.
+/// Estimate indexing work, time, and cost for discovered files. Without a
+/// `throughput`, the default embedding model's latency and pricing are used.
 #[tauri::command]
-pub fn estimate_indexing(files: Vec<DiscoveredFile>) -> (usize, usize) {
-    indexer::estimate_indexing_work(&files)
+pub fn estimate_indexing(
+    files: Vec<DiscoveredFile>,
+    throughput: Option<EmbeddingThroughput>,
+) -> IndexingEstimate {
+    indexer::estimate_indexing_work(&files, &throughput.unwrap_or_default())
 }
 
 /// Compute content hash for change detection.
@@ -185,3 +207,27 @@ pub fn estimate_indexing(files: Vec<DiscoveredFile>) -> (usize, usize) {
 pub fn compute_file_hash(content: String) -> String {
     indexer::compute_hash(&content)
 }
+
+/// Prepare a single changed file for re-indexing: hash it, skip it if the
+/// stored chunks are current, otherwise drop its old chunks and return the new
+/// ones for the frontend to embed. Returns `None` when nothing needs indexing.
+#[tauri::command]
+pub fn reindex_file(
+    app: AppHandle,
+    project_path: String,
+    file_path: String,
+) -> Result<Option<ChunkedFile>, String> {
+    let Some(file) = indexer::discover_file(Path::new(&project_path), Path::new(&file_path)) else {
+        return Ok(None);
+    };
+
+    let conn = vector_store::open_vector_db(&app, &project_path).map_err(|e| e.to_string())?;
+    if !vector_store::file_needs_reindex(&conn, &file.relative_path, &file.hash)
+        .map_err(|e| e.to_string())?
+    {
+        return Ok(None);
+    }
+
+    vector_store::delete_file_chunks(&conn, &file.relative_path).map_err(|e| e.to_string())?;
+    indexer::chunk_file(&file).map(Some)
+}
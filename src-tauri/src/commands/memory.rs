@@ -23,6 +23,13 @@ const AUTH_STORE: &str = "auth.json";
 // Same credential used by claude_memory.rs (fixed in #1511). Resolves #1540.
 const TOKEN_KEY: &str = "seren_api_key";
 
+/// Scope every project can see. Memories stored without a scope belong to it.
+pub const GLOBAL_SCOPE: &str = "global";
+
+/// Scoped recall asks the backend for this many times `limit` so that
+/// filtering out other scopes still leaves enough results.
+const SCOPED_RECALL_OVERFETCH: usize = 3;
+
 static MEMORY_HTTP: LazyLock<reqwest::Client> = LazyLock::new(|| {
     reqwest::Client::builder()
         .connect_timeout(Duration::from_secs(5))
@@ -95,6 +102,12 @@ impl MemoryState {
         Ok(())
     }
 
+    /// Open the scope index inside the local cache database.
+    fn scope_index(&self) -> Result<rusqlite::Connection, String> {
+        self.ensure_cache()?;
+        open_scope_index(&self.cache_path).map_err(|e| e.to_string())
+    }
+
     /// Erase the entire local memory cache from disk. Drops the open connection
     /// first so the file handles are released, then removes `memory_cache.db`
     /// and its `-wal`/`-shm` companions. The next cache access lazily reopens an
@@ -276,6 +289,179 @@ fn parse_sync_user_id(user_id: Option<&str>) -> Result<uuid::Uuid, String> {
         .map_err(|_| "memory sync user id is not a valid UUID".to_string())
 }
 
+/// Trimmed scope; blank or `global` means unscoped.
+fn normalize_scope(scope: Option<String>) -> Option<String> {
+    scope
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty() && value != GLOBAL_SCOPE)
+}
+
+/// Scope assignments live in memory_cache.db next to the SDK's tables, keyed
+/// by local and cloud memory ids.
+fn open_scope_index(cache_path: &std::path::Path) -> rusqlite::Result<rusqlite::Connection> {
+    let conn = rusqlite::Connection::open(cache_path)?;
+    conn.busy_timeout(Duration::from_secs(5))?;
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS memory_scopes (
+            memory_id TEXT PRIMARY KEY,
+            scope TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_memory_scopes_scope ON memory_scopes(scope);",
+    )?;
+    Ok(conn)
+}
+
+fn record_memory_scope(
+    conn: &rusqlite::Connection,
+    memory_id: &str,
+    scope: &str,
+) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO memory_scopes (memory_id, scope) VALUES (?1, ?2)
+         ON CONFLICT(memory_id) DO UPDATE SET scope = excluded.scope",
+        rusqlite::params![memory_id, scope],
+    )?;
+    Ok(())
+}
+
+fn memory_scope_of(
+    conn: &rusqlite::Connection,
+    memory_id: &str,
+) -> rusqlite::Result<Option<String>> {
+    use rusqlite::OptionalExtension;
+    conn.query_row(
+        "SELECT scope FROM memory_scopes WHERE memory_id = ?1",
+        [memory_id],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+/// A memory is visible from `active` when it is global or in that scope.
+fn scope_visible(memory_scope: Option<&str>, active: Option<&str>) -> bool {
+    match memory_scope {
+        None | Some(GLOBAL_SCOPE) => true,
+        Some(scope) => active == Some(scope),
+    }
+}
+
+/// Drop items whose id is recorded under a scope other than `active`. Items
+/// without an id, or never scoped, are treated as global.
+fn retain_in_scope<T>(
+    conn: &rusqlite::Connection,
+    items: &mut Vec<T>,
+    active: Option<&str>,
+    ids: impl Fn(&T) -> Vec<String>,
+) -> rusqlite::Result<()> {
+    let mut keep = Vec::with_capacity(items.len());
+    for item in items.iter() {
+        let mut visible = true;
+        for id in ids(item) {
+            if let Some(scope) = memory_scope_of(conn, &id)? {
+                visible = scope_visible(Some(&scope), active);
+                break;
+            }
+        }
+        keep.push(visible);
+    }
+    let mut flags = keep.into_iter();
+    items.retain(|_| flags.next().unwrap_or(true));
+    Ok(())
+}
+
+/// Scope a memory was stored under, read from its `scope` metadata.
+fn scope_from_metadata(metadata: &Value) -> Option<String> {
+    normalize_scope(
+        metadata
+            .get("scope")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+    )
+}
+
+/// Record the metadata scope of cached memories under both their local and
+/// cloud ids. Scopes travel in cloud metadata, so running this after a sync
+/// restores them on a new machine or after a cache wipe, and maps cloud ids
+/// the remember call could not report.
+fn index_cached_scopes<'a>(
+    conn: &rusqlite::Connection,
+    memories: impl IntoIterator<Item = &'a CachedMemory>,
+) -> rusqlite::Result<()> {
+    for memory in memories {
+        let Some(scope) = scope_from_metadata(&memory.metadata) else {
+            continue;
+        };
+        record_memory_scope(conn, &memory.id.to_string(), &scope)?;
+        if let Some(cloud_id) = memory.cloud_id {
+            record_memory_scope(conn, &cloud_id.to_string(), &scope)?;
+        }
+    }
+    Ok(())
+}
+
+/// Cloud id of a stored memory, when the remember result carries one.
+fn remembered_memory_id(result: &Value) -> Option<String> {
+    let candidate = result
+        .get("id")
+        .or_else(|| result.get("memory_id"))
+        .and_then(Value::as_str)
+        .or_else(|| result.as_str())?;
+    uuid::Uuid::parse_str(candidate.trim())
+        .ok()
+        .map(|id| id.to_string())
+}
+
+/// Remove hidden memories from a bootstrap prompt the SDK assembled, keeping
+/// its formatting and token budget. Lines ending with a line of a hidden
+/// memory go, then any heading left with nothing under it before the next
+/// heading of the same or a higher level.
+fn strip_hidden_memories(prompt: &str, hidden: &[String]) -> String {
+    let hidden_lines: Vec<&str> = hidden
+        .iter()
+        .flat_map(|content| content.lines())
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let mut kept: Vec<&str> = prompt
+        .lines()
+        .filter(|line| {
+            let line = line.trim();
+            line.is_empty() || !hidden_lines.iter().any(|hidden| line.ends_with(hidden))
+        })
+        .collect();
+
+    // Dropping a subsection can empty its parent, so repeat until stable.
+    loop {
+        let mut output: Vec<&str> = Vec::with_capacity(kept.len());
+        for (index, line) in kept.iter().enumerate() {
+            let level = heading_level(line);
+            if level > 0 {
+                let has_body = kept[index + 1..]
+                    .iter()
+                    .find(|next| !next.trim().is_empty())
+                    .is_some_and(|next| !(1..=level).contains(&heading_level(next)));
+                if !has_body {
+                    continue;
+                }
+            }
+            if line.trim().is_empty() && output.last().is_none_or(|last| last.trim().is_empty()) {
+                continue;
+            }
+            output.push(line);
+        }
+        if output.len() == kept.len() {
+            break;
+        }
+        kept = output;
+    }
+    kept.join("\n").trim_end().to_string()
+}
+
+/// Markdown heading level of a line, 0 when it is not a heading.
+fn heading_level(line: &str) -> usize {
+    line.trim_start().chars().take_while(|c| *c == '#').count()
+}
+
 /// Output type for bootstrap (serializable to frontend).
 #[derive(Serialize)]
 pub struct BootstrapResult {
@@ -357,6 +543,7 @@ async fn build_session_context(
     project_id: Option<String>,
     org_id: Option<String>,
     token_budget: Option<usize>,
+    scope: Option<String>,
 ) -> Result<SessionContext, String> {
     let project_uuid = project_id
         .as_deref()
@@ -379,22 +566,56 @@ async fn build_session_context(
         let cache = LocalCache::open(&cache_path).map_err(|e| e.to_string())?;
         let client = MemoryClient::new(base_url, api_key);
         let orchestrator = BootstrapOrchestrator::new(cache, client);
-        handle
+        let mut ctx = handle
             .block_on(orchestrator.bootstrap(project_uuid, org_uuid, token_budget))
-            .map_err(|e| e.to_string())
+            .map_err(|e| e.to_string())?;
+        filter_session_scope(&cache_path, &mut ctx, scope.as_deref())?;
+        Ok(ctx)
     })
     .await
     .map_err(|e| e.to_string())?
 }
 
+/// Keep only memories visible from `scope` in a bootstrap context. When any
+/// are hidden, they are also cut out of the SDK's assembled prompt.
+fn filter_session_scope(
+    cache_path: &std::path::Path,
+    ctx: &mut SessionContext,
+    scope: Option<&str>,
+) -> Result<(), String> {
+    let conn = open_scope_index(cache_path).map_err(|e| e.to_string())?;
+    let mut hidden = Vec::new();
+    for refs in ctx.memories_by_type.values_mut() {
+        let before: Vec<_> = refs
+            .iter()
+            .map(|r| (r.id.to_string(), r.content.clone()))
+            .collect();
+        retain_in_scope(&conn, refs, scope, |r| vec![r.id.to_string()])
+            .map_err(|e| e.to_string())?;
+        hidden.extend(
+            before
+                .into_iter()
+                .filter(|(id, _)| !refs.iter().any(|r| r.id.to_string() == *id))
+                .map(|(_, content)| content),
+        );
+    }
+    if !hidden.is_empty() {
+        ctx.total_memories = ctx.total_memories.saturating_sub(hidden.len());
+        ctx.assembled_prompt = strip_hidden_memories(&ctx.assembled_prompt, &hidden);
+    }
+    Ok(())
+}
+
 /// Assemble project memory context for system prompt injection.
 #[tauri::command]
 pub async fn memory_bootstrap(
     app: tauri::AppHandle,
     state: State<'_, MemoryState>,
     project_id: Option<String>,
+    scope: Option<String>,
 ) -> Result<Option<String>, String> {
-    let ctx = build_session_context(app, state, project_id, None, None).await?;
+    let scope = normalize_scope(scope);
+    let ctx = build_session_context(app, state, project_id, None, None, scope).await?;
 
     if ctx.assembled_prompt.is_empty() {
         Ok(None)
@@ -411,8 +632,10 @@ pub async fn memory_session_bootstrap(
     project_id: Option<String>,
     org_id: Option<String>,
     token_budget: Option<usize>,
+    scope: Option<String>,
 ) -> Result<SessionBootstrapOutput, String> {
-    let ctx = build_session_context(app, state, project_id, org_id, token_budget).await?;
+    let scope = normalize_scope(scope);
+    let ctx = build_session_context(app, state, project_id, org_id, token_budget, scope).await?;
     Ok(session_output(ctx))
 }
 
@@ -430,10 +653,23 @@ pub async fn memory_remember(
     org_id: Option<String>,
     skip_conflict_check: Option<bool>,
     skip_enrichment: Option<bool>,
+    scope: Option<String>,
 ) -> Result<String, String> {
     // Validate auth before writing anything.
     state.client(&app)?;
 
+    // The scope also rides along in metadata so it reaches the cloud copy.
+    let scope = normalize_scope(scope);
+    let metadata = match (&scope, metadata) {
+        (None, metadata) => metadata,
+        (Some(scope), Some(Value::Object(mut map))) => {
+            map.insert("scope".to_string(), json!(scope));
+            Some(Value::Object(map))
+        }
+        (Some(scope), None) => Some(json!({ "scope": scope })),
+        (Some(_), metadata) => metadata,
+    };
+
     // Write to local cache first (synced=false) so memory survives cloud failures
     // such as scale-to-zero cold starts. The sync engine will push pending entries later.
     let local_id = uuid::Uuid::new_v4();
@@ -471,6 +707,10 @@ pub async fn memory_remember(
                 .ok();
         }
     }
+    if let Some(scope) = &scope {
+        let conn = state.scope_index()?;
+        record_memory_scope(&conn, &local_id.to_string(), scope).map_err(|e| e.to_string())?;
+    }
 
     // Attempt cloud sync (best-effort; service may be warming up from scale-to-zero).
     let mut args = json!({
@@ -486,7 +726,13 @@ pub async fn memory_remember(
     insert_optional(&mut args, "skip_enrichment", skip_enrichment);
 
     match state.call_memory_tool(&app, "remember", args).await {
-        Ok(result) => Ok(value_to_string(&result)),
+        Ok(result) => {
+            if let (Some(scope), Some(cloud_id)) = (&scope, remembered_memory_id(&result)) {
+                let conn = state.scope_index()?;
+                record_memory_scope(&conn, &cloud_id, scope).map_err(|e| e.to_string())?;
+            }
+            Ok(value_to_string(&result))
+        }
         Err(e) => {
             log::warn!("Cloud remember failed (local cache saved, will sync later): {e}");
             Ok(local_id.to_string())
@@ -494,7 +740,8 @@ pub async fn memory_remember(
     }
 }
 
-/// Search memories via the cloud MCP recall tool.
+/// Search memories via the cloud MCP recall tool. Results are limited to
+/// `scope` plus the global scope.
 #[tauri::command]
 pub async fn memory_recall(
     app: tauri::AppHandle,
@@ -502,14 +749,17 @@ pub async fn memory_recall(
     query: String,
     project_id: Option<String>,
     limit: Option<usize>,
+    scope: Option<String>,
 ) -> Result<Vec<RecallOutput>, String> {
     let project_uuid = project_id
         .as_deref()
         .and_then(|s| uuid::Uuid::parse_str(s).ok());
+    let scope = normalize_scope(scope);
+    let fetch_limit = limit.map(|limit| limit.saturating_mul(SCOPED_RECALL_OVERFETCH));
 
     let client = state.client(&app)?;
-    match client.recall(&query, project_uuid, limit).await {
-        Ok(results) => Ok(results
+    let mut outputs = match client.recall(&query, project_uuid, fetch_limit).await {
+        Ok(results) => results
             .into_iter()
             .map(|r| RecallOutput {
                 id: (!r.id.is_nil()).then(|| r.id.to_string()),
@@ -519,33 +769,50 @@ pub async fn memory_recall(
                 vector_score: r.vector_score,
                 bm25_score: r.bm25_score,
             })
-            .collect()),
+            .collect::<Vec<_>>(),
         Err(e) => {
             log::warn!("Cloud recall failed, trying local cache: {e}");
             state.ensure_cache()?;
             let guard = state.cache.lock().map_err(|e| e.to_string())?;
-            if let Some(cache) = guard.as_ref() {
-                // No offline embedding source on the desktop, so hybrid_search
-                // degrades to BM25-only — content-aware, unlike list_recent.
-                let local = cache
-                    .hybrid_search(&query, None, limit.unwrap_or(10))
-                    .map_err(|e| e.to_string())?;
-                Ok(local
-                    .into_iter()
-                    .map(|r| RecallOutput {
-                        id: Some(r.memory.cloud_id.unwrap_or(r.memory.id).to_string()),
-                        content: r.memory.content,
-                        memory_type: r.memory.memory_type,
-                        relevance_score: r.rrf_score,
-                        vector_score: r.vector_score,
-                        bm25_score: r.bm25_score,
-                    })
-                    .collect())
-            } else {
-                Err(e.to_string())
-            }
+            let Some(cache) = guard.as_ref() else {
+                return Err(e.to_string());
+            };
+            // No offline embedding source on the desktop, so hybrid_search
+            // degrades to BM25-only — content-aware, unlike list_recent.
+            let mut local = cache
+                .hybrid_search(&query, None, fetch_limit.unwrap_or(10))
+                .map_err(|e| e.to_string())?;
+            let conn = open_scope_index(&state.cache_path).map_err(|e| e.to_string())?;
+            index_cached_scopes(&conn, local.iter().map(|r| &r.memory))
+                .map_err(|e| e.to_string())?;
+            retain_in_scope(&conn, &mut local, scope.as_deref(), |r| {
+                std::iter::once(r.memory.id)
+                    .chain(r.memory.cloud_id)
+                    .map(|id| id.to_string())
+                    .collect()
+            })
+            .map_err(|e| e.to_string())?;
+            local
+                .into_iter()
+                .map(|r| RecallOutput {
+                    id: Some(r.memory.cloud_id.unwrap_or(r.memory.id).to_string()),
+                    content: r.memory.content,
+                    memory_type: r.memory.memory_type,
+                    relevance_score: r.rrf_score,
+                    vector_score: r.vector_score,
+                    bm25_score: r.bm25_score,
+                })
+                .collect()
         }
-    }
+    };
+
+    let conn = state.scope_index()?;
+    retain_in_scope(&conn, &mut outputs, scope.as_deref(), |r| {
+        r.id.iter().cloned().collect()
+    })
+    .map_err(|e| e.to_string())?;
+    outputs.truncate(limit.unwrap_or(usize::MAX));
+    Ok(outputs)
 }
 
 #[tauri::command]
@@ -786,6 +1053,12 @@ pub async fn memory_sync(
     .await
     .map_err(|e| e.to_string())??;
 
+    // Pulled memories carry their scope in metadata; index it so recall and
+    // bootstrap filter them, and map cloud ids assigned by the push.
+    let memories = cached_memories(&state)?;
+    let conn = state.scope_index()?;
+    index_cached_scopes(&conn, &memories).map_err(|e| e.to_string())?;
+
     Ok(SyncOutput {
         pushed: result.pushed,
         pulled: result.pulled,
@@ -793,10 +1066,423 @@ pub async fn memory_sync(
     })
 }
 
+/// Version of the `memory_export` document. Imports reject other versions.
+const MEMORY_EXPORT_VERSION: u32 = 1;
+
+/// Upper bound on memories read from the local cache for an export.
+const MEMORY_EXPORT_LIMIT: usize = 100_000;
+
+/// Embedding dimension of cached memories. Exports omit embeddings; imported
+/// memories get the same zero vector `memory_remember` stores.
+const CACHED_EMBEDDING_DIM: usize = 1536;
+
+/// A `memory_export` backup of the local memory cache.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MemoryExport {
+    version: u32,
+    exported_at: String,
+    memories: Vec<ExportedMemory>,
+}
+
+/// One memory in a backup. Timestamps are RFC 3339.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ExportedMemory {
+    id: uuid::Uuid,
+    #[serde(default)]
+    cloud_id: Option<uuid::Uuid>,
+    content: String,
+    memory_type: String,
+    #[serde(default)]
+    metadata: Value,
+    #[serde(default)]
+    scope: Option<String>,
+    #[serde(default)]
+    pinned: bool,
+    #[serde(default)]
+    synced: bool,
+    #[serde(default = "default_relevance")]
+    relevance_score: f64,
+    created_at: String,
+}
+
+fn default_relevance() -> f64 {
+    1.0
+}
+
+/// How `memory_import` treats a memory whose id is already cached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MemoryMergeStrategy {
+    Overwrite,
+    SkipExisting,
+    NewerWins,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ImportAction {
+    Add,
+    Update,
+    Skip,
+}
+
+/// Counts reported by `memory_import`.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct MemoryImportOutput {
+    pub added: usize,
+    pub updated: usize,
+    pub skipped: usize,
+    /// Memories that could not be written, one message each.
+    pub errors: Vec<String>,
+}
+
+fn parse_timestamp(
+    value: &str,
+) -> Result<seren_memory_sdk::chrono::DateTime<seren_memory_sdk::chrono::Utc>, String> {
+    seren_memory_sdk::chrono::DateTime::parse_from_rfc3339(value)
+        .map(|time| time.with_timezone(&seren_memory_sdk::chrono::Utc))
+        .map_err(|e| format!("invalid created_at {value:?}: {e}"))
+}
+
+/// Parse and validate a backup before anything is written.
+fn parse_memory_export(json: &str) -> Result<MemoryExport, String> {
+    let export: MemoryExport =
+        serde_json::from_str(json).map_err(|e| format!("invalid memory export: {e}"))?;
+    if export.version != MEMORY_EXPORT_VERSION {
+        return Err(format!(
+            "unsupported memory export version {} (expected {MEMORY_EXPORT_VERSION})",
+            export.version
+        ));
+    }
+    for memory in &export.memories {
+        if memory.content.trim().is_empty() {
+            return Err(format!("memory {} has empty content", memory.id));
+        }
+        if memory.memory_type.trim().is_empty() {
+            return Err(format!("memory {} has no memory_type", memory.id));
+        }
+        parse_timestamp(&memory.created_at).map_err(|e| format!("memory {}: {e}", memory.id))?;
+    }
+    Ok(export)
+}
+
+/// Decide what to do with an imported memory given the `created_at` of the
+/// cached copy with the same id, if any.
+fn import_action(
+    existing: Option<&str>,
+    incoming: &str,
+    strategy: MemoryMergeStrategy,
+) -> ImportAction {
+    let Some(existing) = existing else {
+        return ImportAction::Add;
+    };
+    match strategy {
+        MemoryMergeStrategy::Overwrite => ImportAction::Update,
+        MemoryMergeStrategy::SkipExisting => ImportAction::Skip,
+        MemoryMergeStrategy::NewerWins => {
+            match (parse_timestamp(existing), parse_timestamp(incoming)) {
+                (Ok(existing), Ok(incoming)) if incoming > existing => ImportAction::Update,
+                _ => ImportAction::Skip,
+            }
+        }
+    }
+}
+
+fn cached_memories(state: &MemoryState) -> Result<Vec<CachedMemory>, String> {
+    state.ensure_cache()?;
+    let guard = state.cache.lock().map_err(|e| e.to_string())?;
+    let cache = guard
+        .as_ref()
+        .ok_or_else(|| "memory cache unavailable".to_string())?;
+    cache
+        .list_recent(MEMORY_EXPORT_LIMIT)
+        .map_err(|e| e.to_string())
+}
+
+/// Snapshot every memory in the local cache, synced or not, with its scope,
+/// as a JSON document `memory_import` can restore.
+#[tauri::command]
+pub async fn memory_export(state: State<'_, MemoryState>) -> Result<String, String> {
+    state.ensure_cache()?;
+    // LocalCache (rusqlite::Connection) is not Send, so run on a blocking thread.
+    let cache_path = state.cache_path.clone();
+    tokio::task::spawn_blocking(move || export_memories(&cache_path))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn export_memories(cache_path: &std::path::Path) -> Result<String, String> {
+    let cache = LocalCache::open(cache_path).map_err(|e| e.to_string())?;
+    let memories = cache
+        .list_recent(MEMORY_EXPORT_LIMIT)
+        .map_err(|e| e.to_string())?;
+    let conn = open_scope_index(cache_path).map_err(|e| e.to_string())?;
+    let mut exported = Vec::with_capacity(memories.len());
+    for memory in memories {
+        let mut scope =
+            memory_scope_of(&conn, &memory.id.to_string()).map_err(|e| e.to_string())?;
+        if scope.is_none()
+            && let Some(cloud_id) = memory.cloud_id
+        {
+            scope = memory_scope_of(&conn, &cloud_id.to_string()).map_err(|e| e.to_string())?;
+        }
+        exported.push(ExportedMemory {
+            id: memory.id,
+            cloud_id: memory.cloud_id,
+            content: memory.content,
+            memory_type: memory.memory_type,
+            metadata: memory.metadata,
+            scope,
+            pinned: memory.pinned,
+            synced: memory.synced,
+            relevance_score: memory.relevance_score,
+            created_at: memory.created_at.to_rfc3339(),
+        });
+    }
+    let export = MemoryExport {
+        version: MEMORY_EXPORT_VERSION,
+        exported_at: seren_memory_sdk::chrono::Utc::now().to_rfc3339(),
+        memories: exported,
+    };
+    serde_json::to_string_pretty(&export).map_err(|e| e.to_string())
+}
+
+/// Restore a `memory_export` backup into the local cache. The whole document
+/// is validated first; memories whose id is already cached are resolved by
+/// `merge_strategy`. A memory that fails to write is reported in `errors`
+/// and the rest are still imported, so the counts describe what landed.
+/// Restored memories sync to the cloud on the next sync.
+#[tauri::command]
+pub async fn memory_import(
+    state: State<'_, MemoryState>,
+    json: String,
+    merge_strategy: MemoryMergeStrategy,
+) -> Result<MemoryImportOutput, String> {
+    let export = parse_memory_export(&json)?;
+    state.ensure_cache()?;
+    let cache_path = state.cache_path.clone();
+    tokio::task::spawn_blocking(move || import_memories(&cache_path, export, merge_strategy))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn import_memories(
+    cache_path: &std::path::Path,
+    export: MemoryExport,
+    merge_strategy: MemoryMergeStrategy,
+) -> Result<MemoryImportOutput, String> {
+    let cache = LocalCache::open(cache_path).map_err(|e| e.to_string())?;
+    let existing: HashMap<uuid::Uuid, String> = cache
+        .list_recent(MEMORY_EXPORT_LIMIT)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|memory| (memory.id, memory.created_at.to_rfc3339()))
+        .collect();
+    let conn = open_scope_index(cache_path).map_err(|e| e.to_string())?;
+
+    let mut output = MemoryImportOutput::default();
+    for memory in export.memories {
+        let action = import_action(
+            existing.get(&memory.id).map(String::as_str),
+            &memory.created_at,
+            merge_strategy,
+        );
+        if action == ImportAction::Skip {
+            output.skipped += 1;
+            continue;
+        }
+        let id = memory.id;
+        let cached = CachedMemory {
+            id,
+            content: memory.content,
+            memory_type: memory.memory_type,
+            metadata: match memory.metadata {
+                Value::Null => json!({}),
+                metadata => metadata,
+            },
+            embedding: vec![0.0; CACHED_EMBEDDING_DIM],
+            relevance_score: memory.relevance_score,
+            created_at: parse_timestamp(&memory.created_at)?,
+            synced: memory.synced,
+            cloud_id: memory.cloud_id,
+            feedback_signal: None,
+            pinned: memory.pinned,
+        };
+        if let Err(e) = cache.insert_memory_scoped(&cached, MemoryScope::new(None, None, None)) {
+            output
+                .errors
+                .push(format!("failed to import memory {id}: {e}"));
+            continue;
+        }
+        if let Some(scope) = normalize_scope(memory.scope) {
+            for scoped_id in std::iter::once(id).chain(memory.cloud_id) {
+                if let Err(e) = record_memory_scope(&conn, &scoped_id.to_string(), &scope) {
+                    output
+                        .errors
+                        .push(format!("failed to record scope of memory {id}: {e}"));
+                }
+            }
+        }
+        match action {
+            ImportAction::Add => output.added += 1,
+            _ => output.updated += 1,
+        }
+    }
+    Ok(output)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn exported(id: uuid::Uuid, created_at: &str) -> Value {
+        json!({
+            "id": id,
+            "content": "Prefers tabs over spaces",
+            "memory_type": "preference",
+            "scope": "project-a",
+            "created_at": created_at,
+        })
+    }
+
+    #[test]
+    fn memory_export_schema_is_validated() {
+        let id = uuid::Uuid::new_v4();
+        let doc = json!({
+            "version": 1,
+            "exported_at": "2026-01-01T00:00:00Z",
+            "memories": [exported(id, "2026-01-01T00:00:00Z")],
+        });
+        let export = parse_memory_export(&doc.to_string()).unwrap();
+        assert_eq!(export.memories[0].id, id);
+        assert_eq!(export.memories[0].scope.as_deref(), Some("project-a"));
+        assert!(!export.memories[0].pinned);
+
+        let mut wrong_version = doc.clone();
+        wrong_version["version"] = json!(2);
+        let err = parse_memory_export(&wrong_version.to_string()).unwrap_err();
+        assert!(err.contains("unsupported memory export version"));
+
+        let mut blank = doc.clone();
+        blank["memories"][0]["content"] = json!("  ");
+        assert!(
+            parse_memory_export(&blank.to_string())
+                .unwrap_err()
+                .contains("empty content")
+        );
+
+        let mut bad_time = doc.clone();
+        bad_time["memories"][0]["created_at"] = json!("yesterday");
+        assert!(
+            parse_memory_export(&bad_time.to_string())
+                .unwrap_err()
+                .contains("created_at")
+        );
+
+        assert!(parse_memory_export("{\"memories\": []}").is_err());
+    }
+
+    #[test]
+    fn import_action_follows_merge_strategy() {
+        let older = "2026-01-01T00:00:00Z";
+        let newer = "2026-02-01T00:00:00+01:00";
+        for strategy in [
+            MemoryMergeStrategy::Overwrite,
+            MemoryMergeStrategy::SkipExisting,
+            MemoryMergeStrategy::NewerWins,
+        ] {
+            assert_eq!(import_action(None, older, strategy), ImportAction::Add);
+        }
+        assert_eq!(
+            import_action(Some(newer), older, MemoryMergeStrategy::Overwrite),
+            ImportAction::Update
+        );
+        assert_eq!(
+            import_action(Some(older), newer, MemoryMergeStrategy::SkipExisting),
+            ImportAction::Skip
+        );
+        assert_eq!(
+            import_action(Some(older), newer, MemoryMergeStrategy::NewerWins),
+            ImportAction::Update
+        );
+        assert_eq!(
+            import_action(Some(newer), older, MemoryMergeStrategy::NewerWins),
+            ImportAction::Skip
+        );
+        assert_eq!(
+            import_action(Some(older), older, MemoryMergeStrategy::NewerWins),
+            ImportAction::Skip
+        );
+    }
+
+    #[test]
+    fn export_then_import_round_trips_with_each_strategy() {
+        let tmp = tempfile::TempDir::new().expect("tempdir");
+        let source = tmp.path().join("source.db");
+        let scoped = uuid::Uuid::new_v4();
+        let global = uuid::Uuid::new_v4();
+        let mut global_memory = exported(global, "2026-01-02T00:00:00Z");
+        global_memory["scope"] = Value::Null;
+        let doc = json!({
+            "version": 1,
+            "exported_at": "2026-01-03T00:00:00Z",
+            "memories": [exported(scoped, "2026-01-01T00:00:00Z"), global_memory],
+        });
+        let seeded = import_memories(
+            &source,
+            parse_memory_export(&doc.to_string()).unwrap(),
+            MemoryMergeStrategy::SkipExisting,
+        )
+        .unwrap();
+        assert_eq!((seeded.added, seeded.updated), (2, 0));
+
+        let backup = export_memories(&source).unwrap();
+        let export = parse_memory_export(&backup).unwrap();
+        assert_eq!(export.memories.len(), 2);
+        let scopes: HashMap<_, _> = export
+            .memories
+            .iter()
+            .map(|memory| (memory.id, memory.scope.clone()))
+            .collect();
+        assert_eq!(scopes[&scoped].as_deref(), Some("project-a"));
+        assert_eq!(scopes[&global], None);
+
+        let reimport = |strategy| {
+            import_memories(&source, parse_memory_export(&backup).unwrap(), strategy).unwrap()
+        };
+        let overwrite = reimport(MemoryMergeStrategy::Overwrite);
+        assert_eq!((overwrite.updated, overwrite.skipped), (2, 0));
+        let skip = reimport(MemoryMergeStrategy::SkipExisting);
+        assert_eq!((skip.updated, skip.skipped), (0, 2));
+        let newer = reimport(MemoryMergeStrategy::NewerWins);
+        assert_eq!((newer.updated, newer.skipped), (0, 2));
+
+        let restored = tmp.path().join("restored.db");
+        let fresh = import_memories(
+            &restored,
+            parse_memory_export(&backup).unwrap(),
+            MemoryMergeStrategy::NewerWins,
+        )
+        .unwrap();
+        assert_eq!(fresh.added, 2);
+        assert!(fresh.errors.is_empty());
+        let conn = open_scope_index(&restored).unwrap();
+        assert_eq!(
+            memory_scope_of(&conn, &scoped.to_string())
+                .unwrap()
+                .as_deref(),
+            Some("project-a")
+        );
+    }
+
+    #[test]
+    fn merge_strategy_uses_kebab_case_names() {
+        let strategy: MemoryMergeStrategy = serde_json::from_str("\"skip-existing\"").unwrap();
+        assert_eq!(strategy, MemoryMergeStrategy::SkipExisting);
+        let strategy: MemoryMergeStrategy = serde_json::from_str("\"newer-wins\"").unwrap();
+        assert_eq!(strategy, MemoryMergeStrategy::NewerWins);
+    }
+
     #[test]
     fn wipe_local_cache_removes_db_and_sidecar_files() {
         let tmp = tempfile::TempDir::new().expect("tempdir");
@@ -914,4 +1600,92 @@ mod tests {
         let extracted = extract_sse_json(body).unwrap();
         assert!(extracted.contains("\"result\""));
     }
+
+    #[test]
+    fn recall_sees_its_own_scope_and_global_only() {
+        let tmp = tempfile::TempDir::new().expect("tempdir");
+        let conn = open_scope_index(&tmp.path().join("memory_cache.db")).expect("index");
+        record_memory_scope(&conn, "a", "/work/project-a").unwrap();
+        record_memory_scope(&conn, "b", "/work/project-b").unwrap();
+        record_memory_scope(&conn, "g", GLOBAL_SCOPE).unwrap();
+
+        let mut ids = vec!["a", "b", "g", "unscoped"];
+        retain_in_scope(&conn, &mut ids, Some("/work/project-a"), |id| {
+            vec![id.to_string()]
+        })
+        .unwrap();
+        assert_eq!(ids, vec!["a", "g", "unscoped"]);
+
+        let mut ids = vec!["a", "b", "g", "unscoped"];
+        retain_in_scope(&conn, &mut ids, None, |id| vec![id.to_string()]).unwrap();
+        assert_eq!(ids, vec!["g", "unscoped"]);
+
+        assert_eq!(normalize_scope(Some("  ".to_string())), None);
+        assert_eq!(normalize_scope(Some(GLOBAL_SCOPE.to_string())), None);
+        assert_eq!(
+            normalize_scope(Some(" /work/project-a ".to_string())).as_deref(),
+            Some("/work/project-a")
+        );
+    }
+
+    #[test]
+    fn hidden_memories_are_cut_from_the_sdk_prompt() {
+        let prompt = "# Memory context\n\n## Preferences\n- Prefers tabs\n\n\
+                      ## Project facts\n- Project B uses SQLite\n- Deploys on Fridays\n\n\
+                      ## Errors\n- [Project B] migration failed\n  retry with --force";
+        let hidden = vec![
+            "Project B uses SQLite".to_string(),
+            "[Project B] migration failed\nretry with --force".to_string(),
+        ];
+        assert_eq!(
+            strip_hidden_memories(prompt, &hidden),
+            "# Memory context\n\n## Preferences\n- Prefers tabs\n\n\
+             ## Project facts\n- Deploys on Fridays"
+        );
+    }
+
+    #[test]
+    fn cached_scopes_map_local_and_cloud_ids() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let conn = open_scope_index(&tmp.path().join("memory_cache.db")).expect("index");
+        let memory = |metadata: Value, cloud_id: Option<uuid::Uuid>| CachedMemory {
+            id: uuid::Uuid::new_v4(),
+            content: "Project A uses Postgres".to_string(),
+            memory_type: "semantic".to_string(),
+            metadata,
+            embedding: Vec::new(),
+            relevance_score: 1.0,
+            created_at: seren_memory_sdk::chrono::Utc::now(),
+            synced: cloud_id.is_some(),
+            cloud_id,
+            feedback_signal: None,
+            pinned: false,
+        };
+        let cloud_id = uuid::Uuid::new_v4();
+        let scoped = memory(json!({ "scope": "/work/project-a" }), Some(cloud_id));
+        let global = memory(json!({}), None);
+        index_cached_scopes(&conn, [&scoped, &global]).unwrap();
+
+        for id in [scoped.id, cloud_id] {
+            assert_eq!(
+                memory_scope_of(&conn, &id.to_string()).unwrap().as_deref(),
+                Some("/work/project-a")
+            );
+        }
+        assert_eq!(
+            memory_scope_of(&conn, &global.id.to_string()).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn reads_cloud_id_from_remember_result() {
+        let id = "0b6f6f3e-52a2-4a55-9d3b-1f3d8c1f2a10";
+        assert_eq!(
+            remembered_memory_id(&json!({ "id": id })).as_deref(),
+            Some(id)
+        );
+        assert_eq!(remembered_memory_id(&json!(id)).as_deref(), Some(id));
+        assert_eq!(remembered_memory_id(&json!({ "message": "stored" })), None);
+    }
 }
@@ -3,16 +3,20 @@
 
 use tauri::{AppHandle, Manager, State};
 
-use crate::orchestrator::eval::EvalState;
+use crate::orchestrator::eval::{EvalSnapshot, EvalState};
+use crate::orchestrator::routing_rules::{self, RouteFragment, RoutingRule, RuleMatch};
 use crate::orchestrator::service::OrchestratorState;
-use crate::orchestrator::tool_bridge::ToolResultBridge;
-use crate::orchestrator::types::{ImageAttachment, UserCapabilities};
+use crate::orchestrator::tool_bridge::{ToolExecutionResult, ToolImage, ToolResultBridge};
+use crate::orchestrator::types::{ImageAttachment, RoutingDecision, UserCapabilities};
 use crate::services::database::init_db;
 
 /// Send a prompt through the orchestrator pipeline.
 ///
 /// Classifies the task, routes to the appropriate worker, and streams
-/// events back to the frontend via `orchestrator://event` emissions.
+/// events back to the frontend via `orchestrator://event` emissions. A
+/// `routing_override` (typically an edited `classify_only` result) replaces
+/// the classifier's route. `turn_timeout_secs` caps the wall-clock time of
+/// the whole turn across all tool rounds.
 #[tauri::command]
 pub async fn orchestrate(
     app: AppHandle,
@@ -23,6 +27,8 @@ pub async fn orchestrate(
     history: Vec<serde_json::Value>,
     capabilities: UserCapabilities,
     images: Vec<ImageAttachment>,
+    routing_override: Option<RoutingDecision>,
+    turn_timeout_secs: Option<u64>,
 ) -> Result<(), String> {
     crate::orchestrator::service::orchestrate(
         app,
@@ -33,31 +39,135 @@ pub async fn orchestrate(
         history,
         capabilities,
         images,
+        routing_override,
+        turn_timeout_secs,
     )
     .await
 }
 
+/// Continue an interrupted orchestration from its last completed tool round.
+///
+/// `orchestration_id` is the `assistant_message_id` the turn was started
+/// with. Checkpoints are dropped when a turn completes and expire after a
+/// day, so only recently interrupted chat-model turns can be resumed.
+#[tauri::command]
+pub async fn resume_orchestration(
+    app: AppHandle,
+    state: State<'_, OrchestratorState>,
+    orchestration_id: String,
+) -> Result<(), String> {
+    crate::orchestrator::service::resume(app, &state, orchestration_id).await
+}
+
+/// Classify a prompt and return the routing decision without executing it.
+///
+/// Lets the UI explain why a worker/model was picked, and lets the user
+/// confirm or change the route before calling `orchestrate`.
+#[tauri::command]
+pub fn classify_only(
+    app: AppHandle,
+    state: State<'_, OrchestratorState>,
+    prompt: String,
+    capabilities: UserCapabilities,
+) -> Result<RoutingDecision, String> {
+    Ok(crate::orchestrator::service::classify_only(
+        &app,
+        &state,
+        &prompt,
+        &capabilities,
+    ))
+}
+
+/// Number of orchestrations currently running, counted against the
+/// `maxConcurrentOrchestrations` limit.
+#[tauri::command]
+pub fn get_active_orchestration_count(state: State<'_, OrchestratorState>) -> usize {
+    state.active_turn_count()
+}
+
+/// Forget cached routing decisions so the next `classify_only` re-routes.
+#[tauri::command]
+pub fn clear_routing_cache(state: State<'_, OrchestratorState>) -> Result<(), String> {
+    crate::orchestrator::service::clear_routing_cache(&state);
+    Ok(())
+}
+
+/// User routing rules, in the order they are tried.
+#[tauri::command]
+pub fn list_routing_rules(app: AppHandle) -> Result<Vec<RoutingRule>, String> {
+    routing_rules::load_rules(&routing_rules::rules_path(&app)?)
+}
+
+/// Append a routing rule. Earlier rules win when several match.
+#[tauri::command]
+pub fn add_routing_rule(
+    app: AppHandle,
+    matcher: RuleMatch,
+    route: RouteFragment,
+) -> Result<RoutingRule, String> {
+    let rule = RoutingRule::new(matcher, route)?;
+    let path = routing_rules::rules_path(&app)?;
+    let mut rules = routing_rules::load_rules(&path)?;
+    rules.push(rule.clone());
+    routing_rules::save_rules(&path, &rules)?;
+    Ok(rule)
+}
+
+/// Remove a routing rule by id. Returns false when no rule had that id.
+#[tauri::command]
+pub fn remove_routing_rule(app: AppHandle, id: String) -> Result<bool, String> {
+    let path = routing_rules::rules_path(&app)?;
+    let mut rules = routing_rules::load_rules(&path)?;
+    let before = rules.len();
+    rules.retain(|rule| rule.id != id);
+    if rules.len() == before {
+        return Ok(false);
+    }
+    routing_rules::save_rules(&path, &rules)?;
+    Ok(true)
+}
+
 /// Cancel an active orchestration session.
+///
+/// Also releases any frontend tool calls the turn is waiting on, so a worker
+/// blocked in the tool bridge returns immediately instead of hanging.
 #[tauri::command]
 pub async fn cancel_orchestration(
     state: State<'_, OrchestratorState>,
+    bridge: State<'_, ToolResultBridge>,
     conversation_id: String,
 ) -> Result<(), String> {
-    crate::orchestrator::service::cancel(&state, &conversation_id).await
+    crate::orchestrator::service::cancel(&state, &conversation_id).await?;
+    let released = bridge.cancel_all(&conversation_id).await;
+    if released > 0 {
+        log::info!(
+            "[cancel_orchestration] Released {} pending tool call(s) for {}",
+            released,
+            conversation_id
+        );
+    }
+    Ok(())
 }
 
 /// Submit a tool execution result from the frontend back to the waiting ChatModelWorker.
 ///
 /// Called by the frontend after executing a non-local tool (gateway, MCP).
 /// The ChatModelWorker is blocked waiting on the bridge for this result.
+/// `image` carries a tool's image output (e.g. a screenshot) for vision models.
 #[tauri::command]
 pub async fn submit_tool_result(
     bridge: State<'_, ToolResultBridge>,
     tool_call_id: String,
     content: String,
     is_error: bool,
+    image: Option<ToolImage>,
 ) -> Result<(), String> {
-    let found = bridge.submit(&tool_call_id, content, is_error).await;
+    let result = ToolExecutionResult {
+        content,
+        is_error,
+        image,
+    };
+    let found = bridge.submit_result(&tool_call_id, result).await;
     if !found {
         log::warn!(
             "[submit_tool_result] No pending request for tool_call_id: {}",
@@ -68,19 +178,40 @@ pub async fn submit_tool_result(
 }
 
 /// Submit an eval satisfaction signal for a message.
+///
+/// A negative signal while the conversation is still orchestrating asks the
+/// running ChatModelWorker to escalate to a stronger model on its next tool
+/// round. Signals otherwise feed Thompson-sampling rankings for later turns.
 #[tauri::command]
 pub async fn submit_eval_signal(
     app: AppHandle,
-    _eval_state: State<'_, EvalState>,
+    state: State<'_, OrchestratorState>,
+    eval_state: State<'_, EvalState>,
     message_id: String,
     satisfaction: i32,
     auth_token: String,
 ) -> Result<(), String> {
-    tauri::async_runtime::spawn_blocking(move || {
+    let conversation_id = tauri::async_runtime::spawn_blocking(move || {
         let conn = init_db(&app).map_err(|e| e.to_string())?;
         let eval = app.state::<EvalState>();
         crate::orchestrator::eval::submit(&conn, &eval, &message_id, satisfaction, &auth_token)
     })
     .await
-    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())??;
+
+    if satisfaction == 0 && crate::orchestrator::service::is_active(&state, &conversation_id).await
+    {
+        eval_state.request_escalation(&conversation_id);
+    }
+    Ok(())
+}
+
+/// Return the eval signals accumulated for a conversation's orchestration,
+/// with the derived score and any pending or applied model escalation.
+#[tauri::command]
+pub fn get_eval_state(
+    eval_state: State<'_, EvalState>,
+    conversation_id: String,
+) -> Result<EvalSnapshot, String> {
+    Ok(eval_state.snapshot(&conversation_id))
 }
@@ -0,0 +1,170 @@
+// ABOUTME: Checks a direct-provider API key with a cheap authenticated request.
+// ABOUTME: Lets settings reject a mistyped key before it is stored.
+
+use std::time::Duration;
+
+use serde::Serialize;
+
+const VALIDATION_TIMEOUT: Duration = Duration::from_secs(10);
+const ANTHROPIC_MODELS_URL: &str = "https://api.anthropic.com/v1/models";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+const OPENAI_MODELS_URL: &str = "https://api.openai.com/v1/models";
+
+/// What the provider said about a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyStatus {
+    Valid,
+    /// The provider rejected the key.
+    Invalid,
+    /// The provider throttled the check, so the key could not be confirmed.
+    RateLimited,
+    /// The provider returned a server error.
+    ProviderError,
+    /// The provider could not be reached.
+    Network,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProviderKeyValidation {
+    pub status: KeyStatus,
+    /// The provider's error message or the transport error, when not valid.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl ProviderKeyValidation {
+    /// One-line explanation for an error shown in settings.
+    pub fn describe(&self, provider: &str) -> String {
+        let summary = match self.status {
+            KeyStatus::Valid => return format!("{} key is valid", provider),
+            KeyStatus::Invalid => "was rejected",
+            KeyStatus::RateLimited => "could not be checked: rate limited",
+            KeyStatus::ProviderError => "could not be checked: provider error",
+            KeyStatus::Network => "could not be checked: network error",
+        };
+        match &self.detail {
+            Some(detail) => format!("{} key {} ({})", provider, summary, detail),
+            None => format!("{} key {}", provider, summary),
+        }
+    }
+}
+
+/// Whether `validate_provider_key` knows how to check keys for `provider`.
+pub fn supports_key_validation(provider: &str) -> bool {
+    matches!(provider, "anthropic" | "openai")
+}
+
+/// Check `api_key` against `provider` with a models-list request, which
+/// needs authentication but costs nothing.
+#[tauri::command]
+pub async fn validate_provider_key(
+    provider: String,
+    api_key: String,
+) -> Result<ProviderKeyValidation, String> {
+    let api_key = api_key.trim();
+    if api_key.is_empty() {
+        return Err("API key is empty".to_string());
+    }
+    let client = reqwest::Client::builder()
+        .timeout(VALIDATION_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let request = match provider.as_str() {
+        "anthropic" => client
+            .get(ANTHROPIC_MODELS_URL)
+            .header("x-api-key", api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION),
+        "openai" => client.get(OPENAI_MODELS_URL).bearer_auth(api_key),
+        other => {
+            return Err(format!(
+                "Key validation is not supported for provider {}",
+                other
+            ));
+        }
+    };
+
+    let response = match request.send().await {
+        Ok(response) => response,
+        Err(e) => {
+            return Ok(ProviderKeyValidation {
+                status: KeyStatus::Network,
+                detail: Some(e.to_string()),
+            });
+        }
+    };
+    let status = response.status().as_u16();
+    let body = response.text().await.unwrap_or_default();
+    Ok(classify_key_response(status, &body))
+}
+
+/// Map the validation request's HTTP status to a key status, keeping the
+/// provider's error message for anything but success.
+fn classify_key_response(code: u16, body: &str) -> ProviderKeyValidation {
+    let status = match code {
+        200..=299 => {
+            return ProviderKeyValidation {
+                status: KeyStatus::Valid,
+                detail: None,
+            };
+        }
+        429 => KeyStatus::RateLimited,
+        500..=599 => KeyStatus::ProviderError,
+        _ => KeyStatus::Invalid,
+    };
+    let detail = serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|json| {
+            json.pointer("/error/message")
+                .and_then(|m| m.as_str())
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| format!("HTTP {}", code));
+    ProviderKeyValidation {
+        status,
+        detail: Some(detail),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_provider_responses() {
+        assert_eq!(
+            classify_key_response(200, "{}"),
+            ProviderKeyValidation {
+                status: KeyStatus::Valid,
+                detail: None,
+            }
+        );
+
+        let invalid = classify_key_response(
+            401,
+            r#"{"type":"error","error":{"type":"authentication_error","message":"invalid x-api-key"}}"#,
+        );
+        assert_eq!(invalid.status, KeyStatus::Invalid);
+        assert_eq!(invalid.detail.as_deref(), Some("invalid x-api-key"));
+        assert_eq!(
+            invalid.describe("anthropic"),
+            "anthropic key was rejected (invalid x-api-key)"
+        );
+
+        let throttled = classify_key_response(429, "Too Many Requests");
+        assert_eq!(throttled.status, KeyStatus::RateLimited);
+        assert_eq!(throttled.detail.as_deref(), Some("HTTP 429"));
+
+        assert_eq!(
+            classify_key_response(503, "").status,
+            KeyStatus::ProviderError
+        );
+    }
+
+    #[test]
+    fn only_direct_providers_support_validation() {
+        assert!(supports_key_validation("anthropic"));
+        assert!(supports_key_validation("openai"));
+        assert!(!supports_key_validation("seren"));
+    }
+}
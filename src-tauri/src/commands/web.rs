@@ -1,12 +1,34 @@
 // ABOUTME: Web fetch command for retrieving URL content from public URLs.
 // ABOUTME: Converts HTML to markdown for AI readability, no paid publishers required.
 
-use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Runtime};
 
 /// Maximum content size in bytes (1MB) to prevent context overflow
 const MAX_CONTENT_SIZE: usize = 1024 * 1024;
 
+/// Default cap on raw response bytes read off the wire (10MB). Reading stops
+/// here even if the server keeps sending.
+const MAX_RESPONSE_BYTES: usize = 10 * 1024 * 1024;
+
+/// robots.txt files beyond this are ignored past the cap (RFC 9309 asks
+/// crawlers to parse at least 500KiB).
+const MAX_ROBOTS_BYTES: usize = 512 * 1024;
+
+/// Redirects followed when the caller does not set `max_redirects`
+/// (reqwest's own default).
+pub const DEFAULT_MAX_REDIRECTS: usize = 10;
+
+/// User agent sent when the `webFetchUserAgent` setting is unset.
+pub const DEFAULT_USER_AGENT: &str = "Seren-Desktop/1.0";
+
+/// Renderer setting that overrides `DEFAULT_USER_AGENT` for sites that block
+/// unfamiliar clients.
+const USER_AGENT_SETTING: &str = "webFetchUserAgent";
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct WebFetchResult {
     pub content: String,
@@ -14,6 +36,67 @@ pub struct WebFetchResult {
     pub url: String,
     pub status: u16,
     pub truncated: bool,
+    /// Every URL redirected to, in order; the last entry equals `url`.
+    #[serde(default)]
+    pub redirects: Vec<String>,
+    /// Page metadata, populated in `readability` mode for HTML responses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub byline: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excerpt: Option<String>,
+}
+
+/// How an HTML body is reduced before it reaches the caller. Non-HTML
+/// responses are always returned as-is.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebFetchMode {
+    /// HTML converted to markdown (the historical behavior).
+    #[default]
+    Raw,
+    /// Plain text with every tag stripped.
+    Text,
+    /// Main-article text only, with navigation and page chrome dropped.
+    Readability,
+}
+
+/// Title, byline, and excerpt pulled from a page's `<head>`.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct PageMetadata {
+    title: Option<String>,
+    byline: Option<String>,
+    excerpt: Option<String>,
+}
+
+/// Per-request knobs for `fetch_url`, shared by the command and the local
+/// `seren_web_fetch` tool.
+#[derive(Debug, Default)]
+pub struct WebFetchOptions {
+    pub timeout_ms: Option<u64>,
+    /// Extra request headers. A `User-Agent` entry here wins over the
+    /// configured one.
+    pub headers: Option<HashMap<String, String>>,
+    /// Check the origin's `/robots.txt` and refuse disallowed paths.
+    pub respect_robots: bool,
+    pub mode: WebFetchMode,
+    /// Stop reading after this many body bytes (default and ceiling
+    /// `MAX_RESPONSE_BYTES`). Text is returned with `truncated` set; other
+    /// content that hits the cap is an error.
+    pub max_bytes: Option<usize>,
+    /// Media types the caller accepts, e.g. `text/*` or `application/json`.
+    /// Anything else is rejected before the body is read.
+    pub allowed_content_types: Option<Vec<String>>,
+    /// Fail once a response would redirect more than this many times
+    /// (default `DEFAULT_MAX_REDIRECTS`).
+    pub max_redirects: Option<usize>,
+}
+
+/// The user agent from settings, or `DEFAULT_USER_AGENT`.
+pub fn configured_user_agent<R: Runtime>(app: &AppHandle<R>) -> String {
+    crate::app_settings::app_setting_string(app, USER_AGENT_SETTING)
+        .unwrap_or_else(|| DEFAULT_USER_AGENT.to_string())
 }
 
 /// Fetch content from a public URL and convert HTML to markdown.
@@ -21,13 +104,52 @@ pub struct WebFetchResult {
 /// # Arguments
 /// * `url` - The URL to fetch (must be http or https)
 /// * `timeout_ms` - Request timeout in milliseconds (default: 30000)
+/// * `headers` - Optional extra request headers
+/// * `respect_robots` - Opt-in `/robots.txt` check before fetching
+/// * `mode` - `raw` (default), `text`, or `readability` extraction for HTML
+/// * `max_bytes` - Cap on body bytes read (default and maximum 10MB)
+/// * `allowed_content_types` - Optional media-type allowlist (`text/*` style)
+/// * `max_redirects` - Redirects to follow before failing (default 10)
 ///
 /// # Returns
-/// * `WebFetchResult` with content, content_type, final url, and status code
+/// * `WebFetchResult` with content, content_type, final url, redirect chain,
+///   and status code
 #[tauri::command]
-pub async fn web_fetch(url: String, timeout_ms: Option<u64>) -> Result<WebFetchResult, String> {
+pub async fn web_fetch(
+    app: AppHandle,
+    url: String,
+    timeout_ms: Option<u64>,
+    headers: Option<HashMap<String, String>>,
+    respect_robots: Option<bool>,
+    mode: Option<WebFetchMode>,
+    max_bytes: Option<usize>,
+    allowed_content_types: Option<Vec<String>>,
+    max_redirects: Option<usize>,
+) -> Result<WebFetchResult, String> {
+    let user_agent = configured_user_agent(&app);
+    fetch_url(
+        &url,
+        &user_agent,
+        WebFetchOptions {
+            timeout_ms,
+            headers,
+            respect_robots: respect_robots.unwrap_or(false),
+            mode: mode.unwrap_or_default(),
+            max_bytes,
+            allowed_content_types,
+            max_redirects,
+        },
+    )
+    .await
+}
+
+pub async fn fetch_url(
+    url: &str,
+    user_agent: &str,
+    options: WebFetchOptions,
+) -> Result<WebFetchResult, String> {
     // Validate URL
-    let parsed_url = url::Url::parse(&url).map_err(|e| format!("Invalid URL: {}", e))?;
+    let parsed_url = url::Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
 
     // Only allow http/https
     if !["http", "https"].contains(&parsed_url.scheme()) {
@@ -35,22 +157,56 @@ pub async fn web_fetch(url: String, timeout_ms: Option<u64>) -> Result<WebFetchR
     }
 
     // Build client with timeout and user agent
-    let timeout = std::time::Duration::from_millis(timeout_ms.unwrap_or(30000));
-    let mut headers = HeaderMap::new();
-    headers.insert(USER_AGENT, HeaderValue::from_static("Seren-Desktop/1.0"));
+    let timeout = std::time::Duration::from_millis(options.timeout_ms.unwrap_or(30000));
+    let headers = build_headers(user_agent, options.headers.as_ref())?;
+    let effective_user_agent = headers
+        .get(USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or(DEFAULT_USER_AGENT)
+        .to_string();
+
+    // Record each hop so the caller can see shorteners and auth walls
+    let max_redirects = options.max_redirects.unwrap_or(DEFAULT_MAX_REDIRECTS);
+    let hops = Arc::new(Mutex::new(Vec::<String>::new()));
+    let recorder = hops.clone();
+    let redirect_policy = reqwest::redirect::Policy::custom(move |attempt| {
+        if let Ok(mut hops) = recorder.lock() {
+            hops.push(attempt.url().to_string());
+        }
+        match redirect_refusal(attempt.previous(), attempt.url(), max_redirects) {
+            Some(reason) => attempt.error(reason),
+            None => attempt.follow(),
+        }
+    });
 
     let client = reqwest::Client::builder()
         .timeout(timeout)
         .default_headers(headers)
+        .redirect(redirect_policy)
         .build()
         .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
 
+    if options.respect_robots && !robots_permit(&client, &parsed_url, &effective_user_agent).await {
+        return Err(format!("Fetching {} is disallowed by robots.txt", url));
+    }
+    // Hops taken while fetching robots.txt are not part of the page's chain
+    let take_hops = || std::mem::take(&mut *hops.lock().unwrap_or_else(|e| e.into_inner()));
+    take_hops();
+
     // Fetch URL
-    let response = client
-        .get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {}", e))?;
+    let response = client.get(url).send().await.map_err(|e| {
+        if e.is_redirect() {
+            let reason = std::error::Error::source(&e)
+                .map(|source| source.to_string())
+                .unwrap_or_else(|| e.to_string());
+            let mut chain = vec![url.to_string()];
+            chain.extend(take_hops());
+            format!("{}: {}", reason, chain.join(" -> "))
+        } else {
+            format!("Request failed: {}", e)
+        }
+    })?;
+    let redirects = take_hops();
 
     let status = response.status().as_u16();
     let content_type = response
@@ -63,20 +219,58 @@ pub async fn web_fetch(url: String, timeout_ms: Option<u64>) -> Result<WebFetchR
     // Get the final URL after redirects
     let final_url = response.url().to_string();
 
-    let body = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
+    // Reject unwanted types from the headers alone, before any body bytes.
+    let type_rejected = options
+        .allowed_content_types
+        .as_ref()
+        .is_some_and(|allowed| !content_type_allowed(&content_type, allowed));
+    if type_rejected {
+        return Err(format!(
+            "Disallowed content type: {} is not in the allowlist",
+            content_type
+        ));
+    }
+
+    // A partial binary is useless, so refuse oversized non-text bodies up
+    // front; text is streamed and truncated instead.
+    let max_bytes = response_byte_cap(options.max_bytes);
+    let textual = is_textual(&content_type);
+    let declared_length = response.content_length().unwrap_or(0);
+    if declared_length > max_bytes as u64 && !textual {
+        return Err(format!(
+            "Response too large: {} bytes exceeds the {}-byte limit",
+            declared_length, max_bytes
+        ));
+    }
 
-    // Convert HTML to markdown if content is HTML
+    let (bytes, body_truncated) = read_capped(response, max_bytes).await?;
+    // Without a Content-Length the size is only known once the cap is hit.
+    if body_truncated && !textual {
+        return Err(format!(
+            "Response too large: body exceeds the {}-byte limit",
+            max_bytes
+        ));
+    }
+    let body = String::from_utf8_lossy(&bytes).into_owned();
+
+    // Reduce HTML per the requested mode; other content types pass through
+    let mut metadata = PageMetadata::default();
     let raw_content = if content_type.contains("text/html") {
-        html_to_markdown(&body)
+        match options.mode {
+            WebFetchMode::Raw => html_to_markdown(&body),
+            WebFetchMode::Text => html_to_text(&body),
+            WebFetchMode::Readability => {
+                metadata = extract_metadata(&body);
+                readable_text(&body, metadata.title.as_deref())
+            }
+        }
     } else {
         body
     };
 
     // Truncate content if too large
-    let (content, truncated) = truncate_content(&raw_content, MAX_CONTENT_SIZE);
+    let (content, content_truncated) = truncate_content(&raw_content, MAX_CONTENT_SIZE);
+    let truncated = body_truncated || content_truncated;
 
     // Wrap in content markers for prompt injection protection
     let wrapped_content = wrap_with_markers(&content, &final_url, truncated);
@@ -87,9 +281,360 @@ pub async fn web_fetch(url: String, timeout_ms: Option<u64>) -> Result<WebFetchR
         url: final_url,
         status,
         truncated,
+        redirects,
+        title: metadata.title,
+        byline: metadata.byline,
+        excerpt: metadata.excerpt,
+    })
+}
+
+/// A JSON API response parsed into a structured value.
+#[derive(Debug, Serialize)]
+pub struct WebFetchJsonResult {
+    pub status: u16,
+    /// Final URL after redirects.
+    pub url: String,
+    pub json: serde_json::Value,
+}
+
+/// Call a JSON API and return its parsed response.
+///
+/// # Arguments
+/// * `url` - The URL to call (must be http or https)
+/// * `method` - `GET` (default), `POST`, `PUT`, or `DELETE`
+/// * `headers` - Optional extra request headers
+/// * `body` - Optional JSON request body (not allowed with `GET`)
+/// * `timeout_ms` - Request timeout in milliseconds (default: 30000)
+///
+/// # Returns
+/// * `WebFetchJsonResult` with the status code, final url, and parsed body.
+///   Non-JSON and oversized responses are errors.
+#[tauri::command]
+pub async fn web_fetch_json(
+    app: AppHandle,
+    url: String,
+    method: Option<String>,
+    headers: Option<HashMap<String, String>>,
+    body: Option<serde_json::Value>,
+    timeout_ms: Option<u64>,
+) -> Result<WebFetchJsonResult, String> {
+    let user_agent = configured_user_agent(&app);
+    let method = parse_json_method(method.as_deref())?;
+    if method == reqwest::Method::GET && body.is_some() {
+        return Err("GET requests cannot have a body".to_string());
+    }
+
+    let parsed_url = url::Url::parse(&url).map_err(|e| format!("Invalid URL: {}", e))?;
+    if !["http", "https"].contains(&parsed_url.scheme()) {
+        return Err("Only HTTP/HTTPS URLs are supported".to_string());
+    }
+
+    let mut request_headers =
+        HashMap::from([("Accept".to_string(), "application/json".to_string())]);
+    request_headers.extend(headers.unwrap_or_default());
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_millis(
+            timeout_ms.unwrap_or(30000),
+        ))
+        .default_headers(build_headers(&user_agent, Some(&request_headers))?)
+        .redirect(reqwest::redirect::Policy::limited(DEFAULT_MAX_REDIRECTS))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let mut request = client.request(method, parsed_url);
+    if let Some(body) = &body {
+        request = request.json(body);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+
+    let status = response.status().as_u16();
+    let final_url = response.url().to_string();
+    let content_type = response
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_string();
+    let (bytes, truncated) = read_capped(response, MAX_CONTENT_SIZE).await?;
+    let json = parse_json_body(&bytes, &content_type, truncated)?;
+
+    Ok(WebFetchJsonResult {
+        status,
+        url: final_url,
+        json,
     })
 }
 
+/// The HTTP method for `web_fetch_json`, case-insensitive. Defaults to GET.
+fn parse_json_method(method: Option<&str>) -> Result<reqwest::Method, String> {
+    match method.map(|m| m.trim().to_ascii_uppercase()).as_deref() {
+        None | Some("GET") => Ok(reqwest::Method::GET),
+        Some("POST") => Ok(reqwest::Method::POST),
+        Some("PUT") => Ok(reqwest::Method::PUT),
+        Some("DELETE") => Ok(reqwest::Method::DELETE),
+        Some(other) => Err(format!(
+            "Unsupported method {}: use GET, POST, PUT, or DELETE",
+            other
+        )),
+    }
+}
+
+/// Parse a JSON API response body. A truncated body cannot be valid JSON,
+/// so it is reported as too large rather than as a parse error.
+fn parse_json_body(
+    bytes: &[u8],
+    content_type: &str,
+    truncated: bool,
+) -> Result<serde_json::Value, String> {
+    if truncated {
+        return Err(format!(
+            "JSON response exceeds the {}-byte limit",
+            MAX_CONTENT_SIZE
+        ));
+    }
+    serde_json::from_slice(bytes).map_err(|e| {
+        let text = String::from_utf8_lossy(bytes);
+        let preview = text.trim();
+        format!(
+            "Response is not JSON ({}): {}. Body starts with: {}",
+            if content_type.is_empty() {
+                "no content type"
+            } else {
+                content_type
+            },
+            e,
+            &preview[..preview.floor_char_boundary(200)]
+        )
+    })
+}
+
+/// Why a redirect to `next` must not be followed, given the URLs already
+/// visited (`previous` starts with the original request URL).
+fn redirect_refusal(
+    previous: &[url::Url],
+    next: &url::Url,
+    max_redirects: usize,
+) -> Option<String> {
+    if previous.contains(next) {
+        return Some(format!("Redirect loop detected at {}", next));
+    }
+    if previous.len() > max_redirects {
+        return Some(format!("Too many redirects (limit is {})", max_redirects));
+    }
+    None
+}
+
+/// The body cap for a fetch. Callers may lower it but never raise it past
+/// `MAX_RESPONSE_BYTES`.
+fn response_byte_cap(requested: Option<usize>) -> usize {
+    requested.map_or(MAX_RESPONSE_BYTES, |n| n.min(MAX_RESPONSE_BYTES))
+}
+
+/// Read at most `limit` body bytes. The flag reports whether the server had
+/// more to send.
+async fn read_capped(
+    mut response: reqwest::Response,
+    limit: usize,
+) -> Result<(Vec<u8>, bool), String> {
+    let mut body = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Failed to read response: {}", e))?
+    {
+        let remaining = limit - body.len();
+        if chunk.len() > remaining {
+            body.extend_from_slice(&chunk[..remaining]);
+            return Ok((body, true));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok((body, false))
+}
+
+/// The media type without parameters, lowercased (`Text/HTML; charset=x` →
+/// `text/html`).
+fn media_type(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// Match against an allowlist of exact media types or `type/*` wildcards.
+fn content_type_allowed(content_type: &str, allowed: &[String]) -> bool {
+    let media = media_type(content_type);
+    allowed.iter().any(|entry| {
+        let entry = entry.trim().to_ascii_lowercase();
+        match entry.strip_suffix("/*") {
+            Some(top_level) => media
+                .split_once('/')
+                .is_some_and(|(kind, _)| kind == top_level),
+            None => entry == media || entry == "*/*",
+        }
+    })
+}
+
+/// Whether a truncated prefix of this type is still meaningful to read.
+fn is_textual(content_type: &str) -> bool {
+    let media = media_type(content_type);
+    media.starts_with("text/")
+        || media.ends_with("+json")
+        || media.ends_with("+xml")
+        || matches!(
+            media.as_str(),
+            "application/json" | "application/xml" | "application/javascript"
+        )
+}
+
+/// Default headers for a fetch: the configured user agent, then any
+/// caller-supplied headers (which may replace it).
+fn build_headers(
+    user_agent: &str,
+    extra: Option<&HashMap<String, String>>,
+) -> Result<HeaderMap, String> {
+    let mut headers = HeaderMap::new();
+    let user_agent = HeaderValue::from_str(user_agent)
+        .unwrap_or_else(|_| HeaderValue::from_static(DEFAULT_USER_AGENT));
+    headers.insert(USER_AGENT, user_agent);
+    for (name, value) in extra.into_iter().flatten() {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|e| format!("Invalid header name {:?}: {}", name, e))?;
+        let value = HeaderValue::from_str(value)
+            .map_err(|e| format!("Invalid value for header {}: {}", name, e))?;
+        headers.insert(name, value);
+    }
+    Ok(headers)
+}
+
+/// Fetch the origin's robots.txt and evaluate `url` against it. Only a
+/// successful response is enforced; a missing or unreachable file allows
+/// everything.
+async fn robots_permit(client: &reqwest::Client, url: &url::Url, user_agent: &str) -> bool {
+    let mut robots_url = url.clone();
+    robots_url.set_path("/robots.txt");
+    robots_url.set_query(None);
+    robots_url.set_fragment(None);
+
+    let Ok(response) = client.get(robots_url).send().await else {
+        return true;
+    };
+    if !response.status().is_success() {
+        return true;
+    }
+    let Ok((bytes, _)) = read_capped(response, MAX_ROBOTS_BYTES).await else {
+        return true;
+    };
+    let body = String::from_utf8_lossy(&bytes);
+
+    let mut path = url.path().to_string();
+    if let Some(query) = url.query() {
+        path.push('?');
+        path.push_str(query);
+    }
+    robots_allows(&body, user_agent, &path)
+}
+
+/// Evaluate robots.txt rules (RFC 9309) for `path`. Groups naming our
+/// product token take precedence over `*`; within the chosen groups the
+/// longest matching rule wins, and `Allow` wins a tie.
+fn robots_allows(robots_txt: &str, user_agent: &str, path: &str) -> bool {
+    let product = user_agent
+        .split('/')
+        .next()
+        .unwrap_or(user_agent)
+        .trim()
+        .to_ascii_lowercase();
+
+    // (agents, rules) where a rule is (allow, pattern).
+    let mut groups: Vec<(Vec<String>, Vec<(bool, String)>)> = Vec::new();
+    let mut in_agent_lines = false;
+    for line in robots_txt.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim().to_ascii_lowercase().as_str() {
+            "user-agent" => {
+                if !in_agent_lines {
+                    groups.push((Vec::new(), Vec::new()));
+                }
+                if let Some((agents, _)) = groups.last_mut() {
+                    agents.push(value.to_ascii_lowercase());
+                }
+                in_agent_lines = true;
+            }
+            key @ ("allow" | "disallow") => {
+                in_agent_lines = false;
+                if value.is_empty() {
+                    continue;
+                }
+                if let Some((_, rules)) = groups.last_mut() {
+                    rules.push((key == "allow", value.to_string()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let has_named_group = groups.iter().any(|(agents, _)| agents.contains(&product));
+    let wanted = if has_named_group {
+        product.as_str()
+    } else {
+        "*"
+    };
+    let rules = groups
+        .iter()
+        .filter(|(agents, _)| agents.iter().any(|agent| agent.as_str() == wanted))
+        .flat_map(|(_, rules)| rules);
+
+    let mut best: Option<(usize, bool)> = None;
+    for (allow, pattern) in rules {
+        if !robots_pattern_matches(pattern, path) {
+            continue;
+        }
+        let candidate = (pattern.len(), *allow);
+        best = match best {
+            Some(current) if current.0 > candidate.0 => Some(current),
+            Some(current) if current.0 == candidate.0 => Some((current.0, current.1 || *allow)),
+            _ => Some(candidate),
+        };
+    }
+    best.is_none_or(|(_, allow)| allow)
+}
+
+/// Match a robots.txt path pattern supporting `*` (any run) and a trailing
+/// `$` (end anchor).
+fn robots_pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(stripped) => (stripped, true),
+        None => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    if !path.starts_with(first) {
+        return false;
+    }
+    let mut pos = first.len();
+    let rest: Vec<&str> = parts.collect();
+    for (index, part) in rest.iter().enumerate() {
+        if anchored && index == rest.len() - 1 {
+            return path.len() >= pos + part.len() && path.ends_with(part);
+        }
+        match path[pos..].find(part) {
+            Some(offset) => pos += offset + part.len(),
+            None => return false,
+        }
+    }
+    !anchored || pos == path.len()
+}
+
 /// Convert HTML to markdown using html2md.
 ///
 /// Strips `<script>`, `<style>`, and `<noscript>` blocks first because
@@ -134,6 +679,179 @@ fn strip_scripts_and_styles(html: &str) -> String {
     out.into_owned()
 }
 
+/// Plain-text rendering of an HTML document: scripts and styles dropped,
+/// closing block tags turned into line breaks, entities decoded, and runs of
+/// whitespace collapsed.
+fn html_to_text(html: &str) -> String {
+    use std::sync::OnceLock;
+
+    static BLOCK_BREAK: OnceLock<regex::Regex> = OnceLock::new();
+    static TAG: OnceLock<regex::Regex> = OnceLock::new();
+    let block_break = BLOCK_BREAK.get_or_init(|| {
+        regex::Regex::new(
+            r"(?i)<br\s*/?>|</(p|div|section|article|main|h[1-6]|li|tr|blockquote|pre|table|ul|ol)\s*>",
+        )
+        .expect("block regex compiles")
+    });
+    let tag = TAG.get_or_init(|| regex::Regex::new(r"(?s)<[^>]*>").expect("tag regex compiles"));
+
+    let cleaned = strip_scripts_and_styles(html);
+    let with_breaks = block_break.replace_all(&cleaned, "\n");
+    let stripped = tag.replace_all(&with_breaks, "");
+    collapse_whitespace(&decode_entities(&stripped))
+}
+
+/// Main-article text. Page chrome (`nav`, `header`, `footer`, `aside`,
+/// `form`) is removed, then the largest `<article>` wins, falling back to
+/// `<main>` and finally `<body>`. The title, when known, leads the output.
+fn readable_text(html: &str, title: Option<&str>) -> String {
+    use std::sync::OnceLock;
+
+    static CHROME: OnceLock<[regex::Regex; 5]> = OnceLock::new();
+    static CONTAINERS: OnceLock<[regex::Regex; 3]> = OnceLock::new();
+    let chrome = CHROME.get_or_init(|| {
+        ["nav", "header", "footer", "aside", "form"].map(|name| {
+            regex::Regex::new(&format!(r"(?is)<{name}\b[^>]*>.*?</\s*{name}\s*>"))
+                .expect("chrome regex compiles")
+        })
+    });
+    let containers = CONTAINERS.get_or_init(|| {
+        ["article", "main", "body"].map(|name| {
+            regex::Regex::new(&format!(r"(?is)<{name}\b[^>]*>(.*?)</\s*{name}\s*>"))
+                .expect("container regex compiles")
+        })
+    });
+
+    let mut cleaned = strip_scripts_and_styles(html);
+    for re in chrome.iter() {
+        cleaned = re.replace_all(&cleaned, "").into_owned();
+    }
+    let main = containers
+        .iter()
+        .find_map(|re| {
+            re.captures_iter(&cleaned)
+                .filter_map(|caps| caps.get(1))
+                .max_by_key(|m| m.len())
+                .map(|m| m.as_str().to_string())
+        })
+        .unwrap_or(cleaned);
+
+    let text = html_to_text(&main);
+    match title.filter(|title| !text.starts_with(*title)) {
+        Some(title) => format!("# {title}\n\n{text}"),
+        None => text,
+    }
+}
+
+/// Read title, author, and description from `<meta>` tags (Open Graph
+/// first), falling back to `<title>` for the title.
+fn extract_metadata(html: &str) -> PageMetadata {
+    use std::sync::OnceLock;
+
+    static META: OnceLock<regex::Regex> = OnceLock::new();
+    static ATTR: OnceLock<regex::Regex> = OnceLock::new();
+    static TITLE: OnceLock<regex::Regex> = OnceLock::new();
+    let meta =
+        META.get_or_init(|| regex::Regex::new(r"(?i)<meta\b[^>]*>").expect("meta regex compiles"));
+    let attr = ATTR.get_or_init(|| {
+        regex::Regex::new(r#"(?i)([a-z][a-z0-9:_-]*)\s*=\s*(?:"([^"]*)"|'([^']*)')"#)
+            .expect("attribute regex compiles")
+    });
+    let title_tag = TITLE.get_or_init(|| {
+        regex::Regex::new(r"(?is)<title\b[^>]*>(.*?)</\s*title\s*>").expect("title regex compiles")
+    });
+
+    let mut tags: Vec<(String, String)> = Vec::new();
+    for tag in meta.find_iter(html) {
+        let mut key = None;
+        let mut content = None;
+        for caps in attr.captures_iter(tag.as_str()) {
+            let value = caps
+                .get(2)
+                .or_else(|| caps.get(3))
+                .map_or("", |m| m.as_str());
+            match caps[1].to_ascii_lowercase().as_str() {
+                "name" | "property" => key = Some(value.to_ascii_lowercase()),
+                "content" => content = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        if let (Some(key), Some(content)) = (key, content) {
+            tags.push((key, content));
+        }
+    }
+    let lookup = |keys: &[&str]| {
+        keys.iter().find_map(|wanted| {
+            tags.iter()
+                .find(|(key, _)| key.as_str() == *wanted)
+                .map(|(_, content)| collapse_whitespace(&decode_entities(content)))
+                .filter(|value| !value.is_empty())
+        })
+    };
+
+    PageMetadata {
+        title: lookup(&["og:title", "twitter:title"]).or_else(|| {
+            title_tag
+                .captures(html)
+                .map(|caps| collapse_whitespace(&decode_entities(&caps[1])))
+                .filter(|value| !value.is_empty())
+        }),
+        byline: lookup(&["author", "article:author"]),
+        excerpt: lookup(&["description", "og:description", "twitter:description"]),
+    }
+}
+
+/// Decode the named entities common in page text plus numeric references.
+fn decode_entities(text: &str) -> String {
+    use std::sync::OnceLock;
+
+    static ENTITY: OnceLock<regex::Regex> = OnceLock::new();
+    let entity = ENTITY.get_or_init(|| {
+        regex::Regex::new(r"&(#[0-9]+|#[xX][0-9a-fA-F]+|[a-zA-Z]+);")
+            .expect("entity regex compiles")
+    });
+    entity
+        .replace_all(text, |caps: &regex::Captures| {
+            let name = &caps[1];
+            let decoded = match name {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => name
+                    .strip_prefix("#x")
+                    .or_else(|| name.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16).ok())
+                    .unwrap_or_else(|| name.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            decoded.map_or_else(|| caps[0].to_string(), String::from)
+        })
+        .into_owned()
+}
+
+/// Trim each line, squeeze inner whitespace, and keep at most one blank
+/// line between paragraphs.
+fn collapse_whitespace(text: &str) -> String {
+    let mut out = String::new();
+    let mut blank_run = 0;
+    for line in text.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if line.is_empty() {
+            blank_run += 1;
+            continue;
+        }
+        if !out.is_empty() {
+            out.push_str(if blank_run > 0 { "\n\n" } else { "\n" });
+        }
+        out.push_str(&line);
+        blank_run = 0;
+    }
+    out
+}
+
 /// Truncate content to max size, preserving UTF-8 boundaries.
 fn truncate_content(content: &str, max_size: usize) -> (String, bool) {
     if content.len() <= max_size {
@@ -162,6 +880,41 @@ fn wrap_with_markers(content: &str, url: &str, truncated: bool) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn json_method_parses_case_insensitively_and_rejects_others() {
+        assert_eq!(parse_json_method(None).unwrap(), reqwest::Method::GET);
+        assert_eq!(
+            parse_json_method(Some("post")).unwrap(),
+            reqwest::Method::POST
+        );
+        assert_eq!(
+            parse_json_method(Some(" Put ")).unwrap(),
+            reqwest::Method::PUT
+        );
+        assert_eq!(
+            parse_json_method(Some("DELETE")).unwrap(),
+            reqwest::Method::DELETE
+        );
+        assert!(
+            parse_json_method(Some("PATCH"))
+                .unwrap_err()
+                .contains("PATCH")
+        );
+    }
+
+    #[test]
+    fn json_body_parses_or_explains_why_not() {
+        let json = parse_json_body(br#"{"items": [1, 2]}"#, "application/json", false).unwrap();
+        assert_eq!(json["items"][1], 2);
+
+        let err = parse_json_body(b"<html>Sign in</html>", "text/html", false).unwrap_err();
+        assert!(err.contains("not JSON (text/html)"));
+        assert!(err.contains("<html>Sign in"));
+
+        let err = parse_json_body(br#"{"items": ["#, "application/json", true).unwrap_err();
+        assert!(err.contains("exceeds"));
+    }
+
     #[test]
     fn strip_removes_script_style_and_noscript_with_attributes_and_mixed_case() {
         let html = r#"<html><head>
@@ -209,6 +962,166 @@ mod tests {
         assert!(md.contains("Bermuda is not listed"));
     }
 
+    #[test]
+    fn robots_prefers_specific_group_and_longest_rule() {
+        let robots = "\
+User-agent: *
+Disallow: /private
+Allow: /private/public
+
+User-agent: Seren-Desktop
+Disallow: /no-seren
+";
+        // Our own group replaces the `*` group entirely.
+        assert!(robots_allows(robots, "Seren-Desktop/1.0", "/private/x"));
+        assert!(!robots_allows(
+            robots,
+            "Seren-Desktop/1.0",
+            "/no-seren/page"
+        ));
+
+        let other = "Mozilla/5.0";
+        assert!(!robots_allows(robots, other, "/private/x"));
+        assert!(robots_allows(robots, other, "/private/public/x"));
+        assert!(robots_allows(robots, other, "/"));
+    }
+
+    #[test]
+    fn robots_wildcards_anchors_and_empty_disallow() {
+        assert!(robots_pattern_matches("/*.pdf$", "/docs/a.pdf"));
+        assert!(!robots_pattern_matches("/*.pdf$", "/docs/a.pdf?x=1"));
+        assert!(robots_pattern_matches("/search*q=", "/search?lang=en&q=x"));
+        assert!(robots_pattern_matches("/exact$", "/exact"));
+        assert!(!robots_pattern_matches("/exact$", "/exactly"));
+
+        // An empty Disallow means everything is allowed.
+        assert!(robots_allows("User-agent: *\nDisallow:\n", "Bot", "/any"));
+        // Comments and unknown lines are ignored.
+        assert!(!robots_allows(
+            "# hi\nUser-agent: * # all\nSitemap: https://x/s.xml\nDisallow: /a\n",
+            "Bot",
+            "/a/b"
+        ));
+    }
+
+    #[test]
+    fn caller_headers_override_the_configured_user_agent() {
+        let extra = HashMap::from([
+            ("User-Agent".to_string(), "Custom/2.0".to_string()),
+            ("Accept-Language".to_string(), "en".to_string()),
+        ]);
+        let headers = build_headers(DEFAULT_USER_AGENT, Some(&extra)).unwrap();
+        assert_eq!(headers.get(USER_AGENT).unwrap(), "Custom/2.0");
+        assert_eq!(headers.get("accept-language").unwrap(), "en");
+
+        let bad = HashMap::from([("Bad Header".to_string(), "x".to_string())]);
+        assert!(build_headers(DEFAULT_USER_AGENT, Some(&bad)).is_err());
+    }
+
+    const ARTICLE_PAGE: &str = r#"<html><head>
+        <title>Fallback &amp; Title</title>
+        <meta property="og:title" content="Rust &#8211; Ownership">
+        <meta content="Ada Lovelace" name="author">
+        <meta name="description" content='How borrowing works.'>
+        <script>track()</script>
+        </head><body>
+        <nav><a href="/">Home</a> <a href="/blog">Blog</a></nav>
+        <header>Site banner</header>
+        <article><h1>Ownership</h1><p>Every value has an <b>owner</b>.</p>
+        <p>Borrows&nbsp;are checked.</p></article>
+        <aside>Related posts</aside>
+        <footer>Copyright</footer>
+        </body></html>"#;
+
+    #[test]
+    fn readability_keeps_article_and_drops_chrome() {
+        let metadata = extract_metadata(ARTICLE_PAGE);
+        assert_eq!(
+            metadata,
+            PageMetadata {
+                title: Some("Rust \u{2013} Ownership".to_string()),
+                byline: Some("Ada Lovelace".to_string()),
+                excerpt: Some("How borrowing works.".to_string()),
+            }
+        );
+
+        let text = readable_text(ARTICLE_PAGE, metadata.title.as_deref());
+        assert!(text.starts_with("# Rust \u{2013} Ownership\n\n"), "{text}");
+        assert!(text.contains("Every value has an owner."));
+        assert!(text.contains("Borrows are checked."));
+        for chrome in [
+            "Home",
+            "Site banner",
+            "Related posts",
+            "Copyright",
+            "track()",
+        ] {
+            assert!(!text.contains(chrome), "{chrome} leaked: {text}");
+        }
+    }
+
+    #[test]
+    fn text_mode_strips_tags_and_keeps_paragraph_breaks() {
+        let text = html_to_text(ARTICLE_PAGE);
+        assert!(!text.contains('<'));
+        assert!(text.contains("Ownership\nEvery value has an owner."));
+        assert!(text.contains("Home Blog"));
+        assert!(!text.contains("track()"));
+    }
+
+    #[test]
+    fn title_falls_back_to_title_tag() {
+        let metadata = extract_metadata("<title>\n  Plain &lt;Page&gt;\n</title>");
+        assert_eq!(metadata.title.as_deref(), Some("Plain <Page>"));
+        assert_eq!(metadata.byline, None);
+    }
+
+    #[test]
+    fn decode_entities_leaves_unknown_names() {
+        assert_eq!(decode_entities("&copy; &#x41;&#66; &amp;"), "&copy; AB &");
+    }
+
+    #[test]
+    fn content_type_allowlist_supports_wildcards_and_parameters() {
+        let allowed = vec!["text/*".to_string(), "Application/JSON".to_string()];
+        assert!(content_type_allowed("text/html; charset=utf-8", &allowed));
+        assert!(content_type_allowed("application/json", &allowed));
+        assert!(!content_type_allowed("application/octet-stream", &allowed));
+        assert!(!content_type_allowed("textual/odd", &allowed));
+        assert!(content_type_allowed("image/png", &["*/*".to_string()]));
+        assert!(!content_type_allowed("image/png", &[]));
+    }
+
+    #[test]
+    fn only_text_like_types_are_truncatable() {
+        assert!(is_textual("text/plain"));
+        assert!(is_textual("application/ld+json"));
+        assert!(is_textual("application/JSON; charset=utf-8"));
+        assert!(!is_textual("application/octet-stream"));
+        assert!(!is_textual("image/png"));
+    }
+
+    #[test]
+    fn redirects_are_capped_and_loops_refused() {
+        let url = |path: &str| url::Url::parse(&format!("https://example.test/{path}")).unwrap();
+        let visited = vec![url("start"), url("a"), url("b")];
+
+        assert_eq!(redirect_refusal(&visited, &url("c"), 10), None);
+        assert_eq!(redirect_refusal(&visited[..1], &url("a"), 1), None);
+        assert_eq!(
+            redirect_refusal(&visited, &url("c"), 2).as_deref(),
+            Some("Too many redirects (limit is 2)")
+        );
+        assert_eq!(
+            redirect_refusal(&visited[..1], &url("a"), 0).as_deref(),
+            Some("Too many redirects (limit is 0)")
+        );
+        assert_eq!(
+            redirect_refusal(&visited, &url("a"), 10).as_deref(),
+            Some("Redirect loop detected at https://example.test/a")
+        );
+    }
+
     #[test]
     fn wrap_with_markers_preserves_envelope_shape() {
         let wrapped = wrap_with_markers("body", "https://example.test/", false);
@@ -220,4 +1133,11 @@ mod tests {
         let wrapped_trunc = wrap_with_markers("body", "https://example.test/", true);
         assert!(wrapped_trunc.contains("truncated=\"true\""));
     }
+
+    #[test]
+    fn response_byte_cap_never_exceeds_the_default() {
+        assert_eq!(response_byte_cap(None), MAX_RESPONSE_BYTES);
+        assert_eq!(response_byte_cap(Some(1024)), 1024);
+        assert_eq!(response_byte_cap(Some(usize::MAX)), MAX_RESPONSE_BYTES);
+    }
 }
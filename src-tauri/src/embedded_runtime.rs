@@ -1,10 +1,9 @@
 // ABOUTME: Configures embedded Node.js and Git runtime paths at application startup.
 // ABOUTME: Stores bundled runtime directories for injection into child process environments.
 
-#[cfg(any(target_os = "windows", test))]
 use serde::Serialize;
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 use tauri::{AppHandle, Manager};
 
@@ -12,6 +11,10 @@ use tauri::{AppHandle, Manager};
 /// This is set once during app initialization and read when spawning child processes.
 static EMBEDDED_PATH: OnceLock<String> = OnceLock::new();
 
+/// How long a `--version` style probe may run before it is killed. A hung
+/// binary must not stall build info or runtime verification.
+const PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
 /// Paths to the embedded runtime binaries
 #[derive(Debug, Clone)]
 pub struct EmbeddedRuntimePaths {
@@ -146,16 +149,19 @@ pub fn platform_subdir() -> String {
         "linux"
     };
 
-    let arch = if cfg!(target_arch = "x86_64") {
+    format!("{}-{}", platform, host_arch())
+}
+
+/// Host architecture in Node's `process.arch` spelling ("x64", "arm64").
+fn host_arch() -> &'static str {
+    if cfg!(target_arch = "x86_64") {
         "x64"
     } else if cfg!(target_arch = "aarch64") {
         "arm64"
     } else {
         log::warn!("[EmbeddedRuntime] Unknown target architecture, falling back to x64");
         "x64"
-    };
-
-    format!("{}-{}", platform, arch)
+    }
 }
 
 /// The two directories the embedded runtime is spread across: the
@@ -370,6 +376,215 @@ pub fn system_node_fallback() -> PathBuf {
     PathBuf::from(node_executable_name())
 }
 
+fn git_executable_name() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "git.exe"
+    } else {
+        "git"
+    }
+}
+
+/// Result of actually running one bundled runtime binary.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct RuntimeBinaryCheck {
+    pub component: String,
+    pub path: Option<String>,
+    pub version: Option<String>,
+    pub arch: Option<String>,
+    pub ok: bool,
+    pub error: Option<String>,
+}
+
+/// Structured report from [`verify_runtime`].
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct RuntimeVerification {
+    pub ok: bool,
+    pub platform: String,
+    pub checks: Vec<RuntimeBinaryCheck>,
+}
+
+/// Run a bundled binary with `args` and return its trimmed stdout. A binary
+/// that exists but cannot execute (partial extraction, wrong arch) surfaces
+/// here as a spawn error or non-zero exit rather than later in npm.
+fn probe_binary(binary: &Path, args: &[&str]) -> Result<String, String> {
+    let mut command = std::process::Command::new(binary);
+    command.args(args);
+    run_probe(binary, command)
+}
+
+/// Run `<binary> --version` with the embedded PATH, so agent CLIs that are
+/// node shims resolve the bundled node.
+pub(crate) fn probe_cli_version(binary: &Path) -> Result<String, String> {
+    let mut command = std::process::Command::new(binary);
+    command.arg("--version");
+    let path = get_embedded_path();
+    if !path.is_empty() {
+        command.env("PATH", path);
+    }
+    run_probe(binary, command)
+}
+
+fn run_probe(binary: &Path, command: std::process::Command) -> Result<String, String> {
+    run_probe_with_timeout(binary, command, PROBE_TIMEOUT)
+}
+
+/// Run a probe and collect its output, killing it once `timeout` passes.
+/// Probes print a line or two, so output is read after the process exits.
+fn run_probe_with_timeout(
+    binary: &Path,
+    mut command: std::process::Command,
+    timeout: std::time::Duration,
+) -> Result<String, String> {
+    use std::io::Read;
+    use std::process::Stdio;
+
+    sanitize_spawn_env(&mut command);
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run {}: {}", binary.display(), e))?;
+
+    let deadline = std::time::Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if std::time::Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!(
+                    "{} did not respond within {}s",
+                    binary.display(),
+                    timeout.as_secs()
+                ));
+            }
+            Ok(None) => std::thread::sleep(std::time::Duration::from_millis(25)),
+            Err(e) => return Err(format!("failed to wait for {}: {}", binary.display(), e)),
+        }
+    };
+
+    let mut stdout = String::new();
+    let mut stderr = String::new();
+    if let Some(mut pipe) = child.stdout.take() {
+        let _ = pipe.read_to_string(&mut stdout);
+    }
+    if let Some(mut pipe) = child.stderr.take() {
+        let _ = pipe.read_to_string(&mut stderr);
+    }
+    if !status.success() {
+        return Err(format!(
+            "{} exited with {}: {}",
+            binary.display(),
+            status,
+            stderr.trim()
+        ));
+    }
+    Ok(stdout.trim().to_string())
+}
+
+fn missing_binary_check(component: &str, path: Option<&Path>) -> RuntimeBinaryCheck {
+    RuntimeBinaryCheck {
+        component: component.to_string(),
+        path: path.map(|p| p.to_string_lossy().to_string()),
+        version: None,
+        arch: None,
+        ok: false,
+        error: Some(format!(
+            "bundled {} binary was not found; reinstall the app or run `pnpm prepare:runtime:{}`",
+            component,
+            platform_subdir()
+        )),
+    }
+}
+
+/// Fold the node probes into a check. `process.arch` must match the host so
+/// a cross-arch bundle (x64 node under Rosetta on arm64, say) is caught.
+fn node_binary_check(
+    path: &Path,
+    version: Result<String, String>,
+    arch: Result<String, String>,
+    expected_arch: &str,
+) -> RuntimeBinaryCheck {
+    let mut check = RuntimeBinaryCheck {
+        component: "node".to_string(),
+        path: Some(path.to_string_lossy().to_string()),
+        version: None,
+        arch: None,
+        ok: false,
+        error: None,
+    };
+    match (version, arch) {
+        (Ok(version), Ok(arch)) => {
+            if arch != expected_arch {
+                check.error = Some(format!(
+                    "bundled node is built for {} but this machine is {}",
+                    arch, expected_arch
+                ));
+            } else {
+                check.ok = true;
+            }
+            check.version = Some(version);
+            check.arch = Some(arch);
+        }
+        (Err(error), _) | (_, Err(error)) => check.error = Some(error),
+    }
+    check
+}
+
+fn git_binary_check(path: &Path, version: Result<String, String>) -> RuntimeBinaryCheck {
+    let (version, error) = match version {
+        Ok(version) => (Some(version), None),
+        Err(error) => (None, Some(error)),
+    };
+    RuntimeBinaryCheck {
+        component: "git".to_string(),
+        path: Some(path.to_string_lossy().to_string()),
+        ok: error.is_none(),
+        version,
+        arch: None,
+        error,
+    }
+}
+
+/// Run `node --version`, `node -p process.arch` and `git --version` against
+/// the discovered bundled binaries. Presence alone is not enough: a
+/// half-extracted or wrong-arch node only fails once Codex install or npm
+/// runs, with an error that does not point back at the runtime.
+pub fn verify_runtime(paths: &EmbeddedRuntimePaths) -> RuntimeVerification {
+    let node_check = match embedded_node_binary(paths) {
+        Some(node) => node_binary_check(
+            &node,
+            probe_binary(&node, &["--version"]),
+            probe_binary(&node, &["-p", "process.arch"]),
+            host_arch(),
+        ),
+        None => missing_binary_check("node", paths.node_dir.as_deref()),
+    };
+
+    let git = paths
+        .git_dir
+        .as_ref()
+        .map(|dir| dir.join(git_executable_name()))
+        .filter(|git| git.exists());
+    let git_check = match git {
+        Some(git) => git_binary_check(&git, probe_binary(&git, &["--version"])),
+        None => missing_binary_check("git", paths.git_dir.as_deref()),
+    };
+
+    let checks = vec![node_check, git_check];
+    RuntimeVerification {
+        ok: checks.iter().all(|check| check.ok),
+        platform: platform_subdir(),
+        checks,
+    }
+}
+
 /// Configures the embedded runtime paths.
 /// Computes and stores the PATH with embedded runtime directories prepended.
 /// The computed PATH can be retrieved via `get_embedded_path()` for use when spawning processes.
@@ -804,6 +1019,57 @@ mod tests {
         assert_eq!(embedded_node_binary(&paths), Some(node_path));
     }
 
+    #[test]
+    fn node_check_flags_wrong_arch_and_failed_probes() {
+        let node = Path::new("/runtime/node/bin/node");
+
+        let healthy = node_binary_check(
+            node,
+            Ok("v22.12.0".to_string()),
+            Ok("arm64".to_string()),
+            "arm64",
+        );
+        assert!(healthy.ok);
+        assert_eq!(healthy.version.as_deref(), Some("v22.12.0"));
+
+        let wrong_arch = node_binary_check(
+            node,
+            Ok("v22.12.0".to_string()),
+            Ok("x64".to_string()),
+            "arm64",
+        );
+        assert!(!wrong_arch.ok);
+        assert!(wrong_arch.error.unwrap().contains("built for x64"));
+
+        let broken = node_binary_check(
+            node,
+            Err("exec format error".to_string()),
+            Err("exec format error".to_string()),
+            "arm64",
+        );
+        assert!(!broken.ok);
+        assert_eq!(broken.error.as_deref(), Some("exec format error"));
+    }
+
+    #[test]
+    fn verify_runtime_reports_missing_binaries() {
+        let tmp = tempfile::tempdir().expect("create tempdir");
+        let paths = EmbeddedRuntimePaths {
+            node_dir: Some(tmp.path().join("node")),
+            git_dir: None,
+            bin_dir: None,
+            python_dir: None,
+        };
+
+        let report = verify_runtime(&paths);
+        assert!(!report.ok);
+        assert_eq!(report.checks.len(), 2);
+        assert!(report.checks.iter().all(|check| !check.ok));
+        assert!(report.checks[0].path.is_some());
+        assert_eq!(report.checks[1].path, None);
+        assert!(git_binary_check(Path::new("git"), Ok("git version 2.45.1".into())).ok);
+    }
+
     #[test]
     fn windows_runtime_health_fails_closed_without_node_python_or_playwright() {
         let paths = EmbeddedRuntimePaths {
@@ -892,6 +1158,41 @@ mod tests {
             "sanitize_spawn_env must preserve PLAYWRIGHT_MCP_PING_TIMEOUT_MS for child MCP spawns"
         );
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn probe_is_killed_after_timeout() {
+        let mut command = std::process::Command::new("sh");
+        command.args(["-c", "sleep 30"]);
+        let started = std::time::Instant::now();
+        let err = run_probe_with_timeout(
+            Path::new("sh"),
+            command,
+            std::time::Duration::from_millis(200),
+        )
+        .unwrap_err();
+        assert!(err.contains("did not respond"), "{err}");
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn probe_returns_trimmed_stdout() {
+        let mut command = std::process::Command::new("sh");
+        command.args(["-c", "echo ' 1.2.3 '"]);
+        let version = run_probe_with_timeout(Path::new("sh"), command, PROBE_TIMEOUT).unwrap();
+        assert_eq!(version, "1.2.3");
+    }
+}
+
+/// Tauri command that executes the bundled node/git and reports whether they
+/// actually run on this machine.
+#[tauri::command]
+pub async fn verify_embedded_runtime(app: AppHandle) -> Result<RuntimeVerification, String> {
+    let paths = discover_embedded_runtime(&app);
+    tokio::task::spawn_blocking(move || verify_runtime(&paths))
+        .await
+        .map_err(|e| format!("Runtime verification task failed: {}", e))
 }
 
 /// Tauri command to get embedded runtime information (for debugging/UI)
@@ -1,11 +1,19 @@
 // ABOUTME: File system operations for the editor.
-// ABOUTME: Provides commands for reading, writing, and listing files/directories.
+// ABOUTME: Provides commands for reading, writing, listing, and watching files/directories.
 
 use base64::{engine::general_purpose::STANDARD, Engine};
+use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
-
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender, channel};
+use std::sync::{LazyLock, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+use crate::commands::indexing::compute_file_hash;
 use crate::path_util::expand_tilde;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -15,6 +23,44 @@ pub struct FileEntry {
     pub is_directory: bool,
 }
 
+/// Event emitted when a file added with `watch_file` changes.
+pub const FILE_CHANGED_EVENT: &str = "files://changed";
+
+/// A burst of writes is reported once the file has been quiet this long.
+const WATCH_QUIET_PERIOD: Duration = Duration::from_millis(150);
+
+/// Upper bound on how long a constantly-written file can go unreported.
+const WATCH_MAX_DELAY: Duration = Duration::from_secs(1);
+
+/// Files larger than this are reported without their content.
+const WATCH_CONTENT_LIMIT: u64 = 1024 * 1024;
+
+/// File text plus the hash `write_file` checks against `expected_hash`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HashedFileContent {
+    pub content: String,
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileChangedEvent {
+    pub path: String,
+    /// `modified`, `created` (back after a delete) or `deleted`.
+    pub kind: &'static str,
+    /// New text when the file is UTF-8 and under `WATCH_CONTENT_LIMIT`.
+    pub content: Option<String>,
+}
+
+struct FileWatch {
+    _watcher: RecommendedWatcher,
+    stop_sender: Sender<()>,
+    /// `watch_file` calls not yet matched by `unwatch_file`.
+    refs: usize,
+}
+
+static FILE_WATCHES: LazyLock<Mutex<HashMap<PathBuf, FileWatch>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
 /// Read the contents of a file.
 #[tauri::command]
 pub fn read_file(path: String) -> Result<String, String> {
@@ -31,6 +77,15 @@ pub fn read_file(path: String) -> Result<String, String> {
     fs::read_to_string(&resolved).map_err(|e| format!("Failed to read file: {}", e))
 }
 
+/// Read a file along with its content hash, for a later conflict-checked
+/// `write_file`.
+#[tauri::command]
+pub fn read_file_with_hash(path: String) -> Result<HashedFileContent, String> {
+    let content = read_file(path)?;
+    let hash = compute_file_hash(content.clone());
+    Ok(HashedFileContent { content, hash })
+}
+
 /// Read a file and return its contents as base64.
 #[tauri::command]
 pub fn read_file_base64(path: String) -> Result<String, String> {
@@ -54,16 +109,45 @@ pub fn read_file_base64(path: String) -> Result<String, String> {
 /// (see GH #1595). On Windows an independent `cmd.exe /c if exist` check
 /// also runs to defend against any per-process filesystem view that
 /// might diverge from what a user's Explorer or external shell sees.
+///
+/// With an `expected_hash` (from `read_file_with_hash`), the write is
+/// refused when the file has changed or disappeared since it was read.
 #[tauri::command]
-pub fn write_file(path: String, content: String) -> Result<(), String> {
+pub fn write_file(
+    path: String,
+    content: String,
+    expected_hash: Option<String>,
+) -> Result<(), String> {
     let resolved = expand_tilde(&path)?;
     reject_literal_tilde_segment(&resolved)?;
+    if let Some(expected_hash) = expected_hash {
+        check_unchanged(&resolved, &expected_hash)?;
+    }
     let expected = content.len() as u64;
     fs::write(&resolved, content).map_err(|e| format!("Failed to write file: {}", e))?;
     verify_on_disk(&resolved, expected)?;
     Ok(())
 }
 
+/// Fail with a conflict error unless the file's current content still
+/// hashes to `expected_hash`.
+fn check_unchanged(path: &Path, expected_hash: &str) -> Result<(), String> {
+    let current = fs::read_to_string(path).map_err(|e| {
+        format!(
+            "Write conflict: '{}' could not be re-read before writing ({})",
+            path.display(),
+            e
+        )
+    })?;
+    if compute_file_hash(current) != expected_hash {
+        return Err(format!(
+            "Write conflict: '{}' changed since it was read. Read it again and reapply the edit.",
+            path.display()
+        ));
+    }
+    Ok(())
+}
+
 /// List entries in a directory.
 #[tauri::command]
 pub fn list_directory(path: String) -> Result<Vec<FileEntry>, String> {
@@ -110,22 +194,88 @@ pub fn is_directory(path: String) -> bool {
     expand_tilde(&path).map(|p| p.is_dir()).unwrap_or(false)
 }
 
-/// Create a new file with optional content.
+/// Error from `create_file`, serialized as `{ kind, message }` so callers can
+/// tell an existing file apart from a permission problem.
+#[derive(Debug, thiserror::Error)]
+pub enum CreateFileError {
+    #[error("File already exists: {0}")]
+    AlreadyExists(String),
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+impl CreateFileError {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::AlreadyExists(_) => "AlreadyExists",
+            Self::PermissionDenied(_) => "PermissionDenied",
+            Self::Other(_) => "Other",
+        }
+    }
+
+    fn from_io(path: &Path, context: &str, error: std::io::Error) -> Self {
+        match error.kind() {
+            std::io::ErrorKind::AlreadyExists => Self::AlreadyExists(path.display().to_string()),
+            std::io::ErrorKind::PermissionDenied => {
+                Self::PermissionDenied(format!("{}: {}", path.display(), error))
+            }
+            _ => Self::Other(format!("{}: {}", context, error)),
+        }
+    }
+}
+
+impl Serialize for CreateFileError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("CreateFileError", 2)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
+/// Create a file with optional content. With `exclusive`, fail with
+/// `AlreadyExists` if the path exists instead of truncating it.
 #[tauri::command]
-pub fn create_file(path: String, content: Option<String>) -> Result<(), String> {
-    let resolved = expand_tilde(&path)?;
-    reject_literal_tilde_segment(&resolved)?;
+pub fn create_file(
+    path: String,
+    content: Option<String>,
+    exclusive: Option<bool>,
+) -> Result<(), CreateFileError> {
+    let resolved = expand_tilde(&path).map_err(CreateFileError::Other)?;
+    reject_literal_tilde_segment(&resolved).map_err(CreateFileError::Other)?;
+    write_new_file(
+        &resolved,
+        content.unwrap_or_default().as_bytes(),
+        exclusive.unwrap_or(false),
+    )
+}
+
+fn write_new_file(path: &Path, content: &[u8], exclusive: bool) -> Result<(), CreateFileError> {
+    use std::io::Write;
 
     // Create parent directories if they don't exist
-    if let Some(parent) = resolved.parent() {
-        fs::create_dir_all(parent).map_err(|e| format!("Failed to create directories: {}", e))?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| CreateFileError::from_io(parent, "Failed to create directories", e))?;
     }
 
-    let content = content.unwrap_or_default();
-    let expected = content.len() as u64;
-    fs::write(&resolved, content).map_err(|e| format!("Failed to create file: {}", e))?;
-    verify_on_disk(&resolved, expected)?;
-    Ok(())
+    let mut options = fs::OpenOptions::new();
+    options.write(true);
+    if exclusive {
+        options.create_new(true);
+    } else {
+        options.create(true).truncate(true);
+    }
+    let mut file = options
+        .open(path)
+        .map_err(|e| CreateFileError::from_io(path, "Failed to create file", e))?;
+    file.write_all(content)
+        .map_err(|e| CreateFileError::from_io(path, "Failed to create file", e))?;
+    drop(file);
+    verify_on_disk(path, content.len() as u64).map_err(CreateFileError::Other)
 }
 
 /// Create a new directory.
@@ -148,6 +298,123 @@ pub fn delete_path(path: String) -> Result<(), String> {
     }
 }
 
+/// Outcome of `trash_path`.
+#[derive(Debug, Serialize)]
+pub struct TrashResult {
+    /// False when the trash was unavailable and the path was deleted
+    /// permanently instead.
+    pub trashed: bool,
+    /// Why the trash was skipped, when it was.
+    pub message: Option<String>,
+}
+
+/// Move a file or directory to the OS trash so the delete can be undone.
+///
+/// Only when `volume_without_trash` finds no trash on the path's volume does
+/// it fall back to `delete_path` (which refuses non-empty directories) and
+/// report it. Every trash failure is returned so nothing is deleted
+/// permanently by surprise.
+#[tauri::command]
+pub fn trash_path(path: String) -> Result<TrashResult, String> {
+    let resolved = expand_tilde(&path)?;
+    if !resolved.exists() {
+        return Err(format!("Path does not exist: {}", path));
+    }
+
+    if let Some(reason) = volume_without_trash(&resolved) {
+        log::warn!(
+            "[Files] Trash unavailable for {}, deleting permanently: {}",
+            resolved.display(),
+            reason
+        );
+        delete_path(path)?;
+        return Ok(TrashResult {
+            trashed: false,
+            message: Some(format!("Trash unavailable ({reason}); deleted permanently")),
+        });
+    }
+
+    trash::delete(&resolved).map_err(|e| format!("Failed to move to trash: {}", e))?;
+    Ok(TrashResult {
+        trashed: true,
+        message: None,
+    })
+}
+
+/// Why the volume holding `path` has no trash, when it has none. On Linux
+/// and the BSDs that is a volume other than the home one whose top directory
+/// has no `.Trash` or `.Trash-$uid` and is not writable to create one
+/// (read-only media, mounts owned by another user).
+#[cfg(all(unix, not(target_os = "macos")))]
+fn volume_without_trash(path: &Path) -> Option<String> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::fs::MetadataExt;
+
+    let parent = path.parent()?.canonicalize().ok()?;
+    let device = fs::metadata(&parent).ok()?.dev();
+    let home = dirs::home_dir()?;
+    if fs::metadata(home).is_ok_and(|home| home.dev() == device) {
+        return None;
+    }
+    let mut top = parent.clone();
+    for ancestor in parent.ancestors().skip(1) {
+        if !fs::metadata(ancestor).is_ok_and(|meta| meta.dev() == device) {
+            break;
+        }
+        top = ancestor.to_path_buf();
+    }
+    // SAFETY: getuid has no preconditions and cannot fail.
+    let uid = unsafe { libc::getuid() };
+    if top.join(".Trash").is_dir() || top.join(format!(".Trash-{uid}")).is_dir() {
+        return None;
+    }
+    let top_c = CString::new(top.as_os_str().as_bytes()).ok()?;
+    // SAFETY: `top_c` is a valid NUL-terminated path for the call's duration.
+    if unsafe { libc::access(top_c.as_ptr(), libc::W_OK) } == 0 {
+        return None;
+    }
+    Some(format!("the volume at {} has no trash", top.display()))
+}
+
+/// Why the volume holding `path` has no trash, when it has none. On macOS
+/// that is a network volume, which has no Trash.
+#[cfg(target_os = "macos")]
+fn volume_without_trash(path: &Path) -> Option<String> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path_c = CString::new(path.as_os_str().as_bytes()).ok()?;
+    // SAFETY: statfs only writes into the zeroed struct we own.
+    let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statfs(path_c.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    (stat.f_flags & libc::MNT_LOCAL == 0).then(|| "network volumes have no Trash".to_string())
+}
+
+/// Why the volume holding `path` has no trash, when it has none. On Windows
+/// that is a network drive, which has no Recycle Bin.
+#[cfg(windows)]
+fn volume_without_trash(path: &Path) -> Option<String> {
+    use std::os::windows::ffi::OsStrExt;
+    use std::path::Component;
+    use windows::Win32::Storage::FileSystem::GetDriveTypeW;
+    use windows::core::PCWSTR;
+
+    /// `DRIVE_REMOTE` from winbase.h.
+    const DRIVE_REMOTE: u32 = 4;
+
+    let root: PathBuf = path
+        .components()
+        .take_while(|component| matches!(component, Component::Prefix(_) | Component::RootDir))
+        .collect();
+    let wide: Vec<u16> = root.as_os_str().encode_wide().chain(Some(0)).collect();
+    // SAFETY: `wide` is a NUL-terminated UTF-16 path for the call's duration.
+    let kind = unsafe { GetDriveTypeW(PCWSTR(wide.as_ptr())) };
+    (kind == DRIVE_REMOTE).then(|| "network drives have no Recycle Bin".to_string())
+}
+
 /// Rename/move a file or directory.
 #[tauri::command]
 pub fn rename_path(old_path: String, new_path: String) -> Result<(), String> {
@@ -185,6 +452,166 @@ pub fn open_path_with_default_app(app: tauri::AppHandle, path: String) -> Result
         .map_err(|e| format!("Failed to open file with default app: {}", e))
 }
 
+/// Watch a single file and emit `files://changed` when it changes.
+///
+/// The parent directory is watched rather than the file itself, so editors
+/// that save by writing a temp file and renaming it over the original keep
+/// being tracked. Watches are counted per file: watching an already-watched
+/// file only adds a reference. Returns the canonical path that events report,
+/// which is also the key `unwatch_file` takes.
+#[tauri::command]
+pub fn watch_file(app: AppHandle, path: String) -> Result<String, String> {
+    let resolved = expand_tilde(&path)?;
+    if !resolved.is_file() {
+        return Err(format!("File does not exist: {}", path));
+    }
+    let target = watch_key(&resolved)?;
+
+    let mut watches = FILE_WATCHES
+        .lock()
+        .map_err(|e| format!("Failed to lock file watches: {}", e))?;
+    let watched_path = target.to_string_lossy().to_string();
+    if let Some(watch) = watches.get_mut(&target) {
+        watch.refs += 1;
+        return Ok(watched_path);
+    }
+
+    let (stop_tx, stop_rx) = channel::<()>();
+    let (event_tx, event_rx) = channel::<()>();
+    let matched = target.clone();
+    let mut watcher = RecommendedWatcher::new(
+        move |res: notify::Result<Event>| {
+            if let Ok(event) = res
+                && event.paths.iter().any(|p| p == &matched)
+            {
+                let _ = event_tx.send(());
+            }
+        },
+        Config::default(),
+    )
+    .map_err(|e| format!("Failed to create watcher: {}", e))?;
+    let parent = target.parent().unwrap_or(Path::new("/"));
+    watcher
+        .watch(parent, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch path: {}", e))?;
+
+    let watched = target.clone();
+    thread::spawn(move || {
+        run_file_watch(&watched, event_rx, stop_rx, |event| {
+            let _ = app.emit(FILE_CHANGED_EVENT, event);
+        });
+    });
+
+    watches.insert(
+        target,
+        FileWatch {
+            _watcher: watcher,
+            stop_sender: stop_tx,
+            refs: 1,
+        },
+    );
+    Ok(watched_path)
+}
+
+/// Drop one reference to a file watch; the watch stops with the last one.
+/// `path` is the key `watch_file` returned, which keeps working after the
+/// file's directory is gone. Other spellings of the path are resolved as a
+/// fallback. Returns false when the file was not being watched.
+#[tauri::command]
+pub fn unwatch_file(path: String) -> Result<bool, String> {
+    let mut watches = FILE_WATCHES
+        .lock()
+        .map_err(|e| format!("Failed to lock file watches: {}", e))?;
+    let key = PathBuf::from(&path);
+    let target = if watches.contains_key(&key) {
+        key
+    } else {
+        match expand_tilde(&path).and_then(|resolved| watch_key(&resolved)) {
+            Ok(target) => target,
+            Err(_) => return Ok(false),
+        }
+    };
+    let Some(watch) = watches.get_mut(&target) else {
+        return Ok(false);
+    };
+    watch.refs = watch.refs.saturating_sub(1);
+    if watch.refs == 0
+        && let Some(watch) = watches.remove(&target)
+    {
+        let _ = watch.stop_sender.send(());
+    }
+    Ok(true)
+}
+
+/// Canonical path used to key and match a watch. Only the parent is
+/// canonicalized so the key stays valid while the file itself is missing.
+fn watch_key(path: &Path) -> Result<PathBuf, String> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| format!("Not a file path: {}", path.display()))?;
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let parent = fs::canonicalize(parent)
+        .map_err(|e| format!("Failed to resolve {}: {}", parent.display(), e))?;
+    Ok(parent.join(name))
+}
+
+/// Coalesce change signals for `path`, which existed when the watch began,
+/// and report each settled change. Whether the file exists is checked only
+/// after the burst settles, so a delete-and-recreate swap is reported as a
+/// single modification.
+fn run_file_watch(
+    path: &Path,
+    events: Receiver<()>,
+    stop: Receiver<()>,
+    emit: impl Fn(FileChangedEvent),
+) {
+    let mut existed = true;
+    while stop.try_recv().is_err() {
+        match events.recv_timeout(Duration::from_millis(100)) {
+            Ok(()) => {
+                let burst_start = Instant::now();
+                while burst_start.elapsed() < WATCH_MAX_DELAY
+                    && events.recv_timeout(WATCH_QUIET_PERIOD).is_ok()
+                {}
+                if stop.try_recv().is_ok() {
+                    break;
+                }
+
+                let exists = path.is_file();
+                let kind = match (existed, exists) {
+                    (true, true) => "modified",
+                    (false, true) => "created",
+                    (true, false) => "deleted",
+                    (false, false) => continue,
+                };
+                existed = exists;
+                emit(FileChangedEvent {
+                    path: path.to_string_lossy().to_string(),
+                    kind,
+                    content: if exists {
+                        read_watched_content(path)
+                    } else {
+                        None
+                    },
+                });
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+}
+
+fn read_watched_content(path: &Path) -> Option<String> {
+    let metadata = fs::metadata(path).ok()?;
+    if metadata.len() > WATCH_CONTENT_LIMIT {
+        return None;
+    }
+    fs::read_to_string(path).ok()
+}
+
 /// Defence-in-depth guard (GH #1584): reject writes whose resolved path
 /// still contains a literal `~` segment. Before GH #1583 landed, an
 /// unexpanded `~/foo` would fall back to `Path::new("~/foo")` and resolve
@@ -344,7 +771,7 @@ mod tests {
         let file_rel = format!("~/{}/hello.txt", unique);
 
         create_directory(dir_rel.clone()).expect("create_directory");
-        write_file(file_rel.clone(), "hi".to_string()).expect("write_file");
+        write_file(file_rel.clone(), "hi".to_string(), None).expect("write_file");
 
         let expected = home.join(&unique).join("hello.txt");
         assert!(
@@ -381,6 +808,27 @@ mod tests {
         assert!(reject_literal_tilde_segment(Path::new("/tmp/~foo.txt")).is_ok());
     }
 
+    #[test]
+    fn exclusive_create_refuses_to_clobber_an_existing_file() {
+        let dir =
+            std::env::temp_dir().join(format!("serendesktop-create-{}", Uuid::new_v4().simple()));
+        let path = dir.join("notes.txt");
+
+        write_new_file(&path, b"first", true).expect("new file is created");
+        let err = write_new_file(&path, b"second", true).expect_err("existing file is kept");
+        assert!(matches!(err, CreateFileError::AlreadyExists(_)));
+        assert_eq!(
+            serde_json::to_value(&err).unwrap()["kind"],
+            serde_json::json!("AlreadyExists")
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "first");
+
+        write_new_file(&path, b"second", false).expect("non-exclusive truncates");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "second");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     /// GH #1595 critical contract: `verify_on_disk` must refuse to report
     /// success when the file is missing, and must refuse when the on-disk
     /// size disagrees with what the caller claimed to write.
@@ -419,6 +867,111 @@ mod tests {
         let _ = std::fs::remove_file(&tmp);
     }
 
+    fn watch_events(path: &Path, signal: impl FnOnce(&Sender<()>)) -> Vec<FileChangedEvent> {
+        let (event_tx, event_rx) = channel();
+        let (stop_tx, stop_rx) = channel();
+        let emitted = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let sink = emitted.clone();
+        let watched = path.to_path_buf();
+        let handle = thread::spawn(move || {
+            run_file_watch(&watched, event_rx, stop_rx, |event| {
+                sink.lock().unwrap().push(event)
+            });
+        });
+        signal(&event_tx);
+        thread::sleep(WATCH_QUIET_PERIOD * 4);
+        let _ = stop_tx.send(());
+        handle.join().unwrap();
+        emitted.lock().unwrap().clone()
+    }
+
+    /// A rename-swap save (delete, recreate, several writes) must settle
+    /// into one `modified` event carrying the final content.
+    #[test]
+    fn watch_coalesces_a_rename_swap_into_one_change() {
+        let dir = std::env::temp_dir().join(format!("serendesktop-watch-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("notes.md");
+        std::fs::write(&file, "v1").unwrap();
+
+        let events = watch_events(&file, |tx| {
+            std::fs::write(dir.join("notes.md.tmp"), "v2").unwrap();
+            std::fs::remove_file(&file).unwrap();
+            tx.send(()).unwrap();
+            std::fs::rename(dir.join("notes.md.tmp"), &file).unwrap();
+            tx.send(()).unwrap();
+            tx.send(()).unwrap();
+        });
+        assert_eq!(events.len(), 1, "{events:?}");
+        assert_eq!(events[0].kind, "modified");
+        assert_eq!(events[0].content.as_deref(), Some("v2"));
+
+        let events = watch_events(&file, |tx| {
+            std::fs::remove_file(&file).unwrap();
+            tx.send(()).unwrap();
+        });
+        assert_eq!(events.len(), 1, "{events:?}");
+        assert_eq!(events[0].kind, "deleted");
+        assert!(events[0].content.is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn unwatch_releases_references_by_key_after_the_directory_is_gone() {
+        let dir = std::env::temp_dir().join(format!("serendesktop-unwatch-{}", Uuid::new_v4()));
+        let key = dir.join("notes.md");
+        let (stop_tx, stop_rx) = channel();
+        let watcher = RecommendedWatcher::new(|_: notify::Result<Event>| {}, Config::default())
+            .expect("watcher");
+        FILE_WATCHES.lock().unwrap().insert(
+            key.clone(),
+            FileWatch {
+                _watcher: watcher,
+                stop_sender: stop_tx,
+                refs: 2,
+            },
+        );
+        let path = key.to_string_lossy().to_string();
+
+        assert!(unwatch_file(path.clone()).unwrap());
+        assert!(FILE_WATCHES.lock().unwrap().contains_key(&key));
+        assert!(stop_rx.try_recv().is_err(), "still referenced");
+
+        assert!(unwatch_file(path.clone()).unwrap());
+        assert!(!FILE_WATCHES.lock().unwrap().contains_key(&key));
+        assert!(stop_rx.try_recv().is_ok(), "last reference stops the watch");
+
+        assert!(!unwatch_file(path).unwrap());
+    }
+
+    #[test]
+    fn unwatching_an_unknown_file_is_a_no_op() {
+        let missing = std::env::temp_dir().join(format!("serendesktop-missing-{}", Uuid::new_v4()));
+        assert!(watch_key(&missing).is_ok(), "key must not need the file");
+        assert!(!unwatch_file(missing.to_string_lossy().to_string()).unwrap());
+    }
+
+    #[test]
+    fn write_file_rejects_stale_expected_hash() {
+        let tmp =
+            std::env::temp_dir().join(format!("serendesktop-hash-{}.txt", Uuid::new_v4().simple()));
+        let path = tmp.to_string_lossy().to_string();
+        std::fs::write(&tmp, "one").expect("seed write");
+
+        let read = read_file_with_hash(path.clone()).expect("read");
+        assert_eq!(read.content, "one");
+        write_file(path.clone(), "two".to_string(), Some(read.hash.clone()))
+            .expect("unchanged file accepts the write");
+
+        // The file changed after `read`, so its hash is stale now.
+        let err = write_file(path.clone(), "three".to_string(), Some(read.hash))
+            .expect_err("stale hash must be rejected");
+        assert!(err.contains("Write conflict"), "got: {err}");
+        assert_eq!(std::fs::read_to_string(&tmp).unwrap(), "two");
+        let _ = std::fs::remove_file(&tmp);
+    }
+
     /// GH #1595 Windows contract: the cross-process `cmd.exe` probe must
     /// agree with the Rust-side stat for a file that genuinely exists on
     /// disk. If this ever diverges on real hardware we've reproduced the
@@ -447,4 +1000,13 @@ mod tests {
             "Windows xproc err should flag MISSING, got: {err}"
         );
     }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    #[test]
+    fn writable_volumes_keep_the_trash() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("note.txt");
+        fs::write(&file, "x").unwrap();
+        assert_eq!(volume_without_trash(&file), None);
+    }
 }
@@ -13,8 +13,10 @@ pub mod commands {
     pub mod claude_memory;
     pub mod cli_installer;
     pub mod context_intelligence;
+    pub mod conversation_export;
     pub mod conversation_search;
     pub mod credential_lease;
+    pub mod diagnostics;
     pub mod employees_archive;
     pub mod gateway_http;
     pub mod happy_bridge;
@@ -23,6 +25,7 @@ pub mod commands {
     pub mod memory;
     pub mod model_context_cache;
     pub mod orchestrator;
+    pub mod provider_keys;
     pub mod provider_runtime;
     pub mod recording;
     pub mod sandbox;
@@ -46,6 +49,7 @@ pub mod services {
 
 pub mod sandbox;
 
+mod app_settings;
 pub mod approval_continuation;
 pub mod audio;
 mod auth;
@@ -71,6 +75,7 @@ mod polymarket;
 mod provider_runtime;
 mod secret_broker;
 mod shell;
+mod shell_policy;
 mod skills;
 mod support;
 mod sync;
@@ -86,11 +91,6 @@ const REFRESH_TOKEN_KEY: &str = "refresh_token";
 const PROVIDERS_STORE: &str = "providers.json";
 const OAUTH_STORE: &str = "oauth.json";
 
-#[tauri::command]
-fn greet(name: &str) -> String {
-    format!("Hello, {}! You've been greeted from Rust!", name)
-}
-
 /// Fetch a URL with Bearer auth and return the redirect Location header.
 /// Used for OAuth authorize endpoints that return 302 redirects.
 #[tauri::command]
@@ -214,7 +214,7 @@ fn get_setting(
     let store_handle = app.store(&store).map_err(|e| e.to_string())?;
     let value = store_handle
         .get(&key)
-        .and_then(|v| v.as_str().map(|s| s.to_string()));
+        .and_then(|v| app_settings::setting_as_string(&v));
     Ok(value)
 }
 
@@ -232,11 +232,75 @@ fn set_setting(
 }
 
 #[tauri::command]
-fn store_provider_key(
+fn get_setting_json(
+    app: tauri::AppHandle,
+    store: String,
+    key: String,
+) -> Result<Option<serde_json::Value>, String> {
+    let store_handle = app.store(&store).map_err(|e| e.to_string())?;
+    Ok(store_handle.get(&key))
+}
+
+#[tauri::command]
+fn set_setting_json(
+    app: tauri::AppHandle,
+    store: String,
+    key: String,
+    value: serde_json::Value,
+) -> Result<(), String> {
+    let store_handle = app.store(&store).map_err(|e| e.to_string())?;
+    store_handle.set(&key, value);
+    store_handle.save().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+fn get_setting_bool(
+    app: tauri::AppHandle,
+    store: String,
+    key: String,
+) -> Result<Option<bool>, String> {
+    let store_handle = app.store(&store).map_err(|e| e.to_string())?;
+    Ok(store_handle
+        .get(&key)
+        .and_then(|v| app_settings::setting_as_bool(&v)))
+}
+
+#[tauri::command]
+fn get_setting_number(
+    app: tauri::AppHandle,
+    store: String,
+    key: String,
+) -> Result<Option<f64>, String> {
+    let store_handle = app.store(&store).map_err(|e| e.to_string())?;
+    Ok(store_handle
+        .get(&key)
+        .and_then(|v| app_settings::setting_as_number(&v)))
+}
+
+/// Store a provider API key, trimmed as `validate_provider_key` checks it.
+/// With `validate`, a key the provider rejects is refused; a check that
+/// cannot reach a verdict (rate limit, outage, no network) still stores it.
+#[tauri::command]
+async fn store_provider_key(
     app: tauri::AppHandle,
     provider: String,
     api_key: String,
+    validate: Option<bool>,
 ) -> Result<(), String> {
+    let api_key = api_key.trim().to_string();
+    if validate.unwrap_or(false) && commands::provider_keys::supports_key_validation(&provider) {
+        let validation =
+            commands::provider_keys::validate_provider_key(provider.clone(), api_key.clone())
+                .await?;
+        match validation.status {
+            commands::provider_keys::KeyStatus::Valid => {}
+            commands::provider_keys::KeyStatus::Invalid => {
+                return Err(validation.describe(&provider));
+            }
+            _ => log::warn!("{}; storing anyway", validation.describe(&provider)),
+        }
+    }
     let store = app.store(PROVIDERS_STORE).map_err(|e| e.to_string())?;
     store.set(&provider, serde_json::json!(api_key));
     store.save().map_err(|e| e.to_string())?;
@@ -304,6 +368,20 @@ fn clear_oauth_credentials(app: tauri::AppHandle, provider: String) -> Result<()
     Ok(())
 }
 
+/// Revoke a provider's OAuth token with the provider, then delete the local
+/// copy. Local credentials are removed even when revocation is unsupported
+/// or fails; the result says which happened.
+#[tauri::command]
+async fn revoke_oauth_credentials(
+    app: tauri::AppHandle,
+    provider: String,
+) -> Result<oauth::OAuthRevocation, String> {
+    let credentials = get_oauth_credentials(app.clone(), provider.clone())?;
+    let revocation = oauth::revoke_provider_token(&provider, credentials.as_deref()).await;
+    clear_oauth_credentials(app, provider)?;
+    Ok(revocation)
+}
+
 #[tauri::command]
 fn get_oauth_providers(app: tauri::AppHandle) -> Result<Vec<String>, String> {
     let store = app.store(OAUTH_STORE).map_err(|e| e.to_string())?;
@@ -384,13 +462,28 @@ fn redact_auth_url(url: &str) -> String {
     result
 }
 
+/// Generate a PKCE pair for an OAuth flow identified by `state`.
+/// Only the challenge is returned; the verifier is held in Rust and attached
+/// to the matching callback by `start_oauth_browser_flow`.
+#[tauri::command]
+fn begin_oauth_pkce(
+    pkce: tauri::State<'_, oauth::PkceState>,
+    state: String,
+) -> Result<oauth::PkceChallenge, String> {
+    pkce.begin(&state)
+}
+
 /// Start OAuth flow with browser and loopback server.
 /// Opens the auth URL in the default browser and starts a local server to receive the callback.
-/// The auth_url must already contain a redirect_uri with the port to listen on.
-/// Returns the authorization code and state from the callback.
+/// The auth_url must already contain a redirect_uri with the port to listen on
+/// and a `state`; only a callback carrying that state completes the flow.
+/// Returns the authorization code and state from the callback, plus the PKCE
+/// verifier when one was registered for that state via `begin_oauth_pkce`.
 #[tauri::command]
 async fn start_oauth_browser_flow(
     app: tauri::AppHandle,
+    pkce: tauri::State<'_, oauth::PkceState>,
+    flows: tauri::State<'_, oauth::OAuthFlows>,
     auth_url: String,
     timeout_secs: Option<u64>,
 ) -> Result<oauth::OAuthCallbackResult, String> {
@@ -401,23 +494,32 @@ async fn start_oauth_browser_flow(
     // Extract the port from the redirect_uri in the auth URL
     // The frontend already registered the client with this redirect_uri, so we must use the same port
     let port = extract_port_from_redirect_uri(&auth_url)?;
+    let state = extract_state_from_auth_url(&auth_url).unwrap_or_default();
+    let cancelled = flows.begin(&state, port)?;
 
     info!("[OAuth] Starting browser flow on port: {}", port);
     info!("[OAuth] Auth URL: {}", redact_auth_url(&auth_url));
 
     // Open the browser with the original auth URL (don't modify it)
-    app.opener()
-        .open_url(&auth_url, None::<&str>)
-        .map_err(|e| format!("Failed to open browser: {}", e))?;
+    if let Err(e) = app.opener().open_url(&auth_url, None::<&str>) {
+        flows.finish(&state);
+        return Err(format!("Failed to open browser: {}", e));
+    }
 
     // Wait for the callback on the specified port
-    let result =
-        tokio::task::spawn_blocking(move || oauth::wait_for_oauth_callback_on_port(port, timeout))
-            .await
-            .map_err(|e| format!("Task join error: {}", e))??;
+    let expected_state = state.clone();
+    let waited = tokio::task::spawn_blocking(move || {
+        oauth::wait_for_oauth_callback_on_port(port, timeout, &expected_state, &cancelled)
+    })
+    .await;
+    flows.finish(&state);
+    let result = waited.map_err(|e| format!("Task join error: {}", e))??;
 
     match result {
-        Ok(callback) => Ok(callback),
+        Ok(mut callback) => {
+            callback.code_verifier = pkce.take(&callback.state);
+            Ok(callback)
+        }
         Err(oauth_error) => Err(format!(
             "OAuth error: {} - {}",
             oauth_error.error,
@@ -426,7 +528,32 @@ async fn start_oauth_browser_flow(
     }
 }
 
+/// Abandon a pending browser flow (e.g. the user closed the sign-in tab), so
+/// its callback port is released and its PKCE verifier dropped. Returns
+/// false when no flow with that state is pending.
+#[tauri::command]
+fn cancel_oauth_flow(
+    pkce: tauri::State<'_, oauth::PkceState>,
+    flows: tauri::State<'_, oauth::OAuthFlows>,
+    state: String,
+) -> bool {
+    pkce.take(&state);
+    flows.cancel(&state)
+}
+
+/// The `state` query parameter of an OAuth authorize URL.
+fn extract_state_from_auth_url(auth_url: &str) -> Option<String> {
+    url::Url::parse(auth_url)
+        .ok()?
+        .query_pairs()
+        .find(|(key, _)| key == "state")
+        .map(|(_, value)| value.into_owned())
+        .filter(|state| !state.is_empty())
+}
+
 /// Extract the port number from the redirect_uri parameter in an OAuth URL.
+/// Accepts the loopback spellings providers register: `127.0.0.1`,
+/// `localhost`, and `[::1]`.
 fn extract_port_from_redirect_uri(auth_url: &str) -> Result<u16, String> {
     // Find redirect_uri parameter
     let re = regex::Regex::new(r"redirect_uri=([^&]+)").map_err(|e| e.to_string())?;
@@ -438,13 +565,15 @@ fn extract_port_from_redirect_uri(auth_url: &str) -> Result<u16, String> {
     let encoded_uri = captures.get(1).ok_or("No redirect_uri value")?.as_str();
     let decoded_uri = urlencoding::decode(encoded_uri).map_err(|e| e.to_string())?;
 
-    // Extract port from URI like http://127.0.0.1:58688/oauth/callback
+    // Extract port from URIs like http://127.0.0.1:58688/oauth/callback,
+    // http://localhost:58688/oauth/callback or http://[::1]:58688/oauth/callback
     let port_re =
-        regex::Regex::new(r"127\.0\.0\.1:(\d+)").map_err(|e| format!("Regex error: {}", e))?;
+        regex::Regex::new(r"^https?://(?:127\.0\.0\.1|localhost|\[::1\]):(\d+)(?:[/?#]|$)")
+            .map_err(|e| format!("Regex error: {}", e))?;
 
     let port_captures = port_re
         .captures(&decoded_uri)
-        .ok_or("No port found in redirect_uri")?;
+        .ok_or("No loopback port found in redirect_uri")?;
 
     let port_str = port_captures.get(1).ok_or("No port value")?.as_str();
     port_str
@@ -452,10 +581,33 @@ fn extract_port_from_redirect_uri(auth_url: &str) -> Result<u16, String> {
         .map_err(|e| format!("Invalid port: {}", e))
 }
 
+/// Optional pinned OAuth callback port range from Settings.
+fn oauth_callback_port_range(app: &tauri::AppHandle) -> Option<std::ops::RangeInclusive<u16>> {
+    let min = app_settings::app_setting_u64(app, "oauthCallbackPortMin")?;
+    let max = app_settings::app_setting_u64(app, "oauthCallbackPortMax").unwrap_or(min);
+    let min = u16::try_from(min).ok().filter(|port| *port > 0)?;
+    let max = u16::try_from(max).ok()?;
+    if max < min {
+        log::warn!(
+            "[OAuth] Ignoring invalid callback port range {}-{}",
+            min,
+            max
+        );
+        return None;
+    }
+    Some(min..=max)
+}
+
 /// Get an available port for OAuth callback server.
+/// Honors the `oauthCallbackPortMin`/`oauthCallbackPortMax` settings when set.
+/// Ports held by other pending flows are skipped, and the returned port is
+/// reserved for the flow about to start.
 #[tauri::command]
-fn get_oauth_callback_port() -> Result<u16, String> {
-    oauth::get_available_port()
+fn get_oauth_callback_port(
+    app: tauri::AppHandle,
+    flows: tauri::State<'_, oauth::OAuthFlows>,
+) -> Result<u16, String> {
+    flows.reserve_port(oauth_callback_port_range(&app))
 }
 
 #[tauri::command]
@@ -481,7 +633,7 @@ fn get_desktop_oauth_callback_url(path: String) -> Result<String, String> {
 }
 
 #[derive(serde::Serialize)]
-struct BuildInfo {
+pub(crate) struct BuildInfo {
     app_version: String,
     release_tag: String,
     commit: String,
@@ -491,10 +643,28 @@ struct BuildInfo {
     webview: String,
     rust_version: String,
     os: String,
+    /// Optional Cargo features compiled into this build.
+    features: Vec<String>,
+    /// Reported versions of the agent CLIs (`None` when not installed).
+    sidecars: std::collections::BTreeMap<String, Option<String>>,
+}
+
+fn enabled_features() -> Vec<String> {
+    [
+        ("validation", cfg!(feature = "validation")),
+        ("messaging", cfg!(feature = "messaging")),
+        ("telegram", cfg!(feature = "telegram")),
+        ("discord", cfg!(feature = "discord")),
+        ("whatsapp", cfg!(feature = "whatsapp")),
+    ]
+    .into_iter()
+    .filter(|(_, enabled)| *enabled)
+    .map(|(name, _)| name.to_string())
+    .collect()
 }
 
 #[tauri::command]
-fn get_build_info(app: tauri::AppHandle) -> BuildInfo {
+pub(crate) async fn get_build_info(app: tauri::AppHandle) -> BuildInfo {
     let version = app
         .config()
         .version
@@ -516,6 +686,11 @@ fn get_build_info(app: tauri::AppHandle) -> BuildInfo {
         os_version()
     );
 
+    let sidecars =
+        tauri::async_runtime::spawn_blocking(commands::cli_installer::agent_cli_versions)
+            .await
+            .unwrap_or_default();
+
     BuildInfo {
         app_version: version,
         release_tag: env!("BUILT_RELEASE_TAG").to_string(),
@@ -526,6 +701,8 @@ fn get_build_info(app: tauri::AppHandle) -> BuildInfo {
         webview,
         rust_version: env!("BUILT_RUST_VERSION").to_string(),
         os,
+        features: enabled_features(),
+        sidecars,
     }
 }
 
@@ -633,6 +810,9 @@ pub fn run() {
             ))
             .manage(orchestrator::eval::EvalState::new())
             .manage(orchestrator::tool_bridge::ToolResultBridge::new())
+            .manage(oauth::PkceState::new())
+            .manage(oauth::OAuthFlows::new())
+            .manage(shell::ShellInputs::default())
             .manage(provider_runtime::ProviderRuntimeState::new())
             .manage(credential_lease::CredentialLeaseManager::new(
                 // A broker that cannot bind leaves the app without any safe
@@ -889,8 +1069,12 @@ pub fn run() {
             // Install panic sidecar capture for desktop support reports.
             support::init(app.handle());
 
-            // Start OAuth callback server in dev mode
-            // Provides localhost:8787 redirect for OAuth without deep links
+            // Refresh the Gateway access token ahead of its JWT expiry.
+            auth::schedule_token_refresh(app.handle().clone());
+
+            // Start the loopback OAuth callback server on every build. It is
+            // the production redirect on Windows, where deep links are
+            // disabled, and the dev/validation redirect everywhere else.
             if let Some(handle) = oauth_callback_server::start_oauth_callback_server(
                 app.handle().clone(),
                 validation_instance,
@@ -1016,7 +1200,7 @@ pub fn run() {
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
-            greet,
+            commands::diagnostics::diagnostics,
             tray::set_tray_recording,
             get_oauth_redirect_url,
             store_token,
@@ -1028,11 +1212,17 @@ pub fn run() {
             refresh_session,
             get_setting,
             set_setting,
+            get_setting_json,
+            set_setting_json,
+            get_setting_bool,
+            get_setting_number,
             store_provider_key,
+            commands::provider_keys::validate_provider_key,
             get_provider_key,
             clear_provider_key,
             get_configured_providers,
             files::read_file,
+            files::read_file_with_hash,
             files::read_file_base64,
             files::write_file,
             files::list_directory,
@@ -1041,12 +1231,16 @@ pub fn run() {
             files::create_file,
             files::create_directory,
             files::delete_path,
+            files::trash_path,
             files::rename_path,
             files::reveal_in_file_manager,
             files::open_path_with_default_app,
+            files::watch_file,
+            files::unwatch_file,
             // Shell command execution (requires frontend approval)
             shell::execute_shell_command,
             shell::execute_shell_command_streaming,
+            shell::send_shell_input,
             shell::run_skill_script,
             shell::diagnose_shell_network,
             // Interactive terminal buffers
@@ -1065,6 +1259,7 @@ pub fn run() {
             terminal::terminal_claude_version,
             // Web fetch command
             commands::web::web_fetch,
+            commands::web::web_fetch_json,
             // Rust-backed Gateway API bridge
             commands::gateway_http::gateway_http_start,
             commands::gateway_http::gateway_http_cancel,
@@ -1144,8 +1339,11 @@ pub fn run() {
             commands::chat::get_conversation,
             commands::chat::update_conversation,
             commands::chat::set_conversation_privileged,
+            commands::chat::set_conversation_tags,
             commands::chat::archive_conversation,
             commands::chat::delete_conversation,
+            commands::chat::restore_conversation,
+            commands::chat::purge_trashed,
             commands::chat::delete_conversations_by_employee,
             commands::employees_archive::archive_employee,
             commands::employees_archive::list_archived_employees,
@@ -1169,6 +1367,10 @@ pub fn run() {
             // Message commands
             commands::chat::save_message,
             commands::chat::get_messages,
+            commands::chat::get_messages_page,
+            commands::chat::edit_message,
+            commands::chat::truncate_after,
+            commands::chat::regenerate_last,
             commands::chat::clear_conversation_history,
             commands::chat::clear_all_history,
             commands::chat::erase_all_conversation_data,
@@ -1186,7 +1388,10 @@ pub fn run() {
             mcp::mcp_list_tools,
             mcp::mcp_list_resources,
             mcp::mcp_call_tool,
+            mcp::mcp_cancel_tool_call,
             mcp::mcp_read_resource,
+            mcp::mcp_list_prompts,
+            mcp::mcp_get_prompt,
             mcp::mcp_is_connected,
             mcp::mcp_list_connected,
             mcp::resolve_playwright_mcp_script_path,
@@ -1195,6 +1400,8 @@ pub fn run() {
             mcp::mcp_disconnect_http,
             mcp::mcp_list_tools_http,
             mcp::mcp_call_tool_http,
+            mcp::mcp_list_prompts_http,
+            mcp::mcp_get_prompt_http,
             mcp::mcp_is_connected_http,
             mcp::mcp_list_connected_http,
             // Polymarket CLOB API authentication commands
@@ -1202,6 +1409,11 @@ pub fn run() {
             polymarket::commands::get_polymarket_address,
             polymarket::commands::clear_polymarket_credentials,
             polymarket::commands::sign_polymarket_request,
+            // Crypto wallet status and payment history
+            wallet::commands::get_wallet_public_info,
+            wallet::commands::wallet_has_key,
+            wallet::commands::record_x402_payment,
+            wallet::commands::get_payment_history,
             // Skill Keys host-side secret broker
             secret_broker::list_skill_secret_bindings,
             secret_broker::upsert_skill_secret_binding,
@@ -1225,8 +1437,11 @@ pub fn run() {
             // Polymarket WebSocket commands
             polymarket::commands::connect_polymarket_websocket,
             polymarket::commands::subscribe_polymarket_market,
+            polymarket::commands::subscribe_polymarket_markets,
+            polymarket::commands::unsubscribe_polymarket_market,
             polymarket::commands::subscribe_polymarket_user,
             embedded_runtime::get_embedded_runtime_info,
+            embedded_runtime::verify_embedded_runtime,
             provider_runtime::provider_runtime_get_config,
             provider_runtime::provider_runtime_stop,
             provider_runtime::provider_force_kill_session,
@@ -1239,10 +1454,13 @@ pub fn run() {
             store_oauth_credentials,
             get_oauth_credentials,
             clear_oauth_credentials,
+            revoke_oauth_credentials,
             get_oauth_providers,
             // OAuth browser flow commands
             commands::auth::start_social_login,
+            begin_oauth_pkce,
             start_oauth_browser_flow,
+            cancel_oauth_flow,
             get_oauth_callback_port,
             get_desktop_oauth_callback_port,
             get_desktop_oauth_callback_url,
@@ -1262,6 +1480,7 @@ pub fn run() {
             commands::indexing::index_chunk,
             commands::indexing::index_chunks,
             commands::indexing::delete_file_index,
+            commands::indexing::flush_index,
             commands::indexing::file_needs_reindex,
             commands::indexing::search_codebase,
             commands::indexing::get_embedding_dimension,
@@ -1270,7 +1489,9 @@ pub fn run() {
             commands::transcript_search::search_transcripts_like,
             commands::transcript_search::indexed_transcript_meeting_ids,
             commands::transcript_search::delete_meeting_transcript_index,
+            commands::conversation_export::export_conversation,
             commands::conversation_search::search_conversations_fts,
+            commands::conversation_search::search_messages,
             commands::conversation_search::search_conversations,
             commands::conversation_search::index_conversation_embeddings,
             commands::conversation_search::unembedded_conversation_chunks,
@@ -1281,6 +1502,7 @@ pub fn run() {
             commands::indexing::chunk_file,
             commands::indexing::estimate_indexing,
             commands::indexing::compute_file_hash,
+            commands::indexing::reindex_file,
             // Local context-intelligence commands for agent-owned code inspection.
             commands::context_intelligence::seren_index_source,
             commands::context_intelligence::seren_index_file,
@@ -1321,9 +1543,17 @@ pub fn run() {
             messaging::commands::messaging_whatsapp_qr,
             // Orchestrator commands
             commands::orchestrator::orchestrate,
+            commands::orchestrator::resume_orchestration,
+            commands::orchestrator::classify_only,
+            commands::orchestrator::clear_routing_cache,
+            commands::orchestrator::list_routing_rules,
+            commands::orchestrator::add_routing_rule,
+            commands::orchestrator::remove_routing_rule,
+            commands::orchestrator::get_active_orchestration_count,
             commands::orchestrator::cancel_orchestration,
             commands::orchestrator::submit_tool_result,
             commands::orchestrator::submit_eval_signal,
+            commands::orchestrator::get_eval_state,
             // Memory commands
             commands::memory::memory_bootstrap,
             commands::memory::memory_session_bootstrap,
@@ -1342,6 +1572,8 @@ pub fn run() {
             commands::memory::memory_consolidate,
             commands::memory::memory_configure_publishers,
             commands::memory::memory_sync,
+            commands::memory::memory_export,
+            commands::memory::memory_import,
             // Claude Code auto-memory interceptor commands
             commands::claude_memory::claude_memory_start,
             commands::claude_memory::claude_memory_stop,
@@ -1425,7 +1657,50 @@ pub fn run() {
 
 #[cfg(test)]
 mod tests {
-    use super::{InterviewLaunchPayload, parse_interview_launch_url};
+    use super::{
+        InterviewLaunchPayload, extract_port_from_redirect_uri, parse_interview_launch_url,
+    };
+
+    fn auth_url_with_redirect(redirect_uri: &str) -> String {
+        format!(
+            "https://provider.example/authorize?client_id=abc&redirect_uri={}&state=xyz",
+            urlencoding::encode(redirect_uri)
+        )
+    }
+
+    #[test]
+    fn extracts_port_from_ipv4_loopback_redirect() {
+        let url = auth_url_with_redirect("http://127.0.0.1:58688/oauth/callback");
+        assert_eq!(extract_port_from_redirect_uri(&url), Ok(58688));
+    }
+
+    #[test]
+    fn extracts_port_from_localhost_redirect() {
+        let url = auth_url_with_redirect("http://localhost:49152/oauth/callback");
+        assert_eq!(extract_port_from_redirect_uri(&url), Ok(49152));
+    }
+
+    #[test]
+    fn extracts_port_from_ipv6_loopback_redirect() {
+        let url = auth_url_with_redirect("http://[::1]:8790/oauth/callback");
+        assert_eq!(extract_port_from_redirect_uri(&url), Ok(8790));
+    }
+
+    #[test]
+    fn rejects_non_loopback_or_portless_redirects() {
+        for redirect in [
+            "https://example.com:8443/oauth/callback",
+            "http://localhost.evil.com:8080/cb",
+            "http://127.0.0.1/oauth/callback",
+            "seren://callback",
+        ] {
+            let url = auth_url_with_redirect(redirect);
+            assert!(
+                extract_port_from_redirect_uri(&url).is_err(),
+                "{redirect} must not yield a loopback port"
+            );
+        }
+    }
 
     /// Regression guard for #3147.
     ///
@@ -19,6 +19,11 @@ use tauri::{Manager, State};
 /// while still surfacing a clearly-broken child in a reasonable window.
 const MCP_INITIALIZE_TIMEOUT: Duration = Duration::from_secs(15);
 
+/// Default bound on a single `tools/call` when the caller passes no
+/// `timeout_ms`. Well under the orchestrator's frontend-tool timeout so a
+/// stalled server fails the call instead of wedging the tool loop.
+const MCP_TOOL_CALL_TIMEOUT: Duration = Duration::from_secs(120);
+
 /// Global request ID counter for JSON-RPC
 static REQUEST_ID: AtomicU64 = AtomicU64::new(1);
 
@@ -26,7 +31,36 @@ static REQUEST_ID: AtomicU64 = AtomicU64::new(1);
 /// server cannot block operations on any other — which was a second part of
 /// the hang bug: the old code held a single top-level Mutex across every
 /// blocking stdio read, so a slow child would freeze all MCP commands.
-type McpSlot = Arc<Mutex<McpProcess>>;
+///
+/// `child` and `stdin` are shared with the process so an abandoned request
+/// can be cancelled and the child killed while a blocked reader still holds
+/// `process`.
+#[derive(Clone)]
+struct McpSlot {
+    process: Arc<Mutex<McpProcess>>,
+    child: Arc<Mutex<Child>>,
+    stdin: Arc<Mutex<ChildStdin>>,
+}
+
+impl McpSlot {
+    fn new(process: McpProcess) -> Self {
+        Self {
+            child: Arc::clone(&process.child),
+            stdin: Arc::clone(&process.stdin),
+            process: Arc::new(Mutex::new(process)),
+        }
+    }
+
+    fn kill(&self) {
+        kill_child(&self.child);
+    }
+}
+
+fn kill_child(child: &Mutex<Child>) {
+    if let Ok(mut child) = child.lock() {
+        let _ = child.kill();
+    }
+}
 
 /// State for managing MCP server processes.
 ///
@@ -37,15 +71,47 @@ type McpSlot = Arc<Mutex<McpProcess>>;
 /// a child process read.
 pub struct McpState {
     processes: Mutex<HashMap<String, McpSlot>>,
+    /// In-flight `tools/call` requests (stdio and HTTP) by call id, so
+    /// `mcp_cancel_tool_call` can abandon them.
+    tool_calls: Mutex<HashMap<String, tokio::sync::oneshot::Sender<()>>>,
 }
 
 impl McpState {
     pub fn new() -> Self {
         Self {
             processes: Mutex::new(HashMap::new()),
+            tool_calls: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Register an in-flight tool call. The receiver resolves when the call
+    /// is cancelled.
+    fn begin_tool_call(&self, call_id: &str) -> Result<tokio::sync::oneshot::Receiver<()>, String> {
+        let mut calls = self.tool_calls.lock().map_err(|e| e.to_string())?;
+        if calls.contains_key(call_id) {
+            return Err(format!("MCP tool call '{}' is already in flight", call_id));
+        }
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        calls.insert(call_id.to_string(), tx);
+        Ok(rx)
+    }
+
+    fn finish_tool_call(&self, call_id: &str) {
+        if let Ok(mut calls) = self.tool_calls.lock() {
+            calls.remove(call_id);
         }
     }
 
+    /// Signal an in-flight tool call to stop waiting. Returns false when the
+    /// call already finished or never existed.
+    fn cancel_tool_call(&self, call_id: &str) -> bool {
+        let sender = match self.tool_calls.lock() {
+            Ok(mut calls) => calls.remove(call_id),
+            Err(_) => None,
+        };
+        sender.is_some_and(|tx| tx.send(()).is_ok())
+    }
+
     /// Kill all connected MCP server processes. Called on app exit to prevent
     /// orphaned child processes from accumulating across restarts.
     pub fn kill_all(&self) {
@@ -56,9 +122,7 @@ impl McpState {
         };
         for (name, slot) in drained {
             log::info!("[MCP] Killing process on exit: {}", name);
-            if let Ok(mut process) = slot.lock() {
-                let _ = process.child.kill();
-            }
+            slot.kill();
         }
     }
 }
@@ -71,8 +135,8 @@ impl Default for McpState {
 
 /// Represents an active MCP server process
 struct McpProcess {
-    child: Child,
-    stdin: ChildStdin,
+    child: Arc<Mutex<Child>>,
+    stdin: Arc<Mutex<ChildStdin>>,
     stdout: BufReader<ChildStdout>,
     /// Buffered stderr output from the background drain thread.
     /// Used to enrich error messages when the process fails.
@@ -144,6 +208,36 @@ pub struct McpResource {
     mime_type: Option<String>,
 }
 
+/// MCP prompt template, with the arguments `prompts/get` accepts
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct McpPrompt {
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(default)]
+    arguments: Vec<McpPromptArgument>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct McpPromptArgument {
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(default)]
+    required: bool,
+}
+
+/// Rendered prompt returned by `prompts/get`
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct McpPromptResult {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    messages: Vec<serde_json::Value>,
+}
+
 /// MCP tool call result
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -159,8 +253,21 @@ fn send_request<T: Serialize>(
     method: &'static str,
     params: Option<T>,
 ) -> Result<serde_json::Value, String> {
-    let id = REQUEST_ID.fetch_add(1, Ordering::SeqCst);
+    send_request_with_id(process, next_request_id(), method, params)
+}
+
+fn next_request_id() -> u64 {
+    REQUEST_ID.fetch_add(1, Ordering::SeqCst)
+}
 
+/// `send_request` with a caller-chosen id, so the request can later be named
+/// in `notifications/cancelled`.
+fn send_request_with_id<T: Serialize>(
+    process: &mut McpProcess,
+    id: u64,
+    method: &'static str,
+    params: Option<T>,
+) -> Result<serde_json::Value, String> {
     let request = JsonRpcRequest {
         jsonrpc: "2.0",
         id,
@@ -171,8 +278,11 @@ fn send_request<T: Serialize>(
     let request_str = serde_json::to_string(&request).map_err(|e| e.to_string())?;
 
     // Write request
-    writeln!(process.stdin, "{}", request_str).map_err(|e| e.to_string())?;
-    process.stdin.flush().map_err(|e| e.to_string())?;
+    {
+        let mut stdin = process.stdin.lock().map_err(|e| e.to_string())?;
+        writeln!(stdin, "{}", request_str).map_err(|e| e.to_string())?;
+        stdin.flush().map_err(|e| e.to_string())?;
+    }
 
     // Read response
     let mut response_line = String::new();
@@ -424,7 +534,11 @@ fn collect_process_diagnostics(process: &mut McpProcess, base_error: &str) -> St
     let mut diagnostic = base_error.to_string();
 
     // Check if the process has exited and capture the exit code
-    if let Ok(Some(status)) = process.child.try_wait() {
+    let exit_status = match process.child.lock() {
+        Ok(mut child) => child.try_wait().ok().flatten(),
+        Err(_) => None,
+    };
+    if let Some(status) = exit_status {
         let code_str = status
             .code()
             .map(|c| c.to_string())
@@ -537,8 +651,8 @@ pub async fn mcp_connect(
     };
 
     let process = McpProcess {
-        child,
-        stdin,
+        child: Arc::new(Mutex::new(child)),
+        stdin: Arc::new(Mutex::new(stdin)),
         stdout: BufReader::new(stdout),
         stderr_buffer,
     };
@@ -565,7 +679,7 @@ pub async fn mcp_connect(
                 let diagnostic = collect_process_diagnostics(&mut process, &e);
                 // Kill the child so the background stderr-drain thread (and
                 // any OS resources) can be released promptly.
-                let _ = process.child.kill();
+                kill_child(&process.child);
                 Err(diagnostic)
             }
         }
@@ -573,7 +687,7 @@ pub async fn mcp_connect(
 
     let handshake_result = tokio::time::timeout(MCP_INITIALIZE_TIMEOUT, handshake).await;
 
-    let (mut process, result) = match handshake_result {
+    let (process, result) = match handshake_result {
         Ok(Ok(Ok(pair))) => pair,
         Ok(Ok(Err(e))) => {
             log::error!("[MCP:{}] Initialize failed: {}", server_name_for_log, e);
@@ -608,8 +722,11 @@ pub async fn mcp_connect(
         "jsonrpc": "2.0",
         "method": "notifications/initialized"
     });
-    writeln!(process.stdin, "{}", notification).map_err(|e| e.to_string())?;
-    process.stdin.flush().map_err(|e| e.to_string())?;
+    {
+        let mut stdin = process.stdin.lock().map_err(|e| e.to_string())?;
+        writeln!(stdin, "{}", notification).map_err(|e| e.to_string())?;
+        stdin.flush().map_err(|e| e.to_string())?;
+    }
 
     log::debug!(
         "[MCP:{}] Connected successfully (server: {} v{})",
@@ -624,7 +741,7 @@ pub async fn mcp_connect(
         .processes
         .lock()
         .map_err(|e| e.to_string())?
-        .insert(server_name, Arc::new(Mutex::new(process)));
+        .insert(server_name, McpSlot::new(process));
 
     Ok(init_result)
 }
@@ -648,15 +765,30 @@ async fn run_request_off_main<T, R>(
     method: &'static str,
     params: Option<T>,
 ) -> Result<R, String>
+where
+    T: Serialize + Send + 'static,
+    R: serde::de::DeserializeOwned + Send + 'static,
+{
+    run_request_with_id_off_main(slot, next_request_id(), method, params).await
+}
+
+/// `run_request_off_main` with a caller-chosen request id.
+async fn run_request_with_id_off_main<T, R>(
+    slot: McpSlot,
+    id: u64,
+    method: &'static str,
+    params: Option<T>,
+) -> Result<R, String>
 where
     T: Serialize + Send + 'static,
     R: serde::de::DeserializeOwned + Send + 'static,
 {
     tokio::task::spawn_blocking(move || -> Result<R, String> {
         let mut process = slot
+            .process
             .lock()
             .map_err(|e| format!("MCP process mutex poisoned: {e}"))?;
-        let value = send_request(&mut *process, method, params)?;
+        let value = send_request_with_id(&mut *process, id, method, params)?;
         serde_json::from_value::<R>(value)
             .map_err(|e| format!("Failed to parse {method} response: {e}"))
     })
@@ -680,13 +812,9 @@ pub async fn mcp_disconnect(
     };
 
     if let Some(slot) = removed {
-        tokio::task::spawn_blocking(move || {
-            if let Ok(mut process) = slot.lock() {
-                let _ = process.child.kill();
-            }
-        })
-        .await
-        .map_err(|e| format!("MCP disconnect task panicked: {e}"))?;
+        tokio::task::spawn_blocking(move || slot.kill())
+            .await
+            .map_err(|e| format!("MCP disconnect task panicked: {e}"))?;
     }
 
     Ok(())
@@ -725,20 +853,145 @@ struct ResourcesListResponse {
     resources: Vec<McpResource>,
 }
 
-/// Call a tool on an MCP server
+/// Race a tool call against its timeout and `mcp_cancel_tool_call`.
+///
+/// `call_id` is chosen by the caller so it can cancel before the result
+/// arrives; one is generated when omitted. When the call is given up,
+/// `on_abandon` runs with the reason so the transport can stop the request.
+async fn run_tool_call<F, A>(
+    state: &McpState,
+    call_id: Option<String>,
+    timeout_ms: Option<u64>,
+    server_name: &str,
+    tool_name: &str,
+    call: F,
+    on_abandon: A,
+) -> Result<McpToolResult, String>
+where
+    F: std::future::Future<Output = Result<McpToolResult, String>>,
+    A: FnOnce(&str),
+{
+    let call_id = call_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let timeout = timeout_ms
+        .map(Duration::from_millis)
+        .unwrap_or(MCP_TOOL_CALL_TIMEOUT);
+    let cancelled = state.begin_tool_call(&call_id)?;
+
+    let mut abandoned = None;
+    let outcome = tokio::select! {
+        result = call => result,
+        _ = tokio::time::sleep(timeout) => {
+            abandoned = Some("timed out");
+            log::warn!(
+                "[MCP:{}] Tool '{}' timed out after {}ms (call {})",
+                server_name,
+                tool_name,
+                timeout.as_millis(),
+                call_id
+            );
+            Err(format!(
+                "MCP tool '{}' on '{}' timed out after {}ms",
+                tool_name,
+                server_name,
+                timeout.as_millis()
+            ))
+        }
+        _ = cancelled => {
+            abandoned = Some("cancelled by the user");
+            log::info!(
+                "[MCP:{}] Tool '{}' cancelled (call {})",
+                server_name,
+                tool_name,
+                call_id
+            );
+            Err(format!(
+                "MCP tool '{}' on '{}' was cancelled",
+                tool_name, server_name
+            ))
+        }
+    };
+    state.finish_tool_call(&call_id);
+    if let Some(reason) = abandoned {
+        on_abandon(reason);
+    }
+    outcome
+}
+
+/// Stop a stdio request the caller gave up on. The blocked reader still holds
+/// the process lock, so the server is told to stop via
+/// `notifications/cancelled` and then killed: its stdout closes, the reader
+/// returns, and later calls fail fast with "not connected" instead of
+/// queueing behind the abandoned request until the server is reconnected.
+fn abandon_stdio_request(
+    state: &McpState,
+    server_name: &str,
+    slot: &McpSlot,
+    request_id: u64,
+    reason: &str,
+) {
+    let notification = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "notifications/cancelled",
+        "params": { "requestId": request_id, "reason": reason }
+    });
+    if let Ok(mut stdin) = slot.stdin.lock() {
+        let _ = writeln!(stdin, "{}", notification).and_then(|_| stdin.flush());
+    }
+
+    if let Ok(mut processes) = state.processes.lock()
+        && processes
+            .get(server_name)
+            .is_some_and(|current| Arc::ptr_eq(&current.process, &slot.process))
+    {
+        processes.remove(server_name);
+    }
+    slot.kill();
+    log::warn!(
+        "[MCP:{}] Stopped server after request {} was {}",
+        server_name,
+        request_id,
+        reason
+    );
+}
+
+/// Call a tool on an MCP server.
+///
+/// Fails after `timeout_ms` (default 120s), or as soon as
+/// `mcp_cancel_tool_call` is called with the same `call_id`. Either way the
+/// server is sent `notifications/cancelled` and stopped, and must be
+/// reconnected before its next call.
 #[tauri::command]
 pub async fn mcp_call_tool(
     state: State<'_, McpState>,
     server_name: String,
     tool_name: String,
     arguments: serde_json::Value,
+    call_id: Option<String>,
+    timeout_ms: Option<u64>,
 ) -> Result<McpToolResult, String> {
     let slot = lookup_slot(&state, &server_name)?;
     let params = serde_json::json!({
         "name": tool_name,
         "arguments": arguments
     });
-    run_request_off_main(slot, "tools/call", Some(params)).await
+    let request_id = next_request_id();
+    run_tool_call(
+        &state,
+        call_id,
+        timeout_ms,
+        &server_name,
+        &tool_name,
+        run_request_with_id_off_main(slot.clone(), request_id, "tools/call", Some(params)),
+        |reason| abandon_stdio_request(&state, &server_name, &slot, request_id, reason),
+    )
+    .await
+}
+
+/// Cancel an in-flight `mcp_call_tool` / `mcp_call_tool_http` by the
+/// `call_id` it was started with. Returns false if the call already finished.
+#[tauri::command]
+pub fn mcp_cancel_tool_call(state: State<'_, McpState>, call_id: String) -> bool {
+    state.cancel_tool_call(&call_id)
 }
 
 /// Read a resource from an MCP server
@@ -753,6 +1006,36 @@ pub async fn mcp_read_resource(
     run_request_off_main(slot, "resources/read", Some(params)).await
 }
 
+/// List prompt templates from an MCP server
+#[tauri::command]
+pub async fn mcp_list_prompts(
+    state: State<'_, McpState>,
+    server_name: String,
+) -> Result<Vec<McpPrompt>, String> {
+    let slot = lookup_slot(&state, &server_name)?;
+    let response: PromptsListResponse =
+        run_request_off_main(slot, "prompts/list", None::<()>).await?;
+    Ok(response.prompts)
+}
+
+#[derive(Deserialize)]
+struct PromptsListResponse {
+    prompts: Vec<McpPrompt>,
+}
+
+/// Render a prompt template on an MCP server
+#[tauri::command]
+pub async fn mcp_get_prompt(
+    state: State<'_, McpState>,
+    server_name: String,
+    name: String,
+    arguments: Option<HashMap<String, String>>,
+) -> Result<McpPromptResult, String> {
+    let slot = lookup_slot(&state, &server_name)?;
+    let params = serde_json::json!({ "name": name, "arguments": arguments.unwrap_or_default() });
+    run_request_off_main(slot, "prompts/get", Some(params)).await
+}
+
 /// Check if an MCP server is connected
 #[tauri::command]
 pub fn mcp_is_connected(state: State<'_, McpState>, server_name: String) -> bool {
@@ -915,35 +1198,106 @@ pub async fn mcp_list_tools_http(
     Ok(tools)
 }
 
-/// Call a tool on an HTTP MCP server
+/// Call a tool on an HTTP MCP server, with the same timeout and
+/// cancellation as `mcp_call_tool`.
 #[tauri::command]
 pub async fn mcp_call_tool_http(
     state: State<'_, HttpMcpState>,
+    calls: State<'_, McpState>,
     server_name: String,
     tool_name: String,
     arguments: serde_json::Value,
+    call_id: Option<String>,
+    timeout_ms: Option<u64>,
 ) -> Result<McpToolResult, String> {
+    let client = http_client(&state, &server_name).await?;
+
+    let call = async {
+        let result = client
+            .call_tool(
+                rmcp::model::CallToolRequestParams::new(tool_name.clone())
+                    .with_arguments(serde_json::from_value(arguments).unwrap_or_default()),
+            )
+            .await
+            .map_err(|e| format!("Failed to call tool: {}", e))?;
+
+        Ok(McpToolResult {
+            content: result
+                .content
+                .into_iter()
+                .map(|c| serde_json::to_value(&c).unwrap_or_default())
+                .collect(),
+            is_error: result.is_error.unwrap_or(false),
+        })
+    };
+    run_tool_call(
+        &calls,
+        call_id,
+        timeout_ms,
+        &server_name,
+        &tool_name,
+        call,
+        |_| {},
+    )
+    .await
+}
+
+/// Look up a connected HTTP client without holding the lock across a request.
+async fn http_client(
+    state: &HttpMcpState,
+    server_name: &str,
+) -> Result<Arc<HttpMcpClient>, String> {
     let clients = state.clients.read().await;
-    let client = clients
-        .get(&server_name)
-        .ok_or_else(|| format!("Server '{}' not connected", server_name))?;
+    clients
+        .get(server_name)
+        .cloned()
+        .ok_or_else(|| format!("Server '{}' not connected", server_name))
+}
 
+/// List prompt templates from an HTTP MCP server
+#[tauri::command]
+pub async fn mcp_list_prompts_http(
+    state: State<'_, HttpMcpState>,
+    server_name: String,
+) -> Result<Vec<McpPrompt>, String> {
+    let client = http_client(&state, &server_name).await?;
+    let prompts = client
+        .list_all_prompts()
+        .await
+        .map_err(|e| format!("Failed to list prompts: {}", e))?;
+
+    // rmcp's prompt serializes to the same wire shape the stdio path parses
+    prompts
+        .into_iter()
+        .map(|p| {
+            serde_json::to_value(&p)
+                .and_then(serde_json::from_value)
+                .map_err(|e| format!("Invalid prompt from '{}': {}", server_name, e))
+        })
+        .collect()
+}
+
+/// Render a prompt template on an HTTP MCP server
+#[tauri::command]
+pub async fn mcp_get_prompt_http(
+    state: State<'_, HttpMcpState>,
+    server_name: String,
+    name: String,
+    arguments: Option<HashMap<String, String>>,
+) -> Result<McpPromptResult, String> {
+    let client = http_client(&state, &server_name).await?;
+    let params = serde_json::from_value(
+        serde_json::json!({ "name": name, "arguments": arguments.unwrap_or_default() }),
+    )
+    .map_err(|e| format!("Invalid prompt arguments: {}", e))?;
     let result = client
-        .call_tool(
-            rmcp::model::CallToolRequestParams::new(tool_name)
-                .with_arguments(serde_json::from_value(arguments).unwrap_or_default()),
-        )
+        .get_prompt(params)
         .await
-        .map_err(|e| format!("Failed to call tool: {}", e))?;
-
-    Ok(McpToolResult {
-        content: result
-            .content
-            .into_iter()
-            .map(|c| serde_json::to_value(&c).unwrap_or_default())
-            .collect(),
-        is_error: result.is_error.unwrap_or(false),
-    })
+        .map_err(|e| format!("Failed to get prompt: {}", e))?;
+
+    serde_json::to_value(&result)
+        .and_then(serde_json::from_value)
+        .map_err(|e| format!("Invalid prompt from '{}': {}", server_name, e))
 }
 
 /// Check if an HTTP MCP server is connected
@@ -1007,8 +1361,8 @@ mod tests {
         };
 
         let process = McpProcess {
-            child,
-            stdin,
+            child: Arc::new(Mutex::new(child)),
+            stdin: Arc::new(Mutex::new(stdin)),
             stdout: BufReader::new(stdout),
             stderr_buffer,
         };
@@ -1073,6 +1427,49 @@ mod tests {
         let _ = tokio::time::timeout(Duration::from_secs(5), join_handle).await;
     }
 
+    #[tokio::test]
+    async fn timed_out_tool_call_stops_the_hung_server() {
+        let (process, _pid) = spawn_hung_child();
+        let state = McpState::new();
+        let slot = McpSlot::new(process);
+        state
+            .processes
+            .lock()
+            .unwrap()
+            .insert("hung".to_string(), slot.clone());
+
+        let request_id = next_request_id();
+        let err = run_tool_call(
+            &state,
+            None,
+            Some(50),
+            "hung",
+            "slow_tool",
+            run_request_with_id_off_main(slot.clone(), request_id, "tools/call", None::<()>),
+            |reason| abandon_stdio_request(&state, "hung", &slot, request_id, reason),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("timed out after 50ms"), "{err}");
+        assert!(
+            lookup_slot(&state, "hung").is_err(),
+            "abandoned server must be dropped"
+        );
+
+        // Killing the child closes its stdout, so the blocked reader returns
+        // and releases the process lock.
+        let process = Arc::clone(&slot.process);
+        let released = tokio::time::timeout(
+            Duration::from_secs(5),
+            tokio::task::spawn_blocking(move || process.lock().is_ok()),
+        )
+        .await;
+        assert!(
+            matches!(released, Ok(Ok(true))),
+            "process lock must be released"
+        );
+    }
+
     #[test]
     fn mcp_initialize_timeout_constant_is_bounded() {
         // Guard against someone accidentally removing the timeout or making
@@ -1091,6 +1488,110 @@ mod tests {
     }
 }
 
+// ============================================================================
+// Tool-call timeout and cancellation: a stalled server must fail the call
+// within its bound, and mcp_cancel_tool_call must release the waiter.
+// ============================================================================
+
+#[cfg(test)]
+mod tool_call_tests {
+    use super::*;
+
+    fn stalled() -> impl std::future::Future<Output = Result<McpToolResult, String>> {
+        std::future::pending()
+    }
+
+    #[tokio::test]
+    async fn stalled_tool_call_times_out_and_is_unregistered() {
+        let state = McpState::new();
+        let err = run_tool_call(
+            &state,
+            Some("call-1".to_string()),
+            Some(50),
+            "stalled",
+            "slow_tool",
+            stalled(),
+            |_| {},
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("timed out after 50ms"), "{err}");
+        assert!(!state.cancel_tool_call("call-1"));
+    }
+
+    #[tokio::test]
+    async fn cancelling_a_tool_call_releases_the_caller() {
+        let state = Arc::new(McpState::new());
+        let waiter = Arc::clone(&state);
+        let handle = tokio::spawn(async move {
+            run_tool_call(
+                &waiter,
+                Some("call-2".to_string()),
+                Some(60_000),
+                "stalled",
+                "slow_tool",
+                stalled(),
+                |_| {},
+            )
+            .await
+        });
+        while !state.tool_calls.lock().unwrap().contains_key("call-2") {
+            tokio::task::yield_now().await;
+        }
+        assert!(
+            state.begin_tool_call("call-2").is_err(),
+            "call ids must be unique while in flight"
+        );
+
+        assert!(state.cancel_tool_call("call-2"));
+        let err = tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .expect("cancel must release the caller")
+            .unwrap()
+            .unwrap_err();
+        assert!(err.contains("was cancelled"), "{err}");
+        assert!(!state.cancel_tool_call("call-2"));
+    }
+}
+
+// ============================================================================
+// Prompts: list results must carry argument schemas so the UI can collect
+// required inputs, including from servers that omit optional fields.
+// ============================================================================
+
+#[cfg(test)]
+mod prompt_tests {
+    use super::*;
+
+    #[test]
+    fn prompt_list_keeps_argument_schemas() {
+        let response: PromptsListResponse = serde_json::from_value(serde_json::json!({
+            "prompts": [
+                {
+                    "name": "review_pr",
+                    "description": "Review a pull request",
+                    "arguments": [
+                        { "name": "url", "description": "PR link", "required": true },
+                        { "name": "focus" }
+                    ]
+                },
+                { "name": "standup" }
+            ]
+        }))
+        .unwrap();
+
+        let review = &response.prompts[0];
+        assert_eq!(review.arguments.len(), 2);
+        assert!(review.arguments[0].required);
+        assert!(!review.arguments[1].required);
+        assert!(response.prompts[1].arguments.is_empty());
+
+        let wire = serde_json::to_value(review).unwrap();
+        assert_eq!(wire["arguments"][0]["name"], "url");
+        assert!(wire["arguments"][1].get("description").is_none());
+    }
+}
+
 // ============================================================================
 // #1945 — playwright-stealth MCP resolver tests (cross-platform).
 // Skill subprocesses (prophet-arb-bot) need an absolute, OS-aware spawn
@@ -1,17 +1,240 @@
 // ABOUTME: OAuth loopback server for browser-based authentication.
 // ABOUTME: Starts a local HTTP server to receive OAuth callbacks from the browser.
 
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::io::{Read, Write};
-use std::net::TcpListener;
-use std::sync::mpsc;
-use std::time::Duration;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream};
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 /// Result of the OAuth callback
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OAuthCallbackResult {
     pub code: String,
     pub state: String,
+    /// PKCE verifier registered for this `state`, released only once the
+    /// callback has arrived so the frontend can complete the token exchange.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_verifier: Option<String>,
+}
+
+/// PKCE challenge method. Only S256 is generated; `plain` is never offered.
+pub const PKCE_METHOD_S256: &str = "S256";
+
+/// A PKCE verifier/challenge pair (RFC 7636).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PkcePair {
+    pub verifier: String,
+    pub challenge: String,
+    pub method: String,
+}
+
+/// The public half of a PKCE pair, safe to hand to the frontend for the
+/// authorize URL. The verifier stays in [`PkceState`] until the callback.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PkceChallenge {
+    pub challenge: String,
+    pub method: String,
+}
+
+/// Generate a PKCE pair with a 256-bit random verifier.
+///
+/// 32 random bytes base64url-encode to a 43-character verifier, the minimum
+/// length RFC 7636 allows and the length most providers expect.
+pub fn generate_pkce() -> PkcePair {
+    let bytes: [u8; 32] = rand::random();
+    let verifier = URL_SAFE_NO_PAD.encode(bytes);
+    let challenge = pkce_challenge(&verifier);
+    PkcePair {
+        verifier,
+        challenge,
+        method: PKCE_METHOD_S256.to_string(),
+    }
+}
+
+/// Check that `verifier` is well-formed and hashes to `challenge` under S256.
+pub fn verify_pkce(verifier: &str, challenge: &str) -> bool {
+    is_valid_pkce_verifier(verifier) && pkce_challenge(verifier) == challenge
+}
+
+fn pkce_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+/// RFC 7636 §4.1: 43-128 characters from the unreserved set.
+fn is_valid_pkce_verifier(verifier: &str) -> bool {
+    (43..=128).contains(&verifier.len())
+        && verifier
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~'))
+}
+
+/// How long a registered PKCE verifier waits for its callback. Flows that
+/// never start or are abandoned without `cancel_oauth_flow` are dropped after
+/// this; it comfortably outlasts the browser flow's own timeout.
+const PKCE_VERIFIER_TTL: Duration = Duration::from_secs(30 * 60);
+
+/// PKCE verifiers for in-flight browser flows, keyed by OAuth `state`.
+///
+/// The verifier is generated here and only leaves Rust attached to the
+/// callback whose `state` it was registered under.
+#[derive(Default)]
+pub struct PkceState {
+    pending: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+impl PkceState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Generate a pair for `state` and return the challenge half.
+    pub fn begin(&self, state: &str) -> Result<PkceChallenge, String> {
+        self.begin_at(state, Instant::now())
+    }
+
+    fn begin_at(&self, state: &str, now: Instant) -> Result<PkceChallenge, String> {
+        if state.is_empty() {
+            return Err("OAuth state is required for PKCE".to_string());
+        }
+        let pair = generate_pkce();
+        let mut pending = self
+            .pending
+            .lock()
+            .map_err(|_| "PKCE state lock poisoned".to_string())?;
+        pending.retain(|_, (_, created_at)| now.duration_since(*created_at) < PKCE_VERIFIER_TTL);
+        pending.insert(state.to_string(), (pair.verifier, now));
+        Ok(PkceChallenge {
+            challenge: pair.challenge,
+            method: pair.method,
+        })
+    }
+
+    /// Remove and return the verifier registered for `state`, if any and
+    /// not expired.
+    pub fn take(&self, state: &str) -> Option<String> {
+        self.take_at(state, Instant::now())
+    }
+
+    fn take_at(&self, state: &str, now: Instant) -> Option<String> {
+        let (verifier, created_at) = self.pending.lock().ok()?.remove(state)?;
+        (now.duration_since(created_at) < PKCE_VERIFIER_TTL).then_some(verifier)
+    }
+}
+
+/// How long a callback port handed out for a flow stays reserved while the
+/// frontend registers its client and starts the flow.
+const PORT_RESERVATION_TTL: Duration = Duration::from_secs(120);
+
+struct PendingFlow {
+    port: u16,
+    cancelled: Arc<AtomicBool>,
+}
+
+#[derive(Default)]
+struct FlowRegistry {
+    pending: HashMap<String, PendingFlow>,
+    reserved_ports: HashMap<u16, Instant>,
+}
+
+impl FlowRegistry {
+    fn ports_in_use(&mut self, now: Instant) -> HashSet<u16> {
+        self.reserved_ports
+            .retain(|_, reserved_at| now.duration_since(*reserved_at) < PORT_RESERVATION_TTL);
+        self.pending
+            .values()
+            .map(|flow| flow.port)
+            .chain(self.reserved_ports.keys().copied())
+            .collect()
+    }
+}
+
+/// In-flight browser flows keyed by OAuth `state`.
+///
+/// Each flow listens on its own callback port, so several providers can be
+/// connected at once without one flow binding another's port or receiving
+/// its callback.
+#[derive(Default)]
+pub struct OAuthFlows {
+    registry: Mutex<FlowRegistry>,
+}
+
+impl OAuthFlows {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pick a free callback port that no other flow holds and reserve it for
+    /// the flow about to be started.
+    pub fn reserve_port(&self, range: Option<RangeInclusive<u16>>) -> Result<u16, String> {
+        let mut registry = self
+            .registry
+            .lock()
+            .map_err(|_| "OAuth flow lock poisoned".to_string())?;
+        let now = Instant::now();
+        let taken = registry.ports_in_use(now);
+        let port = get_available_port(range, &taken)?;
+        registry.reserved_ports.insert(port, now);
+        Ok(port)
+    }
+
+    /// Register the flow for `state` listening on `port`. Returns the flag
+    /// `cancel` sets to abandon it.
+    pub fn begin(&self, state: &str, port: u16) -> Result<Arc<AtomicBool>, String> {
+        if state.is_empty() {
+            return Err("OAuth auth URL has no state parameter".to_string());
+        }
+        let mut registry = self
+            .registry
+            .lock()
+            .map_err(|_| "OAuth flow lock poisoned".to_string())?;
+        if registry.pending.contains_key(state) {
+            return Err("An OAuth flow with this state is already in progress".to_string());
+        }
+        if registry.pending.values().any(|flow| flow.port == port) {
+            return Err(format!(
+                "OAuth callback port {} is already in use by another sign-in",
+                port
+            ));
+        }
+        registry.reserved_ports.remove(&port);
+        let cancelled = Arc::new(AtomicBool::new(false));
+        registry.pending.insert(
+            state.to_string(),
+            PendingFlow {
+                port,
+                cancelled: Arc::clone(&cancelled),
+            },
+        );
+        Ok(cancelled)
+    }
+
+    /// Forget a flow that has received its callback, failed, or timed out.
+    pub fn finish(&self, state: &str) {
+        if let Ok(mut registry) = self.registry.lock() {
+            registry.pending.remove(state);
+        }
+    }
+
+    /// Abandon the flow for `state`, releasing its port. Returns false when no
+    /// such flow is pending.
+    pub fn cancel(&self, state: &str) -> bool {
+        let Ok(mut registry) = self.registry.lock() else {
+            return false;
+        };
+        match registry.pending.remove(state) {
+            Some(flow) => {
+                flow.cancelled.store(true, Ordering::SeqCst);
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 /// Error from the OAuth callback
@@ -156,6 +379,7 @@ fn parse_oauth_callback(query: &str) -> Result<OAuthCallbackResult, OAuthError>
     Ok(OAuthCallbackResult {
         code: urlencoding_decode(&code),
         state: urlencoding_decode(&state),
+        code_verifier: None,
     })
 }
 
@@ -183,103 +407,284 @@ fn urlencoding_decode(s: &str) -> String {
     result
 }
 
-/// Get a random available port for the OAuth callback server
-pub fn get_available_port() -> Result<u16, String> {
-    let listener =
-        TcpListener::bind("127.0.0.1:0").map_err(|e| format!("Failed to bind: {}", e))?;
-    listener
-        .local_addr()
-        .map(|addr| addr.port())
-        .map_err(|e| format!("Failed to get port: {}", e))
+/// Ephemeral ports to try before giving up when the OS keeps handing out
+/// ports that another flow holds.
+const EPHEMERAL_PORT_ATTEMPTS: usize = 8;
+
+/// Get an available port for the OAuth callback server.
+///
+/// With a `range`, ports are tried in order and the first free one wins so
+/// users behind restrictive firewalls can pin the callback to an allowed
+/// window. Without one, the OS picks an ephemeral port. Ports in `taken`
+/// (held by other flows) are never returned.
+pub fn get_available_port(
+    range: Option<RangeInclusive<u16>>,
+    taken: &HashSet<u16>,
+) -> Result<u16, String> {
+    let Some(range) = range else {
+        for _ in 0..EPHEMERAL_PORT_ATTEMPTS {
+            let listener =
+                TcpListener::bind("127.0.0.1:0").map_err(|e| format!("Failed to bind: {}", e))?;
+            let port = listener
+                .local_addr()
+                .map(|addr| addr.port())
+                .map_err(|e| format!("Failed to get port: {}", e))?;
+            if !taken.contains(&port) {
+                return Ok(port);
+            }
+        }
+        return Err("No free OAuth callback port".to_string());
+    };
+
+    let (start, end) = (*range.start(), *range.end());
+    range
+        .into_iter()
+        .filter(|port| !taken.contains(port))
+        .find(|port| TcpListener::bind(("127.0.0.1", *port)).is_ok())
+        .ok_or_else(|| format!("No free OAuth callback port in range {}-{}", start, end))
+}
+
+/// Bind the IPv4 and IPv6 loopback addresses on `port`.
+///
+/// Providers disagree on whether `localhost` resolves to `127.0.0.1` or
+/// `[::1]`, so both are bound when the host supports it. Only one needs to
+/// succeed.
+fn bind_loopback_listeners(port: u16) -> Result<Vec<TcpListener>, String> {
+    let addrs = [
+        SocketAddr::from((Ipv4Addr::LOCALHOST, port)),
+        SocketAddr::from((Ipv6Addr::LOCALHOST, port)),
+    ];
+    let mut listeners = Vec::new();
+    let mut errors = Vec::new();
+    for addr in addrs {
+        match TcpListener::bind(addr) {
+            Ok(listener) => listeners.push(listener),
+            Err(e) => errors.push(format!("{}: {}", addr, e)),
+        }
+    }
+    if listeners.is_empty() {
+        return Err(format!(
+            "Failed to bind to port {}: {}",
+            port,
+            errors.join("; ")
+        ));
+    }
+    for error in errors {
+        log::debug!("[OAuth] Loopback address unavailable: {}", error);
+    }
+    Ok(listeners)
 }
 
 /// Start a loopback server on a specific port and wait for the OAuth callback.
 /// This is used when the port was already determined during client registration.
+///
+/// Requests whose `state` is not `expected_state` are answered with an error
+/// page and ignored, so a stray or forged callback cannot complete (or end)
+/// the flow. Setting `cancelled` stops the wait.
 pub fn wait_for_oauth_callback_on_port(
     port: u16,
     timeout_secs: u64,
+    expected_state: &str,
+    cancelled: &AtomicBool,
 ) -> Result<Result<OAuthCallbackResult, OAuthError>, String> {
-    // Bind to the specific port
-    let listener = TcpListener::bind(format!("127.0.0.1:{}", port))
-        .map_err(|e| format!("Failed to bind to port {}: {}", port, e))?;
-
-    log::info!("[OAuth] Callback server listening on port {}", port);
-
-    // Set blocking mode
-    listener
-        .set_nonblocking(false)
-        .map_err(|e| format!("Failed to set blocking: {}", e))?;
-
-    // Use a channel to receive the result with timeout
-    let (tx, rx) = mpsc::channel();
-
-    // Accept one connection
-    std::thread::spawn(move || {
-        match listener.accept() {
-            Ok((mut stream, _)) => {
-                let mut buffer = [0; 4096];
-                let bytes_read = stream.read(&mut buffer).unwrap_or(0);
-                let request = String::from_utf8_lossy(&buffer[..bytes_read]);
-
-                // Parse the HTTP request to extract the path
-                let first_line = request.lines().next().unwrap_or("");
-                let path = first_line.split_whitespace().nth(1).unwrap_or("/");
-
-                // Parse query parameters
-                let result = if let Some(query_start) = path.find('?') {
-                    let query = &path[query_start + 1..];
-                    parse_oauth_callback(query)
-                } else {
-                    Err(OAuthError {
-                        error: "invalid_request".to_string(),
-                        error_description: Some("No query parameters in callback".to_string()),
-                    })
-                };
-
-                // Send appropriate response
-                let (status, body) = match &result {
-                    Ok(_) => ("200 OK", SUCCESS_HTML.to_string()),
-                    Err(e) => {
-                        let error_msg = if let Some(desc) = &e.error_description {
-                            format!("{}: {}", e.error, desc)
-                        } else {
-                            e.error.clone()
-                        };
-                        (
-                            "400 Bad Request",
-                            ERROR_HTML.replace("{{ERROR}}", &error_msg),
-                        )
-                    }
-                };
-
-                let response = format!(
-                    "HTTP/1.1 {}\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
-                    status,
-                    body.len(),
-                    body
-                );
+    let listeners = bind_loopback_listeners(port)?;
+    for listener in &listeners {
+        listener
+            .set_nonblocking(true)
+            .map_err(|e| format!("Failed to set non-blocking: {}", e))?;
+    }
 
-                let _ = stream.write_all(response.as_bytes());
-                let _ = stream.flush();
+    log::info!(
+        "[OAuth] Callback server listening on port {} ({} loopback address(es))",
+        port,
+        listeners.len()
+    );
 
-                let _ = tx.send(result);
-            }
-            Err(e) => {
-                let _ = tx.send(Err(OAuthError {
-                    error: "server_error".to_string(),
-                    error_description: Some(format!("Failed to accept connection: {}", e)),
-                }));
+    // Poll every bound address until one of them receives the callback.
+    let deadline = Instant::now() + Duration::from_secs(timeout_secs);
+    loop {
+        if cancelled.load(Ordering::SeqCst) {
+            return Err("OAuth flow was cancelled".to_string());
+        }
+        for listener in &listeners {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    if let Some(result) = handle_callback_connection(stream, expected_state) {
+                        return Ok(result);
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+                Err(e) => {
+                    return Ok(Err(OAuthError {
+                        error: "server_error".to_string(),
+                        error_description: Some(format!("Failed to accept connection: {}", e)),
+                    }));
+                }
             }
         }
-    });
-
-    // Wait for result with timeout
-    match rx.recv_timeout(Duration::from_secs(timeout_secs)) {
-        Ok(result) => Ok(result),
-        Err(mpsc::RecvTimeoutError::Timeout) => Err("OAuth callback timed out".to_string()),
-        Err(mpsc::RecvTimeoutError::Disconnected) => {
-            Err("OAuth callback server disconnected".to_string())
+        if Instant::now() >= deadline {
+            return Err("OAuth callback timed out".to_string());
+        }
+        std::thread::sleep(ACCEPT_POLL_INTERVAL);
+    }
+}
+
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Read the callback request, answer the browser, and return the parsed
+/// result. `None` means the request did not carry `expected_state` and was
+/// rejected.
+fn handle_callback_connection(
+    mut stream: TcpStream,
+    expected_state: &str,
+) -> Option<Result<OAuthCallbackResult, OAuthError>> {
+    // Accepted sockets can inherit non-blocking mode from the listener.
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+
+    let mut buffer = [0; 4096];
+    let bytes_read = stream.read(&mut buffer).unwrap_or(0);
+    let request = String::from_utf8_lossy(&buffer[..bytes_read]);
+
+    // Parse the HTTP request to extract the path
+    let first_line = request.lines().next().unwrap_or("");
+    let path = first_line.split_whitespace().nth(1).unwrap_or("/");
+
+    // Parse query parameters
+    let query = path.find('?').map(|query_start| &path[query_start + 1..]);
+    if callback_state(query.unwrap_or("")).as_deref() != Some(expected_state) {
+        log::warn!("[OAuth] Rejected callback whose state matches no pending flow");
+        respond(
+            &mut stream,
+            "400 Bad Request",
+            &ERROR_HTML.replace(
+                "{{ERROR}}",
+                "state_mismatch: Unknown or expired sign-in request",
+            ),
+        );
+        return None;
+    }
+    let result = if let Some(query) = query {
+        parse_oauth_callback(query)
+    } else {
+        Err(OAuthError {
+            error: "invalid_request".to_string(),
+            error_description: Some("No query parameters in callback".to_string()),
+        })
+    };
+
+    // Send appropriate response
+    let (status, body) = match &result {
+        Ok(_) => ("200 OK", SUCCESS_HTML.to_string()),
+        Err(e) => {
+            let error_msg = if let Some(desc) = &e.error_description {
+                format!("{}: {}", e.error, desc)
+            } else {
+                e.error.clone()
+            };
+            (
+                "400 Bad Request",
+                ERROR_HTML.replace("{{ERROR}}", &error_msg),
+            )
         }
+    };
+
+    respond(&mut stream, status, &body);
+
+    Some(result)
+}
+
+/// Decoded `state` query parameter of a callback, if present.
+fn callback_state(query: &str) -> Option<String> {
+    query.split('&').find_map(|param| {
+        param
+            .strip_prefix("state=")
+            .map(|value| urlencoding_decode(value))
+    })
+}
+
+fn respond(stream: &mut TcpStream, status: &str, body: &str) {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+
+    let _ = stream.write_all(response.as_bytes());
+    let _ = stream.flush();
+}
+
+/// RFC 7009 revocation endpoints for providers that publish one.
+fn revocation_endpoint(provider: &str) -> Option<&'static str> {
+    match provider {
+        "gemini" | "google" => Some("https://oauth2.googleapis.com/revoke"),
+        _ => None,
+    }
+}
+
+const REVOCATION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Outcome of `revoke_oauth_credentials`. Local credentials are removed
+/// either way; `revoked` says whether the provider also invalidated them.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OAuthRevocation {
+    pub revoked: bool,
+    pub message: String,
+}
+
+/// Pick the token to revoke from stored credentials JSON, preferring the
+/// refresh token since revoking it also ends its access tokens.
+fn revocation_token(credentials: &str) -> Option<(String, &'static str)> {
+    let json: serde_json::Value = serde_json::from_str(credentials).ok()?;
+    let token = |key: &str| {
+        json.get(key)
+            .and_then(|v| v.as_str())
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+    };
+    token("refreshToken")
+        .map(|t| (t, "refresh_token"))
+        .or_else(|| token("accessToken").map(|t| (t, "access_token")))
+}
+
+/// Ask `provider` to revoke the token in `credentials` (stored OAuth
+/// credentials JSON). Never fails: problems are reported in the result.
+pub async fn revoke_provider_token(provider: &str, credentials: Option<&str>) -> OAuthRevocation {
+    let local_only = |reason: String| OAuthRevocation {
+        revoked: false,
+        message: format!("{}; credentials were only removed locally", reason),
+    };
+    let Some(endpoint) = revocation_endpoint(provider) else {
+        return local_only(format!("{} has no token revocation endpoint", provider));
+    };
+    let Some((token, hint)) = credentials.and_then(revocation_token) else {
+        return local_only(format!("No stored {} token to revoke", provider));
+    };
+
+    let body = format!(
+        "token={}&token_type_hint={}",
+        urlencoding::encode(&token),
+        hint
+    );
+    let result = reqwest::Client::new()
+        .post(endpoint)
+        .timeout(REVOCATION_TIMEOUT)
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .body(body)
+        .send()
+        .await;
+    match result {
+        Ok(response) if response.status().is_success() => OAuthRevocation {
+            revoked: true,
+            message: format!("{} token revoked", provider),
+        },
+        Ok(response) => local_only(format!(
+            "{} rejected the revocation (HTTP {})",
+            provider,
+            response.status().as_u16()
+        )),
+        Err(e) => local_only(format!("Could not reach {} to revoke: {}", provider, e)),
     }
 }
 
@@ -306,10 +711,183 @@ mod tests {
         );
     }
 
+    // RFC 7636 Appendix B.
+    const RFC_VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+    const RFC_CHALLENGE: &str = "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM";
+
+    #[test]
+    fn test_pkce_challenge_matches_rfc_7636_vector() {
+        assert_eq!(pkce_challenge(RFC_VERIFIER), RFC_CHALLENGE);
+        assert!(verify_pkce(RFC_VERIFIER, RFC_CHALLENGE));
+    }
+
+    #[test]
+    fn test_verify_pkce_rejects_mismatch_and_malformed_verifier() {
+        assert!(!verify_pkce(
+            RFC_VERIFIER,
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cN"
+        ));
+        assert!(!verify_pkce("too-short", &pkce_challenge("too-short")));
+        let bad_charset = format!("{}+", &RFC_VERIFIER[..43]);
+        assert!(!verify_pkce(&bad_charset, &pkce_challenge(&bad_charset)));
+    }
+
+    #[test]
+    fn test_generate_pkce_produces_verifiable_s256_pair() {
+        let pair = generate_pkce();
+        assert_eq!(pair.method, "S256");
+        assert_eq!(pair.verifier.len(), 43);
+        assert!(verify_pkce(&pair.verifier, &pair.challenge));
+        assert_ne!(generate_pkce().verifier, pair.verifier);
+    }
+
+    #[test]
+    fn test_pkce_state_releases_verifier_once_for_matching_state() {
+        let pkce = PkceState::new();
+        let challenge = pkce.begin("state-1").unwrap();
+        assert_eq!(challenge.method, "S256");
+        assert!(pkce.take("other-state").is_none());
+        let verifier = pkce.take("state-1").expect("verifier registered");
+        assert!(verify_pkce(&verifier, &challenge.challenge));
+        assert!(pkce.take("state-1").is_none());
+        assert!(pkce.begin("").is_err());
+    }
+
+    #[test]
+    fn test_pkce_state_evicts_abandoned_verifiers() {
+        let pkce = PkceState::new();
+        let start = Instant::now();
+        pkce.begin_at("abandoned", start).unwrap();
+        pkce.begin_at("expired", start).unwrap();
+
+        let later = start + PKCE_VERIFIER_TTL;
+        assert!(pkce.take_at("expired", later).is_none());
+        pkce.begin_at("fresh", later).unwrap();
+        assert_eq!(pkce.pending.lock().unwrap().len(), 1);
+        assert!(pkce.take_at("fresh", later).is_some());
+    }
+
+    #[test]
+    fn test_get_available_port_skips_busy_ports_in_range() {
+        let busy = TcpListener::bind("127.0.0.1:0").unwrap();
+        let busy_port = busy.local_addr().unwrap().port();
+        let Some(next) = busy_port.checked_add(1) else {
+            return;
+        };
+        match get_available_port(Some(busy_port..=next), &HashSet::new()) {
+            Ok(port) => assert_eq!(port, next),
+            // The neighbouring port can legitimately be taken by another process.
+            Err(error) => assert!(error.contains("No free OAuth callback port")),
+        }
+        assert!(get_available_port(Some(busy_port..=busy_port), &HashSet::new()).is_err());
+    }
+
+    #[test]
+    fn test_callback_is_received_on_ipv4_loopback() {
+        let port = get_available_port(None, &HashSet::new()).unwrap();
+        let server = std::thread::spawn(move || {
+            wait_for_oauth_callback_on_port(port, 5, "xyz", &AtomicBool::new(false))
+        });
+        let mut client = loop {
+            if let Ok(stream) = TcpStream::connect(("127.0.0.1", port)) {
+                break stream;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        };
+        client
+            .write_all(b"GET /oauth/callback?code=abc&state=xyz HTTP/1.1\r\n\r\n")
+            .unwrap();
+        let callback = server.join().unwrap().unwrap().unwrap();
+        assert_eq!(callback.code, "abc");
+        assert_eq!(callback.state, "xyz");
+    }
+
+    #[test]
+    fn test_callback_with_foreign_state_is_rejected_and_flow_keeps_waiting() {
+        let port = get_available_port(None, &HashSet::new()).unwrap();
+        let server = std::thread::spawn(move || {
+            wait_for_oauth_callback_on_port(port, 5, "mine", &AtomicBool::new(false))
+        });
+        let send = |request: &[u8]| {
+            let mut client = loop {
+                if let Ok(stream) = TcpStream::connect(("127.0.0.1", port)) {
+                    break stream;
+                }
+                std::thread::sleep(Duration::from_millis(10));
+            };
+            client.write_all(request).unwrap();
+            let mut response = String::new();
+            let _ = client.read_to_string(&mut response);
+            response
+        };
+
+        let rejected = send(b"GET /oauth/callback?code=evil&state=other HTTP/1.1\r\n\r\n");
+        assert!(rejected.starts_with("HTTP/1.1 400"));
+        send(b"GET /oauth/callback?code=good&state=mine HTTP/1.1\r\n\r\n");
+
+        let callback = server.join().unwrap().unwrap().unwrap();
+        assert_eq!(callback.code, "good");
+        assert_eq!(callback.state, "mine");
+    }
+
+    #[test]
+    fn test_cancelled_flow_stops_waiting() {
+        let port = get_available_port(None, &HashSet::new()).unwrap();
+        let flows = OAuthFlows::new();
+        let cancelled = flows.begin("abandoned", port).unwrap();
+        assert!(flows.cancel("abandoned"));
+        assert!(!flows.cancel("abandoned"));
+        let result = wait_for_oauth_callback_on_port(port, 5, "abandoned", &cancelled);
+        assert_eq!(result.unwrap_err(), "OAuth flow was cancelled");
+    }
+
+    #[test]
+    fn test_flows_get_distinct_ports_and_unique_states() {
+        let flows = OAuthFlows::new();
+        let first = flows.reserve_port(None).unwrap();
+        let second = flows.reserve_port(None).unwrap();
+        assert_ne!(first, second);
+
+        flows.begin("state-a", first).unwrap();
+        assert!(flows.begin("state-a", second).is_err());
+        assert!(flows.begin("state-b", first).is_err());
+        flows.begin("state-b", second).unwrap();
+
+        flows.finish("state-a");
+        flows.begin("state-c", first).unwrap();
+        assert!(flows.begin("", first).is_err());
+    }
+
     #[test]
     fn test_urlencoding_decode() {
         assert_eq!(urlencoding_decode("hello%20world"), "hello world");
         assert_eq!(urlencoding_decode("hello+world"), "hello world");
         assert_eq!(urlencoding_decode("abc%3D123"), "abc=123");
     }
+
+    #[test]
+    fn test_revocation_token_prefers_refresh_token() {
+        let both =
+            r#"{"type":"oauth","accessToken":"at","refreshToken":"rt","tokenType":"Bearer"}"#;
+        assert_eq!(
+            revocation_token(both),
+            Some(("rt".to_string(), "refresh_token"))
+        );
+        let access_only = r#"{"type":"oauth","accessToken":"at","refreshToken":""}"#;
+        assert_eq!(
+            revocation_token(access_only),
+            Some(("at".to_string(), "access_token"))
+        );
+        assert_eq!(revocation_token("not json"), None);
+    }
+
+    #[tokio::test]
+    async fn test_revoke_without_endpoint_reports_local_removal_only() {
+        let result = revoke_provider_token("openai", Some(r#"{"accessToken":"at"}"#)).await;
+        assert!(!result.revoked);
+        assert_eq!(
+            result.message,
+            "openai has no token revocation endpoint; credentials were only removed locally"
+        );
+    }
 }
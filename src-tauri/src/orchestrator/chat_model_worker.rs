@@ -6,20 +6,31 @@ use base64::{Engine, engine::general_purpose::STANDARD};
 use futures::StreamExt;
 use log;
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
 use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 use std::sync::{Arc, Mutex as StdMutex};
 use std::time::Duration;
 use tauri::{Emitter, Listener, Manager};
 use tokio::sync::{Mutex, mpsc, oneshot};
 
+use super::checkpoint::{
+    RoundCheckpoint, WorkerCheckpoint, now_millis, record_round, with_chat_db,
+};
+use super::eval::EvalState;
 use super::file_access_policy::{
     path_is_within, FileAccessDecision, FileAccessKind, FileAccessPolicy, ResolvedFileAccess,
 };
 use super::gateway_envelope::{
     publisher_cost, publisher_status, unwrap_data_response, unwrap_publisher_body,
 };
-use super::tool_bridge::ToolResultBridge;
+use super::rlm::model_context_window_tokens;
+use super::router::{
+    PublisherToolPriority, escalation_model, is_read_only_tool, prioritize_publisher_tools,
+};
+use super::tool_bridge::{CANCELLED_TOOL_RESULT, ToolExecutionResult, ToolImage, ToolResultBridge};
 use super::tool_relevance;
+use super::trace::TurnTracer;
 use super::types::{EffectiveAgentPolicy, ImageAttachment, RoutingDecision, WorkerEvent};
 use super::worker::Worker;
 
@@ -44,6 +55,15 @@ const MAX_TOOL_CALLS_PER_TURN: usize = 60;
 /// Maximum failed tool calls allowed in one chat turn before checkpointing.
 const MAX_TOOL_FAILURES_PER_TURN: usize = 12;
 
+/// Identical failing tool calls in a row (same tool, arguments, and error)
+/// before the model is told to stop repeating the call.
+pub const DEFAULT_TOOL_FAILURE_REPEAT_THRESHOLD: usize = 2;
+
+/// Minimum number of same-error tool failures in a row before the turn is
+/// aborted. Raised to one past the nudge threshold so the nudge always fires
+/// before the abort.
+const REPEATED_TOOL_FAILURE_ABORT: usize = 3;
+
 /// Maximum reported Gateway spend for one chat turn before checkpointing.
 const MAX_TURN_COST_USD: f64 = 2.0;
 
@@ -55,6 +75,37 @@ const CONNECT_TIMEOUT_SECS: u64 = 30;
 const REQUEST_TIMEOUT_SECS: u64 = 600;
 const FILE_APPROVAL_TIMEOUT_SECS: u64 = 300;
 
+/// Default wait for the frontend to return a gateway/MCP tool result. Covers
+/// the user reviewing an approval prompt before the tool runs.
+const TOOL_EXECUTION_TIMEOUT_SECS: u64 = 300;
+
+/// Per-tool overrides of `TOOL_EXECUTION_TIMEOUT_SECS`, matched by tool-name
+/// prefix (first match wins). The wait includes any approval prompt, so only
+/// lengthen it here; browser automation can legitimately take longer.
+const TOOL_EXECUTION_TIMEOUT_OVERRIDES: &[(&str, u64)] = &[("mcp__playwright__", 900)];
+
+/// Timeout applied to a frontend-executed tool call.
+fn frontend_tool_timeout_secs(name: &str) -> u64 {
+    TOOL_EXECUTION_TIMEOUT_OVERRIDES
+        .iter()
+        .find(|(prefix, _)| name.starts_with(prefix))
+        .map(|(_, secs)| *secs)
+        .unwrap_or(TOOL_EXECUTION_TIMEOUT_SECS)
+}
+
+/// Model-id fragments of families that accept `image_url` content parts.
+const VISION_MODEL_MARKERS: &[&str] = &[
+    "claude", "gpt-4o", "gpt-4.1", "gpt-5", "gemini", "grok-4", "llama-4", "pixtral", "-vl",
+];
+
+/// Whether `model_id` can look at images returned by tools.
+fn model_supports_vision(model_id: &str) -> bool {
+    let model_id = model_id.to_ascii_lowercase();
+    VISION_MODEL_MARKERS
+        .iter()
+        .any(|marker| model_id.contains(marker))
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct FileAccessApprovalRequest {
@@ -80,6 +131,27 @@ struct FileAccessApprovalResponse {
 /// via `WorkerEvent::ToolResult`.
 const MAX_TOOL_RESULT_CONTEXT_BYTES: usize = 30_000;
 
+/// Base64 bytes of tool images one round may attach. Images past this are
+/// described instead, so screenshots cannot grow the payload unbounded.
+const MAX_TOOL_IMAGE_CONTEXT_BYTES: usize = 4 * 1024 * 1024;
+
+/// Opens the user message carrying a round's tool images. Also how those
+/// messages are found again when a later round drops them.
+const TOOL_IMAGES_HEADER: &str = "[Images returned by the tool calls above]";
+
+/// Replaces a dropped tool image message.
+const TOOL_IMAGES_DROPPED: &str =
+    "[Images returned by earlier tool calls were removed from context.]";
+
+/// Flat token cost counted per image part; images are not sized by bytes.
+const IMAGE_TOKEN_ESTIMATE: usize = 1_000;
+
+/// Per-message framing overhead counted on top of the content.
+const MESSAGE_TOKEN_OVERHEAD: usize = 4;
+
+/// Persona opening the system prompt when the routing decision supplies none.
+const DEFAULT_SYSTEM_PROMPT: &str = "You are a helpful AI assistant running inside Seren Desktop.";
+
 /// Tone and behavior rules injected into every chat system prompt.
 ///
 /// Kept here as a single source of truth for the Rust orchestrator path.
@@ -163,6 +235,10 @@ struct AccumulatedToolCall {
     id: String,
     name: String,
     arguments: String,
+    /// Set when the streamed arguments were not valid JSON even after
+    /// `repair_tool_arguments`; the call is answered with an error instead of
+    /// being executed.
+    argument_error: Option<String>,
 }
 
 /// Raw tool call chunk from a single SSE event.
@@ -191,6 +267,8 @@ enum StreamOutcome {
         final_content: String,
         thinking: Option<String>,
         cost: f64,
+        /// finish_reason was "length": the model hit its output cap mid-answer.
+        truncated: bool,
     },
     /// Model wants tool results before continuing (finish_reason: "tool_calls").
     ToolCallsPending {
@@ -218,6 +296,126 @@ fn gateway_status_is_retryable(status: u64) -> bool {
     matches!(status, 408 | 429) || (500..600).contains(&status)
 }
 
+/// What a buffered Gateway non-streaming wrapper contained.
+#[derive(Debug, PartialEq)]
+enum WrapperOutcome {
+    /// The buffer is not a JSON wrapper.
+    NotWrapped,
+    /// The wrapper carried an HTTP error status.
+    Failed {
+        error: String,
+        cost: f64,
+        retryable: bool,
+    },
+    /// SSE data payloads to feed through `parse_sse_data`, in order.
+    Payloads { data: Vec<String>, cost: f64 },
+}
+
+/// Parse a response the Gateway returned as one JSON blob instead of a stream:
+///   {"status":200,"body":"data: {...}\n\ndata: {...}\n\n...[DONE]","cost":"..."}
+/// `body` is either a string of embedded SSE (its newlines are JSON escapes,
+/// so the line reader never split it) or a plain completion object.
+fn parse_gateway_wrapper(buffer: &str) -> WrapperOutcome {
+    let Ok(wrapper) = serde_json::from_str::<serde_json::Value>(buffer) else {
+        return WrapperOutcome::NotWrapped;
+    };
+    let envelope = unwrap_data_response(&wrapper);
+    let cost = publisher_cost(&wrapper).unwrap_or(0.0);
+
+    if let Some(status) = publisher_status(&wrapper)
+        && status >= 400
+    {
+        let error_msg = envelope
+            .pointer("/body/error/message")
+            .and_then(|v| v.as_str())
+            .unwrap_or("Gateway API error");
+        // Include raw provider error when available so the orchestrator can
+        // detect context-overflow and reroute to a large-context model.
+        let raw_detail = envelope
+            .pointer("/body/error/metadata/raw")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        let error = if raw_detail.is_empty() {
+            format!("HTTP {}: {}", status, error_msg)
+        } else {
+            format!("HTTP {}: {} — {}", status, error_msg, raw_detail)
+        };
+        return WrapperOutcome::Failed {
+            error,
+            cost,
+            retryable: gateway_status_is_retryable(status),
+        };
+    }
+
+    let data = match envelope.get("body") {
+        Some(serde_json::Value::String(body)) => {
+            let mut data = Vec::new();
+            for raw_line in body.split('\n') {
+                let line = raw_line.trim();
+                if line.is_empty() || line.starts_with(':') {
+                    continue;
+                }
+                let payload = line
+                    .strip_prefix("data: ")
+                    .or_else(|| line.strip_prefix("data:"))
+                    .or_else(|| line.starts_with('{').then_some(line));
+                let Some(payload) = payload else {
+                    continue;
+                };
+                if payload.trim() == "[DONE]" {
+                    break;
+                }
+                data.push(payload.to_string());
+            }
+            data
+        }
+        Some(body) if body.is_object() => vec![body.to_string()],
+        _ => Vec::new(),
+    };
+    WrapperOutcome::Payloads { data, cost }
+}
+
+/// Namespacing prefixes stripped from tool names for display.
+const TOOL_NAME_PREFIXES: &[&str] = &["gateway__", "mcp__", "openclaw__"];
+
+/// Title-case `snake_case` / `kebab-case` words: `send_message` -> `Send Message`.
+fn title_case_words(words: &str) -> String {
+    words
+        .split(['_', '-'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Display title for a tool call: `gateway__gmail__send_message` becomes
+/// `Gmail: Send Message` and `execute_command` becomes `Execute Command`.
+/// The raw name is still what gets executed.
+fn humanize_tool_title(name: &str) -> String {
+    let stripped = TOOL_NAME_PREFIXES
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix));
+    let title = match stripped.and_then(|rest| rest.split_once("__")) {
+        Some((publisher, tool)) if !publisher.is_empty() && !tool.is_empty() => format!(
+            "{}: {}",
+            title_case_words(publisher),
+            title_case_words(tool)
+        ),
+        _ => title_case_words(stripped.unwrap_or(name)),
+    };
+    if title.is_empty() {
+        name.to_string()
+    } else {
+        title
+    }
+}
+
 /// Extract unique publisher names from tool calls in recent conversation messages.
 /// Scans assistant messages for `tool_calls[].function.name` and extracts publisher
 /// names using the `mcp__<publisher>__` / `gateway__<publisher>__` convention.
@@ -268,6 +466,85 @@ fn summarize_gateway_error(status: reqwest::StatusCode, body_text: &str) -> Stri
     )
 }
 
+/// Make streamed tool-call arguments parseable when the damage is mechanical.
+/// Empty arguments become `{}`; a payload streamed twice back to back
+/// collapses to one copy; trailing commas are dropped. Truncated payloads are
+/// never closed up, since running a write or shell call with cut-off content
+/// is worse than failing it. Returns the original serde error when none of
+/// that yields valid JSON.
+fn repair_tool_arguments(raw: &str) -> Result<String, String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Ok("{}".to_string());
+    }
+    let error = match serde_json::from_str::<serde_json::Value>(trimmed) {
+        Ok(_) => return Ok(raw.to_string()),
+        Err(error) => error.to_string(),
+    };
+
+    let values: Result<Vec<serde_json::Value>, _> = serde_json::Deserializer::from_str(trimmed)
+        .into_iter()
+        .collect();
+    if let Ok(values) = values
+        && let Some(first) = values.first()
+        && values.iter().all(|value| value == first)
+    {
+        return Ok(first.to_string());
+    }
+
+    let repaired = strip_trailing_commas(trimmed);
+    if serde_json::from_str::<serde_json::Value>(&repaired).is_ok() {
+        Ok(repaired)
+    } else {
+        Err(error)
+    }
+}
+
+/// Remove commas that directly precede a closing `}` or `]`, ignoring
+/// anything inside string literals.
+fn strip_trailing_commas(json: &str) -> String {
+    let chars: Vec<char> = json.chars().collect();
+    let mut out = String::with_capacity(json.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, &ch) in chars.iter().enumerate() {
+        if in_string {
+            out.push(ch);
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        if ch == '"' {
+            in_string = true;
+        } else if ch == ',' {
+            let next = chars[i + 1..].iter().find(|c| !c.is_whitespace());
+            if matches!(next, Some('}') | Some(']')) {
+                continue;
+            }
+        }
+        out.push(ch);
+    }
+    out
+}
+
+/// Tool result returned to the model for a call whose arguments could not be
+/// parsed. Structured so the model can see what went wrong and re-issue the
+/// call, rather than receiving a bare serde message.
+fn invalid_tool_arguments_result(name: &str, error: &str) -> String {
+    serde_json::json!({
+        "error": "invalid_tool_arguments",
+        "tool": name,
+        "detail": format!("Failed to parse tool arguments: {}", error),
+        "hint": "Arguments must be one complete JSON object matching the tool's parameter schema. Re-issue the call with corrected arguments.",
+    })
+    .to_string()
+}
+
 // =============================================================================
 // ChatModelWorker
 // =============================================================================
@@ -282,6 +559,19 @@ pub struct ChatModelWorker {
     tool_definitions: Vec<serde_json::Value>,
     /// Snapshot of Settings -> Agent captured with the request.
     effective_agent_policy: EffectiveAgentPolicy,
+    /// Publisher the router prioritized for this request.
+    priority_publisher: Option<String>,
+    /// Read-only publisher tool forced as the first round's `tool_choice`.
+    forced_tool: Option<String>,
+    /// Where completed tool rounds are saved, and the round to resume from.
+    checkpoint: Option<WorkerCheckpoint>,
+    /// Per-turn trace of rounds, tool dispatch, and truncations.
+    trace: TurnTracer,
+    /// Identical failing calls before the model is nudged; 0 disables.
+    failure_repeat_threshold: usize,
+    /// Models a negative eval signal may escalate the turn to. Empty when the
+    /// user picked the model or route, which disables escalation.
+    escalation_models: Vec<String>,
 }
 
 impl ChatModelWorker {
@@ -297,6 +587,12 @@ impl ChatModelWorker {
             publisher_slug: DEFAULT_PUBLISHER_SLUG.to_string(),
             tool_definitions: Vec::new(),
             effective_agent_policy: EffectiveAgentPolicy::default(),
+            priority_publisher: None,
+            forced_tool: None,
+            checkpoint: None,
+            trace: TurnTracer::default(),
+            failure_repeat_threshold: DEFAULT_TOOL_FAILURE_REPEAT_THRESHOLD,
+            escalation_models: Vec::new(),
         }
     }
 
@@ -318,6 +614,112 @@ impl ChatModelWorker {
                 .unwrap_or_else(|| DEFAULT_PUBLISHER_SLUG.to_string()),
             tool_definitions: Self::inject_local_tool_definitions(tools),
             effective_agent_policy,
+            priority_publisher: None,
+            forced_tool: None,
+            checkpoint: None,
+            trace: TurnTracer::default(),
+            failure_repeat_threshold: DEFAULT_TOOL_FAILURE_REPEAT_THRESHOLD,
+            escalation_models: Vec::new(),
+        }
+    }
+
+    /// Lead the tool list with a connected publisher's tools so the model
+    /// reaches for them before general ones (browser, `seren_web_fetch`), and
+    /// force the first round onto the router's high-confidence read-only pick.
+    pub fn with_publisher_priority(mut self, priority: Option<PublisherToolPriority>) -> Self {
+        if let Some(priority) = priority {
+            let tools = std::mem::take(&mut self.tool_definitions);
+            self.tool_definitions = prioritize_publisher_tools(tools, &priority);
+            self.priority_publisher = Some(priority.publisher_slug);
+            self.forced_tool = priority.forced_tool;
+        }
+        self
+    }
+
+    /// Save each completed tool round under the orchestration id, and pick up
+    /// from `checkpoint.resume` instead of round 0 when it is set.
+    pub fn with_checkpoint(mut self, checkpoint: Option<WorkerCheckpoint>) -> Self {
+        self.checkpoint = checkpoint;
+        self
+    }
+
+    /// Nudge the model after `threshold` identical failing tool calls in a
+    /// row. None keeps the default; 0 turns the nudge off.
+    pub fn with_failure_repeat_threshold(mut self, threshold: Option<usize>) -> Self {
+        if let Some(threshold) = threshold {
+            self.failure_repeat_threshold = threshold;
+        }
+        self
+    }
+
+    /// Same-error failures in a row that abort the turn: at least
+    /// `REPEATED_TOOL_FAILURE_ABORT`, and one past the nudge threshold.
+    fn repeated_failure_abort_limit(&self) -> usize {
+        REPEATED_TOOL_FAILURE_ABORT.max(self.failure_repeat_threshold.saturating_add(1))
+    }
+
+    /// Allow a negative eval signal to move the turn to a stronger model
+    /// from `models`. Empty disables escalation.
+    pub fn with_escalation_models(mut self, models: Vec<String>) -> Self {
+        self.escalation_models = models;
+        self
+    }
+
+    /// Report rounds, tool dispatch, truncations, and the completion reason
+    /// on `trace` when the turn opted in.
+    pub fn with_trace(mut self, trace: TurnTracer) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    /// Persist the loop state after a completed round. Failures are logged;
+    /// a missing checkpoint only costs the ability to resume.
+    async fn save_round_checkpoint(&self, app: &tauri::AppHandle, progress: RoundCheckpoint) {
+        let Some(checkpoint) = &self.checkpoint else {
+            return;
+        };
+        let orchestration_id = checkpoint.orchestration_id.clone();
+        let round = progress.round;
+        let id_for_db = orchestration_id.clone();
+        let result = with_chat_db(app, move |conn| {
+            record_round(conn, &id_for_db, &progress, now_millis())
+        })
+        .await;
+        if let Err(e) = result {
+            log::warn!(
+                "[ChatModelWorker] Failed to checkpoint round {} of {}: {}",
+                round,
+                orchestration_id,
+                e
+            );
+        }
+    }
+
+    /// `tool_choice` for a request round. A tool forced by the routing
+    /// decision wins over the router's publisher pick; either applies to the
+    /// first round only, so the model can answer once it has the result.
+    /// Like the publisher pick, a routing-forced tool must only read.
+    fn tool_choice(
+        &self,
+        routing: &RoutingDecision,
+        round: usize,
+        tools: &[serde_json::Value],
+    ) -> serde_json::Value {
+        let routed = routing
+            .forced_tool
+            .as_ref()
+            .filter(|name| is_read_only_tool(name));
+        match routed.or(self.forced_tool.as_ref()) {
+            Some(name)
+                if round == 0
+                    && tools.iter().any(|tool| {
+                        tool.pointer("/function/name").and_then(|v| v.as_str())
+                            == Some(name.as_str())
+                    }) =>
+            {
+                serde_json::json!({"type": "function", "function": {"name": name}})
+            }
+            _ => serde_json::json!("auto"),
         }
     }
 
@@ -469,12 +871,18 @@ created if missing.",
                  the user explicitly supplies a different date.",
                 today_utc
             ),
-            "You are a helpful AI assistant running inside Seren Desktop. \
-             The user is already authenticated and all tool calls are pre-authenticated \
-             through the Seren Gateway — you do not need API keys, tokens, or environment \
-             variables to use any of your tools. Never ask the user to configure credentials \
-             or look for keys like SEREN_API_KEY. Just call the tools directly."
-                .to_string(),
+            format!(
+                "{} The user is already authenticated and all tool calls are pre-authenticated \
+                 through the Seren Gateway — you do not need API keys, tokens, or environment \
+                 variables to use any of your tools. Never ask the user to configure credentials \
+                 or look for keys like SEREN_API_KEY. Just call the tools directly.",
+                routing
+                    .system_prompt
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|prompt| !prompt.is_empty())
+                    .unwrap_or(DEFAULT_SYSTEM_PROMPT)
+            ),
             // File output rules (GH #1583, #1585) — tell the model how to
             // hit the user's requested path and format in one tool round.
             "File output rules:\n\
@@ -606,17 +1014,35 @@ created if missing.",
 
         if !tools.is_empty() {
             body["tools"] = serde_json::json!(tools);
-            body["tool_choice"] = serde_json::json!("auto");
+            body["tool_choice"] = self.tool_choice(routing, 0, tools);
         }
 
         // OpenRouter reasoning effort parameter (for models that support extended thinking)
         if let Some(ref effort) = routing.reasoning_effort {
             body["reasoning"] = serde_json::json!({ "effort": effort });
         }
+        Self::apply_generation_params(&mut body, routing);
 
         body
     }
 
+    /// Insert the routing's sampling parameters into a request body. Only the
+    /// fields that were set are written, so provider defaults stay in effect.
+    fn apply_generation_params(body: &mut serde_json::Value, routing: &RoutingDecision) {
+        let Some(params) = routing.generation.as_ref() else {
+            return;
+        };
+        if let Some(temperature) = params.temperature {
+            body["temperature"] = serde_json::json!(temperature);
+        }
+        if let Some(top_p) = params.top_p {
+            body["top_p"] = serde_json::json!(top_p);
+        }
+        if !params.stop.is_empty() {
+            body["stop"] = serde_json::json!(params.stop);
+        }
+    }
+
     /// Extract text from a content value that may be a string, an array of parts,
     /// or an object with a "text" field (Gemini returns array-of-parts format).
     fn normalize_content(value: &serde_json::Value) -> Option<String> {
@@ -805,7 +1231,7 @@ created if missing.",
                     tool_call_id: tc.id.clone(),
                     name: tc.name.clone(),
                     arguments: tc.arguments.clone(),
-                    title: tc.name.clone(),
+                    title: humanize_tool_title(&tc.name),
                 })
                 .await
                 .map_err(|e| format!("Failed to send tool call event: {}", e))?;
@@ -813,6 +1239,60 @@ created if missing.",
         Ok(())
     }
 
+    /// Repair each call's arguments and drop calls that repeat an earlier id.
+    /// Some providers stream duplicate ids across indices; executing both
+    /// would run the tool twice and answer one tool_call_id twice. A
+    /// duplicate replaces the earlier call only when it has usable arguments
+    /// and the earlier one does not.
+    fn normalize_tool_calls(
+        calls: impl IntoIterator<Item = AccumulatedToolCall>,
+    ) -> Vec<AccumulatedToolCall> {
+        let mut normalized: Vec<AccumulatedToolCall> = Vec::new();
+        for mut tc in calls {
+            match repair_tool_arguments(&tc.arguments) {
+                Ok(arguments) => {
+                    if arguments != tc.arguments {
+                        log::warn!(
+                            "[ChatModelWorker] Repaired malformed arguments for tool '{}' (id: {}): {}",
+                            tc.name,
+                            tc.id,
+                            &tc.arguments[..tc.arguments.floor_char_boundary(300)]
+                        );
+                        tc.arguments = arguments;
+                    }
+                }
+                Err(error) => {
+                    log::warn!(
+                        "[ChatModelWorker] Unrepairable arguments for tool '{}' (id: {}): {} — {}",
+                        tc.name,
+                        tc.id,
+                        error,
+                        &tc.arguments[..tc.arguments.floor_char_boundary(300)]
+                    );
+                    tc.argument_error = Some(error);
+                }
+            }
+
+            let duplicate = normalized
+                .iter_mut()
+                .find(|existing| !tc.id.is_empty() && existing.id == tc.id);
+            match duplicate {
+                Some(existing) => {
+                    log::warn!(
+                        "[ChatModelWorker] Dropping duplicate tool call id {} ({})",
+                        tc.id,
+                        tc.name
+                    );
+                    if existing.argument_error.is_some() && tc.argument_error.is_none() {
+                        *existing = tc;
+                    }
+                }
+                None => normalized.push(tc),
+            }
+        }
+        normalized
+    }
+
     /// Build a StreamOutcome from accumulated state.
     fn build_stream_outcome(
         finish_reason: &Option<String>,
@@ -825,7 +1305,7 @@ created if missing.",
             let mut indexed: Vec<(usize, AccumulatedToolCall)> =
                 pending_tool_calls.into_iter().collect();
             indexed.sort_by_key(|(idx, _)| *idx);
-            let tool_calls = indexed.into_iter().map(|(_, tc)| tc).collect();
+            let tool_calls = Self::normalize_tool_calls(indexed.into_iter().map(|(_, tc)| tc));
 
             StreamOutcome::ToolCallsPending {
                 tool_calls,
@@ -834,14 +1314,13 @@ created if missing.",
                 accumulated_cost: cost,
             }
         } else {
+            let truncated = finish_reason.as_deref() == Some("length");
             // Empty assistant turns destroy cross-turn context (#1812, #2002).
             // When the stream stopped because the model hit its output cap
             // (finish_reason="length") without producing any content, backfill
             // a recap so the user sees what happened and the next turn has
             // usable history. Same recap shape as the MAX_TOOL_ROUNDS path.
-            let final_content = if content.is_empty()
-                && finish_reason.as_deref() == Some("length")
-            {
+            let final_content = if content.is_empty() && truncated {
                 "(No response — model hit its output length cap. Try /compact or shortening the request and ask me to continue.)".to_string()
             } else {
                 content
@@ -854,6 +1333,7 @@ created if missing.",
                     Some(thinking)
                 },
                 cost,
+                truncated,
             }
         }
     }
@@ -881,6 +1361,7 @@ created if missing.",
                         id: String::new(),
                         name: String::new(),
                         arguments: String::new(),
+                        argument_error: None,
                     });
             if let Some(ref id) = chunk.id {
                 if !id.is_empty() {
@@ -1042,84 +1523,44 @@ created if missing.",
             accumulated_content.len()
         );
 
-        // Handle Gateway non-streaming wrapper format:
-        // The Gateway may return the entire response as a single JSON blob where
-        // the body field is a string containing embedded SSE data:
-        //   {"status":200,"body":"data: {...}\n\ndata: {...}\n\n...[DONE]","cost":"..."}
-        // In this case the buffer has no real newlines (SSE newlines are JSON escapes).
+        // The Gateway may return the whole response as one JSON wrapper rather
+        // than a stream; the line reader leaves all of it in the buffer.
         if !buffer.is_empty() && accumulated_content.is_empty() && last_finish_reason.is_none() {
-            if let Ok(wrapper) = serde_json::from_str::<serde_json::Value>(&buffer) {
-                let envelope = unwrap_data_response(&wrapper);
-
-                // Extract cost from the non-streaming wrapper
-                if let Some(wrapper_cost) = publisher_cost(&wrapper) {
-                    log::info!("[ChatModelWorker] Gateway reported cost: {}", wrapper_cost);
-                    accumulated_cost += wrapper_cost;
+            match parse_gateway_wrapper(&buffer) {
+                WrapperOutcome::NotWrapped => {}
+                WrapperOutcome::Failed {
+                    error,
+                    cost,
+                    retryable,
+                } => {
+                    accumulated_cost += cost;
+                    log::error!(
+                        "[ChatModelWorker] Non-streaming wrapper error: {} (retryable={})",
+                        error,
+                        retryable,
+                    );
+                    event_tx
+                        .send(WorkerEvent::Error {
+                            message: error.clone(),
+                        })
+                        .await
+                        .map_err(|e| format!("Failed to send error event: {}", e))?;
+                    return Ok(StreamOutcome::Failed {
+                        error,
+                        cost: accumulated_cost,
+                        retryable,
+                    });
                 }
-
-                // Check wrapper status for errors before processing body
-                if let Some(status) = publisher_status(&wrapper) {
-                    if status >= 400 {
-                        let error_msg = envelope
-                            .pointer("/body/error/message")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("Gateway API error");
-                        // Include raw provider error when available so the
-                        // orchestrator can detect context-overflow and reroute
-                        // to a large-context model.
-                        let raw_detail = envelope
-                            .pointer("/body/error/metadata/raw")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("");
-                        let full_error = if raw_detail.is_empty() {
-                            format!("HTTP {}: {}", status, error_msg)
-                        } else {
-                            format!("HTTP {}: {} — {}", status, error_msg, raw_detail)
-                        };
-                        let retryable = gateway_status_is_retryable(status);
-                        log::error!(
-                            "[ChatModelWorker] Non-streaming wrapper error: {} (retryable={})",
-                            full_error,
-                            retryable,
-                        );
-                        event_tx
-                            .send(WorkerEvent::Error {
-                                message: full_error.clone(),
-                            })
-                            .await
-                            .map_err(|e| format!("Failed to send error event: {}", e))?;
-                        return Ok(StreamOutcome::Failed {
-                            error: full_error,
-                            cost: accumulated_cost,
-                            retryable,
-                        });
+                WrapperOutcome::Payloads { data, cost } => {
+                    if cost > 0.0 {
+                        log::info!("[ChatModelWorker] Gateway reported cost: {}", cost);
                     }
-                }
-
-                if let Some(body_str) = envelope.get("body").and_then(|b| b.as_str()) {
+                    accumulated_cost += cost;
                     log::info!(
-                        "[ChatModelWorker] Gateway returned non-streaming wrapper, extracting embedded SSE ({} bytes)",
-                        body_str.len()
+                        "[ChatModelWorker] Gateway returned non-streaming wrapper with {} payloads",
+                        data.len()
                     );
-                    // Process the embedded SSE content
-                    for raw_line in body_str.split('\n') {
-                        let line = raw_line.trim();
-                        if line.is_empty() || line.starts_with(':') {
-                            continue;
-                        }
-                        let data = line
-                            .strip_prefix("data: ")
-                            .or_else(|| line.strip_prefix("data:"));
-                        let data_str = if let Some(d) = data {
-                            d
-                        } else if line.starts_with('{') {
-                            line
-                        } else {
-                            continue;
-                        };
-                        if data_str.trim() == "[DONE]" {
-                            break;
-                        }
+                    for data_str in &data {
                         let result = Self::parse_sse_data(data_str);
                         Self::process_parse_result(
                             &result,
@@ -1132,22 +1573,6 @@ created if missing.",
                         )
                         .await?;
                     }
-                } else if let Some(body_obj) = envelope.get("body") {
-                    // body is a JSON object (non-streaming response), extract content directly
-                    if body_obj.is_object() {
-                        log::info!("[ChatModelWorker] Gateway returned non-streaming JSON body");
-                        let result = Self::parse_sse_data(&body_obj.to_string());
-                        Self::process_parse_result(
-                            &result,
-                            &mut pending_tool_calls,
-                            &mut accumulated_content,
-                            &mut accumulated_thinking,
-                            &mut accumulated_cost,
-                            &mut last_finish_reason,
-                            event_tx,
-                        )
-                        .await?;
-                    }
                 }
             }
         }
@@ -1198,6 +1623,97 @@ created if missing.",
         truncated
     }
 
+    /// Tool result message for the model. Tool messages carry text only;
+    /// chat completions accepts image parts in user messages alone, so an
+    /// `attached` image goes into the round's `tool_images_message` and the
+    /// result just points at it.
+    fn tool_result_message(
+        tool_call_id: &str,
+        content: String,
+        image: Option<&ToolImage>,
+        attached: bool,
+        model_id: &str,
+    ) -> serde_json::Value {
+        let content = match image {
+            None => content,
+            Some(image) if attached => format!(
+                "{}\n\n[The tool returned an image ({}); it is attached after the tool results.]",
+                content, image.mime_type
+            ),
+            Some(image) => {
+                let kib = image.base64.len() * 3 / 4 / 1024;
+                let reason = if model_supports_vision(model_id) {
+                    "that was left out because this round's images exceed the context budget"
+                } else {
+                    "that this model cannot view"
+                };
+                format!(
+                    "{}\n\n[The tool returned an image ({}, ~{} KiB) {}.]",
+                    content, image.mime_type, kib, reason
+                )
+            }
+        };
+        serde_json::json!({
+            "role": "tool",
+            "tool_call_id": tool_call_id,
+            "content": content
+        })
+    }
+
+    /// User message carrying the images this round's tools returned, sent
+    /// after all of the round's tool results.
+    fn tool_images_message(images: &[ToolImage]) -> serde_json::Value {
+        let mut parts = vec![serde_json::json!({ "type": "text", "text": TOOL_IMAGES_HEADER })];
+        parts.extend(images.iter().map(|image| {
+            serde_json::json!({
+                "type": "image_url",
+                "image_url": {
+                    "url": format!("data:{};base64,{}", image.mime_type, image.base64)
+                }
+            })
+        }));
+        serde_json::json!({ "role": "user", "content": parts })
+    }
+
+    /// Replace earlier rounds' tool image messages with a short note, so
+    /// images are sent for one round only and never checkpointed.
+    fn drop_tool_images(messages: &mut [serde_json::Value]) {
+        for message in messages.iter_mut() {
+            if message["role"] == "user" && message["content"][0]["text"] == TOOL_IMAGES_HEADER {
+                *message = serde_json::json!({ "role": "user", "content": TOOL_IMAGES_DROPPED });
+            }
+        }
+    }
+
+    /// Rough prompt size in tokens at 4 characters per token, counting text,
+    /// tool-call arguments, and a flat cost per image. Drives the context
+    /// meter only; it is not a tokenizer.
+    fn estimate_prompt_tokens(messages: &[serde_json::Value]) -> usize {
+        let mut chars = 0;
+        let mut images = 0;
+        for message in messages {
+            match &message["content"] {
+                serde_json::Value::String(text) => chars += text.len(),
+                serde_json::Value::Array(parts) => {
+                    for part in parts {
+                        if part["type"] == "image_url" {
+                            images += 1;
+                        } else if let Some(text) = part["text"].as_str() {
+                            chars += text.len();
+                        }
+                    }
+                }
+                _ => {}
+            }
+            for call in message["tool_calls"].as_array().into_iter().flatten() {
+                let function = &call["function"];
+                chars += function["name"].as_str().map_or(0, str::len);
+                chars += function["arguments"].as_str().map_or(0, str::len);
+            }
+        }
+        chars.div_ceil(4) + images * IMAGE_TOKEN_ESTIMATE + messages.len() * MESSAGE_TOKEN_OVERHEAD
+    }
+
     /// Build the message list for a tool-call follow-up round.
     ///
     /// `messages` is laid out as `[system] + history.. + [current user prompt] +
@@ -1255,6 +1771,19 @@ created if missing.",
         )
     }
 
+    /// Where a tool call is executed, mirroring the dispatch in `execute`.
+    fn tool_dispatch_target(call: &AccumulatedToolCall) -> &'static str {
+        if call.argument_error.is_some() {
+            "rejected"
+        } else if Self::file_access_kind(&call.name).is_some() {
+            "local_file"
+        } else if Self::is_local_tool(&call.name) {
+            "local"
+        } else {
+            "frontend"
+        }
+    }
+
     /// Check if a tool is a file-read operation (for deduplication).
     fn is_file_read_tool(name: &str) -> bool {
         name == "read_file"
@@ -1422,7 +1951,7 @@ created if missing.",
 
     /// Track repeated identical tool failures within a single `execute()`
     /// invocation. Returns true after the same non-parse failure class repeats
-    /// 3 times consecutively. This catches raw diagnostic loops where the model
+    /// `limit` times consecutively. This catches raw diagnostic loops where the model
     /// keeps probing the same broken runtime path or shell command after the
     /// app has already supplied the relevant error.
     fn track_repeated_tool_failure_loop(
@@ -1430,6 +1959,7 @@ created if missing.",
         tool_name: &str,
         result_content: &str,
         is_error: bool,
+        limit: usize,
     ) -> bool {
         if !is_error {
             *tracker = None;
@@ -1450,7 +1980,7 @@ created if missing.",
         match tracker {
             Some((sig, count)) if sig == &signature => {
                 *count += 1;
-                *count >= 3
+                *count >= limit
             }
             _ => {
                 *tracker = Some((signature, 1));
@@ -1459,6 +1989,72 @@ created if missing.",
         }
     }
 
+    /// Count consecutive identical failing calls: same tool, same arguments,
+    /// and same error. Returns how many times in a row the current failure
+    /// has occurred, or 0 when the call succeeded.
+    fn track_identical_tool_failure(
+        tracker: &mut Option<(u64, usize)>,
+        tool_name: &str,
+        arguments: &str,
+        result_content: &str,
+        is_error: bool,
+    ) -> usize {
+        if !is_error {
+            *tracker = None;
+            return 0;
+        }
+        let mut hasher = DefaultHasher::new();
+        (tool_name, arguments, result_content).hash(&mut hasher);
+        let signature = hasher.finish();
+        match tracker {
+            Some((sig, count)) if *sig == signature => {
+                *count += 1;
+                *count
+            }
+            _ => {
+                *tracker = Some((signature, 1));
+                1
+            }
+        }
+    }
+
+    /// Note appended to a tool result once the same call has failed the same
+    /// way `repeats` times in a row.
+    fn repeated_failure_note(tool_name: &str, repeats: usize) -> String {
+        format!(
+            "\n\n[Note: this exact call to '{}' has now failed {} times in a row with the same error. \
+             Repeating it will not help. Try a different approach, or stop and tell the user what is blocking you.]",
+            tool_name, repeats
+        )
+    }
+
+    /// Append one round's reasoning to the turn-level accumulator, separating
+    /// rounds with a blank line.
+    fn append_thinking(turn_thinking: &mut String, round_thinking: &str) {
+        if round_thinking.is_empty() {
+            return;
+        }
+        if !turn_thinking.is_empty() {
+            turn_thinking.push_str("\n\n");
+        }
+        turn_thinking.push_str(round_thinking);
+    }
+
+    /// Combine reasoning from earlier tool rounds with the final round's.
+    fn finish_thinking(
+        mut turn_thinking: String,
+        final_thinking: Option<String>,
+    ) -> Option<String> {
+        if let Some(thinking) = final_thinking {
+            Self::append_thinking(&mut turn_thinking, &thinking);
+        }
+        if turn_thinking.is_empty() {
+            None
+        } else {
+            Some(turn_thinking)
+        }
+    }
+
     fn turn_guard_recap(
         total_cost: f64,
         tool_call_count: usize,
@@ -1537,8 +2133,15 @@ created if missing.",
                 if path.is_empty() {
                     return ("Missing required parameter: path".to_string(), true);
                 }
-                match crate::files::read_file(path) {
-                    Ok(content) => (content, false),
+                match crate::files::read_file_with_hash(path) {
+                    Ok(read) => (
+                        format!(
+                            "{}\n\n[file hash: {}. Pass it to write_file as expected_hash \
+so the write is refused if the file changed since this read.]",
+                            read.content, read.hash
+                        ),
+                        false,
+                    ),
                     Err(e) => (e, true),
                 }
             }
@@ -1555,10 +2158,11 @@ created if missing.",
             "write_file" => {
                 let path = args["path"].as_str().unwrap_or("").to_string();
                 let content = args["content"].as_str().unwrap_or("").to_string();
+                let expected_hash = args["expected_hash"].as_str().map(String::from);
                 if path.is_empty() {
                     return ("Missing required parameter: path".to_string(), true);
                 }
-                match crate::files::write_file(path.clone(), content) {
+                match crate::files::write_file(path.clone(), content, expected_hash) {
                     Ok(()) => (format!("Successfully wrote file: {}", path), false),
                     Err(e) => (e, true),
                 }
@@ -1613,9 +2217,23 @@ created if missing.",
                 if url.is_empty() {
                     return ("Missing required parameter: url".to_string(), true);
                 }
-                let timeout_ms = args["timeout_ms"].as_u64();
-                match crate::commands::web::web_fetch(url, timeout_ms).await {
-                    Ok(fetch_result) => (fetch_result.content, false),
+                let options = crate::commands::web::WebFetchOptions {
+                    timeout_ms: args["timeout_ms"].as_u64(),
+                    headers: serde_json::from_value(args["headers"].clone()).ok(),
+                    respect_robots: args["respect_robots"].as_bool().unwrap_or(false),
+                    mode: serde_json::from_value(args["mode"].clone()).unwrap_or_default(),
+                    max_bytes: args["max_bytes"].as_u64().map(|bytes| bytes as usize),
+                    allowed_content_types: serde_json::from_value(
+                        args["allowed_content_types"].clone(),
+                    )
+                    .ok(),
+                    max_redirects: args["max_redirects"].as_u64().map(|count| count as usize),
+                };
+                let user_agent = app
+                    .map(crate::commands::web::configured_user_agent)
+                    .unwrap_or_else(|| crate::commands::web::DEFAULT_USER_AGENT.to_string());
+                match crate::commands::web::fetch_url(&url, &user_agent, options).await {
+                    Ok(fetch_result) => (web_fetch_tool_output(&url, fetch_result), false),
                     Err(e) => (e, true),
                 }
             }
@@ -1677,15 +2295,16 @@ created if missing.",
 
     /// Route a non-local tool call to the frontend for execution via the tool bridge.
     ///
-    /// Emits an `orchestrator://tool-request` event, then waits for the frontend to
-    /// call `submit_tool_result` with the result.
+    /// Emits an `orchestrator://tool-request` event (carrying the timeout so the
+    /// UI can show a countdown), then waits for the frontend to call
+    /// `submit_tool_result` with the result, up to the tool's timeout.
     async fn execute_frontend_tool(
         app: &tauri::AppHandle,
         conversation_id: &str,
         tool_call_id: &str,
         name: &str,
         arguments: &str,
-    ) -> (String, bool) {
+    ) -> ToolExecutionResult {
         log::info!(
             "[ChatModelWorker] Frontend tool execution starting: {} (id: {}, args: {})",
             name,
@@ -1693,8 +2312,9 @@ created if missing.",
             &arguments[..arguments.floor_char_boundary(200)]
         );
 
+        let timeout_secs = frontend_tool_timeout_secs(name);
         let bridge = app.state::<ToolResultBridge>();
-        let rx = bridge.register(tool_call_id).await;
+        let rx = bridge.register(conversation_id, tool_call_id).await;
         log::debug!(
             "[ChatModelWorker] Tool bridge registered for {}",
             tool_call_id
@@ -1709,6 +2329,7 @@ created if missing.",
             "tool_call_id": tool_call_id,
             "name": name,
             "arguments": arguments,
+            "timeout_secs": timeout_secs,
         });
         if let Err(e) = app.emit("orchestrator://tool-request", &payload) {
             log::error!(
@@ -1716,30 +2337,55 @@ created if missing.",
                 name,
                 e
             );
-            return (format!("Failed to request tool execution: {}", e), true);
+            return ToolExecutionResult {
+                content: format!("Failed to request tool execution: {}", e),
+                is_error: true,
+                image: None,
+            };
         }
         log::debug!(
-            "[ChatModelWorker] Tool request emitted, waiting for frontend result (no timeout)"
+            "[ChatModelWorker] Tool request emitted, waiting up to {}s for frontend result",
+            timeout_secs
         );
 
-        // Wait for the frontend to submit the result (no timeout — user may need time to review)
-        match rx.await {
-            Ok(result) => {
+        match tokio::time::timeout(Duration::from_secs(timeout_secs), rx).await {
+            Ok(Ok(result)) => {
                 log::info!(
                     "[ChatModelWorker] Frontend tool completed: {} (is_error={}, result_len={})",
                     name,
                     result.is_error,
                     result.content.len()
                 );
-                (result.content, result.is_error)
+                result
             }
-            Err(_) => {
+            Ok(Err(_)) => {
                 // Sender was dropped (bridge cleaned up or cancelled)
                 log::warn!(
                     "[ChatModelWorker] Tool result channel closed for {} — bridge cleaned up or cancelled",
                     name
                 );
-                ("Tool execution was cancelled".to_string(), true)
+                ToolExecutionResult {
+                    content: CANCELLED_TOOL_RESULT.to_string(),
+                    is_error: true,
+                    image: None,
+                }
+            }
+            Err(_) => {
+                // Drop the registration so a late submit is ignored.
+                bridge.unregister(tool_call_id).await;
+                log::warn!(
+                    "[ChatModelWorker] Frontend tool {} timed out after {}s",
+                    name,
+                    timeout_secs
+                );
+                ToolExecutionResult {
+                    content: format!(
+                        "Tool '{}' timed out after {}s waiting for a result",
+                        name, timeout_secs
+                    ),
+                    is_error: true,
+                    image: None,
+                }
             }
         }
     }
@@ -1780,12 +2426,14 @@ impl Worker for ChatModelWorker {
 
         // Select tools relevant to this query via BM25 scoring.
         // Model-aware budgets (Phase 1), publisher-set scoping (Phase 2),
-        // and conversation-aware boosting (Phase 3).
-        let budgeted_tools = tool_relevance::select_relevant_tools(
+        // and conversation-aware boosting (Phase 3). The router's priority
+        // publisher is scoped in first, inside the same budget.
+        let budgeted_tools = tool_relevance::select_relevant_tools_with_priority(
             prompt,
             &self.tool_definitions,
             &routing.model_id,
             &recent_publishers,
+            self.priority_publisher.as_deref(),
         );
 
         log::info!(
@@ -1833,6 +2481,9 @@ impl Worker for ChatModelWorker {
         let mut file_access_grants: HashSet<(FileAccessKind, std::path::PathBuf)> = HashSet::new();
 
         let mut total_cost: f64 = 0.0;
+        // Reasoning from tool-call rounds, carried into the final Complete so
+        // "show reasoning" covers the whole turn rather than the last round.
+        let mut turn_thinking = String::new();
 
         // Track repeated identical parse-error tool calls so we can break out
         // of an infinite retry storm where the model is not correcting the
@@ -1841,26 +2492,107 @@ impl Worker for ChatModelWorker {
         // assistant turns wipe cross-turn context for the next user prompt.
         let mut parse_error_tracker: Option<(String, usize)> = None;
         let mut repeated_failure_tracker: Option<(String, usize)> = None;
+        let mut identical_failure_tracker: Option<(u64, usize)> = None;
         let mut tool_call_count: usize = 0;
         let mut tool_failure_count: usize = 0;
 
         // Track where the current prompt's messages start (after system + history).
         // On tool-call rounds (1+), history is trimmed down to a recent tail to cut
         // prompt tokens (#1433) — see `trim_history_for_tool_round`.
-        let current_prompt_start = messages.len().saturating_sub(1); // user message index
+        let mut current_prompt_start = messages.len().saturating_sub(1); // user message index
+
+        // May change mid-turn when a negative eval signal requests escalation.
+        let mut model_id = routing.model_id.clone();
+
+        // A resumed orchestration continues after its last checkpointed round
+        // with the messages and spend it had then.
+        let first_round = match self.checkpoint.as_ref().and_then(|c| c.resume.clone()) {
+            Some(resume) => {
+                log::info!(
+                    "[ChatModelWorker] Resuming at round {} with {} messages",
+                    resume.round,
+                    resume.messages.len()
+                );
+                messages = resume.messages;
+                current_prompt_start = resume.prompt_start;
+                total_cost = resume.total_cost;
+                tool_call_count = resume.tool_call_count;
+                tool_failure_count = resume.tool_failure_count;
+                resume.round.min(MAX_TOOL_ROUNDS)
+            }
+            None => 0,
+        };
 
-        for round in 0..=MAX_TOOL_ROUNDS {
+        for round in first_round..=MAX_TOOL_ROUNDS {
             // Check cancellation
             if *self.cancelled.lock().await {
                 return Ok(());
             }
 
-            // On tool-call follow-up rounds, drop all but a recent tail of history.
-            let round_messages = if round > 0 {
-                Self::trim_history_for_tool_round(&messages, current_prompt_start)
+            // A negative eval signal submitted mid-turn moves the next tool
+            // round to a stronger model. Private-model threads never leave
+            // their deployment.
+            let escalate_to = if round > 0
+                && self.publisher_slug == DEFAULT_PUBLISHER_SLUG
+                && app
+                    .try_state::<EvalState>()
+                    .is_some_and(|eval| eval.take_escalation(conversation_id))
+            {
+                escalation_model(&model_id, &self.escalation_models)
             } else {
-                messages.clone()
+                None
             };
+            if let Some(stronger) = escalate_to {
+                log::info!(
+                    "[ChatModelWorker] Escalating from {} to {} after negative eval signal",
+                    model_id,
+                    stronger
+                );
+                let _ = event_tx
+                    .send(WorkerEvent::Reroute {
+                        from_model: model_id.clone(),
+                        to_model: stronger.clone(),
+                        reason: "Escalated to a stronger model after negative feedback".to_string(),
+                    })
+                    .await;
+                model_id = stronger;
+                if let Some(eval) = app.try_state::<EvalState>() {
+                    eval.mark_escalated(conversation_id, &model_id);
+                }
+            }
+
+            // An escalation may have moved the turn to a model that cannot
+            // view the previous round's tool images.
+            if !model_supports_vision(&model_id) {
+                Self::drop_tool_images(&mut messages);
+            }
+
+            // On tool-call follow-up rounds, drop all but a recent tail of history.
+            let round_messages = if round > 0 {
+                Self::trim_history_for_tool_round(&messages, current_prompt_start)
+            } else {
+                messages.clone()
+            };
+            self.trace.emit(
+                "round_start",
+                serde_json::json!({
+                    "round": round,
+                    "model": model_id,
+                    "messages": round_messages.len(),
+                    "tools": tools.len(),
+                }),
+            );
+            if round_messages.len() < messages.len() {
+                self.trace.emit(
+                    "truncation",
+                    serde_json::json!({
+                        "kind": "history",
+                        "round": round,
+                        "from_messages": messages.len(),
+                        "to_messages": round_messages.len(),
+                    }),
+                );
+            }
             if round > 0 {
                 log::info!(
                     "[ChatModelWorker] Round {} sending {} of {} messages ({} history retained)",
@@ -1873,16 +2605,24 @@ impl Worker for ChatModelWorker {
                 );
             }
 
+            let _ = event_tx
+                .send(WorkerEvent::ContextUsage {
+                    estimated_tokens: Self::estimate_prompt_tokens(&round_messages),
+                    model_context_window: model_context_window_tokens(&model_id),
+                })
+                .await;
+
             // Build request body
             let mut body = serde_json::json!({
-                "model": routing.model_id,
+                "model": model_id,
                 "messages": round_messages,
                 "stream": true
             });
             if !tools.is_empty() {
                 body["tools"] = serde_json::json!(tools);
-                body["tool_choice"] = serde_json::json!("auto");
+                body["tool_choice"] = self.tool_choice(routing, round, tools);
             }
+            Self::apply_generation_params(&mut body, routing);
             // Cap output tokens on tool-call rounds — tool selections are small.
             if round > 0 {
                 body["max_tokens"] = serde_json::json!(4096);
@@ -1939,6 +2679,7 @@ impl Worker for ChatModelWorker {
                     final_content,
                     thinking,
                     cost,
+                    truncated,
                 } => {
                     total_cost += cost;
                     let total = if total_cost > 0.0 {
@@ -1952,12 +2693,17 @@ impl Worker for ChatModelWorker {
                         final_content.len(),
                         total
                     );
+                    self.trace.emit(
+                        "complete",
+                        serde_json::json!({ "reason": if truncated { "length" } else { "stop" }, "round": round }),
+                    );
                     if let Err(e) = event_tx
                         .send(WorkerEvent::Complete {
                             final_content,
-                            thinking,
+                            thinking: Self::finish_thinking(turn_thinking, thinking),
                             cost: total,
                             rlm_steps: None,
+                            truncated,
                         })
                         .await
                     {
@@ -1973,7 +2719,7 @@ impl Worker for ChatModelWorker {
                 StreamOutcome::ToolCallsPending {
                     tool_calls,
                     accumulated_content,
-                    accumulated_thinking: _,
+                    accumulated_thinking,
                     accumulated_cost,
                 } => {
                     log::info!(
@@ -1982,21 +2728,27 @@ impl Worker for ChatModelWorker {
                         tool_calls.len()
                     );
                     total_cost += accumulated_cost;
+                    Self::append_thinking(&mut turn_thinking, &accumulated_thinking);
 
                     if let Some(recap) =
                         Self::turn_guard_recap(total_cost, tool_call_count, tool_failure_count)
                     {
                         log::warn!("[ChatModelWorker] Turn guard triggered before tool execution");
+                        self.trace.emit(
+                            "complete",
+                            serde_json::json!({ "reason": "turn_guard", "round": round }),
+                        );
                         event_tx
                             .send(WorkerEvent::Complete {
                                 final_content: recap,
-                                thinking: None,
+                                thinking: Self::finish_thinking(turn_thinking, None),
                                 cost: if total_cost > 0.0 {
                                     Some(total_cost)
                                 } else {
                                     None
                                 },
                                 rlm_steps: None,
+                                truncated: false,
                             })
                             .await
                             .map_err(|e| format!("Failed to send Complete event: {}", e))?;
@@ -2008,6 +2760,10 @@ impl Worker for ChatModelWorker {
                             "[ChatModelWorker] Max tool rounds ({}) reached, forcing completion",
                             MAX_TOOL_ROUNDS
                         );
+                        self.trace.emit(
+                            "complete",
+                            serde_json::json!({ "reason": "max_tool_rounds", "round": round }),
+                        );
                         // Empty assistant turns destroy cross-turn context
                         // (#1812). When the model never produced text but did
                         // run tools, backfill a recap so the next user prompt
@@ -2024,13 +2780,14 @@ impl Worker for ChatModelWorker {
                         event_tx
                             .send(WorkerEvent::Complete {
                                 final_content,
-                                thinking: None,
+                                thinking: Self::finish_thinking(turn_thinking, None),
                                 cost: if total_cost > 0.0 {
                                     Some(total_cost)
                                 } else {
                                     None
                                 },
                                 rlm_steps: None,
+                                truncated: false,
                             })
                             .await
                             .map_err(|e| format!("Failed to send Complete event: {}", e))?;
@@ -2068,6 +2825,8 @@ impl Worker for ChatModelWorker {
                     messages.push(assistant_msg);
 
                     // Execute each tool and build result messages
+                    let mut round_images: Vec<ToolImage> = Vec::new();
+                    let mut round_image_bytes = 0;
                     for tc in &tool_calls {
                         // Check cancellation between tool executions
                         if *self.cancelled.lock().await {
@@ -2082,9 +2841,20 @@ impl Worker for ChatModelWorker {
                             tc.name,
                             tc.id
                         );
+                        self.trace.emit(
+                            "tool_dispatch",
+                            serde_json::json!({
+                                "round": round,
+                                "tool": tc.name,
+                                "tool_call_id": tc.id,
+                                "target": Self::tool_dispatch_target(tc),
+                            }),
+                        );
 
-                        let (result_content, is_error) = if Self::file_access_kind(&tc.name).is_some()
-                        {
+                        let mut result_image = None;
+                        let (result_content, is_error) = if let Some(error) = &tc.argument_error {
+                            (invalid_tool_arguments_result(&tc.name, error), true)
+                        } else if Self::file_access_kind(&tc.name).is_some() {
                             match &file_access_policy {
                                 Ok(policy) => {
                                     Self::execute_model_file_tool(
@@ -2104,14 +2874,16 @@ impl Worker for ChatModelWorker {
                         } else {
                             // Route non-local tools (gateway__, mcp__)
                             // to the frontend for execution via the tool bridge.
-                            Self::execute_frontend_tool(
+                            let result = Self::execute_frontend_tool(
                                 app,
                                 conversation_id,
                                 &tc.id,
                                 &tc.name,
                                 &tc.arguments,
                             )
-                            .await
+                            .await;
+                            result_image = result.image;
+                            (result.content, result.is_error)
                         };
 
                         // Emit ToolResult event to frontend
@@ -2147,21 +2919,75 @@ impl Worker for ChatModelWorker {
 
                         // Truncate tool result for LLM context to prevent
                         // unbounded payload growth that causes upstream 408s.
-                        let context_content = Self::truncate_for_context(&deduped_content, &tc.name);
+                        let mut context_content =
+                            Self::truncate_for_context(&deduped_content, &tc.name);
+                        if context_content.len() < deduped_content.len() {
+                            self.trace.emit(
+                                "truncation",
+                                serde_json::json!({
+                                    "kind": "tool_result",
+                                    "round": round,
+                                    "tool": tc.name,
+                                    "from_bytes": deduped_content.len(),
+                                    "to_bytes": context_content.len(),
+                                }),
+                            );
+                        }
+
+                        // A model that repeats a call which keeps failing the
+                        // same way is told so before it burns another round.
+                        let repeats = Self::track_identical_tool_failure(
+                            &mut identical_failure_tracker,
+                            &tc.name,
+                            &tc.arguments,
+                            &result_content,
+                            is_error,
+                        );
+                        if self.failure_repeat_threshold > 0
+                            && repeats >= self.failure_repeat_threshold
+                        {
+                            log::warn!(
+                                "[ChatModelWorker] Tool '{}' failed identically {} times in a row",
+                                tc.name,
+                                repeats
+                            );
+                            self.trace.emit(
+                                "repeated_tool_failure",
+                                serde_json::json!({
+                                    "round": round,
+                                    "tool": tc.name,
+                                    "repeats": repeats,
+                                }),
+                            );
+                            context_content
+                                .push_str(&Self::repeated_failure_note(&tc.name, repeats));
+                        }
 
                         // Add tool result message for the next API call
-                        messages.push(serde_json::json!({
-                            "role": "tool",
-                            "tool_call_id": tc.id,
-                            "content": context_content
-                        }));
+                        let attach_image = result_image.as_ref().is_some_and(|image| {
+                            model_supports_vision(&model_id)
+                                && round_image_bytes + image.base64.len()
+                                    <= MAX_TOOL_IMAGE_CONTEXT_BYTES
+                        });
+                        messages.push(Self::tool_result_message(
+                            &tc.id,
+                            context_content,
+                            result_image.as_ref(),
+                            attach_image,
+                            &model_id,
+                        ));
+                        if attach_image && let Some(image) = result_image {
+                            round_image_bytes += image.base64.len();
+                            round_images.push(image);
+                        }
 
                         tool_call_count += 1;
                         if is_error {
                             tool_failure_count += 1;
                         }
-                        let is_parse_error =
-                            is_error && result_content.starts_with("Failed to parse tool arguments");
+                        let is_parse_error = tc.argument_error.is_some()
+                            || (is_error
+                                && result_content.starts_with("Failed to parse tool arguments"));
                         if Self::track_tool_arg_parse_loop(
                             &mut parse_error_tracker,
                             &tc.name,
@@ -2178,6 +3004,10 @@ impl Worker for ChatModelWorker {
                                 "[ChatModelWorker] Parse-error loop detected for tool '{}'. Aborting with recap.",
                                 tc.name
                             );
+                            self.trace.emit(
+                                "complete",
+                                serde_json::json!({ "reason": "parse_error_loop", "round": round }),
+                            );
                             let total = if total_cost > 0.0 {
                                 Some(total_cost)
                             } else {
@@ -2189,6 +3019,7 @@ impl Worker for ChatModelWorker {
                                     thinking: None,
                                     cost: total,
                                     rlm_steps: None,
+                                    truncated: false,
                                 })
                                 .await;
                             return Ok(());
@@ -2199,18 +3030,26 @@ impl Worker for ChatModelWorker {
                                 &tc.name,
                                 &result_content,
                                 is_error,
+                                self.repeated_failure_abort_limit(),
                             )
                         {
                             let recap = format!(
-                                "(Aborted: tool '{}' returned the same error 3 times in a row. \
+                                "(Aborted: tool '{}' returned the same error {} times in a row. \
                                  Continuing would likely keep burning funds on the same failed diagnostic. \
                                  {} tool calls fired this turn, {} failed.)",
-                                tc.name, tool_call_count, tool_failure_count
+                                tc.name,
+                                self.repeated_failure_abort_limit(),
+                                tool_call_count,
+                                tool_failure_count
                             );
                             log::warn!(
                                 "[ChatModelWorker] Repeated failure loop detected for tool '{}'. Aborting with recap.",
                                 tc.name
                             );
+                            self.trace.emit(
+                                "complete",
+                                serde_json::json!({ "reason": "repeated_tool_failure", "round": round }),
+                            );
                             let _ = event_tx
                                 .send(WorkerEvent::Complete {
                                     final_content: recap,
@@ -2221,6 +3060,7 @@ impl Worker for ChatModelWorker {
                                         None
                                     },
                                     rlm_steps: None,
+                                    truncated: false,
                                 })
                                 .await;
                             return Ok(());
@@ -2231,6 +3071,10 @@ impl Worker for ChatModelWorker {
                             log::warn!(
                                 "[ChatModelWorker] Turn guard triggered after tool execution"
                             );
+                            self.trace.emit(
+                                "complete",
+                                serde_json::json!({ "reason": "turn_guard", "round": round }),
+                            );
                             let _ = event_tx
                                 .send(WorkerEvent::Complete {
                                     final_content: recap,
@@ -2241,17 +3085,37 @@ impl Worker for ChatModelWorker {
                                         None
                                     },
                                     rlm_steps: None,
+                                    truncated: false,
                                 })
                                 .await;
                             return Ok(());
                         }
                     }
 
+                    // Only the latest round's images stay in context.
+                    Self::drop_tool_images(&mut messages);
+                    let checkpoint_messages = messages.clone();
+                    if !round_images.is_empty() {
+                        messages.push(Self::tool_images_message(&round_images));
+                    }
+
                     log::info!(
                         "[ChatModelWorker] All tools executed for round {}, continuing to next round with {} messages",
                         round,
                         messages.len()
                     );
+                    self.save_round_checkpoint(
+                        app,
+                        RoundCheckpoint {
+                            round: round + 1,
+                            messages: checkpoint_messages,
+                            prompt_start: current_prompt_start,
+                            total_cost,
+                            tool_call_count,
+                            tool_failure_count,
+                        },
+                    )
+                    .await;
                 }
                 StreamOutcome::Failed {
                     error,
@@ -2271,6 +3135,14 @@ impl Worker for ChatModelWorker {
                         retryable,
                         total
                     );
+                    self.trace.emit(
+                        "complete",
+                        serde_json::json!({
+                            "reason": "stream_failed",
+                            "round": round,
+                            "retryable": retryable,
+                        }),
+                    );
                     // The error event was already forwarded by stream_response,
                     // so the destructive UI is already showing. Send a final
                     // Complete event to clear the loading spinner — but mark
@@ -2290,9 +3162,10 @@ impl Worker for ChatModelWorker {
                     if let Err(e) = event_tx
                         .send(WorkerEvent::Complete {
                             final_content: failed_final_content,
-                            thinking: None,
+                            thinking: Self::finish_thinking(turn_thinking, None),
                             cost: total,
                             rlm_steps: None,
+                            truncated: false,
                         })
                         .await
                     {
@@ -2333,6 +3206,22 @@ struct ChatCompletionRequest {
     tool_choice: Option<String>,
 }
 
+/// `seren_web_fetch` tool output: the page content, led by the redirect
+/// chain when there was one so shorteners and auth walls are visible. Matches
+/// the renderer's executor.
+fn web_fetch_tool_output(
+    requested_url: &str,
+    result: crate::commands::web::WebFetchResult,
+) -> String {
+    if result.redirects.is_empty() {
+        return result.content;
+    }
+    let chain: Vec<&str> = std::iter::once(requested_url)
+        .chain(result.redirects.iter().map(String::as_str))
+        .collect();
+    format!("Redirected: {}\n{}", chain.join(" -> "), result.content)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2354,6 +3243,55 @@ mod tests {
         (messages, current_prompt_start)
     }
 
+    #[test]
+    fn tool_images_travel_in_a_user_message_and_are_dropped_later() {
+        let screenshot = ToolImage {
+            mime_type: "image/png".to_string(),
+            base64: "A".repeat(4096),
+        };
+
+        let attached = ChatModelWorker::tool_result_message(
+            "call_1",
+            "Took a screenshot".to_string(),
+            Some(&screenshot),
+            true,
+            "openai/gpt-4o",
+        );
+        assert_eq!(attached["role"], "tool");
+        let content = attached["content"].as_str().unwrap();
+        assert!(content.starts_with("Took a screenshot"));
+        assert!(content.contains("attached after the tool results"));
+
+        let text_only = ChatModelWorker::tool_result_message(
+            "call_1",
+            "Took a screenshot".to_string(),
+            Some(&screenshot),
+            false,
+            "deepseek/deepseek-chat",
+        );
+        let content = text_only["content"].as_str().unwrap();
+        assert!(content.contains("image (image/png, ~3 KiB) that this model cannot view"));
+
+        let plain =
+            ChatModelWorker::tool_result_message("call_1", "ok".to_string(), None, false, "x");
+        assert_eq!(plain["content"], "ok");
+
+        let images = ChatModelWorker::tool_images_message(std::slice::from_ref(&screenshot));
+        assert_eq!(images["role"], "user");
+        assert_eq!(images["content"][1]["type"], "image_url");
+        assert!(
+            images["content"][1]["image_url"]["url"]
+                .as_str()
+                .unwrap()
+                .starts_with("data:image/png;base64,AAAA")
+        );
+
+        let mut messages = vec![attached, images];
+        ChatModelWorker::drop_tool_images(&mut messages);
+        assert_eq!(messages[0]["role"], "tool");
+        assert_eq!(messages[1]["content"], TOOL_IMAGES_DROPPED);
+    }
+
     #[test]
     fn tool_round_keeps_recent_history() {
         let history: Vec<serde_json::Value> = (0..40)
@@ -2412,6 +3350,30 @@ mod tests {
         assert_eq!(trimmed, messages);
     }
 
+    #[test]
+    fn prompt_token_estimate_counts_text_tool_calls_and_images() {
+        let image_url = format!("data:image/png;base64,{}", "A".repeat(100_000));
+        let messages = vec![
+            serde_json::json!({"role": "system", "content": "a".repeat(400)}),
+            serde_json::json!({"role": "user", "content": [
+                {"type": "text", "text": "b".repeat(40)},
+                {"type": "image_url", "image_url": {"url": image_url}},
+            ]}),
+            serde_json::json!({"role": "assistant", "content": null, "tool_calls": [{
+                "id": "call_1",
+                "type": "function",
+                "function": {"name": "read_file", "arguments": "{\"path\":\"x\"}"},
+            }]}),
+        ];
+
+        // 400 + 40 + 9 + 12 chars -> 116 tokens, one image, three messages.
+        assert_eq!(
+            ChatModelWorker::estimate_prompt_tokens(&messages),
+            116 + IMAGE_TOKEN_ESTIMATE + 3 * MESSAGE_TOKEN_OVERHEAD
+        );
+        assert_eq!(ChatModelWorker::estimate_prompt_tokens(&[]), 0);
+    }
+
     #[test]
     fn builds_correct_request_body() {
         let worker = ChatModelWorker::new();
@@ -2423,7 +3385,10 @@ mod tests {
             selected_skills: vec![],
             publisher_slug: None,
             reasoning_effort: None,
+            generation: None,
             project_root: None,
+            forced_tool: None,
+            system_prompt: None,
         };
 
         let body = worker.build_request_body(
@@ -2461,7 +3426,10 @@ mod tests {
             selected_skills: vec![],
             publisher_slug: None,
             reasoning_effort: None,
+            generation: None,
             project_root: None,
+            forced_tool: None,
+            system_prompt: None,
         };
 
         let body =
@@ -2499,7 +3467,10 @@ mod tests {
             selected_skills: vec![],
             publisher_slug: None,
             reasoning_effort: None,
+            generation: None,
             project_root: None,
+            forced_tool: None,
+            system_prompt: None,
         };
 
         let body = worker.build_request_body(
@@ -2517,6 +3488,41 @@ mod tests {
         assert!(system_msg.contains("Prose"));
     }
 
+    #[test]
+    fn custom_system_prompt_replaces_default_persona_and_keeps_skills() {
+        let worker = ChatModelWorker::new();
+        let routing = RoutingDecision {
+            worker_type: super::super::types::WorkerType::ChatModel,
+            model_id: "anthropic/claude-sonnet-4".to_string(),
+            delegation: super::super::types::DelegationType::InLoop,
+            reason: "General chat".to_string(),
+            selected_skills: vec![],
+            publisher_slug: None,
+            reasoning_effort: None,
+            generation: None,
+            project_root: None,
+            forced_tool: None,
+            system_prompt: Some("You are a meticulous writing assistant.".to_string()),
+        };
+
+        let body = worker.build_request_body(
+            "Hello",
+            &[],
+            &routing,
+            "# Active Skills\n\n## Skill: Prose",
+            &[],
+            &[],
+            None,
+        );
+
+        let system_msg = body["messages"][0]["content"].as_str().unwrap();
+        assert!(system_msg.contains("You are a meticulous writing assistant."));
+        assert!(!system_msg.contains(DEFAULT_SYSTEM_PROMPT));
+        let persona = system_msg.find("meticulous writing assistant").unwrap();
+        let skills = system_msg.find("# Active Skills").unwrap();
+        assert!(persona < skills, "skills are appended after the persona");
+    }
+
     #[test]
     fn builds_request_with_tools() {
         let worker = ChatModelWorker::new();
@@ -2528,7 +3534,10 @@ mod tests {
             selected_skills: vec![],
             publisher_slug: None,
             reasoning_effort: None,
+            generation: None,
             project_root: None,
+            forced_tool: None,
+            system_prompt: None,
         };
 
         let tools = vec![serde_json::json!({
@@ -2546,6 +3555,121 @@ mod tests {
         assert_eq!(body["tool_choice"], "auto");
     }
 
+    #[test]
+    fn forces_routing_tool_on_first_round_only() {
+        let worker = ChatModelWorker::new();
+        let mut routing = RoutingDecision {
+            worker_type: super::super::types::WorkerType::ChatModel,
+            model_id: "anthropic/claude-sonnet-4".to_string(),
+            delegation: super::super::types::DelegationType::InLoop,
+            reason: "Research".to_string(),
+            selected_skills: vec![],
+            publisher_slug: None,
+            reasoning_effort: None,
+            generation: None,
+            project_root: None,
+            forced_tool: Some("seren_web_fetch".to_string()),
+            system_prompt: None,
+        };
+        let tools = vec![serde_json::json!({
+            "type": "function",
+            "function": {
+                "name": "seren_web_fetch",
+                "description": "Fetch a URL",
+                "parameters": {"type": "object", "properties": {}}
+            }
+        })];
+
+        let body =
+            worker.build_request_body("Research Rust 2024", &[], &routing, "", &tools, &[], None);
+        assert_eq!(
+            body["tool_choice"],
+            serde_json::json!({"type": "function", "function": {"name": "seren_web_fetch"}})
+        );
+        assert_eq!(worker.tool_choice(&routing, 1, &tools), "auto");
+
+        // A forced tool that was not offered would be rejected by the provider.
+        routing.forced_tool = Some("web_search".to_string());
+        assert_eq!(worker.tool_choice(&routing, 0, &tools), "auto");
+
+        // Tools that write are never forced, even when offered.
+        let send = serde_json::json!({
+            "type": "function",
+            "function": {"name": "gateway__gmail__send_message", "parameters": {}}
+        });
+        let tools = vec![tools[0].clone(), send];
+        routing.forced_tool = Some("gateway__gmail__send_message".to_string());
+        assert_eq!(worker.tool_choice(&routing, 0, &tools), "auto");
+        routing.forced_tool = Some("seren_web_fetch".to_string());
+        assert_eq!(
+            worker.tool_choice(&routing, 0, &tools)["function"]["name"],
+            "seren_web_fetch"
+        );
+    }
+
+    #[test]
+    fn web_fetch_output_leads_with_the_redirect_chain() {
+        let fetched = |redirects: Vec<&str>| crate::commands::web::WebFetchResult {
+            content: "# Page".to_string(),
+            content_type: "text/html".to_string(),
+            url: redirects
+                .last()
+                .copied()
+                .unwrap_or("https://a.example")
+                .to_string(),
+            status: 200,
+            truncated: false,
+            redirects: redirects.into_iter().map(str::to_string).collect(),
+            title: None,
+            byline: None,
+            excerpt: None,
+        };
+        assert_eq!(
+            web_fetch_tool_output("https://a.example", fetched(vec![])),
+            "# Page"
+        );
+        assert_eq!(
+            web_fetch_tool_output(
+                "https://a.example",
+                fetched(vec!["https://b.example", "https://c.example/final"])
+            ),
+            "Redirected: https://a.example -> https://b.example -> https://c.example/final\n# Page"
+        );
+    }
+
+    #[test]
+    fn passes_generation_params_only_when_set() {
+        let worker = ChatModelWorker::new();
+        let mut routing = RoutingDecision {
+            worker_type: super::super::types::WorkerType::ChatModel,
+            model_id: "openai/gpt-4o".to_string(),
+            delegation: super::super::types::DelegationType::InLoop,
+            reason: "Deterministic extraction".to_string(),
+            selected_skills: vec![],
+            publisher_slug: None,
+            reasoning_effort: None,
+            generation: None,
+            project_root: None,
+            forced_tool: None,
+            system_prompt: None,
+        };
+
+        let body = worker.build_request_body("Hi", &[], &routing, "", &[], &[], None);
+        assert!(body.get("temperature").is_none());
+        assert!(body.get("top_p").is_none());
+        assert!(body.get("stop").is_none());
+
+        routing.generation = Some(super::super::types::GenerationParams {
+            temperature: Some(0.0),
+            top_p: None,
+            stop: vec!["END".to_string()],
+        });
+        let body = worker.build_request_body("Hi", &[], &routing, "", &[], &[], None);
+        assert_eq!(body["temperature"], 0.0);
+        assert!(body.get("top_p").is_none());
+        assert_eq!(body["stop"], serde_json::json!(["END"]));
+    }
+
     #[test]
     fn parses_content_sse_data() {
         let data = r#"{"choices":[{"delta":{"content":"Hello"},"finish_reason":null}]}"#;
@@ -2684,7 +3808,7 @@ mod tests {
         let (id, name, args, title) = &tool_calls[0];
         assert_eq!(id, "tc_abc");
         assert_eq!(name, "execute_command");
-        assert_eq!(title, "execute_command");
+        assert_eq!(title, "Execute Command");
         assert_eq!(
             args, r#"{"command":"ls /tmp"}"#,
             "emitted arguments must be the fully concatenated JSON, not the first-chunk fragment"
@@ -2789,7 +3913,10 @@ mod tests {
             selected_skills: vec![],
             publisher_slug: None,
             reasoning_effort: None,
+            generation: None,
             project_root: None,
+            forced_tool: None,
+            system_prompt: None,
         };
 
         let images = vec![ImageAttachment {
@@ -2955,10 +4082,12 @@ mod tests {
                 final_content,
                 thinking,
                 cost,
+                truncated,
             } => {
                 assert_eq!(final_content, "Hello");
                 assert!(thinking.is_none());
                 assert_eq!(cost, 0.005);
+                assert!(!truncated);
             }
             _ => panic!("Expected Complete outcome"),
         }
@@ -2973,6 +4102,7 @@ mod tests {
                 id: "tc_1".to_string(),
                 name: "write_file".to_string(),
                 arguments: r#"{"path":"/tmp/test.txt","content":"hello"}"#.to_string(),
+                argument_error: None,
             },
         );
 
@@ -2997,6 +4127,77 @@ mod tests {
         }
     }
 
+    fn pending_call(id: &str, arguments: &str) -> AccumulatedToolCall {
+        AccumulatedToolCall {
+            id: id.to_string(),
+            name: "read_file".to_string(),
+            arguments: arguments.to_string(),
+            argument_error: None,
+        }
+    }
+
+    #[test]
+    fn repair_tool_arguments_leaves_split_valid_json_untouched() {
+        let arguments = ["{\"pa", "th\":\"/tmp/", "a.txt\"", "}"].concat();
+        assert_eq!(repair_tool_arguments(&arguments).unwrap(), arguments);
+        assert_eq!(repair_tool_arguments("  ").unwrap(), "{}");
+    }
+
+    #[test]
+    fn repair_tool_arguments_fixes_mechanical_damage() {
+        assert_eq!(
+            repair_tool_arguments(r#"{"path":"/tmp/a.txt",}"#).unwrap(),
+            r#"{"path":"/tmp/a.txt"}"#
+        );
+        assert_eq!(
+            repair_tool_arguments(r#"{"paths":["a","b",],"note":"x, }"}"#).unwrap(),
+            r#"{"paths":["a","b"],"note":"x, }"}"#
+        );
+        assert_eq!(
+            repair_tool_arguments(r#"{"path":"/tmp/a.txt"}{"path":"/tmp/a.txt"}"#).unwrap(),
+            r#"{"path":"/tmp/a.txt"}"#
+        );
+    }
+
+    #[test]
+    fn repair_tool_arguments_keeps_truncated_payloads_an_error() {
+        assert!(repair_tool_arguments(r#"{"path":"a.txt","content":"first half"#).is_err());
+        assert!(repair_tool_arguments(r#"{"command":"rm -rf build","args":["a""#).is_err());
+        assert!(repair_tool_arguments(r#"{"path":"a.txt","#).is_err());
+    }
+
+    #[test]
+    fn unrepairable_arguments_get_a_structured_error_result() {
+        let error = repair_tool_arguments(r#"{"path": /tmp/a.txt}"#).unwrap_err();
+
+        let result: serde_json::Value =
+            serde_json::from_str(&invalid_tool_arguments_result("read_file", &error)).unwrap();
+        assert_eq!(result["error"], "invalid_tool_arguments");
+        assert_eq!(result["tool"], "read_file");
+        assert!(
+            result["detail"]
+                .as_str()
+                .unwrap()
+                .starts_with("Failed to parse tool arguments")
+        );
+    }
+
+    #[test]
+    fn normalize_tool_calls_dedupes_ids_and_flags_broken_arguments() {
+        let calls = ChatModelWorker::normalize_tool_calls([
+            pending_call("tc_1", r#"{"path":"/tmp/a.txt""#),
+            pending_call("tc_1", r#"{"path":"/tmp/a.txt"}"#),
+            pending_call("tc_2", r#"{"path": ]"#),
+        ]);
+
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].id, "tc_1");
+        assert_eq!(calls[0].arguments, r#"{"path":"/tmp/a.txt"}"#);
+        assert!(calls[0].argument_error.is_none());
+        assert_eq!(calls[1].id, "tc_2");
+        assert!(calls[1].argument_error.is_some());
+    }
+
     #[test]
     fn build_stream_outcome_complete_when_no_pending_tool_calls() {
         // Even if finish_reason is "tool_calls", if no pending tool calls, return Complete
@@ -3013,6 +4214,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn build_stream_outcome_flags_length_finish_as_truncated() {
+        let outcome = ChatModelWorker::build_stream_outcome(
+            &Some("length".to_string()),
+            HashMap::new(),
+            "The first three steps are".to_string(),
+            String::new(),
+            0.01,
+        );
+        match outcome {
+            StreamOutcome::Complete {
+                final_content,
+                truncated,
+                ..
+            } => {
+                assert_eq!(final_content, "The first three steps are");
+                assert!(truncated);
+            }
+            other => panic!("Expected truncated Complete outcome, got: {:?}", other),
+        }
+    }
+
     #[test]
     fn build_stream_outcome_backfills_empty_content_when_finish_reason_length() {
         // Regression for #2002: a stream that finishes with finish_reason="length"
@@ -3057,6 +4280,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn thinking_from_tool_rounds_is_carried_into_final_thinking() {
+        let mut turn_thinking = String::new();
+        ChatModelWorker::append_thinking(&mut turn_thinking, "need the inbox");
+        ChatModelWorker::append_thinking(&mut turn_thinking, "");
+        ChatModelWorker::append_thinking(&mut turn_thinking, "search returned 3 threads");
+
+        assert_eq!(
+            ChatModelWorker::finish_thinking(turn_thinking, Some("summarize them".to_string())),
+            Some("need the inbox\n\nsearch returned 3 threads\n\nsummarize them".to_string())
+        );
+        assert_eq!(ChatModelWorker::finish_thinking(String::new(), None), None);
+    }
+
     #[test]
     fn turn_guard_recap_blocks_cost_tool_and_failure_runaways() {
         let cost_recap = ChatModelWorker::turn_guard_recap(MAX_TURN_COST_USD, 2, 0)
@@ -3073,6 +4310,29 @@ mod tests {
         assert!(failure_recap.contains("failed tool-call count"));
     }
 
+    #[test]
+    fn tool_titles_are_humanized() {
+        assert_eq!(
+            humanize_tool_title("gateway__gmail__send_message"),
+            "Gmail: Send Message"
+        );
+        assert_eq!(
+            humanize_tool_title("mcp__playwright__playwright_navigate"),
+            "Playwright: Playwright Navigate"
+        );
+        assert_eq!(
+            humanize_tool_title("gateway__firecrawl-serenai__scrape"),
+            "Firecrawl Serenai: Scrape"
+        );
+        assert_eq!(
+            humanize_tool_title("openclaw__slack__post_message"),
+            "Slack: Post Message"
+        );
+        assert_eq!(humanize_tool_title("seren_web_fetch"), "Seren Web Fetch");
+        assert_eq!(humanize_tool_title("mcp__memory"), "Memory");
+        assert_eq!(humanize_tool_title("__"), "__");
+    }
+
     #[test]
     fn gateway_status_retryable_classification() {
         // 5xx — all retryable (transient upstream/server failure)
@@ -3101,6 +4361,87 @@ mod tests {
         assert!(!gateway_status_is_retryable(301));
     }
 
+    #[test]
+    fn gateway_wrapper_extracts_embedded_sse_from_body_string() {
+        let wrapper = serde_json::json!({
+            "status": 200,
+            "body": "data: {\"choices\":[{\"delta\":{\"content\":\"Hi\"}}]}\n\n: keep-alive\ndata:{\"choices\":[{\"delta\":{},\"finish_reason\":\"stop\"}]}\n\ndata: [DONE]\n\ndata: {\"ignored\":true}\n"
+        });
+        let WrapperOutcome::Payloads { data, cost } = parse_gateway_wrapper(&wrapper.to_string())
+        else {
+            panic!("expected payloads");
+        };
+        assert_eq!(cost, 0.0);
+        assert_eq!(data.len(), 2);
+        let first = ChatModelWorker::parse_sse_data(&data[0]);
+        assert!(matches!(&first.events[..], [WorkerEvent::Content { text }] if text == "Hi"));
+        let last = ChatModelWorker::parse_sse_data(&data[1]);
+        assert_eq!(last.finish_reason.as_deref(), Some("stop"));
+    }
+
+    #[test]
+    fn gateway_wrapper_passes_json_object_body_through() {
+        let wrapper = serde_json::json!({
+            "data": {
+                "status": 200,
+                "body": {"choices": [{"message": {"content": "Done"}, "finish_reason": "stop"}]}
+            }
+        });
+        let WrapperOutcome::Payloads { data, .. } = parse_gateway_wrapper(&wrapper.to_string())
+        else {
+            panic!("expected payloads");
+        };
+        assert_eq!(data.len(), 1);
+        let parsed: serde_json::Value = serde_json::from_str(&data[0]).unwrap();
+        assert_eq!(parsed["choices"][0]["message"]["content"], "Done");
+    }
+
+    #[test]
+    fn gateway_wrapper_error_status_fails_with_raw_detail() {
+        let wrapper = serde_json::json!({
+            "status": 400,
+            "body": {"error": {
+                "message": "Provider returned error",
+                "metadata": {"raw": "prompt is too long"}
+            }},
+            "cost": "0.002"
+        });
+        assert_eq!(
+            parse_gateway_wrapper(&wrapper.to_string()),
+            WrapperOutcome::Failed {
+                error: "HTTP 400: Provider returned error — prompt is too long".to_string(),
+                cost: 0.002,
+                retryable: false,
+            }
+        );
+
+        let wrapper = serde_json::json!({"status": 503, "body": {}});
+        let WrapperOutcome::Failed {
+            error, retryable, ..
+        } = parse_gateway_wrapper(&wrapper.to_string())
+        else {
+            panic!("expected failure");
+        };
+        assert_eq!(error, "HTTP 503: Gateway API error");
+        assert!(retryable);
+    }
+
+    #[test]
+    fn gateway_wrapper_reports_cost_and_ignores_non_json() {
+        let wrapper = serde_json::json!({"status": 200, "body": "data: [DONE]", "cost": 0.015});
+        assert_eq!(
+            parse_gateway_wrapper(&wrapper.to_string()),
+            WrapperOutcome::Payloads {
+                data: Vec::new(),
+                cost: 0.015,
+            }
+        );
+        assert_eq!(
+            parse_gateway_wrapper("data: {\"choices\":[]"),
+            WrapperOutcome::NotWrapped
+        );
+    }
+
     #[tokio::test]
     async fn execute_tool_read_file_missing_path() {
         let (content, is_error) = ChatModelWorker::execute_tool("read_file", "{}").await;
@@ -3108,6 +4449,28 @@ mod tests {
         assert!(content.contains("Missing required parameter"));
     }
 
+    #[tokio::test]
+    async fn read_file_hash_guards_write_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("notes.txt");
+        std::fs::write(&path, "v1").unwrap();
+        let path = path.to_string_lossy().to_string();
+
+        let read_args = serde_json::json!({ "path": path }).to_string();
+        let (content, is_error) = ChatModelWorker::execute_tool("read_file", &read_args).await;
+        assert!(!is_error, "{content}");
+        let hash = crate::commands::indexing::compute_file_hash("v1".to_string());
+        assert!(content.starts_with("v1"));
+        assert!(content.contains(&hash), "{content}");
+
+        std::fs::write(&path, "edited elsewhere").unwrap();
+        let stale =
+            serde_json::json!({ "path": path, "content": "v2", "expected_hash": hash }).to_string();
+        let (_, is_error) = ChatModelWorker::execute_tool("write_file", &stale).await;
+        assert!(is_error, "stale hash must refuse the write");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "edited elsewhere");
+    }
+
     // Regression guard for #1812: model retries the same malformed-JSON tool
     // call indefinitely, causing context loss on the next user prompt. The
     // helper must signal abort after exactly 3 consecutive identical parse
@@ -3217,21 +4580,72 @@ mod tests {
             "run_skill_script",
             error,
             true,
+            3,
         ));
         assert!(!ChatModelWorker::track_repeated_tool_failure_loop(
             &mut tracker,
             "run_skill_script",
             error,
             true,
+            3,
         ));
         assert!(ChatModelWorker::track_repeated_tool_failure_loop(
             &mut tracker,
             "run_skill_script",
             error,
             true,
+            3,
         ));
     }
 
+    #[test]
+    fn track_repeated_tool_failure_loop_honors_a_higher_limit() {
+        let mut tracker: Option<(String, usize)> = None;
+        let error = "Timeout 30000ms exceeded";
+
+        for _ in 0..3 {
+            assert!(!ChatModelWorker::track_repeated_tool_failure_loop(
+                &mut tracker,
+                "playwright__navigate",
+                error,
+                true,
+                4,
+            ));
+        }
+        assert!(ChatModelWorker::track_repeated_tool_failure_loop(
+            &mut tracker,
+            "playwright__navigate",
+            error,
+            true,
+            4,
+        ));
+    }
+
+    #[test]
+    fn track_identical_tool_failure_counts_same_call_and_error() {
+        let mut tracker: Option<(u64, usize)> = None;
+        let args = r#"{"url":"https://example.com"}"#;
+        let error = "Timeout 30000ms exceeded";
+
+        let mut track = |arguments: &str, result: &str, is_error: bool| {
+            ChatModelWorker::track_identical_tool_failure(
+                &mut tracker,
+                "playwright__navigate",
+                arguments,
+                result,
+                is_error,
+            )
+        };
+        assert_eq!(track(args, error, true), 1);
+        assert_eq!(track(args, error, true), 2);
+        // Different arguments are a different call, so the count restarts.
+        assert_eq!(track(r#"{"url":"https://example.org"}"#, error, true), 1);
+        assert_eq!(track(args, error, true), 1);
+        // A success clears the streak.
+        assert_eq!(track(args, "ok", false), 0);
+        assert_eq!(track(args, error, true), 1);
+    }
+
     #[tokio::test]
     async fn execute_tool_read_file_base64_round_trips_bytes() {
         // Critical regression guard: the read_file_base64 dispatcher arm must
@@ -3325,6 +4739,76 @@ mod tests {
         assert!(names.contains(&"read_file"));
     }
 
+    #[test]
+    fn publisher_priority_leads_tools_and_forces_first_round_only() {
+        let tool = |name: &str| {
+            serde_json::json!({
+                "type": "function",
+                "function": {"name": name, "description": "", "parameters": {}}
+            })
+        };
+        let worker = ChatModelWorker::with_tools(
+            vec![
+                tool("mcp__playwright__playwright_navigate"),
+                tool("gateway__gmail__list_messages"),
+            ],
+            None,
+            EffectiveAgentPolicy::default(),
+        )
+        .with_publisher_priority(Some(PublisherToolPriority {
+            publisher_slug: "gmail".to_string(),
+            tool_names: vec!["gateway__gmail__list_messages".to_string()],
+            forced_tool: Some("gateway__gmail__list_messages".to_string()),
+        }));
+
+        assert_eq!(
+            worker.tool_definitions[0]["function"]["name"],
+            "gateway__gmail__list_messages"
+        );
+        assert_eq!(worker.priority_publisher.as_deref(), Some("gmail"));
+
+        let routing = RoutingDecision {
+            worker_type: super::super::types::WorkerType::ChatModel,
+            model_id: "anthropic/claude-sonnet-4".to_string(),
+            delegation: super::super::types::DelegationType::InLoop,
+            reason: "Email".to_string(),
+            selected_skills: vec![],
+            publisher_slug: None,
+            reasoning_effort: None,
+            generation: None,
+            project_root: None,
+            forced_tool: None,
+            system_prompt: None,
+        };
+        let tools = worker.tool_definitions.clone();
+        assert_eq!(
+            worker.tool_choice(&routing, 0, &tools)["function"]["name"],
+            "gateway__gmail__list_messages"
+        );
+        assert_eq!(worker.tool_choice(&routing, 1, &tools), "auto");
+        assert_eq!(
+            ChatModelWorker::new().tool_choice(&routing, 0, &tools),
+            "auto"
+        );
+    }
+
+    #[test]
+    fn frontend_tool_timeout_uses_overrides_then_default() {
+        // Gmail tools wait on an approval prompt, so they keep the default.
+        assert_eq!(
+            frontend_tool_timeout_secs("gateway__gmail__send_message"),
+            TOOL_EXECUTION_TIMEOUT_SECS
+        );
+        assert_eq!(
+            frontend_tool_timeout_secs("mcp__playwright__playwright_navigate"),
+            900
+        );
+        assert_eq!(
+            frontend_tool_timeout_secs("gateway__firecrawl-serenai__scrape"),
+            TOOL_EXECUTION_TIMEOUT_SECS
+        );
+    }
+
     #[test]
     fn inject_local_tool_definitions_is_idempotent() {
         // If the frontend catalog ever ships `write_pdf_from_html`, we must
@@ -3391,7 +4875,10 @@ mod tests {
             selected_skills: vec![],
             publisher_slug: None,
             reasoning_effort: None,
+            generation: None,
             project_root: None,
+            forced_tool: None,
+            system_prompt: None,
         };
         let tools = vec![
             make_tool("gateway__gmail__get_messages"),
@@ -3424,7 +4911,10 @@ mod tests {
             selected_skills: vec![],
             publisher_slug: None,
             reasoning_effort: None,
+            generation: None,
             project_root: None,
+            forced_tool: None,
+            system_prompt: None,
         };
         let tools = vec![make_tool("gateway__gmail__send_message")];
         let skill_content = "# Active Skills\n\n## Skill: Google Docs\n\nCreate documents.";
@@ -3456,7 +4946,10 @@ mod tests {
             selected_skills: vec![],
             publisher_slug: None,
             reasoning_effort: None,
+            generation: None,
             project_root: None,
+            forced_tool: None,
+            system_prompt: None,
         };
 
         let body = worker.build_request_body("Hi", &[], &routing, "", &[], &[], None);
@@ -3491,7 +4984,10 @@ mod tests {
             selected_skills: vec![],
             publisher_slug: None,
             reasoning_effort: None,
+            generation: None,
             project_root: None,
+            forced_tool: None,
+            system_prompt: None,
         };
 
         let body = worker.build_request_body("Hi", &[], &routing, "", &[], &[], None);
@@ -3523,7 +5019,10 @@ mod tests {
             selected_skills: vec![],
             publisher_slug: None,
             reasoning_effort: None,
+            generation: None,
             project_root: None,
+            forced_tool: None,
+            system_prompt: None,
         };
 
         let body = worker.build_request_body("Hi", &[], &routing, "", &[], &[], None);
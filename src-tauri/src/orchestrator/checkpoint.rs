@@ -0,0 +1,329 @@
+// ABOUTME: Per-round checkpoints of the chat-model tool loop, stored in chat.db.
+// ABOUTME: Lets resume_orchestration continue a long turn after a disconnect instead of restarting it.
+
+use rusqlite::{Connection, OptionalExtension, Result};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use super::types::{RoutingDecision, UserCapabilities};
+use crate::services::database::DbPool;
+
+/// Checkpoints older than this are treated as gone and deleted.
+pub const CHECKPOINT_TTL_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Tool-loop state after a completed round.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoundCheckpoint {
+    /// Next round to run.
+    pub round: usize,
+    /// The loop's full message list, including the system prompt and history.
+    pub messages: Vec<serde_json::Value>,
+    /// Index of the turn's user message in `messages`.
+    pub prompt_start: usize,
+    pub total_cost: f64,
+    pub tool_call_count: usize,
+    pub tool_failure_count: usize,
+}
+
+/// Everything needed to rebuild the worker for a resumed orchestration. The
+/// assistant message id of the turn doubles as the orchestration id.
+#[derive(Debug, Clone)]
+pub struct OrchestrationCheckpoint {
+    pub orchestration_id: String,
+    pub conversation_id: String,
+    pub prompt: String,
+    pub routing: RoutingDecision,
+    pub capabilities: UserCapabilities,
+    /// None until the first tool round completes.
+    pub progress: Option<RoundCheckpoint>,
+    pub updated_at: i64,
+}
+
+/// Checkpointing handed to a `ChatModelWorker`.
+#[derive(Debug, Clone)]
+pub struct WorkerCheckpoint {
+    pub orchestration_id: String,
+    /// Round state to continue from instead of starting at round 0.
+    pub resume: Option<RoundCheckpoint>,
+}
+
+/// Milliseconds since the Unix epoch, the unit of `updated_at`.
+pub fn now_millis() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String> {
+    serde_json::to_string(value).map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+}
+
+fn from_json<T: for<'de> Deserialize<'de>>(column: usize, text: &str) -> Result<T> {
+    serde_json::from_str(text).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(column, rusqlite::types::Type::Text, Box::new(e))
+    })
+}
+
+/// Record the start of a checkpointed orchestration. Re-running for the same
+/// id (a resume) refreshes the header but keeps the saved round.
+pub fn begin_checkpoint(
+    conn: &Connection,
+    orchestration_id: &str,
+    conversation_id: &str,
+    prompt: &str,
+    routing: &RoutingDecision,
+    capabilities: &UserCapabilities,
+    now: i64,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO orchestration_checkpoints
+            (id, conversation_id, prompt, routing, capabilities, progress, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, NULL, ?6, ?6)
+         ON CONFLICT(id) DO UPDATE SET
+            routing = excluded.routing,
+            capabilities = excluded.capabilities,
+            updated_at = excluded.updated_at",
+        rusqlite::params![
+            orchestration_id,
+            conversation_id,
+            prompt,
+            to_json(routing)?,
+            to_json(capabilities)?,
+            now
+        ],
+    )?;
+    Ok(())
+}
+
+/// Save the state after a completed round. Returns false when the
+/// orchestration was never begun (or already cleared).
+pub fn record_round(
+    conn: &Connection,
+    orchestration_id: &str,
+    progress: &RoundCheckpoint,
+    now: i64,
+) -> Result<bool> {
+    let updated = conn.execute(
+        "UPDATE orchestration_checkpoints SET progress = ?1, updated_at = ?2 WHERE id = ?3",
+        rusqlite::params![to_json(progress)?, now, orchestration_id],
+    )?;
+    Ok(updated > 0)
+}
+
+/// Load a checkpoint that has not expired.
+pub fn load_checkpoint(
+    conn: &Connection,
+    orchestration_id: &str,
+    now: i64,
+) -> Result<Option<OrchestrationCheckpoint>> {
+    let row = conn
+        .query_row(
+            "SELECT conversation_id, prompt, routing, capabilities, progress, updated_at
+             FROM orchestration_checkpoints
+             WHERE id = ?1 AND updated_at > ?2",
+            rusqlite::params![orchestration_id, now - CHECKPOINT_TTL_MS],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                    row.get::<_, i64>(5)?,
+                ))
+            },
+        )
+        .optional()?;
+    let Some((conversation_id, prompt, routing, capabilities, progress, updated_at)) = row else {
+        return Ok(None);
+    };
+    Ok(Some(OrchestrationCheckpoint {
+        orchestration_id: orchestration_id.to_string(),
+        conversation_id,
+        prompt,
+        routing: from_json(2, &routing)?,
+        capabilities: from_json(3, &capabilities)?,
+        progress: progress.map(|text| from_json(4, &text)).transpose()?,
+        updated_at,
+    }))
+}
+
+/// Drop the checkpoint of an orchestration that finished.
+pub fn clear_checkpoint(conn: &Connection, orchestration_id: &str) -> Result<()> {
+    conn.execute(
+        "DELETE FROM orchestration_checkpoints WHERE id = ?1",
+        rusqlite::params![orchestration_id],
+    )?;
+    Ok(())
+}
+
+/// Delete checkpoints not touched within the TTL. Returns how many were removed.
+pub fn gc_expired_checkpoints(conn: &Connection, now: i64) -> Result<usize> {
+    conn.execute(
+        "DELETE FROM orchestration_checkpoints WHERE updated_at <= ?1",
+        rusqlite::params![now - CHECKPOINT_TTL_MS],
+    )
+}
+
+/// Run `f` against chat.db off the async runtime.
+pub async fn with_chat_db<T, F>(app: &AppHandle, f: F) -> std::result::Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&Connection) -> Result<T> + Send + 'static,
+{
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Some(pool) = app.try_state::<DbPool>() {
+            pool.with_connection(f)
+        } else {
+            let conn = crate::services::database::init_db(&app).map_err(|err| err.to_string())?;
+            f(&conn).map_err(|err| err.to_string())
+        }
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orchestrator::types::{DelegationType, EffectiveAgentPolicy, WorkerType};
+    use crate::services::database::setup_schema;
+
+    fn routing() -> RoutingDecision {
+        RoutingDecision {
+            worker_type: WorkerType::ChatModel,
+            model_id: "anthropic/claude-sonnet-4".to_string(),
+            delegation: DelegationType::InLoop,
+            reason: "Research".to_string(),
+            selected_skills: vec![],
+            publisher_slug: None,
+            reasoning_effort: None,
+            generation: None,
+            project_root: None,
+            forced_tool: None,
+            system_prompt: None,
+        }
+    }
+
+    fn capabilities() -> UserCapabilities {
+        UserCapabilities {
+            has_local_agent: false,
+            agent_type: None,
+            active_agent_session_id: None,
+            selected_model: None,
+            force_private_chat: false,
+            private_chat_deployment_id: None,
+            available_models: vec!["anthropic/claude-sonnet-4".to_string()],
+            available_tools: vec![],
+            tool_definitions: vec![],
+            installed_skills: vec![],
+            model_rankings: vec![],
+            reasoning_effort: None,
+            generation: None,
+            project_root: None,
+            forced_tool: None,
+            system_prompt: None,
+            trace: false,
+            effective_agent_policy: EffectiveAgentPolicy::default(),
+            tool_failure_repeat_threshold: None,
+        }
+    }
+
+    fn progress(round: usize) -> RoundCheckpoint {
+        RoundCheckpoint {
+            round,
+            messages: vec![
+                serde_json::json!({"role": "system", "content": "You are helpful."}),
+                serde_json::json!({"role": "user", "content": "Research this"}),
+            ],
+            prompt_start: 1,
+            total_cost: 0.25,
+            tool_call_count: 3,
+            tool_failure_count: 1,
+        }
+    }
+
+    #[test]
+    fn rounds_are_saved_and_survive_a_resume_begin() {
+        let conn = Connection::open_in_memory().unwrap();
+        setup_schema(&conn).unwrap();
+
+        begin_checkpoint(
+            &conn,
+            "msg-1",
+            "conv-1",
+            "Research this",
+            &routing(),
+            &capabilities(),
+            1_000,
+        )
+        .unwrap();
+        let fresh = load_checkpoint(&conn, "msg-1", 1_000).unwrap().unwrap();
+        assert_eq!(fresh.conversation_id, "conv-1");
+        assert_eq!(fresh.routing.model_id, "anthropic/claude-sonnet-4");
+        assert!(fresh.progress.is_none());
+
+        assert!(record_round(&conn, "msg-1", &progress(2), 2_000).unwrap());
+        assert!(!record_round(&conn, "missing", &progress(2), 2_000).unwrap());
+
+        // Resuming re-begins the same id without losing the saved round.
+        begin_checkpoint(
+            &conn,
+            "msg-1",
+            "conv-1",
+            "Research this",
+            &routing(),
+            &capabilities(),
+            3_000,
+        )
+        .unwrap();
+        let resumed = load_checkpoint(&conn, "msg-1", 3_000).unwrap().unwrap();
+        assert_eq!(resumed.progress, Some(progress(2)));
+        assert_eq!(resumed.updated_at, 3_000);
+
+        clear_checkpoint(&conn, "msg-1").unwrap();
+        assert!(load_checkpoint(&conn, "msg-1", 3_000).unwrap().is_none());
+    }
+
+    #[test]
+    fn expired_checkpoints_are_hidden_and_collected() {
+        let conn = Connection::open_in_memory().unwrap();
+        setup_schema(&conn).unwrap();
+
+        begin_checkpoint(
+            &conn,
+            "old",
+            "conv-1",
+            "a",
+            &routing(),
+            &capabilities(),
+            1_000,
+        )
+        .unwrap();
+        begin_checkpoint(
+            &conn,
+            "new",
+            "conv-1",
+            "b",
+            &routing(),
+            &capabilities(),
+            5_000,
+        )
+        .unwrap();
+        let now = 1_000 + CHECKPOINT_TTL_MS;
+
+        assert!(load_checkpoint(&conn, "old", now).unwrap().is_none());
+        assert!(load_checkpoint(&conn, "new", now).unwrap().is_some());
+        assert_eq!(gc_expired_checkpoints(&conn, now).unwrap(), 1);
+        let remaining: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM orchestration_checkpoints",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(remaining, 1);
+    }
+}
@@ -1,7 +1,15 @@
 // ABOUTME: Bootstrap task classifier using heuristic keyword matching.
 // ABOUTME: Classifies prompts into task types and selects relevant skills.
 
-use super::types::{SkillRef, TaskClassification, TaskComplexity};
+use std::collections::VecDeque;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::types::{
+    RoutingDecision, SkillRef, TaskClassification, TaskComplexity, UserCapabilities,
+};
 
 // =============================================================================
 // Keyword Lists
@@ -376,6 +384,173 @@ pub fn select_meeting_skills(skills: &[SkillRef]) -> Vec<String> {
         .collect()
 }
 
+// =============================================================================
+// Routing Cache
+// =============================================================================
+
+/// Most routing decisions kept at once; the least recently used is evicted.
+const ROUTING_CACHE_CAPACITY: usize = 64;
+
+/// How long a cached decision stays valid: long enough to cover an edit and
+/// resend, short enough not to pin a route for the rest of the session.
+const ROUTING_CACHE_TTL: Duration = Duration::from_secs(120);
+
+struct CachedRoute {
+    key: u64,
+    inserted_at: Instant,
+    decision: RoutingDecision,
+}
+
+#[derive(Default)]
+struct RoutingCacheInner {
+    /// Fingerprint of the tool/publisher inventory the entries were built for.
+    inventory: Option<u64>,
+    /// Entries ordered from least to most recently used.
+    entries: VecDeque<CachedRoute>,
+}
+
+/// Bounded LRU of recent routing decisions, so an edited-and-resent prompt
+/// does not go through classification and routing again.
+///
+/// Keys hash the trimmed prompt together with the capability fields routing
+/// reads.
+/// Whenever the connected tools or publishers change, every entry is dropped
+/// rather than left to age out.
+#[derive(Default)]
+pub struct RoutingCache {
+    inner: Mutex<RoutingCacheInner>,
+}
+
+impl RoutingCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the cached decision for this prompt, or compute and cache it.
+    pub fn get_or_insert_with(
+        &self,
+        prompt: &str,
+        capabilities: &UserCapabilities,
+        compute: impl FnOnce() -> RoutingDecision,
+    ) -> RoutingDecision {
+        self.get_or_insert_at(prompt, capabilities, Instant::now(), compute)
+    }
+
+    fn get_or_insert_at(
+        &self,
+        prompt: &str,
+        capabilities: &UserCapabilities,
+        now: Instant,
+        compute: impl FnOnce() -> RoutingDecision,
+    ) -> RoutingDecision {
+        let inventory = inventory_fingerprint(capabilities);
+        let key = routing_cache_key(prompt, capabilities);
+        {
+            let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+            if inner.inventory != Some(inventory) {
+                inner.entries.clear();
+                inner.inventory = Some(inventory);
+            }
+            inner
+                .entries
+                .retain(|entry| now.duration_since(entry.inserted_at) < ROUTING_CACHE_TTL);
+            if let Some(pos) = inner.entries.iter().position(|entry| entry.key == key)
+                && let Some(entry) = inner.entries.remove(pos)
+            {
+                let decision = entry.decision.clone();
+                inner.entries.push_back(entry);
+                log::debug!("[Orchestrator] Routing cache hit");
+                return decision;
+            }
+        }
+
+        // Compute outside the lock so a slow route never blocks other lookups.
+        let decision = compute();
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if inner.inventory == Some(inventory) {
+            inner.entries.retain(|entry| entry.key != key);
+            if inner.entries.len() >= ROUTING_CACHE_CAPACITY {
+                inner.entries.pop_front();
+            }
+            inner.entries.push_back(CachedRoute {
+                key,
+                inserted_at: now,
+                decision: decision.clone(),
+            });
+        }
+        decision
+    }
+
+    /// Drop every cached decision.
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.entries.clear();
+        inner.inventory = None;
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.inner
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entries
+            .len()
+    }
+}
+
+/// Hash of the connected tool and publisher set, independent of order.
+fn inventory_fingerprint(capabilities: &UserCapabilities) -> u64 {
+    let mut names: Vec<&str> = capabilities
+        .available_tools
+        .iter()
+        .map(String::as_str)
+        .chain(capabilities.tool_definitions.iter().filter_map(|def| {
+            def.get("function")
+                .and_then(|function| function.get("name"))
+                .and_then(|name| name.as_str())
+        }))
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+    let mut hasher = DefaultHasher::new();
+    names.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Cache key for a prompt under the capability fields classification and
+/// routing read. Tool definitions are left out: they are large, and a change
+/// in tool names already clears the cache through `inventory_fingerprint`.
+/// Only surrounding whitespace is normalized: keyword and skill-invocation
+/// matching are sensitive to everything else.
+fn routing_cache_key(prompt: &str, capabilities: &UserCapabilities) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    prompt.trim().hash(&mut hasher);
+    capabilities.has_local_agent.hash(&mut hasher);
+    capabilities.active_agent_session_id.hash(&mut hasher);
+    capabilities.selected_model.hash(&mut hasher);
+    capabilities.force_private_chat.hash(&mut hasher);
+    capabilities.available_models.hash(&mut hasher);
+    capabilities.available_tools.hash(&mut hasher);
+    for skill in &capabilities.installed_skills {
+        skill.slug.hash(&mut hasher);
+        skill.name.hash(&mut hasher);
+        skill.description.hash(&mut hasher);
+        skill.tags.hash(&mut hasher);
+        skill.path.hash(&mut hasher);
+    }
+    for (model_id, score) in &capabilities.model_rankings {
+        (model_id, score.to_bits()).hash(&mut hasher);
+    }
+    capabilities.reasoning_effort.hash(&mut hasher);
+    serde_json::to_string(&capabilities.generation)
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    capabilities.project_root.hash(&mut hasher);
+    capabilities.forced_tool.hash(&mut hasher);
+    capabilities.system_prompt.hash(&mut hasher);
+    hasher.finish()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -707,4 +882,146 @@ cd ~/.config/seren/skills/polymarket-bot && python3 scripts/agent.py --config co
         let result = classify("Write a python function to sort a list", &[]);
         assert_eq!(result.task_type, "code_generation");
     }
+
+    // =========================================================================
+    // Routing Cache Tests
+    // =========================================================================
+
+    fn cache_capabilities(tools: &[&str]) -> UserCapabilities {
+        UserCapabilities {
+            has_local_agent: false,
+            agent_type: None,
+            active_agent_session_id: None,
+            selected_model: None,
+            force_private_chat: false,
+            private_chat_deployment_id: None,
+            available_models: vec!["anthropic/claude-sonnet-4".to_string()],
+            available_tools: tools.iter().map(|t| t.to_string()).collect(),
+            tool_definitions: vec![],
+            installed_skills: vec![],
+            model_rankings: vec![],
+            reasoning_effort: None,
+            generation: None,
+            project_root: None,
+            forced_tool: None,
+            system_prompt: None,
+            trace: false,
+            effective_agent_policy: Default::default(),
+            tool_failure_repeat_threshold: None,
+        }
+    }
+
+    fn cache_decision(model_id: &str) -> RoutingDecision {
+        RoutingDecision {
+            worker_type: super::super::types::WorkerType::ChatModel,
+            model_id: model_id.to_string(),
+            delegation: super::super::types::DelegationType::InLoop,
+            reason: "General chat".to_string(),
+            selected_skills: vec![],
+            publisher_slug: None,
+            reasoning_effort: None,
+            generation: None,
+            project_root: None,
+            forced_tool: None,
+            system_prompt: None,
+        }
+    }
+
+    #[test]
+    fn routing_cache_reuses_decision_for_resent_prompt() {
+        let cache = RoutingCache::new();
+        let caps = cache_capabilities(&["gateway__github__list_issues"]);
+        let now = Instant::now();
+
+        let first = cache.get_or_insert_at("Summarize my issues", &caps, now, || {
+            cache_decision("first")
+        });
+        let second = cache.get_or_insert_at("  Summarize my issues\n", &caps, now, || {
+            cache_decision("second")
+        });
+        assert_eq!(first.model_id, "first");
+        assert_eq!(second.model_id, "first");
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn routing_cache_expires_after_ttl() {
+        let cache = RoutingCache::new();
+        let caps = cache_capabilities(&[]);
+        let now = Instant::now();
+
+        cache.get_or_insert_at("hello", &caps, now, || cache_decision("first"));
+        let later = now + ROUTING_CACHE_TTL;
+        let decision = cache.get_or_insert_at("hello", &caps, later, || cache_decision("second"));
+        assert_eq!(decision.model_id, "second");
+    }
+
+    #[test]
+    fn routing_cache_invalidates_when_inventory_changes() {
+        let cache = RoutingCache::new();
+        let now = Instant::now();
+        let before = cache_capabilities(&["gateway__github__list_issues"]);
+        cache.get_or_insert_at("hello", &before, now, || cache_decision("first"));
+        cache.get_or_insert_at("other", &before, now, || cache_decision("first"));
+        assert_eq!(cache.len(), 2);
+
+        let after = cache_capabilities(&[
+            "gateway__github__list_issues",
+            "gateway__gmail__send_message",
+        ]);
+        let decision = cache.get_or_insert_at("hello", &after, now, || cache_decision("second"));
+        assert_eq!(decision.model_id, "second");
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn routing_cache_evicts_least_recently_used() {
+        let cache = RoutingCache::new();
+        let caps = cache_capabilities(&[]);
+        let now = Instant::now();
+
+        for i in 0..ROUTING_CACHE_CAPACITY {
+            cache.get_or_insert_at(&format!("prompt {i}"), &caps, now, || {
+                cache_decision("cached")
+            });
+        }
+        // Touch the oldest entry so "prompt 1" becomes the eviction candidate.
+        cache.get_or_insert_at("prompt 0", &caps, now, || cache_decision("miss"));
+        cache.get_or_insert_at("overflow", &caps, now, || cache_decision("cached"));
+        assert_eq!(cache.len(), ROUTING_CACHE_CAPACITY);
+
+        let kept = cache.get_or_insert_at("prompt 0", &caps, now, || cache_decision("miss"));
+        let evicted = cache.get_or_insert_at("prompt 1", &caps, now, || cache_decision("miss"));
+        assert_eq!(kept.model_id, "cached");
+        assert_eq!(evicted.model_id, "miss");
+    }
+
+    #[test]
+    fn routing_cache_clear_drops_entries() {
+        let cache = RoutingCache::new();
+        let caps = cache_capabilities(&[]);
+        let now = Instant::now();
+        cache.get_or_insert_at("hello", &caps, now, || cache_decision("first"));
+        cache.clear();
+        assert_eq!(cache.len(), 0);
+        let decision = cache.get_or_insert_at("hello", &caps, now, || cache_decision("second"));
+        assert_eq!(decision.model_id, "second");
+    }
+
+    #[test]
+    fn routing_cache_key_tracks_routing_inputs_only() {
+        let caps = cache_capabilities(&["gateway__github__list_issues"]);
+        let key = routing_cache_key("hello", &caps);
+
+        let mut selected = caps.clone();
+        selected.selected_model = Some("anthropic/claude-sonnet-4".to_string());
+        assert_ne!(routing_cache_key("hello", &selected), key);
+
+        let mut described = caps.clone();
+        described.tool_definitions = vec![serde_json::json!({
+            "type": "function",
+            "function": { "name": "gateway__github__list_issues", "description": "v2" }
+        })];
+        assert_eq!(routing_cache_key("hello", &described), key);
+    }
 }
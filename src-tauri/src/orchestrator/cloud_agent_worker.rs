@@ -346,6 +346,7 @@ impl CloudAgentWorker {
                         thinking,
                         cost: None,
                         rlm_steps: None,
+                        truncated: false,
                     },
                 )
                 .await?;
@@ -471,6 +472,7 @@ impl CloudAgentWorker {
                                     thinking,
                                     cost: None,
                                     rlm_steps: None,
+                                    truncated: false,
                                 },
                             )
                             .await?;
@@ -144,22 +144,145 @@ fn parse_community_prior(value: serde_json::Value) -> Option<CommunityPrior> {
         })
 }
 
+/// Signals accumulated for one conversation while it is being evaluated.
+#[derive(Debug, Default)]
+struct ConversationEval {
+    signals: VecDeque<EvalSignal>,
+    escalation_pending: bool,
+    escalated_to: Option<String>,
+    /// Order of last use across conversations, for eviction.
+    touched: u64,
+}
+
+/// Entry for `conversation_id`, created on first use. At the cap the least
+/// recently touched conversation is evicted to make room.
+fn conversation_entry<'a>(
+    conversations: &'a mut HashMap<String, ConversationEval>,
+    conversation_id: &str,
+) -> &'a mut ConversationEval {
+    if !conversations.contains_key(conversation_id)
+        && conversations.len() >= MAX_TRACKED_CONVERSATIONS
+        && let Some(oldest) = conversations
+            .iter()
+            .min_by_key(|(_, entry)| entry.touched)
+            .map(|(id, _)| id.clone())
+    {
+        conversations.remove(&oldest);
+    }
+    let tick = conversations
+        .values()
+        .map(|entry| entry.touched)
+        .max()
+        .unwrap_or(0)
+        + 1;
+    let entry = conversations
+        .entry(conversation_id.to_string())
+        .or_default();
+    entry.touched = tick;
+    entry
+}
+
+/// Read-only view of a conversation's eval signals for the UI.
+#[derive(Debug, Clone, Serialize)]
+pub struct EvalSnapshot {
+    pub conversation_id: String,
+    pub signals: Vec<EvalSignal>,
+    pub positive: usize,
+    pub negative: usize,
+    /// Share of positive signals, `None` until the first signal arrives.
+    pub score: Option<f64>,
+    /// A negative signal arrived mid-turn and the next tool round has not
+    /// picked it up yet.
+    pub escalation_pending: bool,
+    /// Model the running turn escalated to in response to a negative signal.
+    pub escalated_to: Option<String>,
+}
+
 /// Managed state for the eval signal queue.
 pub struct EvalState {
     queue: Mutex<VecDeque<EvalSignal>>,
+    conversations: Mutex<HashMap<String, ConversationEval>>,
     client: reqwest::Client,
 }
 
 const BATCH_SIZE: usize = 10;
 
+/// Signals kept per conversation for `snapshot`; older ones are still in SQLite.
+const MAX_TRACKED_SIGNALS: usize = 50;
+
+/// Conversations tracked for `snapshot` and escalation at once.
+const MAX_TRACKED_CONVERSATIONS: usize = 100;
+
 impl EvalState {
     pub fn new() -> Self {
         Self {
             queue: Mutex::new(VecDeque::new()),
+            conversations: Mutex::new(HashMap::new()),
             client: reqwest::Client::new(),
         }
     }
 
+    /// Remember a signal against its conversation for `snapshot`.
+    fn record(&self, conversation_id: &str, signal: EvalSignal) {
+        let mut conversations = self.conversations.lock().unwrap();
+        let entry = conversation_entry(&mut conversations, conversation_id);
+        if entry.signals.len() >= MAX_TRACKED_SIGNALS {
+            entry.signals.pop_front();
+        }
+        entry.signals.push_back(signal);
+    }
+
+    /// Accumulated signals and derived score for a conversation.
+    pub fn snapshot(&self, conversation_id: &str) -> EvalSnapshot {
+        let conversations = self.conversations.lock().unwrap();
+        let entry = conversations.get(conversation_id);
+        let signals: Vec<EvalSignal> = entry
+            .map(|entry| entry.signals.iter().cloned().collect())
+            .unwrap_or_default();
+        let positive = signals.iter().filter(|s| s.satisfaction == 1).count();
+        let negative = signals.len() - positive;
+        EvalSnapshot {
+            conversation_id: conversation_id.to_string(),
+            score: (!signals.is_empty()).then(|| positive as f64 / signals.len() as f64),
+            signals,
+            positive,
+            negative,
+            escalation_pending: entry.is_some_and(|entry| entry.escalation_pending),
+            escalated_to: entry.and_then(|entry| entry.escalated_to.clone()),
+        }
+    }
+
+    /// Ask the running turn to move to a stronger model on its next tool round.
+    pub fn request_escalation(&self, conversation_id: &str) {
+        let mut conversations = self.conversations.lock().unwrap();
+        conversation_entry(&mut conversations, conversation_id).escalation_pending = true;
+    }
+
+    /// Clear escalation state left by an earlier turn, so a new turn starts on
+    /// its routed model.
+    pub fn begin_turn(&self, conversation_id: &str) {
+        let mut conversations = self.conversations.lock().unwrap();
+        if let Some(entry) = conversations.get_mut(conversation_id) {
+            entry.escalation_pending = false;
+            entry.escalated_to = None;
+        }
+    }
+
+    /// Consume a pending escalation request. Returns true at most once per request.
+    pub fn take_escalation(&self, conversation_id: &str) -> bool {
+        let mut conversations = self.conversations.lock().unwrap();
+        conversations
+            .get_mut(conversation_id)
+            .is_some_and(|entry| std::mem::take(&mut entry.escalation_pending))
+    }
+
+    /// Record the model a turn escalated to so the UI can show it.
+    pub fn mark_escalated(&self, conversation_id: &str, model_id: &str) {
+        let mut conversations = self.conversations.lock().unwrap();
+        conversation_entry(&mut conversations, conversation_id).escalated_to =
+            Some(model_id.to_string());
+    }
+
     /// Add a signal to the queue. Flushes if queue reaches batch size.
     ///
     /// When the batch threshold is reached, spawns an async task to POST
@@ -243,24 +366,25 @@ impl EvalState {
 /// Submit a satisfaction signal for a message.
 ///
 /// Looks up message metadata from the database, constructs the feature
-/// vector, stores it locally, and queues it for Gateway sync.
+/// vector, stores it locally, and queues it for Gateway sync. Returns the
+/// message's conversation ID so callers can react to the signal.
 pub fn submit(
     conn: &rusqlite::Connection,
     eval_state: &EvalState,
     message_id: &str,
     satisfaction: i32,
     auth_token: &str,
-) -> Result<(), String> {
+) -> Result<String, String> {
     if satisfaction != 0 && satisfaction != 1 {
         return Err("satisfaction must be 0 or 1".to_string());
     }
 
     // Look up message metadata from database
-    let metadata_json: Option<String> = conn
+    let (metadata_json, conversation_id): (Option<String>, String) = conn
         .query_row(
-            "SELECT metadata FROM messages WHERE id = ?1",
+            "SELECT metadata, conversation_id FROM messages WHERE id = ?1",
             rusqlite::params![message_id],
-            |row| row.get(0),
+            |row| Ok((row.get(0)?, row.get(1)?)),
         )
         .map_err(|e| format!("Message not found: {e}"))?;
 
@@ -298,9 +422,10 @@ pub fn submit(
         created_at: now,
     };
 
+    eval_state.record(&conversation_id, signal.clone());
     eval_state.enqueue(signal, auth_token);
 
-    Ok(())
+    Ok(conversation_id)
 }
 
 /// Parsed metadata fields from a message's JSON blob.
@@ -414,6 +539,75 @@ mod tests {
         assert_eq!(state.queue_len(), 1);
     }
 
+    #[test]
+    fn snapshot_accumulates_signals_per_conversation() {
+        let conn = setup_test_db();
+        let state = EvalState::new();
+        insert_message(&conn, "msg1", None);
+        insert_message(&conn, "msg2", None);
+
+        assert_eq!(state.snapshot("c1").score, None);
+
+        assert_eq!(
+            submit(&conn, &state, "msg1", 1, "test-token").unwrap(),
+            "c1"
+        );
+        submit(&conn, &state, "msg2", 0, "test-token").unwrap();
+
+        let snapshot = state.snapshot("c1");
+        assert_eq!(snapshot.signals.len(), 2);
+        assert_eq!(snapshot.positive, 1);
+        assert_eq!(snapshot.negative, 1);
+        assert_eq!(snapshot.score, Some(0.5));
+        assert!(state.snapshot("other").signals.is_empty());
+    }
+
+    #[test]
+    fn escalation_request_is_consumed_once() {
+        let state = EvalState::new();
+        assert!(!state.take_escalation("c1"));
+
+        state.request_escalation("c1");
+        assert!(state.snapshot("c1").escalation_pending);
+        assert!(state.take_escalation("c1"));
+        assert!(!state.take_escalation("c1"));
+
+        state.mark_escalated("c1", "anthropic/claude-opus-4-6");
+        let snapshot = state.snapshot("c1");
+        assert!(!snapshot.escalation_pending);
+        assert_eq!(
+            snapshot.escalated_to.as_deref(),
+            Some("anthropic/claude-opus-4-6")
+        );
+
+        // A new turn starts without the previous turn's escalation.
+        state.request_escalation("c1");
+        state.begin_turn("c1");
+        let snapshot = state.snapshot("c1");
+        assert!(!snapshot.escalation_pending);
+        assert_eq!(snapshot.escalated_to, None);
+        assert!(!state.take_escalation("c1"));
+    }
+
+    #[test]
+    fn tracked_conversations_are_capped() {
+        let state = EvalState::new();
+        for i in 0..MAX_TRACKED_CONVERSATIONS + 5 {
+            state.request_escalation(&format!("c{i}"));
+        }
+        assert_eq!(
+            state.conversations.lock().unwrap().len(),
+            MAX_TRACKED_CONVERSATIONS
+        );
+        // The most recent conversation is kept; the oldest was evicted.
+        assert!(
+            state
+                .snapshot(&format!("c{}", MAX_TRACKED_CONVERSATIONS + 4))
+                .escalation_pending
+        );
+        assert!(!state.snapshot("c0").escalation_pending);
+    }
+
     #[test]
     fn submit_rejects_invalid_satisfaction() {
         let conn = setup_test_db();
@@ -164,6 +164,7 @@ impl McpPublisherWorker {
                 thinking: None,
                 cost: None, // Cost set by stream_response from accumulated total
                 rlm_steps: None,
+                truncated: false,
             });
         }
 
@@ -213,6 +214,7 @@ impl McpPublisherWorker {
                                     thinking: None,
                                     cost,
                                     rlm_steps: None,
+                                    truncated: false,
                                 })
                                 .await
                                 .map_err(|e| format!("Failed to send Complete event: {}", e))?;
@@ -242,6 +244,7 @@ impl McpPublisherWorker {
                                         thinking: None,
                                         cost,
                                         rlm_steps: None,
+                                        truncated: false,
                                     })
                                     .await
                                     .map_err(|e| format!("Failed to send Complete event: {}", e))?;
@@ -270,6 +273,7 @@ impl McpPublisherWorker {
                     thinking: None,
                     cost,
                     rlm_steps: None,
+                    truncated: false,
                 })
                 .await
                 .map_err(|e| format!("Failed to send final Complete event: {}", e))?;
@@ -368,7 +372,10 @@ mod tests {
             selected_skills: vec![],
             publisher_slug: publisher_slug.map(String::from),
             reasoning_effort: None,
+            generation: None,
             project_root: None,
+            forced_tool: None,
+            system_prompt: None,
         }
     }
 
@@ -2,6 +2,7 @@
 // ABOUTME: Contains types, worker trait, classifier, router, and worker adapters.
 
 pub mod chat_model_worker;
+pub mod checkpoint;
 pub mod classifier;
 pub mod cloud_agent_worker;
 pub mod decomposer;
@@ -12,10 +13,12 @@ pub mod mcp_publisher_worker;
 pub mod provider_worker;
 pub mod rlm;
 pub mod router;
+pub mod routing_rules;
 pub mod service;
 pub mod subtask_context;
 pub mod tool_bridge;
 pub mod tool_relevance;
+pub mod trace;
 pub mod trust;
 pub mod types;
 pub mod worker;
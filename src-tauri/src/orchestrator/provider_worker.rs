@@ -7,12 +7,12 @@ use serde::Deserialize;
 use serde_json::{Value, json};
 use std::sync::Arc;
 use tauri::{AppHandle, Manager};
-use tauri_plugin_store::StoreExt;
 use tokio::sync::{Mutex, mpsc};
 use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async, tungstenite::Message};
 
 use super::types::{ImageAttachment, RoutingDecision, WorkerEvent};
 use super::worker::Worker;
+use crate::app_settings::app_setting_string;
 
 const AUTH_REQUEST_ID: i64 = 1;
 const PROMPT_REQUEST_ID: i64 = 2;
@@ -22,8 +22,6 @@ const SPAWN_ONESHOT_REQUEST_ID: i64 = 5;
 const SET_MODEL_ONESHOT_REQUEST_ID: i64 = 6;
 const TERMINATE_ONESHOT_REQUEST_ID: i64 = 7;
 const SET_MODE_ONESHOT_REQUEST_ID: i64 = 8;
-const SETTINGS_STORE: &str = "settings.json";
-const APP_SETTINGS_KEY: &str = "app";
 
 type RuntimeSocket = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
 
@@ -144,6 +142,7 @@ impl Worker for ProviderRuntimeWorker {
                             thinking: None,
                             cost: None,
                             rlm_steps: None,
+                            truncated: false,
                         })
                         .await
                         .map_err(|err| format!("Failed to send completion event: {}", err))?;
@@ -179,6 +178,7 @@ impl Worker for ProviderRuntimeWorker {
                             thinking: None,
                             cost: None,
                             rlm_steps: None,
+                            truncated: false,
                         })
                         .await
                         .map_err(|err| format!("Failed to send completion event: {}", err))?;
@@ -639,19 +639,6 @@ fn provider_oneshot_permission_mode(agent_type: &str) -> &'static str {
     }
 }
 
-fn app_setting_string(app: &AppHandle, key: &str) -> Option<String> {
-    let store = app.store(SETTINGS_STORE).ok()?;
-    let raw = store.get(APP_SETTINGS_KEY)?;
-    let raw = raw.as_str()?;
-    let parsed: Value = serde_json::from_str(raw).ok()?;
-    parsed
-        .get(key)
-        .and_then(Value::as_str)
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-        .map(str::to_string)
-}
-
 fn response_error_message(payload: &Value) -> Option<String> {
     payload
         .get("error")
@@ -752,6 +739,7 @@ fn map_provider_event(method: &str, payload: &Value) -> Option<WorkerEvent> {
             thinking: None,
             cost: None,
             rlm_steps: None,
+            truncated: false,
         }),
         _ => None,
     }
@@ -37,19 +37,21 @@ const REQUEST_TIMEOUT_SECS: u64 = 600;
 // Model context limits (characters, not tokens; 1 token ≈ 4 chars)
 // =============================================================================
 
+/// Context window of `model` in tokens, by model family.
+pub fn model_context_window_tokens(model: &str) -> usize {
+    if model.contains("gemini-1.5") || model.contains("gemini-2") || model.contains("gemini-3") {
+        1_000_000
+    } else if model.contains("claude") {
+        200_000
+    } else if model.contains("gpt-4") {
+        128_000
+    } else {
+        100_000
+    }
+}
+
 fn model_context_limit_chars(model: &str) -> usize {
-    let tokens: usize =
-        if model.contains("gemini-1.5") || model.contains("gemini-2") || model.contains("gemini-3")
-        {
-            1_000_000
-        } else if model.contains("claude") {
-            200_000
-        } else if model.contains("gpt-4") {
-            128_000
-        } else {
-            100_000
-        };
-    tokens * 4
+    model_context_window_tokens(model) * 4
 }
 
 /// Estimate character count for text-based image attachments.
@@ -229,6 +231,7 @@ pub async fn process(
             thinking: None,
             cost: None,
             rlm_steps: Some(steps_json),
+            truncated: false,
         })
         .await;
 
@@ -36,6 +36,36 @@ const SIMPLE_PREFERRED_MODELS: &[&str] = &[
 /// (e.g., a future "direct_publisher_query" task type with an explicit publisher target).
 const MCP_PUBLISHER_ELIGIBLE_TASK_TYPES: &[&str] = &[];
 
+/// Intent keywords that make a connected publisher the preferred way to satisfy
+/// a request, ahead of general tools like browser automation or
+/// `seren_web_fetch`. Keys match the gateway slug by substring, so "calendar"
+/// also covers "google-calendar". Keywords match whole words, and phrases
+/// match consecutive words, so generic words alone ("event", "issue") never
+/// pick a publisher.
+const PUBLISHER_TOOL_PRIORITY: &[(&str, &[&str])] = &[
+    ("gmail", &["email", "emails", "gmail", "inbox"]),
+    ("calendar", &["calendar", "my schedule", "my meetings"]),
+    (
+        "drive",
+        &["google drive", "google docs", "google doc", "google sheets"],
+    ),
+    ("slack", &["slack"]),
+    ("github", &["github", "pull request", "pull requests"]),
+];
+
+/// Shortest query word that can select a publisher tool by action name, so
+/// filler words ("to", "my") never force a tool.
+const MIN_ACTION_TERM_LEN: usize = 3;
+
+/// Action prefixes of publisher tools that only read. Only these may be
+/// forced through `tool_choice`; anything that sends, creates, or deletes is
+/// left to the model.
+const READ_ONLY_ACTION_PREFIXES: &[&str] = &["list", "get", "search", "read", "find", "query"];
+
+/// Built-in tools that only read but whose names do not start with a
+/// read-only action.
+const READ_ONLY_BUILTIN_TOOLS: &[&str] = &["seren_web_fetch"];
+
 /// Fallback models for context-overflow errors (all have 1M+ token windows).
 /// Tried in order when the primary model rejects a request for exceeding its
 /// context limit (e.g. Claude 4.5 at 200K).
@@ -83,7 +113,10 @@ pub fn route(
         selected_skills,
         publisher_slug,
         reasoning_effort: capabilities.reasoning_effort.clone(),
+        generation: capabilities.generation.clone(),
         project_root: capabilities.project_root.clone(),
+        forced_tool: capabilities.forced_tool.clone(),
+        system_prompt: capabilities.system_prompt.clone(),
     }
 }
 
@@ -229,6 +262,116 @@ fn extract_publisher_slug(
     extract_gateway_slug(capabilities, query)
 }
 
+/// A connected publisher whose tools should lead the tool list for a request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublisherToolPriority {
+    pub publisher_slug: String,
+    pub tool_names: Vec<String>,
+    /// Set when the intent names a single publisher and exactly one of its
+    /// tools matches an action word in the query (e.g. "list" →
+    /// `list_messages`) and that tool only reads. High enough confidence to
+    /// force via `tool_choice`.
+    pub forced_tool: Option<String>,
+}
+
+/// Pick the connected gateway publisher that best matches the prompt's intent.
+///
+/// Returns `None` when no connected publisher matches, leaving tool selection
+/// to BM25 relevance alone.
+pub fn publisher_tool_priority(
+    query: &str,
+    available_tools: &[String],
+) -> Option<PublisherToolPriority> {
+    let query_lower = query.to_lowercase();
+    let terms: Vec<&str> = query_lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .collect();
+
+    let mut matched: Vec<(&str, Vec<&str>)> = Vec::new();
+    for tool_name in available_tools {
+        let Some(slug) = parse_gateway_slug(tool_name) else {
+            continue;
+        };
+        let intended = PUBLISHER_TOOL_PRIORITY.iter().any(|(key, intents)| {
+            slug.contains(key) && intents.iter().any(|intent| contains_phrase(&terms, intent))
+        });
+        if !intended {
+            continue;
+        }
+        match matched.iter_mut().find(|(existing, _)| *existing == slug) {
+            Some((_, tools)) => tools.push(tool_name.as_str()),
+            None => matched.push((slug, vec![tool_name.as_str()])),
+        }
+    }
+
+    let (slug, tools) = matched.first()?;
+    let forced_tool = if matched.len() == 1 {
+        let hits: Vec<&&str> = tools
+            .iter()
+            .filter(|tool_name| action_matches(tool_name, &terms))
+            .collect();
+        match hits.as_slice() {
+            [only] if is_read_only_action(only) => Some(only.to_string()),
+            _ => None,
+        }
+    } else {
+        None
+    };
+
+    Some(PublisherToolPriority {
+        publisher_slug: slug.to_string(),
+        tool_names: tools.iter().map(|name| name.to_string()).collect(),
+        forced_tool,
+    })
+}
+
+/// Whether the words of `phrase` appear consecutively in `terms`.
+fn contains_phrase(terms: &[&str], phrase: &str) -> bool {
+    let words: Vec<&str> = phrase.split_whitespace().collect();
+    !words.is_empty()
+        && terms
+            .windows(words.len())
+            .any(|window| window == words.as_slice())
+}
+
+/// Whether a tool may be forced through `tool_choice`: a read-only built-in
+/// or a tool whose action only reads.
+pub fn is_read_only_tool(tool_name: &str) -> bool {
+    READ_ONLY_BUILTIN_TOOLS.contains(&tool_name) || is_read_only_action(tool_name)
+}
+
+/// Whether a publisher tool's action (the part after the last `__`) only reads.
+fn is_read_only_action(tool_name: &str) -> bool {
+    let action = tool_name.rsplit("__").next().unwrap_or_default();
+    let verb = action.split(['_', '-']).next().unwrap_or_default();
+    READ_ONLY_ACTION_PREFIXES.contains(&verb)
+}
+
+/// Whether any word of the tool's action (the part after the last `__`)
+/// appears in the query.
+fn action_matches(tool_name: &str, terms: &[&str]) -> bool {
+    let action = tool_name.rsplit("__").next().unwrap_or_default();
+    action
+        .split('_')
+        .any(|word| word.len() >= MIN_ACTION_TERM_LEN && terms.contains(&word))
+}
+
+/// Move the prioritized publisher's tools to the front of an OpenAI-format
+/// tool list, keeping the relative order of everything else.
+pub fn prioritize_publisher_tools(
+    tools: Vec<serde_json::Value>,
+    priority: &PublisherToolPriority,
+) -> Vec<serde_json::Value> {
+    let (mut preferred, rest): (Vec<_>, Vec<_>) = tools.into_iter().partition(|tool| {
+        tool.pointer("/function/name")
+            .and_then(|name| name.as_str())
+            .is_some_and(|name| priority.tool_names.iter().any(|n| n == name))
+    });
+    preferred.extend(rest);
+    preferred
+}
+
 /// Select the best available model for the task.
 ///
 /// Priority:
@@ -362,6 +505,22 @@ fn humanize_model_id(model_id: &str) -> &str {
     }
 }
 
+/// Stronger model to move a running turn to after a negative eval signal,
+/// chosen from `available_models`.
+///
+/// Returns `None` when the current model is already one of the most capable,
+/// so escalation never trades one top model for another, or when none of
+/// them is available.
+pub fn escalation_model(current_model: &str, available_models: &[String]) -> Option<String> {
+    if CODE_PREFERRED_MODELS.contains(&current_model) {
+        return None;
+    }
+    CODE_PREFERRED_MODELS
+        .iter()
+        .find(|model| available_models.iter().any(|m| m == *model))
+        .map(|model| model.to_string())
+}
+
 /// HTTP status codes that indicate a transient failure eligible for model reroute.
 const REROUTABLE_STATUS_CODES: &[u16] = &[408, 429, 502, 503, 504];
 
@@ -563,8 +722,13 @@ mod tests {
             installed_skills: vec![],
             model_rankings: vec![],
             reasoning_effort: None,
+            generation: None,
             project_root: None,
+            forced_tool: None,
+            system_prompt: None,
+            trace: false,
             effective_agent_policy: EffectiveAgentPolicy::default(),
+            tool_failure_repeat_threshold: None,
         }
     }
 
@@ -594,8 +758,13 @@ mod tests {
             installed_skills: skills,
             model_rankings: vec![],
             reasoning_effort: None,
+            generation: None,
             project_root: None,
+            forced_tool: None,
+            system_prompt: None,
+            trace: false,
             effective_agent_policy: EffectiveAgentPolicy::default(),
+            tool_failure_repeat_threshold: None,
         }
     }
 
@@ -1360,4 +1529,156 @@ mod tests {
         assert!(reason.contains("rated helpful"));
         assert!(reason.contains("score: 3"));
     }
+
+    // =========================================================================
+    // Publisher Tool Priority
+    // =========================================================================
+
+    fn tool_names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    fn tool_def(name: &str) -> serde_json::Value {
+        serde_json::json!({
+            "type": "function",
+            "function": {"name": name, "description": "", "parameters": {}}
+        })
+    }
+
+    #[test]
+    fn email_intent_with_gmail_connected_prefers_gmail_over_browser() {
+        let available = tool_names(&[
+            "mcp__playwright__playwright_navigate",
+            "seren_web_fetch",
+            "gateway__gmail__send_message",
+            "gateway__gmail__list_messages",
+            "gateway__firecrawl-serenai__scrape",
+        ]);
+        let priority =
+            publisher_tool_priority("Send an email to Dana about Friday", &available).unwrap();
+        assert_eq!(priority.publisher_slug, "gmail");
+        assert_eq!(
+            priority.tool_names,
+            vec![
+                "gateway__gmail__send_message",
+                "gateway__gmail__list_messages"
+            ]
+        );
+        // Sending mutates, so it is ranked first but never forced.
+        assert_eq!(priority.forced_tool, None);
+        let listing = publisher_tool_priority("list my email messages", &available).unwrap();
+        assert_eq!(
+            listing.forced_tool.as_deref(),
+            Some("gateway__gmail__list_messages")
+        );
+
+        let tools: Vec<serde_json::Value> = available.iter().map(|n| tool_def(n)).collect();
+        let ordered = prioritize_publisher_tools(tools, &priority);
+        let names: Vec<&str> = ordered
+            .iter()
+            .map(|t| t["function"]["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "gateway__gmail__send_message",
+                "gateway__gmail__list_messages",
+                "mcp__playwright__playwright_navigate",
+                "seren_web_fetch",
+                "gateway__firecrawl-serenai__scrape",
+            ]
+        );
+    }
+
+    #[test]
+    fn vague_email_intent_boosts_without_forcing_a_tool() {
+        let available = tool_names(&[
+            "gateway__gmail__send_message",
+            "gateway__gmail__list_messages",
+        ]);
+        let priority = publisher_tool_priority("anything new in my inbox?", &available).unwrap();
+        assert_eq!(priority.publisher_slug, "gmail");
+        assert_eq!(priority.forced_tool, None);
+    }
+
+    #[test]
+    fn no_priority_without_a_connected_matching_publisher() {
+        let browser_only = tool_names(&["mcp__playwright__playwright_navigate", "seren_web_fetch"]);
+        assert_eq!(
+            publisher_tool_priority("check my email", &browser_only),
+            None
+        );
+
+        let gmail = tool_names(&["gateway__gmail__send_message"]);
+        assert_eq!(
+            publisher_tool_priority("summarize this article", &gmail),
+            None
+        );
+    }
+
+    #[test]
+    fn generic_words_do_not_select_a_publisher() {
+        let available = tool_names(&[
+            "gateway__github__create_issue",
+            "gateway__google-calendar__create_event",
+            "gateway__slack__post_message",
+            "gateway__google-drive__get_file",
+        ]);
+        for prompt in [
+            "there is an issue with this event handler",
+            "send a dm about the pr",
+            "read the docs for this repo",
+        ] {
+            assert_eq!(
+                publisher_tool_priority(prompt, &available),
+                None,
+                "{prompt}"
+            );
+        }
+        let priority =
+            publisher_tool_priority("review the open pull requests", &available).unwrap();
+        assert_eq!(priority.publisher_slug, "github");
+        assert_eq!(priority.forced_tool, None);
+    }
+
+    #[test]
+    fn multiple_matching_publishers_never_force_a_tool() {
+        let available = tool_names(&[
+            "gateway__gmail__send_message",
+            "gateway__google-calendar__create_event",
+        ]);
+        let priority =
+            publisher_tool_priority("email the team and create a calendar event", &available)
+                .unwrap();
+        assert_eq!(priority.publisher_slug, "gmail");
+        assert_eq!(priority.forced_tool, None);
+    }
+
+    // =========================================================================
+    // Eval Escalation
+    // =========================================================================
+
+    #[test]
+    fn escalation_moves_to_the_most_capable_available_model_once() {
+        let available =
+            |models: &[&str]| -> Vec<String> { models.iter().map(|m| m.to_string()).collect() };
+        let all = available(&[
+            "minimax/minimax-m2.5",
+            "anthropic/claude-opus-4-6",
+            "openai/gpt-5.3",
+        ]);
+        assert_eq!(
+            escalation_model("minimax/minimax-m2.5", &all).as_deref(),
+            Some("anthropic/claude-opus-4-6")
+        );
+        assert_eq!(escalation_model("anthropic/claude-opus-4-6", &all), None);
+        assert_eq!(escalation_model("openai/gpt-5.3", &all), None);
+
+        let no_opus = available(&["minimax/minimax-m2.5", "openai/gpt-5.3"]);
+        assert_eq!(
+            escalation_model("minimax/minimax-m2.5", &no_opus).as_deref(),
+            Some("openai/gpt-5.3")
+        );
+        assert_eq!(escalation_model("minimax/minimax-m2.5", &[]), None);
+    }
 }
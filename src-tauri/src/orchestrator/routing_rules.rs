@@ -0,0 +1,285 @@
+// ABOUTME: User-defined routing rules that pin matching prompts to a route.
+// ABOUTME: Stored in routing_rules.json and consulted before the classifier's route.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex, PoisonError};
+use tauri::{AppHandle, Manager};
+
+use super::types::{DelegationType, RoutingDecision, WorkerType};
+
+pub const ROUTING_RULES_FILE: &str = "routing_rules.json";
+
+/// Compiled rules from the last load or save, with the file they belong to.
+/// `save_rules` replaces the entry, so the file is only re-read after a
+/// restart or when a different path is asked for.
+static RULES_CACHE: LazyLock<Mutex<Option<(PathBuf, Arc<Vec<CompiledRule>>)>>> =
+    LazyLock::new(|| Mutex::new(None));
+
+/// What a rule looks for in the prompt.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleMatch {
+    /// Case-insensitive regular expression.
+    Regex { pattern: String },
+    /// Case-insensitive substring.
+    Keyword { keyword: String },
+}
+
+impl RuleMatch {
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::Regex { pattern } if pattern.trim().is_empty() => {
+                Err("Routing rule pattern is empty".to_string())
+            }
+            Self::Regex { pattern } => compile(pattern)
+                .map(|_| ())
+                .map_err(|e| format!("Invalid routing rule pattern: {}", e)),
+            Self::Keyword { keyword } if keyword.trim().is_empty() => {
+                Err("Routing rule keyword is empty".to_string())
+            }
+            Self::Keyword { .. } => Ok(()),
+        }
+    }
+}
+
+fn compile(pattern: &str) -> Result<regex::Regex, regex::Error> {
+    regex::RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+}
+
+/// The parts of a route a rule pins. Unset fields keep the router's choice.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RouteFragment {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worker_type: Option<WorkerType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publisher_slug: Option<String>,
+}
+
+impl RouteFragment {
+    fn is_empty(&self) -> bool {
+        self.worker_type.is_none() && self.model_id.is_none() && self.publisher_slug.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RoutingRule {
+    pub id: String,
+    #[serde(rename = "match")]
+    pub matcher: RuleMatch,
+    pub route: RouteFragment,
+}
+
+impl RoutingRule {
+    pub fn new(matcher: RuleMatch, route: RouteFragment) -> Result<Self, String> {
+        matcher.validate()?;
+        if route.is_empty() {
+            return Err("Routing rule must set a worker, model, or publisher".to_string());
+        }
+        Ok(Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            matcher,
+            route,
+        })
+    }
+
+    /// Pin the rule's fields onto `decision`.
+    pub fn apply(&self, decision: &mut RoutingDecision) {
+        if let Some(worker_type) = &self.route.worker_type {
+            decision.delegation = match worker_type {
+                WorkerType::LocalAgent => DelegationType::FullHandoff,
+                _ => DelegationType::InLoop,
+            };
+            decision.worker_type = worker_type.clone();
+        }
+        if let Some(model_id) = &self.route.model_id {
+            decision.model_id = model_id.clone();
+        }
+        if let Some(publisher_slug) = &self.route.publisher_slug {
+            decision.publisher_slug = Some(publisher_slug.clone());
+        }
+        decision.reason = format!("Matched routing rule {}", self.id);
+    }
+}
+
+/// A rule with its regex compiled once, when the rules are loaded.
+struct CompiledRule {
+    rule: RoutingRule,
+    regex: Option<regex::Regex>,
+}
+
+impl CompiledRule {
+    fn new(rule: RoutingRule) -> Self {
+        let regex = match &rule.matcher {
+            RuleMatch::Regex { pattern } => compile(pattern).ok(),
+            RuleMatch::Keyword { .. } => None,
+        };
+        Self { rule, regex }
+    }
+
+    fn matches(&self, prompt: &str) -> bool {
+        match &self.rule.matcher {
+            RuleMatch::Regex { .. } => self.regex.as_ref().is_some_and(|re| re.is_match(prompt)),
+            RuleMatch::Keyword { keyword } => {
+                prompt.to_lowercase().contains(&keyword.to_lowercase())
+            }
+        }
+    }
+}
+
+fn compile_rules(rules: &[RoutingRule]) -> Arc<Vec<CompiledRule>> {
+    Arc::new(rules.iter().cloned().map(CompiledRule::new).collect())
+}
+
+/// The first rule whose match applies to `prompt`.
+fn first_match<'a>(rules: &'a [CompiledRule], prompt: &str) -> Option<&'a RoutingRule> {
+    rules
+        .iter()
+        .find(|compiled| compiled.matches(prompt))
+        .map(|compiled| &compiled.rule)
+}
+
+/// The rules in `path`, compiled, from the cache when it holds that file.
+fn cached_rules(path: &Path) -> Result<Arc<Vec<CompiledRule>>, String> {
+    let mut cache = RULES_CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some((cached_path, rules)) = cache.as_ref()
+        && cached_path == path
+    {
+        return Ok(Arc::clone(rules));
+    }
+    let rules = compile_rules(&load_rules(path)?);
+    *cache = Some((path.to_path_buf(), Arc::clone(&rules)));
+    Ok(rules)
+}
+
+pub fn rules_path(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(ROUTING_RULES_FILE))
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))
+}
+
+/// Read the rules file. A missing file means no rules.
+pub fn load_rules(path: &Path) -> Result<Vec<RoutingRule>, String> {
+    match std::fs::read_to_string(path) {
+        Ok(raw) => serde_json::from_str(&raw)
+            .map_err(|e| format!("Failed to parse {}: {}", ROUTING_RULES_FILE, e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(format!("Failed to read {}: {}", ROUTING_RULES_FILE, e)),
+    }
+}
+
+pub fn save_rules(path: &Path, rules: &[RoutingRule]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(rules).map_err(|e| e.to_string())?;
+    std::fs::write(path, json)
+        .map_err(|e| format!("Failed to write {}: {}", ROUTING_RULES_FILE, e))?;
+    *RULES_CACHE.lock().unwrap_or_else(PoisonError::into_inner) =
+        Some((path.to_path_buf(), compile_rules(rules)));
+    Ok(())
+}
+
+/// The rule matching `prompt`, if any. An unreadable rules file is logged
+/// and treated as empty so routing never fails on it.
+pub fn matching_rule(app: &AppHandle, prompt: &str) -> Option<RoutingRule> {
+    let rules = rules_path(app)
+        .and_then(|path| cached_rules(&path))
+        .inspect_err(|e| log::warn!("[RoutingRules] Ignoring routing rules: {}", e))
+        .ok()?;
+    first_match(&rules, prompt).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyword_rule(keyword: &str, publisher: &str) -> RoutingRule {
+        RoutingRule::new(
+            RuleMatch::Keyword {
+                keyword: keyword.to_string(),
+            },
+            RouteFragment {
+                worker_type: Some(WorkerType::McpPublisher),
+                publisher_slug: Some(publisher.to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let rules = compile_rules(&[
+            keyword_rule("email", "gmail"),
+            RoutingRule::new(
+                RuleMatch::Regex {
+                    pattern: r"\bsend\b".to_string(),
+                },
+                RouteFragment {
+                    model_id: Some("openai/gpt-5".to_string()),
+                    ..Default::default()
+                },
+            )
+            .unwrap(),
+        ]);
+
+        let rule = first_match(&rules, "Send an EMAIL to Sam").unwrap();
+        assert_eq!(rule.route.publisher_slug.as_deref(), Some("gmail"));
+        let rule = first_match(&rules, "send the report").unwrap();
+        assert_eq!(rule.route.model_id.as_deref(), Some("openai/gpt-5"));
+        assert!(first_match(&rules, "summarize this thread").is_none());
+    }
+
+    #[test]
+    fn invalid_or_empty_rules_are_rejected() {
+        let route = RouteFragment {
+            model_id: Some("openai/gpt-5".to_string()),
+            ..Default::default()
+        };
+        let bad_regex = RuleMatch::Regex {
+            pattern: "(unclosed".to_string(),
+        };
+        assert!(RoutingRule::new(bad_regex, route.clone()).is_err());
+        let blank = RuleMatch::Keyword {
+            keyword: "  ".to_string(),
+        };
+        assert!(RoutingRule::new(blank, route).is_err());
+        let no_route = RuleMatch::Keyword {
+            keyword: "email".to_string(),
+        };
+        assert!(RoutingRule::new(no_route, RouteFragment::default()).is_err());
+    }
+
+    #[test]
+    fn rules_round_trip_through_the_file() {
+        let dir = std::env::temp_dir().join(format!(
+            "serendesktop-rules-{}",
+            uuid::Uuid::new_v4().simple()
+        ));
+        let path = dir.join(ROUTING_RULES_FILE);
+        assert!(load_rules(&path).unwrap().is_empty());
+
+        let rules = vec![keyword_rule("email", "gmail")];
+        save_rules(&path, &rules).unwrap();
+        assert_eq!(load_rules(&path).unwrap(), rules);
+
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(raw.contains(r#""type": "keyword""#));
+        assert!(raw.contains(r#""match""#));
+
+        // Saving refreshes the compiled rules that prompts are matched against.
+        let matched = cached_rules(&path).unwrap();
+        assert!(first_match(&matched, "draft an email").is_some());
+        save_rules(&path, &[]).unwrap();
+        let matched = cached_rules(&path).unwrap();
+        assert!(first_match(&matched, "draft an email").is_none());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
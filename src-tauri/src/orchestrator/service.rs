@@ -5,21 +5,27 @@ use log;
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::sync::{Mutex, mpsc, watch};
 use uuid::Uuid;
 
 use super::chat_model_worker::ChatModelWorker;
+use super::checkpoint::{self, RoundCheckpoint, WorkerCheckpoint, with_chat_db};
 use super::classifier;
 use super::cloud_agent_worker::CloudAgentWorker;
 use super::decomposer;
+use super::eval::EvalState;
 use super::mcp_publisher_worker::McpPublisherWorker;
 use super::rlm;
 use super::router;
+use super::routing_rules::{self, RoutingRule};
 use super::subtask_context::{
     MAX_CONTEXT_SUBTASKS, MAX_SUBTASK_RESULT_BYTES, inject_dependency_results,
 };
+use super::tool_bridge::ToolResultBridge;
+use super::trace::TurnTracer;
 use super::trust;
 use super::types::{
     DelegationType, ImageAttachment, OrchestratorEvent, RoutingDecision, SkillRef, SubTask,
@@ -32,6 +38,11 @@ use crate::services::database::{
 
 const COMMUNITY_PRIOR_TIMEOUT_MS: u64 = 200;
 
+/// Orchestrations allowed to run at once across all conversations, unless
+/// the `maxConcurrentOrchestrations` setting says otherwise.
+pub const DEFAULT_MAX_CONCURRENT_ORCHESTRATIONS: usize = 4;
+const MAX_CONCURRENT_ORCHESTRATIONS_SETTING: &str = "maxConcurrentOrchestrations";
+
 // =============================================================================
 // Orchestrator State
 // =============================================================================
@@ -51,14 +62,65 @@ pub struct OrchestratorState {
     ///   first cancel, so subsequent clicks during the same run find the
     ///   session and are silently absorbed.
     active_sessions: Mutex<HashMap<String, watch::Sender<bool>>>,
+    /// Recent `classify_only` decisions, reused when a prompt is resent.
+    routing_cache: classifier::RoutingCache,
+    /// Orchestrations currently holding a turn slot. Counted separately from
+    /// `active_sessions` because slots are taken before routing and RLM
+    /// turns never register a session.
+    active_turns: Arc<AtomicUsize>,
+}
+
+/// One running orchestration's share of the concurrency limit, released on
+/// drop so every exit path gives it back.
+pub struct TurnSlot(Arc<AtomicUsize>);
+
+impl Drop for TurnSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
 }
 
 impl OrchestratorState {
     pub fn new() -> Self {
         Self {
             active_sessions: Mutex::new(HashMap::new()),
+            routing_cache: classifier::RoutingCache::new(),
+            active_turns: Arc::new(AtomicUsize::new(0)),
         }
     }
+
+    /// Number of orchestrations currently running.
+    pub fn active_turn_count(&self) -> usize {
+        self.active_turns.load(Ordering::SeqCst)
+    }
+
+    /// Take a turn slot, or refuse when `limit` orchestrations are running.
+    fn try_acquire_turn(&self, limit: usize) -> Result<TurnSlot, String> {
+        self.active_turns
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |active| {
+                (active < limit).then_some(active + 1)
+            })
+            .map(|_| TurnSlot(Arc::clone(&self.active_turns)))
+            .map_err(|active| {
+                format!(
+                    "Too many active turns ({} running, limit {}). Wait for one to finish or stop it first.",
+                    active, limit
+                )
+            })
+    }
+}
+
+/// Take a turn slot under the configured concurrency limit. Callers that
+/// must not change anything unless the turn can run reserve first and hand
+/// the slot to `orchestrate_in_slot`.
+pub fn reserve_turn(app: &AppHandle, state: &OrchestratorState) -> Result<TurnSlot, String> {
+    state.try_acquire_turn(max_concurrent_orchestrations(app))
+}
+
+fn max_concurrent_orchestrations(app: &AppHandle) -> usize {
+    crate::app_settings::app_setting_u64(app, MAX_CONCURRENT_ORCHESTRATIONS_SETTING)
+        .map(|limit| limit.max(1) as usize)
+        .unwrap_or(DEFAULT_MAX_CONCURRENT_ORCHESTRATIONS)
 }
 
 impl Default for OrchestratorState {
@@ -91,6 +153,7 @@ fn completion_message_record(
         final_content,
         cost,
         rlm_steps,
+        truncated,
         ..
     } = event
     else {
@@ -117,6 +180,9 @@ fn completion_message_record(
     if let Some(rlm_steps) = rlm_steps.as_deref().filter(|steps| !steps.is_empty()) {
         metadata["rlm_steps"] = serde_json::Value::String(rlm_steps.to_string());
     }
+    if *truncated {
+        metadata["truncated"] = serde_json::Value::Bool(true);
+    }
 
     Some(PersistedMessage {
         id: message_id.to_string(),
@@ -130,7 +196,121 @@ fn completion_message_record(
     })
 }
 
-async fn persist_completion_message(app: AppHandle, mut message: PersistedMessage) {
+/// Id of the persisted row for a tool call or tool result. `orchestrator.ts`
+/// derives the same id so its save and the backend's upsert one row.
+fn tool_message_id(conversation_id: &str, message_type: &str, tool_call_id: &str) -> String {
+    format!("{conversation_id}:{message_type}:{tool_call_id}")
+}
+
+/// Persisted row for a tool call or tool result, saved as soon as the event
+/// arrives so an app exit mid-turn keeps the rounds that already ran. The
+/// metadata mirrors `serializeMetadata` so the row reloads as a tool card; a
+/// call is `running` until `save_tool_aware_record` saves its result row and
+/// settles the call row to the result's status.
+fn tool_message_record(
+    conversation_id: &str,
+    event: &WorkerEvent,
+    model_id: Option<&str>,
+    timestamp: i64,
+) -> Option<PersistedMessage> {
+    let (message_type, tool_call_id, content, tool_call) = match event {
+        WorkerEvent::ToolCall {
+            tool_call_id,
+            name,
+            arguments,
+            title,
+        } => (
+            "tool_call",
+            tool_call_id,
+            if title.is_empty() { name } else { title }.clone(),
+            serde_json::json!({
+                "id": tool_call_id,
+                "name": name,
+                "arguments": arguments,
+                "title": title,
+                "kind": "",
+                "status": "running",
+            }),
+        ),
+        WorkerEvent::ToolResult {
+            tool_call_id,
+            content,
+            is_error,
+        } => (
+            "tool_result",
+            tool_call_id,
+            content.clone(),
+            serde_json::json!({
+                "id": tool_call_id,
+                "title": "",
+                "kind": "",
+                "status": if *is_error { "error" } else { "completed" },
+                "result": content,
+                "is_error": is_error,
+            }),
+        ),
+        _ => return None,
+    };
+
+    let metadata = serde_json::json!({
+        "v": 1,
+        "message_type": message_type,
+        "worker_type": "orchestrator",
+        "model_id": model_id,
+        "tool_call": tool_call,
+    });
+    Some(PersistedMessage {
+        id: tool_message_id(conversation_id, message_type, tool_call_id),
+        conversation_id: conversation_id.to_string(),
+        role: "assistant".to_string(),
+        content,
+        model: model_id.map(str::to_string),
+        timestamp,
+        metadata: Some(metadata.to_string()),
+        provider: None,
+    })
+}
+
+/// For a tool result row, the id of its call row and the status to settle
+/// that row at.
+fn settled_tool_call(message: &PersistedMessage) -> Option<(String, String)> {
+    let metadata: serde_json::Value = serde_json::from_str(message.metadata.as_deref()?).ok()?;
+    if metadata["message_type"] != "tool_result" {
+        return None;
+    }
+    let tool_call = &metadata["tool_call"];
+    Some((
+        tool_message_id(
+            &message.conversation_id,
+            "tool_call",
+            tool_call["id"].as_str()?,
+        ),
+        tool_call["status"].as_str()?.to_string(),
+    ))
+}
+
+/// Save `message`. A tool result also moves its call row out of `running`,
+/// so a reloaded thread does not show the call as still in flight.
+fn save_tool_aware_record(
+    conn: &rusqlite::Connection,
+    message: &PersistedMessage,
+) -> rusqlite::Result<()> {
+    save_message_record(conn, message)?;
+    let Some((call_row_id, status)) = settled_tool_call(message) else {
+        return Ok(());
+    };
+    conn.execute(
+        "UPDATE messages
+         SET metadata = json_set(metadata, '$.tool_call.status', ?2),
+             row_version = COALESCE(row_version, 1) + 1,
+             updated_at = ?3
+         WHERE id = ?1 AND json_valid(metadata)",
+        rusqlite::params![call_row_id, status, message.timestamp],
+    )?;
+    Ok(())
+}
+
+async fn persist_message_record(app: AppHandle, mut message: PersistedMessage) {
     let message_id = message.id.clone();
     let conversation_id = message.conversation_id.clone();
     let conversation_id_for_db = conversation_id.clone();
@@ -141,7 +321,7 @@ async fn persist_completion_message(app: AppHandle, mut message: PersistedMessag
                     message.provider =
                         resolve_conversation_provider(conn, &conversation_id_for_db)?;
                 }
-                save_message_record(conn, &message)
+                save_tool_aware_record(conn, &message)
             })
         } else {
             let conn = crate::services::database::init_db(&app).map_err(|err| err.to_string())?;
@@ -149,7 +329,7 @@ async fn persist_completion_message(app: AppHandle, mut message: PersistedMessag
                 message.provider = resolve_conversation_provider(&conn, &conversation_id_for_db)
                     .map_err(|err| err.to_string())?;
             }
-            save_message_record(&conn, &message).map_err(|err| err.to_string())
+            save_tool_aware_record(&conn, &message).map_err(|err| err.to_string())
         }
     })
     .await
@@ -158,7 +338,7 @@ async fn persist_completion_message(app: AppHandle, mut message: PersistedMessag
 
     if let Err(err) = result {
         log::error!(
-            "[Orchestrator] Failed to persist completion message {} for conversation {}: {}",
+            "[Orchestrator] Failed to persist message {} for conversation {}: {}",
             message_id,
             conversation_id,
             err
@@ -296,6 +476,10 @@ async fn fetch_community_priors_for_rankings(
 /// 2. Decompose into subtasks
 /// 3. Single subtask → fast path (route, trust, execute)
 /// 4. Multiple subtasks → parallel execution by dependency layers
+///
+/// A `routing_override` (the user confirmed or changed the route returned by
+/// `classify_only`) skips decomposition and routing: the prompt runs as a
+/// single task on exactly that worker, model, and publisher.
 pub async fn orchestrate(
     app: AppHandle,
     state: &OrchestratorState,
@@ -305,18 +489,71 @@ pub async fn orchestrate(
     history: Vec<serde_json::Value>,
     capabilities: UserCapabilities,
     images: Vec<ImageAttachment>,
+    routing_override: Option<RoutingDecision>,
+    turn_timeout_secs: Option<u64>,
+) -> Result<(), String> {
+    let turn = reserve_turn(&app, state)?;
+    orchestrate_in_slot(
+        turn,
+        app,
+        state,
+        conversation_id,
+        assistant_message_id,
+        prompt,
+        history,
+        capabilities,
+        images,
+        routing_override,
+        turn_timeout_secs,
+    )
+    .await
+}
+
+/// `orchestrate` on a turn slot the caller already reserved.
+pub async fn orchestrate_in_slot(
+    turn: TurnSlot,
+    app: AppHandle,
+    state: &OrchestratorState,
+    conversation_id: String,
+    assistant_message_id: String,
+    prompt: String,
+    history: Vec<serde_json::Value>,
+    capabilities: UserCapabilities,
+    images: Vec<ImageAttachment>,
+    routing_override: Option<RoutingDecision>,
+    turn_timeout_secs: Option<u64>,
 ) -> Result<(), String> {
+    let _turn = turn;
+    if let Some(eval) = app.try_state::<EvalState>() {
+        eval.begin_turn(&conversation_id);
+    }
     log::info!(
         "[Orchestrator] Starting orchestration for conversation {}",
         conversation_id
     );
     let started_at_ms = now_millis();
+    let trace = TurnTracer::new(&app, &conversation_id, capabilities.trace);
+
+    if let Some(decision) = &routing_override {
+        validate_routing_override(decision, &capabilities)?;
+    }
+    // A matching user routing rule pins the route like an explicit override.
+    let routing_override =
+        routing_override.or_else(|| rule_override(&app, &prompt, &capabilities, &trace));
+
+    // A turn can run many tool rounds; the optional timeout bounds the turn
+    // as a whole, RLM turns included.
+    let turn_limit = turn_timeout_secs
+        .filter(|secs| *secs > 0)
+        .map(Duration::from_secs);
 
     // 0. RLM check: if input exceeds context window threshold, process recursively.
-    //    Use the user-selected model (or a sensible default) for the limit check.
-    let model_for_limit = capabilities
-        .selected_model
-        .as_deref()
+    //    Use the overridden or user-selected model (or a sensible default) for
+    //    the limit check.
+    let model_for_limit = routing_override
+        .as_ref()
+        .map(|decision| decision.model_id.as_str())
+        .or(capabilities.selected_model.as_deref())
         .filter(|m| !m.is_empty())
         .unwrap_or("anthropic/claude-sonnet-4");
 
@@ -349,6 +586,7 @@ pub async fn orchestrate(
         }
 
         log::info!("[Orchestrator] Input exceeds context threshold — activating RLM");
+        trace.emit("rlm", serde_json::json!({ "model": model_for_limit }));
 
         // Create an event channel and forward events to the frontend.
         let (event_tx, mut event_rx) = tokio::sync::mpsc::channel::<WorkerEvent>(64);
@@ -364,7 +602,7 @@ pub async fn orchestrate(
         let rlm_history = history.clone();
         let rlm_tools = capabilities.tool_definitions.clone();
         let rlm_app = app.clone();
-        tokio::spawn(async move {
+        let rlm_task = tokio::spawn(async move {
             if let Err(e) = rlm::process(
                 &rlm_app,
                 &conv_id,
@@ -382,31 +620,49 @@ pub async fn orchestrate(
 
         // Forward all events to the frontend
         let mut streamed_content = String::new();
-        while let Some(event) = event_rx.recv().await {
-            if let WorkerEvent::Content { text } = &event {
-                streamed_content.push_str(text);
-            }
-            if matches!(event, WorkerEvent::Complete { .. }) {
-                if let Some(record) = completion_message_record(
-                    &conversation_id,
-                    &assistant_message_id_for_rlm,
-                    &streamed_content,
-                    &event,
-                    Some(&rlm_model_for_persistence),
-                    None,
-                    started_at_for_rlm,
-                    now_millis(),
-                    None,
-                ) {
-                    persist_completion_message(app_clone.clone(), record).await;
+        let forward = async {
+            while let Some(event) = event_rx.recv().await {
+                if let WorkerEvent::Content { text } = &event {
+                    streamed_content.push_str(text);
+                }
+                if matches!(event, WorkerEvent::Complete { .. }) {
+                    if let Some(record) = completion_message_record(
+                        &conversation_id,
+                        &assistant_message_id_for_rlm,
+                        &streamed_content,
+                        &event,
+                        Some(&rlm_model_for_persistence),
+                        None,
+                        started_at_for_rlm,
+                        now_millis(),
+                        None,
+                    ) {
+                        persist_message_record(app_clone.clone(), record).await;
+                    }
                 }
+                let orch_event = OrchestratorEvent {
+                    conversation_id: conversation_id.clone(),
+                    worker_event: event,
+                    subtask_id: None,
+                };
+                let _ = app_clone.emit("orchestrator://event", &orch_event);
             }
-            let orch_event = OrchestratorEvent {
-                conversation_id: conversation_id.clone(),
-                worker_event: event,
-                subtask_id: None,
-            };
-            let _ = app_clone.emit("orchestrator://event", &orch_event);
+        };
+        let finished = match turn_limit {
+            Some(limit) => tokio::time::timeout(limit, forward).await.is_ok(),
+            None => {
+                forward.await;
+                true
+            }
+        };
+        if !finished && let Some(limit) = turn_limit {
+            log::warn!(
+                "[Orchestrator] RLM turn for conversation {} exceeded {}s — aborting",
+                conversation_id,
+                limit.as_secs()
+            );
+            rlm_task.abort();
+            emit_turn_timeout(&app, &trace, &conversation_id, limit);
         }
 
         return Ok(());
@@ -415,7 +671,18 @@ pub async fn orchestrate(
     // 0b. Trim history if it exceeds the context budget. This prevents the
     //     case where large history (e.g. from prior failed attempts) would cause
     //     an oversized request to the model.
-    let history = rlm::trim_history(&history, &prompt, &images, model_for_limit);
+    let trimmed_history = rlm::trim_history(&history, &prompt, &images, model_for_limit);
+    if trimmed_history.len() < history.len() {
+        trace.emit(
+            "truncation",
+            serde_json::json!({
+                "kind": "history_budget",
+                "from_messages": history.len(),
+                "to_messages": trimmed_history.len(),
+            }),
+        );
+    }
+    let history = trimmed_history;
 
     // 1. Classify the task
     let classification = classifier::classify(&prompt, &capabilities.installed_skills);
@@ -425,8 +692,18 @@ pub async fn orchestrate(
         classification.complexity
     );
 
-    // 2. Decompose into subtasks
-    let subtasks = decomposer::decompose(&prompt, &classification, &capabilities.installed_skills);
+    // 2. Decompose into subtasks. An explicit route applies to the prompt as a
+    //    whole, so overridden turns always run as a single task.
+    let subtasks = if routing_override.is_some() {
+        vec![SubTask {
+            id: Uuid::new_v4().to_string(),
+            prompt: prompt.clone(),
+            classification,
+            depends_on: vec![],
+        }]
+    } else {
+        decomposer::decompose(&prompt, &classification, &capabilities.installed_skills)
+    };
     log::info!(
         "[Orchestrator] Decomposed into {} subtask(s)",
         subtasks.len()
@@ -443,34 +720,69 @@ pub async fn orchestrate(
     }
 
     // 4. Branch: single task (fast path) vs multi-task (parallel execution)
-    let result = if subtasks.len() <= 1 {
-        execute_single_task(
-            &app,
-            &conversation_id,
-            &subtasks[0],
-            &history,
-            &capabilities,
-            &images,
-            cancel_rx,
-            &assistant_message_id,
-            started_at_ms,
-        )
-        .await
-    } else {
-        execute_multi_task(
-            &app,
-            &conversation_id,
-            &prompt,
-            subtasks,
-            &history,
-            &capabilities,
-            &images,
-            cancel_rx,
-            &assistant_message_id,
-            started_at_ms,
-        )
-        .await
+    let run = async {
+        if subtasks.len() <= 1 {
+            execute_single_task(
+                &app,
+                &conversation_id,
+                &subtasks[0],
+                &history,
+                &capabilities,
+                &images,
+                cancel_rx,
+                &assistant_message_id,
+                started_at_ms,
+                routing_override,
+                None,
+            )
+            .await
+        } else {
+            execute_multi_task(
+                &app,
+                &conversation_id,
+                &prompt,
+                subtasks,
+                &history,
+                &capabilities,
+                &images,
+                cancel_rx,
+                &assistant_message_id,
+                started_at_ms,
+            )
+            .await
+        }
+    };
+    tokio::pin!(run);
+
+    // On expiry of the turn limit the turn is cancelled like a user stop,
+    // then closed out with a note explaining why it ended early.
+    let finished = match turn_limit {
+        Some(limit) => tokio::select! {
+            result = &mut run => Some(result),
+            _ = tokio::time::sleep(limit) => None,
+        },
+        None => Some(run.as_mut().await),
     };
+    let timed_out = finished.is_none();
+    let result = match finished {
+        Some(result) => result,
+        None => {
+            log::warn!(
+                "[Orchestrator] Turn for conversation {} exceeded {}s — cancelling",
+                conversation_id,
+                turn_limit.map_or(0, |limit| limit.as_secs())
+            );
+            let _ = cancel(state, &conversation_id).await;
+            app.state::<ToolResultBridge>()
+                .cancel_all(&conversation_id)
+                .await;
+            run.await
+        }
+    };
+
+    if timed_out && let Some(limit) = turn_limit {
+        emit_turn_timeout(&app, &trace, &conversation_id, limit);
+    }
 
     // 5. Clean up session
     {
@@ -481,6 +793,225 @@ pub async fn orchestrate(
     result
 }
 
+/// Close out a turn stopped by its time limit. The note is also streamed as
+/// content because the renderer keeps streamed text over `final_content`.
+fn emit_turn_timeout(app: &AppHandle, trace: &TurnTracer, conversation_id: &str, limit: Duration) {
+    trace.emit(
+        "turn_timeout",
+        serde_json::json!({ "limit_secs": limit.as_secs() }),
+    );
+    let note = format!(
+        "Stopped: this turn hit its {}s time limit before finishing.",
+        limit.as_secs()
+    );
+    let events = [
+        WorkerEvent::Content {
+            text: format!("\n\n{note}"),
+        },
+        WorkerEvent::Complete {
+            final_content: note,
+            thinking: None,
+            cost: None,
+            rlm_steps: None,
+            truncated: false,
+        },
+    ];
+    for worker_event in events {
+        let orch_event = OrchestratorEvent {
+            conversation_id: conversation_id.to_string(),
+            worker_event,
+            subtask_id: None,
+        };
+        let _ = app.emit("orchestrator://event", &orch_event);
+    }
+}
+
+/// Continue a chat-model orchestration from its last checkpointed tool round.
+///
+/// The orchestration id is the turn's assistant message id. The saved route
+/// and capabilities are reused as-is and events stream on
+/// `orchestrator://event` exactly as for `orchestrate`.
+pub async fn resume(
+    app: AppHandle,
+    state: &OrchestratorState,
+    orchestration_id: String,
+) -> Result<(), String> {
+    let _turn = state.try_acquire_turn(max_concurrent_orchestrations(&app))?;
+    let id = orchestration_id.clone();
+    let saved = with_chat_db(&app, move |conn| {
+        let now = checkpoint::now_millis();
+        checkpoint::gc_expired_checkpoints(conn, now)?;
+        checkpoint::load_checkpoint(conn, &id, now)
+    })
+    .await?
+    .ok_or_else(|| {
+        format!(
+            "No checkpoint for orchestration {} (it finished or expired)",
+            orchestration_id
+        )
+    })?;
+    let Some(progress) = saved.progress else {
+        return Err(format!(
+            "Orchestration {} has no completed tool round to resume from",
+            orchestration_id
+        ));
+    };
+    log::info!(
+        "[Orchestrator] Resuming orchestration {} at round {}",
+        orchestration_id,
+        progress.round
+    );
+
+    let conversation_id = saved.conversation_id;
+    let (cancel_tx, cancel_rx) = watch::channel(false);
+    {
+        let mut sessions = state.active_sessions.lock().await;
+        if sessions.contains_key(&conversation_id) {
+            return Err(format!(
+                "Conversation {} already has an orchestration running",
+                conversation_id
+            ));
+        }
+        sessions.insert(conversation_id.clone(), cancel_tx);
+    }
+
+    let subtask = SubTask {
+        id: Uuid::new_v4().to_string(),
+        classification: classifier::classify(&saved.prompt, &saved.capabilities.installed_skills),
+        prompt: saved.prompt,
+        depends_on: vec![],
+    };
+    // History and images are already part of the checkpointed messages.
+    let result = execute_single_task(
+        &app,
+        &conversation_id,
+        &subtask,
+        &[],
+        &saved.capabilities,
+        &[],
+        cancel_rx,
+        &orchestration_id,
+        now_millis(),
+        Some(saved.routing),
+        Some(progress),
+    )
+    .await;
+
+    {
+        let mut sessions = state.active_sessions.lock().await;
+        sessions.remove(&conversation_id);
+    }
+
+    result
+}
+
+/// Classify and route a prompt without executing anything.
+///
+/// Returns the decision the fast path would start from, so the UI can explain
+/// or let the user override the route before `orchestrate` runs. Thompson
+/// sampling rankings and trust graduation are not applied: both read the eval
+/// database and only adjust the decision at execution time.
+///
+/// Decisions are cached briefly per prompt and capabilities, so re-asking for
+/// an unchanged prompt returns the same route.
+///
+/// A matching user routing rule is applied on top of the cached decision,
+/// unless it would be rejected as an override (see [`apply_routing_rule`]).
+pub fn classify_only(
+    app: &AppHandle,
+    state: &OrchestratorState,
+    prompt: &str,
+    capabilities: &UserCapabilities,
+) -> RoutingDecision {
+    let rule = routing_rules::matching_rule(app, prompt);
+    route_with_rule(state, prompt, capabilities, rule.as_ref())
+}
+
+/// The cached classifier route for `prompt`, with `rule` pinned on top when
+/// it passes validation.
+fn route_with_rule(
+    state: &OrchestratorState,
+    prompt: &str,
+    capabilities: &UserCapabilities,
+    rule: Option<&RoutingRule>,
+) -> RoutingDecision {
+    let decision = state
+        .routing_cache
+        .get_or_insert_with(prompt, capabilities, || {
+            let classification = classifier::classify(prompt, &capabilities.installed_skills);
+            router::route(&classification, capabilities, prompt)
+        });
+    rule.and_then(|rule| apply_routing_rule(rule, decision.clone(), capabilities))
+        .unwrap_or(decision)
+}
+
+/// `decision` with `rule` pinned on top. A rule pinning a model the user
+/// cannot use is logged and skipped, so `classify_only` and `orchestrate`
+/// agree on which rules apply.
+fn apply_routing_rule(
+    rule: &RoutingRule,
+    mut decision: RoutingDecision,
+    capabilities: &UserCapabilities,
+) -> Option<RoutingDecision> {
+    rule.apply(&mut decision);
+    if rule.route.model_id.is_some()
+        && let Err(e) = validate_routing_override(&decision, capabilities)
+    {
+        log::warn!("[Orchestrator] Skipping routing rule {}: {}", rule.id, e);
+        return None;
+    }
+    Some(decision)
+}
+
+/// The route forced by the first user routing rule matching `prompt`.
+fn rule_override(
+    app: &AppHandle,
+    prompt: &str,
+    capabilities: &UserCapabilities,
+    trace: &TurnTracer,
+) -> Option<RoutingDecision> {
+    let rule = routing_rules::matching_rule(app, prompt)?;
+    let classification = classifier::classify(prompt, &capabilities.installed_skills);
+    let decision = router::route(&classification, capabilities, prompt);
+    let decision = apply_routing_rule(&rule, decision, capabilities)?;
+    log::info!("[Orchestrator] Routing rule {} matched", rule.id);
+    trace.emit(
+        "routing_rule",
+        serde_json::json!({
+            "rule_id": rule.id,
+            "worker_type": decision.worker_type,
+            "model_id": decision.model_id,
+            "publisher_slug": decision.publisher_slug,
+        }),
+    );
+    Some(decision)
+}
+
+/// Drop every cached `classify_only` decision.
+pub fn clear_routing_cache(state: &OrchestratorState) {
+    state.routing_cache.clear();
+}
+
+/// Reject an override whose model the frontend did not report as available.
+/// The user-selected model is accepted too, since private-chat deployments
+/// expose models that are not in the public catalog.
+pub(crate) fn validate_routing_override(
+    decision: &RoutingDecision,
+    capabilities: &UserCapabilities,
+) -> Result<(), String> {
+    let model_id = decision.model_id.as_str();
+    let known = capabilities
+        .available_models
+        .iter()
+        .any(|model| model == model_id)
+        || capabilities.selected_model.as_deref() == Some(model_id);
+    if known {
+        Ok(())
+    } else {
+        Err(format!("Unknown model in routing override: {}", model_id))
+    }
+}
+
 // =============================================================================
 // Single-Task Execution (Fast Path)
 // =============================================================================
@@ -489,7 +1020,8 @@ pub async fn orchestrate(
 ///
 /// When a worker hits a 408/429/5xx, the orchestrator queries eval_signals
 /// for satisfaction-ranked fallback models and retries with a different model.
-/// Respects user-selected models (no reroute when user explicitly chose a model).
+/// Respects user-selected models and routing overrides (no reroute when the
+/// user explicitly chose a model or route).
 async fn execute_single_task(
     app: &AppHandle,
     conversation_id: &str,
@@ -500,6 +1032,8 @@ async fn execute_single_task(
     cancel_rx: watch::Receiver<bool>,
     assistant_message_id: &str,
     started_at_ms: i64,
+    routing_override: Option<RoutingDecision>,
+    resume_from: Option<RoundCheckpoint>,
 ) -> Result<(), String> {
     // Compute Thompson sampling rankings before routing
     let mut capabilities = capabilities.clone();
@@ -516,31 +1050,74 @@ async fn execute_single_task(
         .map(|r| (r.model_id.clone(), r.score))
         .collect();
 
-    let user_explicitly_selected = capabilities
-        .selected_model
-        .as_ref()
-        .is_some_and(|m| !m.is_empty());
+    let overridden = routing_override.is_some();
+    let user_explicitly_selected = overridden
+        || capabilities
+            .selected_model
+            .as_ref()
+            .is_some_and(|m| !m.is_empty());
 
-    // Route with rankings-enriched capabilities
-    let mut routing = router::route(&subtask.classification, &capabilities, &subtask.prompt);
+    // Route with rankings-enriched capabilities unless the user forced a route
+    let mut routing = routing_override
+        .unwrap_or_else(|| router::route(&subtask.classification, &capabilities, &subtask.prompt));
 
-    // Trust graduation
+    // Trust graduation (an override already fixes the delegation)
     let app_for_trust = app.clone();
     let task_type = subtask.classification.task_type.clone();
     let model_id = routing.model_id.clone();
-    let trusted = tauri::async_runtime::spawn_blocking(move || {
-        match crate::services::database::init_db(&app_for_trust) {
-            Ok(conn) => trust::is_trusted(&conn, &task_type, &model_id),
-            Err(_) => false,
-        }
-    })
-    .await
-    .unwrap_or(false);
+    let trusted = !overridden
+        && tauri::async_runtime::spawn_blocking(move || {
+            match crate::services::database::init_db(&app_for_trust) {
+                Ok(conn) => trust::is_trusted(&conn, &task_type, &model_id),
+                Err(_) => false,
+            }
+        })
+        .await
+        .unwrap_or(false);
 
     if trusted {
         routing.delegation = DelegationType::FullHandoff;
         routing.reason = format!("{} (trusted)", routing.reason);
     }
+    let trace = TurnTracer::new(app, conversation_id, capabilities.trace);
+    trace.emit(
+        "routing",
+        routing_trace_fields(&routing, overridden, trusted),
+    );
+
+    // Chat-model tool rounds are checkpointed under the assistant message id
+    // so a dropped connection costs a retry, not the whole turn (see `resume`).
+    let checkpointed = routing.worker_type == WorkerType::ChatModel;
+    if checkpointed {
+        let orchestration_id = assistant_message_id.to_string();
+        let conv_id = conversation_id.to_string();
+        let prompt = subtask.prompt.clone();
+        let routing_for_db = routing.clone();
+        let capabilities_for_db = capabilities.clone();
+        let result = with_chat_db(app, move |conn| {
+            let now = checkpoint::now_millis();
+            checkpoint::gc_expired_checkpoints(conn, now)?;
+            checkpoint::begin_checkpoint(
+                conn,
+                &orchestration_id,
+                &conv_id,
+                &prompt,
+                &routing_for_db,
+                &capabilities_for_db,
+                now,
+            )
+        })
+        .await;
+        if let Err(e) = result {
+            log::warn!(
+                "[Orchestrator] Failed to start checkpoint for {}: {}",
+                assistant_message_id,
+                e
+            );
+        }
+    }
+    let mut resume_from = resume_from;
+    let mut completed = false;
 
     // Track tried models for reroute
     let mut tried_models: Vec<String> = vec![routing.model_id.clone()];
@@ -574,7 +1151,19 @@ async fn execute_single_task(
 
         // Create channel and spawn worker
         let (event_tx, mut event_rx) = mpsc::channel::<WorkerEvent>(256);
-        let worker = create_worker(&routing, app, &capabilities)?;
+        let worker_checkpoint = checkpointed.then(|| WorkerCheckpoint {
+            orchestration_id: assistant_message_id.to_string(),
+            resume: resume_from.take(),
+        });
+        let worker = create_worker(
+            &routing,
+            app,
+            &capabilities,
+            &subtask.prompt,
+            worker_checkpoint,
+            &trace,
+            !user_explicitly_selected,
+        )?;
         let worker_for_cancel = Arc::clone(&worker);
         let worker_prompt = subtask.prompt.clone();
         let worker_routing = routing.clone();
@@ -626,6 +1215,14 @@ async fn execute_single_task(
                                 if let WorkerEvent::Error { ref message } = worker_event {
                                     captured_error = Some(message.clone());
                                 }
+                                if let Some(record) = tool_message_record(
+                                    &conv_id,
+                                    &worker_event,
+                                    Some(&model_id_for_events),
+                                    now_millis(),
+                                ) {
+                                    persist_message_record(app_for_events.clone(), record).await;
+                                }
                                 if matches!(worker_event, WorkerEvent::Complete { .. }) {
                                     if let Some(record) = completion_message_record(
                                         &conv_id,
@@ -638,7 +1235,7 @@ async fn execute_single_task(
                                         now_millis(),
                                         None,
                                     ) {
-                                        persist_completion_message(app_for_events.clone(), record).await;
+                                        persist_message_record(app_for_events.clone(), record).await;
                                     }
                                 }
                                 let orchestrator_event = OrchestratorEvent {
@@ -689,6 +1286,7 @@ async fn execute_single_task(
                     "[Orchestrator] Completed single-task orchestration for conversation {}",
                     conversation_id
                 );
+                completed = reroutable_error.is_none();
             }
             Ok(Err(e)) => {
                 log::error!("[Orchestrator] Worker error: {}", e);
@@ -736,6 +1334,17 @@ async fn execute_single_task(
                     backoff_secs,
                     reroutable_error.as_deref().unwrap_or("unknown"),
                 );
+                trace.emit(
+                    "retry",
+                    serde_json::json!({
+                        "kind": "network",
+                        "attempt": network_retry_count,
+                        "max_attempts": router::MAX_NETWORK_RETRIES,
+                        "backoff_secs": backoff_secs,
+                        "model": routing.model_id,
+                        "error": reroutable_error,
+                    }),
+                );
                 let mut cancel_sleep = cancel_rx.clone();
                 if !sleep_or_cancel(
                     std::time::Duration::from_secs(backoff_secs),
@@ -749,6 +1358,10 @@ async fn execute_single_task(
                     );
                     break;
                 }
+                // Pick up after the last round that finished before the drop.
+                if checkpointed {
+                    resume_from = saved_round(app, assistant_message_id).await;
+                }
                 continue;
             }
             log::error!(
@@ -798,6 +1411,15 @@ async fn execute_single_task(
                     subtask_id: None,
                 };
                 let _ = app.emit("orchestrator://event", &reroute_event);
+                trace.emit(
+                    "retry",
+                    serde_json::json!({
+                        "kind": "context_overflow",
+                        "from_model": failed_model,
+                        "to_model": fallback_model,
+                        "error": error_msg,
+                    }),
+                );
 
                 routing.model_id = fallback_model.clone();
                 tried_models.push(fallback_model);
@@ -847,6 +1469,15 @@ async fn execute_single_task(
                         subtask_id: None,
                     };
                     let _ = app.emit("orchestrator://event", &reroute_event);
+                    trace.emit(
+                        "retry",
+                        serde_json::json!({
+                            "kind": "timeout_fallback",
+                            "from_model": failed_model,
+                            "to_model": fallback_model,
+                            "error": error_msg,
+                        }),
+                    );
 
                     // Update routing to use fallback model
                     routing.model_id = fallback_model.clone();
@@ -885,6 +1516,16 @@ async fn execute_single_task(
                 MAX_SAME_MODEL_RETRIES,
                 error_msg,
             );
+            trace.emit(
+                "retry",
+                serde_json::json!({
+                    "kind": "same_model",
+                    "attempt": same_model_retry_count,
+                    "max_attempts": MAX_SAME_MODEL_RETRIES,
+                    "model": routing.model_id,
+                    "error": error_msg,
+                }),
+            );
 
             // Brief backoff before retry
             let mut cancel_sleep = cancel_rx.clone();
@@ -951,6 +1592,17 @@ async fn execute_single_task(
                     subtask_id: None,
                 };
                 let _ = app.emit("orchestrator://event", &reroute_event);
+                trace.emit(
+                    "retry",
+                    serde_json::json!({
+                        "kind": "reroute",
+                        "attempt": reroute_count + 1,
+                        "from_model": failed_model,
+                        "to_model": new_model,
+                        "reason": reason,
+                        "error": error_msg,
+                    }),
+                );
 
                 // Update routing for next iteration
                 routing.model_id = new_model.clone();
@@ -975,9 +1627,74 @@ async fn execute_single_task(
         }
     }
 
+    let outcome = if completed {
+        "completed"
+    } else if *cancel_rx.borrow() {
+        "cancelled"
+    } else {
+        "failed"
+    };
+    trace.emit(
+        "turn_end",
+        serde_json::json!({ "outcome": outcome, "models_tried": tried_models }),
+    );
+
+    // Failed and cancelled turns keep their checkpoint until it expires.
+    if checkpointed && completed {
+        let orchestration_id = assistant_message_id.to_string();
+        if let Err(e) = with_chat_db(app, move |conn| {
+            checkpoint::clear_checkpoint(conn, &orchestration_id)
+        })
+        .await
+        {
+            log::warn!(
+                "[Orchestrator] Failed to clear checkpoint for {}: {}",
+                assistant_message_id,
+                e
+            );
+        }
+    }
+
     Ok(())
 }
 
+/// Trace fields describing the route a task runs on.
+fn routing_trace_fields(
+    routing: &RoutingDecision,
+    overridden: bool,
+    trusted: bool,
+) -> serde_json::Value {
+    serde_json::json!({
+        "worker_type": routing.worker_type,
+        "model": routing.model_id,
+        "delegation": routing.delegation,
+        "publisher": routing.publisher_slug,
+        "reason": routing.reason,
+        "overridden": overridden,
+        "trusted": trusted,
+    })
+}
+
+/// The last checkpointed round of an orchestration, if one was saved.
+async fn saved_round(app: &AppHandle, orchestration_id: &str) -> Option<RoundCheckpoint> {
+    let id = orchestration_id.to_string();
+    match with_chat_db(app, move |conn| {
+        checkpoint::load_checkpoint(conn, &id, checkpoint::now_millis())
+    })
+    .await
+    {
+        Ok(checkpoint) => checkpoint.and_then(|checkpoint| checkpoint.progress),
+        Err(e) => {
+            log::warn!(
+                "[Orchestrator] Failed to load checkpoint for {}: {}",
+                orchestration_id,
+                e
+            );
+            None
+        }
+    }
+}
+
 // =============================================================================
 // Multi-Task Execution (Parallel by Dependency Layers)
 // =============================================================================
@@ -999,6 +1716,8 @@ async fn execute_multi_task(
     assistant_message_id: &str,
     started_at_ms: i64,
 ) -> Result<(), String> {
+    let trace = TurnTracer::new(app, conversation_id, capabilities.trace);
+
     // Persist plan to SQLite
     let plan_id = Uuid::new_v4().to_string();
     let now = std::time::SystemTime::now()
@@ -1084,6 +1803,11 @@ async fn execute_multi_task(
                                     .or_default()
                                     .push_str(text);
                             }
+                            if let Some(record) =
+                                tool_message_record(&conv_id, &worker_event, None, now_millis())
+                            {
+                                persist_message_record(app_for_events.clone(), record).await;
+                            }
                             if matches!(worker_event, WorkerEvent::Complete { .. }) {
                                 let message_id = format!("{}:{}", assistant_message_id_for_events, subtask_id);
                                 let streamed_content = streamed_by_subtask
@@ -1101,7 +1825,7 @@ async fn execute_multi_task(
                                     now_millis(),
                                     None,
                                 ) {
-                                    persist_completion_message(app_for_events.clone(), record).await;
+                                    persist_message_record(app_for_events.clone(), record).await;
                                 }
                             }
                             let orchestrator_event = OrchestratorEvent {
@@ -1190,6 +1914,9 @@ async fn execute_multi_task(
                 routing.delegation = DelegationType::FullHandoff;
                 routing.reason = format!("{} (trusted)", routing.reason);
             }
+            let mut routing_fields = routing_trace_fields(&routing, false, trusted);
+            routing_fields["subtask_id"] = serde_json::json!(subtask.id);
+            trace.emit("routing", routing_fields);
 
             // Load skill content
             let skill_content = load_skill_content(&routing.selected_skills)?;
@@ -1204,7 +1931,19 @@ async fn execute_multi_task(
                 .map_err(|e| format!("Failed to emit transition: {}", e))?;
 
             // Spawn worker — keep Arc clone for cancellation
-            let worker = create_worker(&routing, app, capabilities)?;
+            let user_selected = capabilities
+                .selected_model
+                .as_ref()
+                .is_some_and(|m| !m.is_empty());
+            let worker = create_worker(
+                &routing,
+                app,
+                capabilities,
+                &subtask.prompt,
+                None,
+                &trace,
+                !user_selected,
+            )?;
             active_workers.push(Arc::clone(&worker));
             let subtask_prompt = subtask.prompt.clone();
             let subtask_id = subtask.id.clone();
@@ -1396,6 +2135,10 @@ async fn execute_multi_task(
         conversation_id,
         plan_id
     );
+    trace.emit(
+        "turn_end",
+        serde_json::json!({ "outcome": "completed", "plan_id": plan_id }),
+    );
 
     Ok(())
 }
@@ -1434,22 +2177,52 @@ pub async fn cancel(state: &OrchestratorState, conversation_id: &str) -> Result<
     }
 }
 
+/// Whether an orchestration is currently running for a conversation.
+pub async fn is_active(state: &OrchestratorState, conversation_id: &str) -> bool {
+    state
+        .active_sessions
+        .lock()
+        .await
+        .contains_key(conversation_id)
+}
+
 // =============================================================================
 // Worker Creation
 // =============================================================================
 
 /// Create the appropriate worker based on the routing decision.
+///
+/// `allow_escalation` lets a negative eval signal move a chat-model turn to a
+/// stronger available model; pass false when the user picked the model.
 fn create_worker(
     routing: &RoutingDecision,
     _app: &AppHandle,
     capabilities: &UserCapabilities,
+    prompt: &str,
+    checkpoint: Option<WorkerCheckpoint>,
+    trace: &TurnTracer,
+    allow_escalation: bool,
 ) -> Result<Arc<dyn Worker>, String> {
     match routing.worker_type {
-        WorkerType::ChatModel => Ok(Arc::new(ChatModelWorker::with_tools(
-            capabilities.tool_definitions.clone(),
-            routing.publisher_slug.clone(),
-            capabilities.effective_agent_policy.clone(),
-        ))),
+        WorkerType::ChatModel => Ok(Arc::new(
+            ChatModelWorker::with_tools(
+                capabilities.tool_definitions.clone(),
+                routing.publisher_slug.clone(),
+                capabilities.effective_agent_policy.clone(),
+            )
+            .with_publisher_priority(router::publisher_tool_priority(
+                prompt,
+                &capabilities.available_tools,
+            ))
+            .with_checkpoint(checkpoint)
+            .with_trace(trace.clone())
+            .with_failure_repeat_threshold(capabilities.tool_failure_repeat_threshold)
+            .with_escalation_models(if allow_escalation {
+                capabilities.available_models.clone()
+            } else {
+                Vec::new()
+            }),
+        )),
         WorkerType::CloudAgent => {
             let deployment_id = capabilities
                 .configured_private_chat_deployment_id()
@@ -1552,6 +2325,101 @@ pub fn strip_frontmatter(content: &str) -> &str {
 mod tests {
     use super::*;
 
+    // =========================================================================
+    // Classify Only
+    // =========================================================================
+
+    fn classify_only_capabilities() -> UserCapabilities {
+        UserCapabilities {
+            has_local_agent: false,
+            agent_type: None,
+            active_agent_session_id: None,
+            selected_model: Some("us.anthropic.claude-opus-4-6-v1".to_string()),
+            force_private_chat: true,
+            private_chat_deployment_id: Some("deployment_123".to_string()),
+            available_models: vec!["anthropic/claude-sonnet-4".to_string()],
+            available_tools: vec![],
+            tool_definitions: vec![],
+            installed_skills: vec![],
+            model_rankings: vec![],
+            reasoning_effort: Some("high".to_string()),
+            generation: None,
+            project_root: None,
+            forced_tool: None,
+            system_prompt: None,
+            trace: false,
+            effective_agent_policy: Default::default(),
+            tool_failure_repeat_threshold: None,
+        }
+    }
+
+    #[test]
+    fn classify_only_returns_route_without_executing() {
+        let state = OrchestratorState::new();
+        let capabilities = classify_only_capabilities();
+        let decision = route_with_rule(&state, "Summarize this thread for me", &capabilities, None);
+        assert_eq!(decision.worker_type, WorkerType::ChatModel);
+        assert_eq!(decision.model_id, "us.anthropic.claude-opus-4-6-v1");
+        assert_eq!(decision.delegation, DelegationType::InLoop);
+        assert_eq!(
+            decision.publisher_slug.as_deref(),
+            Some("seren-private-models")
+        );
+        assert_eq!(decision.reasoning_effort.as_deref(), Some("high"));
+    }
+
+    #[test]
+    fn routing_override_requires_a_known_model() {
+        let state = OrchestratorState::new();
+        let mut capabilities = classify_only_capabilities();
+        let mut decision =
+            route_with_rule(&state, "Summarize this thread for me", &capabilities, None);
+
+        decision.model_id = "openai/gpt-5.3".to_string();
+        let err = validate_routing_override(&decision, &capabilities).unwrap_err();
+        assert!(err.contains("openai/gpt-5.3"));
+
+        capabilities
+            .available_models
+            .push("openai/gpt-5.3".to_string());
+        assert!(validate_routing_override(&decision, &capabilities).is_ok());
+
+        decision.model_id = "us.anthropic.claude-opus-4-6-v1".to_string();
+        assert!(validate_routing_override(&decision, &capabilities).is_ok());
+    }
+
+    #[test]
+    fn routing_rules_pinning_unavailable_models_are_skipped() {
+        let state = OrchestratorState::new();
+        let capabilities = classify_only_capabilities();
+        let prompt = "Summarize this thread for me";
+        let rule = |model: &str| {
+            RoutingRule::new(
+                routing_rules::RuleMatch::Keyword {
+                    keyword: "summarize".to_string(),
+                },
+                routing_rules::RouteFragment {
+                    model_id: Some(model.to_string()),
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        };
+
+        let unknown = rule("openai/gpt-5.3");
+        let decision = route_with_rule(&state, prompt, &capabilities, Some(&unknown));
+        assert_eq!(decision.model_id, "us.anthropic.claude-opus-4-6-v1");
+        assert!(validate_routing_override(&decision, &capabilities).is_ok());
+
+        let known = rule("anthropic/claude-sonnet-4");
+        let decision = route_with_rule(&state, prompt, &capabilities, Some(&known));
+        assert_eq!(decision.model_id, "anthropic/claude-sonnet-4");
+        assert_eq!(
+            decision.reason,
+            format!("Matched routing rule {}", known.id)
+        );
+    }
+
     // =========================================================================
     // Frontmatter Stripping
     // =========================================================================
@@ -1710,6 +2578,80 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn tool_events_persist_as_tool_rows_with_shared_ids() {
+        let call = WorkerEvent::ToolCall {
+            tool_call_id: "tc1".to_string(),
+            name: "read_file".to_string(),
+            arguments: r#"{"path":"/tmp/foo"}"#.to_string(),
+            title: "read_file".to_string(),
+        };
+        let record = tool_message_record("conv-1", &call, Some("model-a"), 1000).unwrap();
+        assert_eq!(record.id, "conv-1:tool_call:tc1");
+        assert_eq!(record.content, "read_file");
+        let metadata: serde_json::Value =
+            serde_json::from_str(record.metadata.as_deref().unwrap()).unwrap();
+        assert_eq!(metadata["message_type"], "tool_call");
+        assert_eq!(metadata["tool_call"]["status"], "running");
+        assert_eq!(metadata["tool_call"]["arguments"], r#"{"path":"/tmp/foo"}"#);
+
+        let result = WorkerEvent::ToolResult {
+            tool_call_id: "tc1".to_string(),
+            content: "denied".to_string(),
+            is_error: true,
+        };
+        let record = tool_message_record("conv-1", &result, None, 2000).unwrap();
+        assert_eq!(record.id, "conv-1:tool_result:tc1");
+        assert_eq!(record.content, "denied");
+        let metadata: serde_json::Value =
+            serde_json::from_str(record.metadata.as_deref().unwrap()).unwrap();
+        assert_eq!(metadata["message_type"], "tool_result");
+        assert_eq!(metadata["tool_call"]["status"], "error");
+        assert_eq!(metadata["tool_call"]["is_error"], true);
+
+        let content = WorkerEvent::Content {
+            text: "hi".to_string(),
+        };
+        assert!(tool_message_record("conv-1", &content, None, 3000).is_none());
+    }
+
+    #[test]
+    fn saving_a_tool_result_settles_its_call_row() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        crate::services::database::setup_schema(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO conversations (id, title, created_at) VALUES ('conv-1', 'Chat', 1000)",
+            [],
+        )
+        .unwrap();
+        let call = WorkerEvent::ToolCall {
+            tool_call_id: "tc1".to_string(),
+            name: "read_file".to_string(),
+            arguments: "{}".to_string(),
+            title: String::new(),
+        };
+        let result = WorkerEvent::ToolResult {
+            tool_call_id: "tc1".to_string(),
+            content: "ok".to_string(),
+            is_error: false,
+        };
+        for (event, timestamp) in [(&call, 1000), (&result, 2000)] {
+            let record = tool_message_record("conv-1", event, None, timestamp).unwrap();
+            save_tool_aware_record(&conn, &record).unwrap();
+        }
+
+        let metadata: String = conn
+            .query_row(
+                "SELECT metadata FROM messages WHERE id = 'conv-1:tool_call:tc1'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        let metadata: serde_json::Value = serde_json::from_str(&metadata).unwrap();
+        assert_eq!(metadata["tool_call"]["status"], "completed");
+        assert_eq!(metadata["tool_call"]["name"], "read_file");
+    }
+
     #[test]
     fn completion_record_uses_shared_id_and_streamed_content() {
         let event = WorkerEvent::Complete {
@@ -1717,6 +2659,7 @@ mod tests {
             thinking: None,
             cost: Some(0.25),
             rlm_steps: None,
+            truncated: false,
         };
         let record = completion_message_record(
             "conv-1",
@@ -1749,6 +2692,22 @@ mod tests {
         assert_eq!(metadata["cost"], 0.25);
     }
 
+    #[test]
+    fn turn_slots_enforce_the_limit_and_release_on_drop() {
+        let state = OrchestratorState::new();
+        let first = state.try_acquire_turn(2).unwrap();
+        let _second = state.try_acquire_turn(2).unwrap();
+        assert_eq!(state.active_turn_count(), 2);
+
+        let err = state.try_acquire_turn(2).err().unwrap();
+        assert!(err.contains("Too many active turns"), "{err}");
+        assert_eq!(state.active_turn_count(), 2);
+
+        drop(first);
+        assert_eq!(state.active_turn_count(), 1);
+        assert!(state.try_acquire_turn(2).is_ok());
+    }
+
     #[tokio::test]
     async fn cancel_flips_flag_and_keeps_session_entry() {
         // Contract (GH #1581): cancel() signals via the watch channel but
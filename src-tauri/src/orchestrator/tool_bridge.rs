@@ -1,13 +1,25 @@
 // ABOUTME: Bridge for routing non-local tool calls to the frontend for execution.
 // ABOUTME: ChatModelWorker registers pending tool calls; frontend submits results.
 
+use serde::Deserialize;
 use std::collections::HashMap;
 use tokio::sync::{Mutex, oneshot};
 
+/// Content delivered to a waiting worker when its turn is cancelled.
+pub const CANCELLED_TOOL_RESULT: &str = "Tool execution was cancelled";
+
+/// Image produced by a tool (e.g. a screenshot), base64-encoded.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ToolImage {
+    pub mime_type: String,
+    pub base64: String,
+}
+
 /// Result of a tool execution performed by the frontend.
 pub struct ToolExecutionResult {
     pub content: String,
     pub is_error: bool,
+    pub image: Option<ToolImage>,
 }
 
 /// Shared bridge between the Rust ChatModelWorker and the frontend tool executor.
@@ -15,8 +27,16 @@ pub struct ToolExecutionResult {
 /// When ChatModelWorker encounters a non-local tool (gateway__, mcp__),
 /// it registers a pending request here and waits. The frontend executes the tool
 /// and submits the result via the `submit_tool_result` Tauri command.
+///
+/// Each registration is tagged with the turn that issued it (the orchestrated
+/// conversation ID) so cancelling the turn can release every pending wait.
 pub struct ToolResultBridge {
-    pending: Mutex<HashMap<String, oneshot::Sender<ToolExecutionResult>>>,
+    pending: Mutex<HashMap<String, PendingToolCall>>,
+}
+
+struct PendingToolCall {
+    turn_id: String,
+    tx: oneshot::Sender<ToolExecutionResult>,
 }
 
 impl ToolResultBridge {
@@ -26,19 +46,43 @@ impl ToolResultBridge {
         }
     }
 
-    /// Register a pending tool call. Returns a receiver that the worker awaits.
-    pub async fn register(&self, tool_call_id: &str) -> oneshot::Receiver<ToolExecutionResult> {
+    /// Register a pending tool call for a turn. Returns a receiver that the worker awaits.
+    pub async fn register(
+        &self,
+        turn_id: &str,
+        tool_call_id: &str,
+    ) -> oneshot::Receiver<ToolExecutionResult> {
         let (tx, rx) = oneshot::channel();
         let mut pending = self.pending.lock().await;
-        pending.insert(tool_call_id.to_string(), tx);
+        pending.insert(
+            tool_call_id.to_string(),
+            PendingToolCall {
+                turn_id: turn_id.to_string(),
+                tx,
+            },
+        );
         rx
     }
 
-    /// Submit a tool result from the frontend. Returns true if a pending request was found.
+    /// Submit a text-only tool result. Returns true if a pending request was found.
+    #[cfg(test)]
     pub async fn submit(&self, tool_call_id: &str, content: String, is_error: bool) -> bool {
+        self.submit_result(
+            tool_call_id,
+            ToolExecutionResult {
+                content,
+                is_error,
+                image: None,
+            },
+        )
+        .await
+    }
+
+    /// Submit a full tool result, including any image the tool produced.
+    pub async fn submit_result(&self, tool_call_id: &str, result: ToolExecutionResult) -> bool {
         let mut pending = self.pending.lock().await;
-        if let Some(tx) = pending.remove(tool_call_id) {
-            let _ = tx.send(ToolExecutionResult { content, is_error });
+        if let Some(call) = pending.remove(tool_call_id) {
+            let _ = call.tx.send(result);
             true
         } else {
             log::warn!(
@@ -48,6 +92,34 @@ impl ToolResultBridge {
             false
         }
     }
+
+    /// Drop a pending tool call without delivering a result (the worker gave up
+    /// waiting). A later submit for it returns false.
+    pub async fn unregister(&self, tool_call_id: &str) {
+        self.pending.lock().await.remove(tool_call_id);
+    }
+
+    /// Release every pending tool call of a cancelled turn with an error
+    /// result, so workers stop waiting on the frontend immediately. Returns
+    /// the number of waits released.
+    pub async fn cancel_all(&self, turn_id: &str) -> usize {
+        let mut pending = self.pending.lock().await;
+        let ids: Vec<String> = pending
+            .iter()
+            .filter(|(_, call)| call.turn_id == turn_id)
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in &ids {
+            if let Some(call) = pending.remove(id) {
+                let _ = call.tx.send(ToolExecutionResult {
+                    content: CANCELLED_TOOL_RESULT.to_string(),
+                    is_error: true,
+                    image: None,
+                });
+            }
+        }
+        ids.len()
+    }
 }
 
 #[cfg(test)]
@@ -58,7 +130,7 @@ mod tests {
     async fn register_and_submit_round_trip() {
         let bridge = ToolResultBridge::new();
 
-        let rx = bridge.register("tc_1").await;
+        let rx = bridge.register("conv_1", "tc_1").await;
 
         let submitted = bridge
             .submit("tc_1", "result content".to_string(), false)
@@ -82,7 +154,7 @@ mod tests {
     #[tokio::test]
     async fn submit_error_result() {
         let bridge = ToolResultBridge::new();
-        let rx = bridge.register("tc_err").await;
+        let rx = bridge.register("conv_1", "tc_err").await;
 
         bridge
             .submit("tc_err", "tool failed".to_string(), true)
@@ -97,8 +169,8 @@ mod tests {
     async fn multiple_concurrent_requests() {
         let bridge = ToolResultBridge::new();
 
-        let rx1 = bridge.register("tc_a").await;
-        let rx2 = bridge.register("tc_b").await;
+        let rx1 = bridge.register("conv_1", "tc_a").await;
+        let rx2 = bridge.register("conv_1", "tc_b").await;
 
         bridge.submit("tc_b", "result_b".to_string(), false).await;
         bridge.submit("tc_a", "result_a".to_string(), false).await;
@@ -108,4 +180,59 @@ mod tests {
         assert_eq!(r1.content, "result_a");
         assert_eq!(r2.content, "result_b");
     }
+
+    #[tokio::test]
+    async fn cancelled_turn_unblocks_pending_tool_promptly() {
+        let bridge = std::sync::Arc::new(ToolResultBridge::new());
+        let rx = bridge.register("conv_1", "tc_wait").await;
+        let other = bridge.register("conv_2", "tc_other").await;
+
+        let canceller = std::sync::Arc::clone(&bridge);
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            assert_eq!(canceller.cancel_all("conv_1").await, 1);
+        });
+
+        let result = tokio::time::timeout(std::time::Duration::from_secs(2), rx)
+            .await
+            .expect("cancelled tool wait should return well before the tool timeout")
+            .unwrap();
+        assert!(result.is_error);
+        assert_eq!(result.content, CANCELLED_TOOL_RESULT);
+
+        // Other turns keep waiting for their own results.
+        assert!(bridge.submit("tc_other", "ok".to_string(), false).await);
+        assert_eq!(other.await.unwrap().content, "ok");
+        assert!(!bridge.submit("tc_wait", "late".to_string(), false).await);
+    }
+
+    #[tokio::test]
+    async fn submitted_image_reaches_the_worker() {
+        let bridge = ToolResultBridge::new();
+        let rx = bridge.register("conv_1", "tc_shot").await;
+
+        let image = ToolImage {
+            mime_type: "image/png".to_string(),
+            base64: "iVBORw0KGgo=".to_string(),
+        };
+        let result = ToolExecutionResult {
+            content: "Took a screenshot".to_string(),
+            is_error: false,
+            image: Some(image),
+        };
+        assert!(bridge.submit_result("tc_shot", result).await);
+
+        let received = rx.await.unwrap();
+        let image = received.image.expect("image is delivered");
+        assert_eq!(image.mime_type, "image/png");
+        assert_eq!(image.base64, "iVBORw0KGgo=");
+    }
+
+    #[tokio::test]
+    async fn unregistered_call_ignores_late_result() {
+        let bridge = ToolResultBridge::new();
+        let _rx = bridge.register("conv_1", "tc_slow").await;
+        bridge.unregister("tc_slow").await;
+        assert!(!bridge.submit("tc_slow", "late".to_string(), false).await);
+    }
 }
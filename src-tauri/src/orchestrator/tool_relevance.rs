@@ -118,6 +118,20 @@ pub fn select_relevant_tools(
     tools: &[serde_json::Value],
     model_id: &str,
     recently_used_publishers: &[String],
+) -> Vec<serde_json::Value> {
+    select_relevant_tools_with_priority(query, tools, model_id, recently_used_publishers, None)
+}
+
+/// [`select_relevant_tools`], with `priority_publisher`'s toolset scoped in
+/// ahead of the BM25-ranked publishers. Its tools count against the same
+/// budget, so a match with no words in common with the prompt ("email" vs
+/// `send_message`) is still offered without growing the tool list.
+pub fn select_relevant_tools_with_priority(
+    query: &str,
+    tools: &[serde_json::Value],
+    model_id: &str,
+    recently_used_publishers: &[String],
+    priority_publisher: Option<&str>,
 ) -> Vec<serde_json::Value> {
     let (max_tools, token_budget) = model_budget(model_id);
 
@@ -194,11 +208,17 @@ pub fn select_relevant_tools(
         .filter(|(_, s)| *s > 0.0)
         .collect();
     ranked_publishers.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-    let top_publishers: Vec<String> = ranked_publishers
+    let mut top_publishers: Vec<String> = ranked_publishers
         .iter()
         .take(TOP_K_PUBLISHERS)
         .map(|(name, _)| name.clone())
         .collect();
+    if let Some(priority) = priority_publisher
+        && publisher_pool_indices.contains_key(priority)
+    {
+        top_publishers.retain(|name| name != priority);
+        top_publishers.insert(0, priority.to_string());
+    }
 
     // Build selection: start with pinned tools, then add full toolsets for top publishers,
     // then fill remaining budget with highest-scoring individual tools.
@@ -673,6 +693,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn priority_publisher_is_selected_within_budget() {
+        let mut tools: Vec<serde_json::Value> = (0..200)
+            .map(|i| {
+                make_tool(
+                    &format!("notes_{i}"),
+                    &format!("write notes to Dana about Friday {i}"),
+                )
+            })
+            .collect();
+        tools.push(make_tool(
+            "gateway__gmail__send_message",
+            "Send a message from the connected account",
+        ));
+
+        let result = select_relevant_tools_with_priority(
+            "email Dana about Friday",
+            &tools,
+            TEST_MODEL,
+            &[],
+            Some("gmail"),
+        );
+
+        assert!(
+            result.iter().any(|t| {
+                t.pointer("/function/name").and_then(|v| v.as_str())
+                    == Some("gateway__gmail__send_message")
+            }),
+            "the priority publisher's tool should be selected"
+        );
+        assert!(result.len() <= model_budget(TEST_MODEL).0);
+    }
+
     #[test]
     fn original_ordering_preserved_in_output() {
         let tools = vec![
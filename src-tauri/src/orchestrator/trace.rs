@@ -0,0 +1,91 @@
+// ABOUTME: Opt-in per-turn trace of orchestrator decisions for debugging.
+// ABOUTME: Emits timestamped `orchestrator://trace` events when a turn enables tracing.
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use super::checkpoint::now_millis;
+
+pub const TRACE_EVENT: &str = "orchestrator://trace";
+
+/// One step in a traced turn: routing, round start, tool dispatch,
+/// truncation, retry, or completion.
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceEvent {
+    pub conversation_id: String,
+    pub stage: String,
+    /// Unix epoch milliseconds.
+    pub timestamp: i64,
+    pub fields: serde_json::Value,
+}
+
+/// Emits trace events for one turn. A disabled tracer (the default) drops
+/// every event, so call sites never need to check whether tracing is on.
+#[derive(Clone, Default)]
+pub struct TurnTracer {
+    target: Option<(AppHandle, String)>,
+}
+
+impl TurnTracer {
+    pub fn new(app: &AppHandle, conversation_id: &str, enabled: bool) -> Self {
+        Self {
+            target: enabled.then(|| (app.clone(), conversation_id.to_string())),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn is_enabled(&self) -> bool {
+        self.target.is_some()
+    }
+
+    pub fn emit(&self, stage: &str, fields: serde_json::Value) {
+        let Some((app, conversation_id)) = &self.target else {
+            return;
+        };
+        let event = trace_event(conversation_id, stage, fields, now_millis());
+        if let Err(e) = app.emit(TRACE_EVENT, &event) {
+            log::debug!("[Orchestrator] Failed to emit trace event: {}", e);
+        }
+    }
+}
+
+fn trace_event(
+    conversation_id: &str,
+    stage: &str,
+    fields: serde_json::Value,
+    timestamp: i64,
+) -> TraceEvent {
+    TraceEvent {
+        conversation_id: conversation_id.to_string(),
+        stage: stage.to_string(),
+        timestamp,
+        fields,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trace_event_serializes_stage_timestamp_and_fields() {
+        let event = trace_event(
+            "conv-1",
+            "tool_dispatch",
+            serde_json::json!({ "tool": "read_file", "target": "local" }),
+            1_700_000_000_000,
+        );
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["conversation_id"], "conv-1");
+        assert_eq!(json["stage"], "tool_dispatch");
+        assert_eq!(json["timestamp"], 1_700_000_000_000i64);
+        assert_eq!(json["fields"]["target"], "local");
+    }
+
+    #[test]
+    fn default_tracer_is_disabled_and_drops_events() {
+        let tracer = TurnTracer::default();
+        assert!(!tracer.is_enabled());
+        tracer.emit("round_start", serde_json::json!({ "round": 0 }));
+    }
+}
@@ -68,6 +68,9 @@ pub enum WorkerEvent {
         /// JSON-encoded Vec<rlm::ChunkResult> set when RLM processed this response.
         #[serde(skip_serializing_if = "Option::is_none")]
         rlm_steps: Option<String>,
+        /// The model stopped at its output length cap (finish_reason "length"),
+        /// so `final_content` is cut off and the UI can offer to continue.
+        truncated: bool,
     },
     Error {
         message: String,
@@ -88,6 +91,12 @@ pub enum WorkerEvent {
         total: usize,
         summary: String,
     },
+    /// Rough size of the prompt about to be sent, emitted before each
+    /// request round so the UI can show how full the context is.
+    ContextUsage {
+        estimated_tokens: usize,
+        model_context_window: usize,
+    },
 }
 
 /// Routing decision made by the orchestrator.
@@ -105,9 +114,32 @@ pub struct RoutingDecision {
     /// Reasoning effort level forwarded from the frontend.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reasoning_effort: Option<String>,
+    /// Sampling controls forwarded from the frontend.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generation: Option<GenerationParams>,
     /// Project root for live repo context injection.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub project_root: Option<String>,
+    /// Tool the first request round must call, sent as a named `tool_choice`.
+    /// Later rounds revert to `"auto"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forced_tool: Option<String>,
+    /// Base persona for the chat-model system prompt. Skill content is still
+    /// appended after it. None = the built-in Seren Desktop persona.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
+}
+
+/// Optional sampling parameters for chat-model requests. Unset fields are
+/// left out of the request body so the provider's defaults still apply.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct GenerationParams {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stop: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -206,13 +238,35 @@ pub struct UserCapabilities {
     /// Values: "minimal", "low", "medium", "high", "xhigh". None = provider default.
     #[serde(default)]
     pub reasoning_effort: Option<String>,
+    /// Sampling controls (temperature, top_p, stop sequences) for skills that
+    /// need deterministic output. None = provider defaults.
+    #[serde(default)]
+    pub generation: Option<GenerationParams>,
     /// Project root directory path. Used to gather live repo context (git status,
     /// branch, directory structure) for injection into the system prompt.
     #[serde(default)]
     pub project_root: Option<String>,
+    /// Tool the first round must call (e.g. `seren_web_fetch` for research
+    /// flows). Ignored unless the tool only reads. None = the model chooses.
+    #[serde(default)]
+    pub forced_tool: Option<String>,
+    /// Persona replacing the default system prompt (e.g. a coding or writing
+    /// assistant mode). None = the built-in persona.
+    #[serde(default)]
+    pub system_prompt: Option<String>,
+    /// Emit `orchestrator://trace` events for this turn (routing, rounds,
+    /// tool dispatch, truncations, retries, completion).
+    #[serde(default)]
+    pub trace: bool,
     /// Backend-enforced policy for model-originated local file operations.
     #[serde(default)]
     pub effective_agent_policy: EffectiveAgentPolicy,
+    /// After this many identical failing calls in a row (same tool,
+    /// arguments, and error), the chat worker tells the model to change
+    /// approach. None = `DEFAULT_TOOL_FAILURE_REPEAT_THRESHOLD`, 0 = off.
+    /// The turn aborts one repeat later (never before the third).
+    #[serde(default)]
+    pub tool_failure_repeat_threshold: Option<usize>,
 }
 
 impl UserCapabilities {
@@ -391,11 +445,13 @@ mod tests {
             thinking: None,
             cost: Some(0.005),
             rlm_steps: None,
+            truncated: true,
         };
         let json = serde_json::to_value(&complete).unwrap();
         assert_eq!(json["type"], "complete");
         assert_eq!(json["thinking"], serde_json::Value::Null);
         assert_eq!(json["cost"], 0.005);
+        assert_eq!(json["truncated"], true);
 
         // cost: None should be omitted from serialized JSON
         let complete_no_cost = WorkerEvent::Complete {
@@ -403,6 +459,7 @@ mod tests {
             thinking: None,
             cost: None,
             rlm_steps: None,
+            truncated: false,
         };
         let json = serde_json::to_value(&complete_no_cost).unwrap();
         assert!(json.get("cost").is_none());
@@ -459,7 +516,10 @@ mod tests {
             }],
             publisher_slug: None,
             reasoning_effort: None,
+            generation: None,
             project_root: None,
+            forced_tool: None,
+            system_prompt: None,
         };
 
         let json = serde_json::to_string(&decision).unwrap();
@@ -582,8 +642,13 @@ mod tests {
             installed_skills: vec![],
             model_rankings: vec![],
             reasoning_effort: None,
+            generation: None,
             project_root: None,
+            forced_tool: None,
+            system_prompt: None,
+            trace: false,
             effective_agent_policy: EffectiveAgentPolicy::default(),
+            tool_failure_repeat_threshold: None,
         };
 
         assert_eq!(
@@ -607,8 +672,13 @@ mod tests {
             installed_skills: vec![],
             model_rankings: vec![],
             reasoning_effort: None,
+            generation: None,
             project_root: None,
+            forced_tool: None,
+            system_prompt: None,
+            trace: false,
             effective_agent_policy: EffectiveAgentPolicy::default(),
+            tool_failure_repeat_threshold: None,
         };
 
         assert_eq!(caps.configured_private_chat_deployment_id(), None);
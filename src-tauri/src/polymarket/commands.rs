@@ -293,6 +293,46 @@ pub async fn subscribe_polymarket_market(
     }
 }
 
+/// Subscribe to several markets in one frame. Each market's updates are also
+/// emitted on `polymarket-market-update:<token_id>`.
+#[tauri::command]
+pub async fn subscribe_polymarket_markets(
+    token_ids: Vec<String>,
+    ws_state: State<'_, PolymarketWsState>,
+) -> Result<Vec<String>, String> {
+    let state = ws_state.lock().await;
+
+    match &*state {
+        Some(ws) => ws
+            .subscribe_markets(token_ids)
+            .await
+            .map_err(|e| format!("Subscription failed: {}", e)),
+        None => Err("WebSocket not connected".to_string()),
+    }
+}
+
+/// Stop receiving updates for a market, e.g. when its view closes
+#[tauri::command]
+pub async fn unsubscribe_polymarket_market(
+    token_id: String,
+    ws_state: State<'_, PolymarketWsState>,
+) -> Result<String, String> {
+    let state = ws_state.lock().await;
+
+    match &*state {
+        Some(ws) => {
+            let channel = Channel::Market {
+                market_id: token_id.clone(),
+            };
+            match ws.unsubscribe(&channel).await {
+                Ok(()) => Ok(format!("Unsubscribed from market {}", token_id)),
+                Err(e) => Err(format!("Unsubscribe failed: {}", e)),
+            }
+        }
+        None => Err("WebSocket not connected".to_string()),
+    }
+}
+
 /// Subscribe to user order updates (authenticated)
 #[tauri::command]
 pub async fn subscribe_polymarket_user<R: Runtime>(
@@ -1,5 +1,5 @@
-// ABOUTME: Polymarket types and errors for CLOB API authentication.
-// ABOUTME: Defines PolymarketError enum for authentication operations.
+// ABOUTME: Polymarket types and errors for CLOB API authentication and market data.
+// ABOUTME: Defines PolymarketError and the typed market-channel WebSocket events.
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -25,3 +25,284 @@ pub enum PolymarketError {
     #[error("Connection failed: {0}")]
     ConnectionFailed(String),
 }
+
+// ============================================================================
+// Market Channel Events
+// ============================================================================
+
+/// Number sent either as a JSON string ("0.52") or a bare number.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum NumberOrString {
+    Number(f64),
+    String(String),
+}
+
+fn parse_number<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match NumberOrString::deserialize(deserializer)? {
+        NumberOrString::Number(n) => Ok(n),
+        NumberOrString::String(s) => s.trim().parse().map_err(serde::de::Error::custom),
+    }
+}
+
+fn parse_optional_number<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match Option::<NumberOrString>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(NumberOrString::Number(n)) => Ok(Some(n)),
+        Some(NumberOrString::String(s)) => {
+            s.trim().parse().map(Some).map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+fn parse_optional_timestamp<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(parse_optional_number(deserializer)?.map(|ms| ms as i64))
+}
+
+/// One price level of an order book.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct OrderLevel {
+    #[serde(deserialize_with = "parse_number")]
+    pub price: f64,
+    #[serde(deserialize_with = "parse_number")]
+    pub size: f64,
+}
+
+/// Full order book snapshot for one token.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BookEvent {
+    #[serde(rename(deserialize = "asset_id"))]
+    pub token_id: String,
+    #[serde(default, alias = "buys")]
+    pub bids: Vec<OrderLevel>,
+    #[serde(default, alias = "sells")]
+    pub asks: Vec<OrderLevel>,
+    #[serde(default, deserialize_with = "parse_optional_timestamp")]
+    pub timestamp: Option<i64>,
+}
+
+/// A change at one price level, as nested in `price_changes`.
+#[derive(Debug, Clone, Deserialize)]
+struct RawPriceLevelChange {
+    asset_id: Option<String>,
+    #[serde(deserialize_with = "parse_number")]
+    price: f64,
+    #[serde(deserialize_with = "parse_number")]
+    size: f64,
+    side: String,
+}
+
+/// `price_change` frame. Older frames carry a single change at the top level;
+/// newer ones batch them in `price_changes`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PriceChangeFrame {
+    asset_id: Option<String>,
+    #[serde(default, deserialize_with = "parse_optional_number")]
+    price: Option<f64>,
+    #[serde(default, deserialize_with = "parse_optional_number")]
+    size: Option<f64>,
+    side: Option<String>,
+    #[serde(default)]
+    price_changes: Vec<RawPriceLevelChange>,
+    #[serde(default, deserialize_with = "parse_optional_timestamp")]
+    timestamp: Option<i64>,
+}
+
+/// Payload of the `polymarket://price-change` event.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceChange {
+    pub token_id: String,
+    pub price: f64,
+    pub size: f64,
+    pub side: String,
+    pub timestamp: Option<i64>,
+}
+
+impl PriceChangeFrame {
+    /// Flatten the frame into one change per token and price level.
+    pub fn changes(&self) -> Vec<PriceChange> {
+        let mut changes: Vec<PriceChange> = self
+            .price_changes
+            .iter()
+            .filter_map(|change| {
+                Some(PriceChange {
+                    token_id: change.asset_id.clone().or_else(|| self.asset_id.clone())?,
+                    price: change.price,
+                    size: change.size,
+                    side: change.side.clone(),
+                    timestamp: self.timestamp,
+                })
+            })
+            .collect();
+        if let (Some(token_id), Some(price), Some(size), Some(side)) =
+            (&self.asset_id, self.price, self.size, &self.side)
+        {
+            changes.push(PriceChange {
+                token_id: token_id.clone(),
+                price,
+                size,
+                side: side.clone(),
+                timestamp: self.timestamp,
+            });
+        }
+        changes
+    }
+}
+
+/// Minimum tick size change for one token.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TickSizeChangeEvent {
+    #[serde(rename(deserialize = "asset_id"))]
+    pub token_id: String,
+    #[serde(
+        rename(deserialize = "old_tick_size"),
+        deserialize_with = "parse_number"
+    )]
+    pub old_tick_size: f64,
+    #[serde(
+        rename(deserialize = "new_tick_size"),
+        deserialize_with = "parse_number"
+    )]
+    pub new_tick_size: f64,
+    #[serde(default, deserialize_with = "parse_optional_timestamp")]
+    pub timestamp: Option<i64>,
+}
+
+/// `market_resolved` frame, sent once a market settles.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MarketResolvedFrame {
+    market: String,
+    #[serde(default)]
+    assets_ids: Vec<String>,
+    winning_asset_id: Option<String>,
+    winning_outcome: Option<String>,
+    #[serde(default, deserialize_with = "parse_optional_timestamp")]
+    timestamp: Option<i64>,
+}
+
+impl MarketResolvedFrame {
+    /// Event payload; `now` stands in for a missing frame timestamp.
+    pub fn resolution(&self, now: i64) -> MarketResolution {
+        MarketResolution {
+            market_id: self.market.clone(),
+            outcome: self.winning_outcome.clone(),
+            winning_token_id: self.winning_asset_id.clone(),
+            token_ids: self.assets_ids.clone(),
+            timestamp: self.timestamp.unwrap_or(now),
+        }
+    }
+}
+
+/// Payload of the `polymarket://market-resolved` event.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketResolution {
+    /// Condition id of the resolved market
+    pub market_id: String,
+    /// Winning outcome label, e.g. "Yes"
+    pub outcome: Option<String>,
+    pub winning_token_id: Option<String>,
+    /// Outcome tokens of the market, for matching held positions
+    pub token_ids: Vec<String>,
+    pub timestamp: i64,
+}
+
+/// Market channel frame, discriminated by `event_type`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "event_type", rename_all = "snake_case")]
+pub enum MarketEvent {
+    Book(BookEvent),
+    PriceChange(PriceChangeFrame),
+    TickSizeChange(TickSizeChangeEvent),
+    MarketResolved(MarketResolvedFrame),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_single_price_change() {
+        let frame = r#"{"event_type":"price_change","asset_id":"123","market":"0xabc","price":"0.52","size":"150","side":"BUY","timestamp":"1718000000000"}"#;
+        let MarketEvent::PriceChange(frame) = serde_json::from_str(frame).unwrap() else {
+            panic!("expected price_change");
+        };
+        assert_eq!(
+            frame.changes(),
+            vec![PriceChange {
+                token_id: "123".to_string(),
+                price: 0.52,
+                size: 150.0,
+                side: "BUY".to_string(),
+                timestamp: Some(1_718_000_000_000),
+            }]
+        );
+    }
+
+    #[test]
+    fn parses_batched_price_changes() {
+        let frame = r#"{"event_type":"price_change","market":"0xabc","timestamp":"1718000000000","price_changes":[{"asset_id":"123","price":"0.5","size":"10","side":"BUY"},{"asset_id":"456","price":0.5,"size":0,"side":"SELL"}]}"#;
+        let MarketEvent::PriceChange(frame) = serde_json::from_str(frame).unwrap() else {
+            panic!("expected price_change");
+        };
+        let changes = frame.changes();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[1].token_id, "456");
+        assert_eq!(changes[1].size, 0.0);
+        assert_eq!(changes[1].side, "SELL");
+    }
+
+    #[test]
+    fn parses_book_and_tick_size_change() {
+        let book = r#"{"event_type":"book","asset_id":"123","bids":[{"price":"0.48","size":"30"}],"asks":[{"price":"0.52","size":"25"}],"timestamp":"1"}"#;
+        let MarketEvent::Book(book) = serde_json::from_str(book).unwrap() else {
+            panic!("expected book");
+        };
+        assert_eq!(book.token_id, "123");
+        assert_eq!(book.bids[0].price, 0.48);
+        assert_eq!(book.asks[0].size, 25.0);
+
+        let tick = r#"{"event_type":"tick_size_change","asset_id":"123","old_tick_size":"0.01","new_tick_size":"0.001"}"#;
+        let MarketEvent::TickSizeChange(tick) = serde_json::from_str(tick).unwrap() else {
+            panic!("expected tick_size_change");
+        };
+        assert_eq!(tick.new_tick_size, 0.001);
+        assert_eq!(tick.timestamp, None);
+    }
+
+    #[test]
+    fn parses_market_resolved() {
+        let frame = r#"{"event_type":"market_resolved","market":"0xabc","assets_ids":["123","456"],"winning_asset_id":"123","winning_outcome":"Yes","timestamp":"1718000000000"}"#;
+        let MarketEvent::MarketResolved(frame) = serde_json::from_str(frame).unwrap() else {
+            panic!("expected market_resolved");
+        };
+        assert_eq!(
+            frame.resolution(5),
+            MarketResolution {
+                market_id: "0xabc".to_string(),
+                outcome: Some("Yes".to_string()),
+                winning_token_id: Some("123".to_string()),
+                token_ids: vec!["123".to_string(), "456".to_string()],
+                timestamp: 1_718_000_000_000,
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_price() {
+        let frame = r#"{"event_type":"price_change","asset_id":"123","price":"abc","size":"1","side":"BUY"}"#;
+        assert!(serde_json::from_str::<MarketEvent>(frame).is_err());
+    }
+}
@@ -4,14 +4,36 @@
 use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
 use tauri::{AppHandle, Emitter, Runtime};
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, mpsc};
+use tokio::task::JoinHandle;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
+use super::types::{MarketEvent, MarketResolution};
+
 /// WebSocket endpoint for Polymarket CLOB subscriptions
 const WS_ENDPOINT: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/";
 
+/// Gamma market lookup used to poll resolution status by token id
+const GAMMA_MARKETS_URL: &str = "https://gamma-api.polymarket.com/markets";
+
+/// Setting with the resolution poll interval in seconds; 0 disables polling.
+const RESOLUTION_POLL_SETTING: &str = "polymarketResolutionPollSecs";
+const DEFAULT_RESOLUTION_POLL_SECS: u64 = 60;
+
+/// Event emitted once per market when it resolves.
+pub const MARKET_RESOLVED_EVENT: &str = "polymarket://market-resolved";
+
+static GAMMA_HTTP: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(15))
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new())
+});
+
 /// WebSocket message types from Polymarket
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(tag = "type")]
@@ -36,6 +58,15 @@ pub enum PolymarketWsMessage {
     Error { message: String },
 }
 
+/// A decoded frame. Anything that is not a recognized market event or control
+/// message is kept raw so it can still be forwarded.
+#[derive(Debug)]
+enum IncomingFrame {
+    Market(MarketEvent),
+    Control(PolymarketWsMessage),
+    Unknown(serde_json::Value),
+}
+
 /// Subscription channel types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -49,7 +80,14 @@ pub enum Channel {
 /// WebSocket client state
 pub struct PolymarketWebSocket<R: Runtime = tauri::Wry> {
     app: AppHandle<R>,
+    /// Active subscriptions, without duplicates. Replayed on every connect.
     subscriptions: Arc<RwLock<Vec<Channel>>>,
+    /// Outgoing frames for the live connection; `None` while disconnected.
+    outbound: Arc<RwLock<Option<mpsc::UnboundedSender<String>>>>,
+    /// REST resolution polls, one per subscribed market token.
+    resolution_polls: Arc<Mutex<HashMap<String, JoinHandle<()>>>>,
+    /// Markets whose resolution was already emitted, by either source.
+    resolved_markets: Arc<Mutex<HashSet<String>>>,
 }
 
 impl<R: Runtime> PolymarketWebSocket<R> {
@@ -58,6 +96,9 @@ impl<R: Runtime> PolymarketWebSocket<R> {
         Self {
             app,
             subscriptions: Arc::new(RwLock::new(Vec::new())),
+            outbound: Arc::new(RwLock::new(None)),
+            resolution_polls: Arc::new(Mutex::new(HashMap::new())),
+            resolved_markets: Arc::new(Mutex::new(HashSet::new())),
         }
     }
 
@@ -73,22 +114,45 @@ impl<R: Runtime> PolymarketWebSocket<R> {
 
         let (mut write, mut read) = ws_stream.split();
 
-        // Subscribe to initial channels
+        // Restore tracked subscriptions: all markets in one frame, then users
         let subs = self.subscriptions.read().await.clone();
-        for channel in subs {
-            let subscribe_msg = self.build_subscribe_message(&channel);
+        let market_ids = market_ids(&subs);
+        if !market_ids.is_empty() {
+            let subscribe_msg = build_market_message("subscribe", &market_ids);
+            write.send(Message::Text(subscribe_msg.into())).await?;
+            log::info!("Subscribed to {} market(s)", market_ids.len());
+        }
+        for channel in subs
+            .iter()
+            .filter(|channel| matches!(channel, Channel::User { .. }))
+        {
+            let subscribe_msg = self.build_subscribe_message(channel);
             write.send(Message::Text(subscribe_msg.into())).await?;
-            log::info!("Subscribed to channel: {:?}", channel);
+            log::info!("Subscribed to user channel");
         }
 
+        // Spawn writer task so subscriptions can change on the live connection
+        let (outbound_tx, mut outbound_rx) = mpsc::unbounded_channel::<String>();
+        *self.outbound.write().await = Some(outbound_tx);
+        tokio::spawn(async move {
+            while let Some(text) = outbound_rx.recv().await {
+                if let Err(e) = write.send(Message::Text(text.into())).await {
+                    log::error!("Failed to send WebSocket message: {}", e);
+                    break;
+                }
+            }
+        });
+
         // Spawn message listener task
         let app = self.app.clone();
+        let outbound = Arc::clone(&self.outbound);
+        let resolved_markets = Arc::clone(&self.resolved_markets);
 
         tokio::spawn(async move {
             while let Some(msg) = read.next().await {
                 match msg {
                     Ok(Message::Text(text)) => {
-                        if let Err(e) = Self::handle_message(&app, &text).await {
+                        if let Err(e) = Self::handle_message(&app, &resolved_markets, &text).await {
                             log::error!("Error handling WebSocket message: {}", e);
                         }
                     }
@@ -110,7 +174,8 @@ impl<R: Runtime> PolymarketWebSocket<R> {
                 }
             }
 
-            // Connection closed - emit event
+            // Connection closed - stop the writer and emit event
+            *outbound.write().await = None;
             let _ = app.emit("polymarket-ws-disconnected", ());
             log::warn!("Polymarket WebSocket disconnected");
         });
@@ -123,31 +188,122 @@ impl<R: Runtime> PolymarketWebSocket<R> {
         &self,
         channel: Channel,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if let Channel::Market { market_id } = channel {
+            return self.subscribe_markets(vec![market_id]).await.map(|_| ());
+        }
         log::info!("Adding subscription: {:?}", channel);
-        self.subscriptions.write().await.push(channel);
+        let added = track(&mut *self.subscriptions.write().await, vec![channel]);
+        for channel in &added {
+            self.send(self.build_subscribe_message(channel)).await?;
+        }
         Ok(())
     }
 
+    /// Subscribe to several markets with one frame. Returns the token ids that
+    /// were not already tracked.
+    pub async fn subscribe_markets(
+        &self,
+        token_ids: Vec<String>,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let channels = token_ids
+            .into_iter()
+            .map(|market_id| Channel::Market { market_id })
+            .collect();
+        let added = track(&mut *self.subscriptions.write().await, channels);
+        let added_ids = market_ids(&added);
+        if !added_ids.is_empty() {
+            log::info!("Adding {} market subscription(s)", added_ids.len());
+            self.send(build_market_message("subscribe", &added_ids))
+                .await?;
+        }
+        for token_id in &added_ids {
+            self.start_resolution_poll(token_id.clone());
+        }
+        Ok(added_ids)
+    }
+
     /// Unsubscribe from a channel
     pub async fn unsubscribe(
         &self,
         channel: &Channel,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         log::info!("Removing subscription: {:?}", channel);
-        let mut subs = self.subscriptions.write().await;
-        subs.retain(|c| !channels_equal(c, channel));
+        let removed = {
+            let mut subs = self.subscriptions.write().await;
+            let before = subs.len();
+            subs.retain(|c| !channels_equal(c, channel));
+            before != subs.len()
+        };
+        if let Channel::Market { market_id } = channel
+            && let Some(poll) = lock(&self.resolution_polls).remove(market_id)
+        {
+            poll.abort();
+        }
+        if removed && let Channel::Market { market_id } = channel {
+            self.send(build_market_message(
+                "unsubscribe",
+                std::slice::from_ref(market_id),
+            ))
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Currently tracked subscriptions.
+    pub async fn subscriptions(&self) -> Vec<Channel> {
+        self.subscriptions.read().await.clone()
+    }
+
+    /// Poll the market's resolution status over REST, since the market
+    /// channel only pushes `market_resolved` to some subscribers. Stops once
+    /// the market resolves or when it is unsubscribed.
+    fn start_resolution_poll(&self, token_id: String) {
+        let secs = crate::app_settings::app_setting_u64(&self.app, RESOLUTION_POLL_SETTING)
+            .unwrap_or(DEFAULT_RESOLUTION_POLL_SECS);
+        if secs == 0 {
+            return;
+        }
+        let app = self.app.clone();
+        let resolved_markets = Arc::clone(&self.resolved_markets);
+        let poll_token = token_id.clone();
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(Duration::from_secs(secs));
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                match fetch_resolution(&poll_token).await {
+                    Ok(Some(resolution)) => {
+                        if let Err(e) = Self::emit_resolution(&app, &resolved_markets, resolution) {
+                            log::error!("Failed to emit market resolution: {}", e);
+                        }
+                        break;
+                    }
+                    Ok(None) => {}
+                    Err(e) => log::debug!("Resolution poll for {} failed: {}", poll_token, e),
+                }
+            }
+        });
+        if let Some(previous) = lock(&self.resolution_polls).insert(token_id, handle) {
+            previous.abort();
+        }
+    }
+
+    /// Queue a frame on the live connection. Without one, the change is only
+    /// tracked and goes out when `connect` replays the subscriptions.
+    async fn send(&self, text: String) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if let Some(tx) = self.outbound.read().await.as_ref() {
+            tx.send(text)
+                .map_err(|_| "WebSocket connection is closed".to_string())?;
+        }
         Ok(())
     }
 
     /// Build subscribe message for a channel
     fn build_subscribe_message(&self, channel: &Channel) -> String {
         match channel {
-            Channel::Market { market_id } => json!({
-                "type": "subscribe",
-                "channel": "market",
-                "market_id": market_id
-            })
-            .to_string(),
+            Channel::Market { market_id } => {
+                build_market_message("subscribe", std::slice::from_ref(market_id))
+            }
             Channel::User { api_key } => json!({
                 "type": "subscribe",
                 "channel": "user",
@@ -160,28 +316,105 @@ impl<R: Runtime> PolymarketWebSocket<R> {
     /// Handle incoming WebSocket message
     async fn handle_message(
         app: &AppHandle<R>,
+        resolved_markets: &Mutex<HashSet<String>>,
         text: &str,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         log::debug!("Received WebSocket message: {}", text);
 
-        // Parse message
-        let msg: PolymarketWsMessage = serde_json::from_str(text)?;
+        for frame in parse_frames(text) {
+            match frame {
+                IncomingFrame::Market(MarketEvent::MarketResolved(frame)) => {
+                    Self::emit_resolution(app, resolved_markets, frame.resolution(now_millis()))?
+                }
+                IncomingFrame::Market(event) => Self::emit_market_event(app, event)?,
+                IncomingFrame::Control(msg) => Self::emit_control_message(app, msg)?,
+                IncomingFrame::Unknown(raw) => {
+                    log::warn!("Unrecognized Polymarket WebSocket frame: {}", raw);
+                    app.emit("polymarket://message", raw)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Emit a typed market event, plus the per-market update for its token
+    fn emit_market_event(
+        app: &AppHandle<R>,
+        event: MarketEvent,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        match event {
+            MarketEvent::Book(book) => {
+                app.emit(
+                    &market_event_name(&book.token_id),
+                    json!({ "event": "book", "tokenId": book.token_id, "data": book }),
+                )?;
+                app.emit("polymarket://book", book)?;
+            }
+            MarketEvent::PriceChange(frame) => {
+                for change in frame.changes() {
+                    app.emit(
+                        &market_event_name(&change.token_id),
+                        json!({
+                            "event": "price_change",
+                            "tokenId": change.token_id,
+                            "data": change
+                        }),
+                    )?;
+                    app.emit("polymarket://price-change", change)?;
+                }
+            }
+            MarketEvent::TickSizeChange(tick) => {
+                app.emit(
+                    &market_event_name(&tick.token_id),
+                    json!({
+                        "event": "tick_size_change",
+                        "tokenId": tick.token_id,
+                        "data": tick
+                    }),
+                )?;
+                app.emit("polymarket://tick-size-change", tick)?;
+            }
+            MarketEvent::MarketResolved(_) => {}
+        }
+        Ok(())
+    }
+
+    /// Emit `polymarket://market-resolved` unless the market was already
+    /// announced by the other source.
+    fn emit_resolution(
+        app: &AppHandle<R>,
+        resolved_markets: &Mutex<HashSet<String>>,
+        resolution: MarketResolution,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if !lock(resolved_markets).insert(resolution.market_id.clone()) {
+            return Ok(());
+        }
+        log::info!("Polymarket market resolved: {}", resolution.market_id);
+        app.emit(MARKET_RESOLVED_EVENT, resolution)?;
+        Ok(())
+    }
 
-        // Emit Tauri event based on message type
+    /// Emit a Tauri event for a control or legacy update message
+    fn emit_control_message(
+        app: &AppHandle<R>,
+        msg: PolymarketWsMessage,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         match msg {
             PolymarketWsMessage::Market {
                 event,
                 market,
                 data,
             } => {
-                app.emit(
-                    "polymarket-market-update",
-                    json!({
-                        "event": event,
-                        "market": market,
-                        "data": data
-                    }),
-                )?;
+                let payload = json!({
+                    "event": event,
+                    "market": market,
+                    "tokenId": market,
+                    "data": data
+                });
+                // Per-market channel so a market view only hears its own token
+                app.emit(&market_event_name(&market), &payload)?;
+                app.emit("polymarket-market-update", payload)?;
             }
             PolymarketWsMessage::User { event, data } => {
                 app.emit(
@@ -210,6 +443,160 @@ impl<R: Runtime> PolymarketWebSocket<R> {
     }
 }
 
+impl<R: Runtime> Drop for PolymarketWebSocket<R> {
+    fn drop(&mut self) {
+        for (_, poll) in lock(&self.resolution_polls).drain() {
+            poll.abort();
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn now_millis() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+/// Look up the market holding `token_id` and return its resolution, if any.
+async fn fetch_resolution(token_id: &str) -> Result<Option<MarketResolution>, String> {
+    let markets: Vec<serde_json::Value> = GAMMA_HTTP
+        .get(GAMMA_MARKETS_URL)
+        .query(&[("clob_token_ids", token_id)])
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .error_for_status()
+        .map_err(|e| e.to_string())?
+        .json()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(markets
+        .first()
+        .and_then(|market| resolution_from_gamma(market, token_id, now_millis())))
+}
+
+/// A closed Gamma market whose outcome prices settled on a winner.
+fn resolution_from_gamma(
+    market: &serde_json::Value,
+    token_id: &str,
+    now: i64,
+) -> Option<MarketResolution> {
+    if market.get("closed").and_then(serde_json::Value::as_bool) != Some(true) {
+        return None;
+    }
+    let outcomes = string_list(market.get("outcomes"));
+    let prices = string_list(market.get("outcomePrices"));
+    let mut token_ids = string_list(market.get("clobTokenIds"));
+    let winner = prices
+        .iter()
+        .position(|price| price.parse::<f64>().is_ok_and(|price| price >= 0.99))?;
+    let winning_token_id = token_ids.get(winner).cloned();
+    if token_ids.is_empty() {
+        token_ids.push(token_id.to_string());
+    }
+    Some(MarketResolution {
+        market_id: market
+            .get("conditionId")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or(token_id)
+            .to_string(),
+        outcome: outcomes.get(winner).cloned(),
+        winning_token_id,
+        token_ids,
+        timestamp: now,
+    })
+}
+
+/// Gamma sends list fields as JSON-encoded strings (`"[\"Yes\", \"No\"]"`).
+fn string_list(value: Option<&serde_json::Value>) -> Vec<String> {
+    let items = match value {
+        Some(serde_json::Value::String(text)) => {
+            serde_json::from_str::<Vec<serde_json::Value>>(text).unwrap_or_default()
+        }
+        Some(serde_json::Value::Array(items)) => items.clone(),
+        _ => Vec::new(),
+    };
+    items
+        .into_iter()
+        .filter_map(|item| match item {
+            serde_json::Value::String(s) => Some(s),
+            serde_json::Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Decode a text frame. Market frames may batch several events in an array;
+/// frames that are not JSON come back as `Unknown` with the raw text.
+fn parse_frames(text: &str) -> Vec<IncomingFrame> {
+    let value: serde_json::Value = match serde_json::from_str(text) {
+        Ok(value) => value,
+        Err(_) => return vec![IncomingFrame::Unknown(json!(text))],
+    };
+    let frames = match value {
+        serde_json::Value::Array(items) => items,
+        other => vec![other],
+    };
+    frames.into_iter().map(parse_frame).collect()
+}
+
+fn parse_frame(frame: serde_json::Value) -> IncomingFrame {
+    if frame.get("event_type").is_some() {
+        match serde_json::from_value::<MarketEvent>(frame.clone()) {
+            Ok(event) => return IncomingFrame::Market(event),
+            Err(e) => log::warn!("Could not decode Polymarket market event: {}", e),
+        }
+    } else if let Ok(msg) = serde_json::from_value::<PolymarketWsMessage>(frame.clone()) {
+        return IncomingFrame::Control(msg);
+    }
+    IncomingFrame::Unknown(frame)
+}
+
+/// Build a market subscribe/unsubscribe frame covering several token ids.
+fn build_market_message(operation: &str, token_ids: &[String]) -> String {
+    json!({
+        "type": operation,
+        "channel": "market",
+        "assets_ids": token_ids,
+        // Opts in to `market_resolved` frames
+        "custom_feature_enabled": true
+    })
+    .to_string()
+}
+
+/// Add channels that are not already tracked. Returns the ones added.
+fn track(subscriptions: &mut Vec<Channel>, channels: Vec<Channel>) -> Vec<Channel> {
+    let mut added = Vec::new();
+    for channel in channels {
+        if !subscriptions.iter().any(|c| channels_equal(c, &channel)) {
+            subscriptions.push(channel.clone());
+            added.push(channel);
+        }
+    }
+    added
+}
+
+/// Event carrying updates for a single market token.
+pub fn market_event_name(token_id: &str) -> String {
+    format!("polymarket-market-update:{}", token_id)
+}
+
+/// Token ids of the market channels in `channels`.
+fn market_ids(channels: &[Channel]) -> Vec<String> {
+    channels
+        .iter()
+        .filter_map(|channel| match channel {
+            Channel::Market { market_id } => Some(market_id.clone()),
+            Channel::User { .. } => None,
+        })
+        .collect()
+}
+
 /// Helper to compare channels for equality
 fn channels_equal(a: &Channel, b: &Channel) -> bool {
     match (a, b) {
@@ -238,4 +625,92 @@ mod tests {
         assert!(channels_equal(&market1, &market2));
         assert!(!channels_equal(&market1, &market3));
     }
+
+    #[test]
+    fn track_skips_already_subscribed_markets() {
+        let mut subs = vec![Channel::Market {
+            market_id: "123".to_string(),
+        }];
+        let added = track(
+            &mut subs,
+            vec![
+                Channel::Market {
+                    market_id: "123".to_string(),
+                },
+                Channel::Market {
+                    market_id: "456".to_string(),
+                },
+                Channel::Market {
+                    market_id: "456".to_string(),
+                },
+            ],
+        );
+        assert_eq!(market_ids(&added), vec!["456".to_string()]);
+        assert_eq!(
+            market_ids(&subs),
+            vec!["123".to_string(), "456".to_string()]
+        );
+    }
+
+    #[test]
+    fn parse_frames_types_market_events_and_keeps_unknown() {
+        let frames = parse_frames(
+            r#"[{"event_type":"price_change","asset_id":"123","price":"0.5","size":"10","side":"BUY"},
+                {"event_type":"last_trade_price","asset_id":"123"},
+                {"type":"subscribed","channel":"market"}]"#,
+        );
+        assert_eq!(frames.len(), 3);
+        assert!(matches!(
+            frames[0],
+            IncomingFrame::Market(MarketEvent::PriceChange(_))
+        ));
+        assert!(
+            matches!(&frames[1], IncomingFrame::Unknown(raw) if raw["event_type"] == "last_trade_price")
+        );
+        assert!(matches!(
+            frames[2],
+            IncomingFrame::Control(PolymarketWsMessage::Subscribed { .. })
+        ));
+
+        let garbage = parse_frames("not json");
+        assert!(matches!(&garbage[0], IncomingFrame::Unknown(raw) if raw == "not json"));
+    }
+
+    #[test]
+    fn market_message_batches_token_ids() {
+        let msg: serde_json::Value = serde_json::from_str(&build_market_message(
+            "subscribe",
+            &["123".to_string(), "456".to_string()],
+        ))
+        .unwrap();
+        assert_eq!(msg["type"], "subscribe");
+        assert_eq!(msg["channel"], "market");
+        assert_eq!(msg["assets_ids"], json!(["123", "456"]));
+    }
+
+    #[test]
+    fn gamma_resolution_needs_a_closed_market_with_a_winner() {
+        let market = json!({
+            "conditionId": "0xabc",
+            "closed": true,
+            "outcomes": "[\"Yes\", \"No\"]",
+            "outcomePrices": "[\"0\", \"1\"]",
+            "clobTokenIds": "[\"123\", \"456\"]"
+        });
+        assert_eq!(
+            resolution_from_gamma(&market, "123", 7),
+            Some(MarketResolution {
+                market_id: "0xabc".to_string(),
+                outcome: Some("No".to_string()),
+                winning_token_id: Some("456".to_string()),
+                token_ids: vec!["123".to_string(), "456".to_string()],
+                timestamp: 7,
+            })
+        );
+
+        let open = json!({ "closed": false, "outcomePrices": "[\"1\", \"0\"]" });
+        assert_eq!(resolution_from_gamma(&open, "123", 7), None);
+        let unsettled = json!({ "closed": true, "outcomePrices": "[\"0.5\", \"0.5\"]" });
+        assert_eq!(resolution_from_gamma(&unsettled, "123", 7), None);
+    }
 }
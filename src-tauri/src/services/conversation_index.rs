@@ -70,6 +70,23 @@ pub struct SearchFilters {
     pub before_ms: Option<i64>,
     #[serde(default)]
     pub include_archived: bool,
+    #[serde(default)]
+    pub conversation_id: Option<String>,
+    #[serde(default)]
+    pub role: Option<String>,
+}
+
+/// One message matched by `search_messages`: where it lives plus an FTS5
+/// snippet of its best-ranked chunk with the matched terms bracketed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageSearchHit {
+    pub message_id: String,
+    pub conversation_id: String,
+    pub role: String,
+    pub title: Option<String>,
+    pub snippet: String,
+    pub timestamp: i64,
 }
 
 /// A chunk of a single message, in order.
@@ -439,6 +456,14 @@ fn build_filter_clause(filters: &SearchFilters) -> (String, Vec<Value>) {
     if !filters.include_archived {
         clause.push_str(" AND c.is_archived = 0");
     }
+    if let Some(conversation_id) = &filters.conversation_id {
+        clause.push_str(" AND c.conversation_id = ?");
+        vals.push(Value::Text(conversation_id.clone()));
+    }
+    if let Some(role) = &filters.role {
+        clause.push_str(" AND c.role = ?");
+        vals.push(Value::Text(role.clone()));
+    }
     (clause, vals)
 }
 
@@ -506,6 +531,58 @@ pub fn search_fts(
     Ok(hits)
 }
 
+/// Exact full-text search collapsed to one hit per message ("where did I ask
+/// about X"). A long message spans several chunks; SQLite's bare-column `MIN()`
+/// rule keeps the snippet from whichever chunk ranked best. The CTE is
+/// materialized because FTS5 auxiliary functions can't run inside a flattened
+/// aggregate.
+pub fn search_messages(
+    conn: &Connection,
+    query: &str,
+    filters: &SearchFilters,
+    limit: usize,
+) -> Result<Vec<MessageSearchHit>> {
+    let match_query = fts_query(query);
+    if match_query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let (clause, filter_vals) = build_filter_clause(filters);
+    let sql = format!(
+        "WITH matches AS MATERIALIZED (
+            SELECT c.message_id, c.conversation_id, c.role, c.title, c.timestamp,
+                   snippet(conv_fts, 0, '[', ']', '…', 16) AS snippet,
+                   bm25(conv_fts) AS rank
+            FROM conv_fts f JOIN conv_chunks c ON c.id = f.rowid
+            WHERE conv_fts MATCH ?{clause}
+         )
+         SELECT message_id, conversation_id, role, title, snippet, timestamp, MIN(rank)
+         FROM matches
+         GROUP BY message_id
+         ORDER BY MIN(rank)
+         LIMIT ?"
+    );
+    let mut binds: Vec<Value> = Vec::with_capacity(filter_vals.len() + 2);
+    binds.push(Value::Text(match_query));
+    binds.extend(filter_vals);
+    binds.push(Value::Integer(limit as i64));
+
+    let mut stmt = conn.prepare(&sql)?;
+    let hits = stmt
+        .query_map(params_from_iter(binds), |row| {
+            Ok(MessageSearchHit {
+                message_id: row.get(0)?,
+                conversation_id: row.get(1)?,
+                role: row.get(2)?,
+                title: row.get(3)?,
+                snippet: row.get(4)?,
+                timestamp: row.get(5)?,
+            })
+        })?
+        .filter_map(|row| row.ok())
+        .collect();
+    Ok(hits)
+}
+
 /// Semantic KNN search over embedded chunks. The KNN runs in an isolated subquery
 /// (vec0 requires the MATCH + `k` together on the vtable and nothing else), then
 /// the joined metadata is filtered and re-limited. We over-fetch so filtering
@@ -882,6 +959,70 @@ mod tests {
         );
     }
 
+    #[test]
+    fn message_search_returns_one_snippet_per_message() {
+        let conn = test_conn();
+        // Long enough to split into several chunks that all mention the term.
+        let long = "signing ".repeat(CHUNK_CHAR_BUDGET / 4);
+        reindex_message(&conn, &msg("m1", "c1", "chat", "user", &long)).unwrap();
+        reindex_message(
+            &conn,
+            &msg("m2", "c2", "chat", "assistant", "the signing key rotated"),
+        )
+        .unwrap();
+
+        let hits = search_messages(&conn, "signing", &SearchFilters::default(), 10).unwrap();
+        let mut ids: Vec<&str> = hits.iter().map(|hit| hit.message_id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["m1", "m2"]);
+        let m2 = hits.iter().find(|hit| hit.message_id == "m2").unwrap();
+        assert!(m2.snippet.contains("[signing]"), "snippet: {}", m2.snippet);
+        assert_eq!(m2.conversation_id, "c2");
+    }
+
+    #[test]
+    fn message_search_filters_by_conversation_and_role() {
+        let conn = test_conn();
+        reindex_message(
+            &conn,
+            &msg("m1", "c1", "chat", "user", "deploy the release"),
+        )
+        .unwrap();
+        reindex_message(
+            &conn,
+            &msg("m2", "c1", "chat", "assistant", "release deployed"),
+        )
+        .unwrap();
+        reindex_message(&conn, &msg("m3", "c2", "chat", "user", "release notes")).unwrap();
+
+        let in_c1 = search_messages(
+            &conn,
+            "release",
+            &SearchFilters {
+                conversation_id: Some("c1".into()),
+                ..Default::default()
+            },
+            10,
+        )
+        .unwrap();
+        assert_eq!(in_c1.len(), 2);
+        assert!(in_c1.iter().all(|hit| hit.conversation_id == "c1"));
+
+        let user_in_c1 = search_messages(
+            &conn,
+            "release",
+            &SearchFilters {
+                conversation_id: Some("c1".into()),
+                role: Some("user".into()),
+                ..Default::default()
+            },
+            10,
+        )
+        .unwrap();
+        assert_eq!(user_in_c1.len(), 1);
+        assert_eq!(user_in_c1[0].message_id, "m1");
+    }
+
     #[test]
     fn chunking_caps_and_skips_empty() {
         assert!(chunk_message("   \n  ").is_empty());
@@ -475,6 +475,71 @@ pub fn stamp_existing_privileged_messages(
     Ok(())
 }
 
+/// Replace the content of a stored message in place. Returns false when the
+/// message does not exist.
+pub fn update_message_content(conn: &Connection, message_id: &str, content: &str) -> Result<bool> {
+    let changed = conn.execute(
+        "UPDATE messages SET content = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+        rusqlite::params![content, message_id],
+    )?;
+    if changed == 0 {
+        return Ok(false);
+    }
+    mark_sync_upsert(conn, "messages", message_id)?;
+    Ok(true)
+}
+
+/// Delete every message that follows `message_id` in its conversation, ordered
+/// by `(timestamp, id)`. Tool and diff rows produced by a later assistant turn
+/// go with it, along with their audit events and eval signals, so nothing is
+/// left pointing at a removed message. Returns the deleted message ids.
+pub fn truncate_messages_after(conn: &Connection, message_id: &str) -> Result<Vec<String>> {
+    let tx = conn.unchecked_transaction()?;
+    let (conversation_id, timestamp): (String, i64) = tx.query_row(
+        "SELECT conversation_id, timestamp FROM messages WHERE id = ?1",
+        rusqlite::params![message_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+
+    let message_ids = {
+        let mut stmt = tx.prepare(
+            "SELECT id FROM messages
+             WHERE conversation_id = ?1 AND (timestamp, id) > (?2, ?3)",
+        )?;
+        stmt.query_map(
+            rusqlite::params![conversation_id, timestamp, message_id],
+            |row| row.get::<_, String>(0),
+        )?
+        .collect::<Result<Vec<_>>>()?
+    };
+
+    let mut event_stmt = tx.prepare("SELECT id FROM message_events WHERE message_id = ?1")?;
+    for removed_id in &message_ids {
+        let event_ids = event_stmt
+            .query_map(rusqlite::params![removed_id], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>>>()?;
+        for event_id in event_ids {
+            enqueue_sync_tombstone(&tx, "message_events", &event_id)?;
+        }
+        enqueue_sync_tombstone(&tx, "messages", removed_id)?;
+        tx.execute(
+            "DELETE FROM eval_signals WHERE message_id = ?1",
+            rusqlite::params![removed_id],
+        )?;
+        tx.execute(
+            "DELETE FROM message_events WHERE message_id = ?1",
+            rusqlite::params![removed_id],
+        )?;
+        tx.execute(
+            "DELETE FROM messages WHERE id = ?1",
+            rusqlite::params![removed_id],
+        )?;
+    }
+    drop(event_stmt);
+    tx.commit()?;
+    Ok(message_ids)
+}
+
 pub fn resolve_conversation_provider(
     conn: &Connection,
     conversation_id: &str,
@@ -1153,6 +1218,15 @@ pub fn setup_schema(conn: &Connection) -> Result<()> {
             .ok(); // Ignore error if column already exists
     }
 
+    // Backs paginated message loading (`get_messages_page`) and the latest-N
+    // `get_messages` query.
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_messages_conversation_timestamp
+         ON messages(conversation_id, timestamp, id)",
+        [],
+    )
+    .ok();
+
     // Migration: Add metadata column for orchestrator fields (JSON blob)
     let has_metadata: bool = conn
         .prepare("SELECT metadata FROM messages LIMIT 1")
@@ -1374,9 +1448,133 @@ pub fn setup_schema(conn: &Connection) -> Result<()> {
     setup_provider_runtime_schema(conn)?;
     setup_happy_provider_session_lifecycle_schema(conn)?;
 
+    run_migrations(conn)?;
+
     Ok(())
 }
 
+/// Schema version of a database built by the probe-and-patch steps in
+/// `setup_schema`, before numbered migrations existed.
+pub const BASELINE_SCHEMA_VERSION: i64 = 1;
+
+/// One numbered schema change. Versions are applied in ascending order and
+/// never edited once shipped; add a new entry instead.
+struct Migration {
+    version: i64,
+    name: &'static str,
+    sql: &'static str,
+    /// `(table, column)` the step adds. Databases that already have it, from
+    /// a build that added the column with an ad-hoc probe, only record the
+    /// version.
+    adds_column: Option<(&'static str, &'static str)>,
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 2,
+        name: "conversation_tags",
+        // Sidebar labels, stored as a JSON array of strings.
+        sql: "ALTER TABLE conversations ADD COLUMN tags TEXT NOT NULL DEFAULT '[]';",
+        adds_column: Some(("conversations", "tags")),
+    },
+    Migration {
+        version: 3,
+        name: "orchestration_checkpoints",
+        // Round-level tool-loop state for `resume_orchestration`. `routing`,
+        // `capabilities` and `progress` are JSON.
+        sql: "CREATE TABLE IF NOT EXISTS orchestration_checkpoints (
+                id TEXT PRIMARY KEY,
+                conversation_id TEXT NOT NULL,
+                prompt TEXT NOT NULL,
+                routing TEXT NOT NULL,
+                capabilities TEXT NOT NULL,
+                progress TEXT,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_orchestration_checkpoints_updated
+                ON orchestration_checkpoints(updated_at);",
+        adds_column: None,
+    },
+    Migration {
+        version: 4,
+        name: "conversation_trash",
+        // Trash is a local, recoverable state distinct from the history-sync
+        // `deleted_at` tombstone, which would propagate the deletion to every
+        // device.
+        sql: "ALTER TABLE conversations ADD COLUMN trashed_at INTEGER;",
+        adds_column: Some(("conversations", "trashed_at")),
+    },
+];
+
+/// Highest migration version in this build.
+pub fn latest_schema_version() -> i64 {
+    MIGRATIONS
+        .last()
+        .map_or(BASELINE_SCHEMA_VERSION, |migration| migration.version)
+}
+
+/// Version recorded in `schema_version`, or the baseline for databases that
+/// predate the table.
+pub fn current_schema_version(conn: &Connection) -> Result<i64> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    conn.query_row(
+        "SELECT COALESCE(MAX(version), ?1) FROM schema_version",
+        rusqlite::params![BASELINE_SCHEMA_VERSION],
+        |row| row.get(0),
+    )
+}
+
+fn table_has_column(conn: &Connection, table: &str, column: &str) -> Result<bool> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info(?1) WHERE name = ?2",
+        rusqlite::params![table, column],
+        |row| row.get::<_, i64>(0),
+    )
+    .map(|count| count > 0)
+}
+
+/// Apply every migration newer than the recorded version. Each step runs in
+/// its own transaction together with its `schema_version` row, so a failure
+/// leaves the database at the last completed version and the step is retried
+/// on the next startup. Returns the resulting version.
+pub fn run_migrations(conn: &Connection) -> Result<i64> {
+    let recorded = current_schema_version(conn)?;
+    let mut version = recorded;
+    for migration in MIGRATIONS
+        .iter()
+        .filter(|migration| migration.version > recorded)
+    {
+        let tx = conn.unchecked_transaction()?;
+        let already_applied = match migration.adds_column {
+            Some((table, column)) => table_has_column(&tx, table, column)?,
+            None => false,
+        };
+        if !already_applied {
+            tx.execute_batch(migration.sql)?;
+        }
+        tx.execute(
+            "INSERT INTO schema_version (version, name, applied_at) VALUES (?1, ?2, ?3)",
+            rusqlite::params![migration.version, migration.name, now_ms()],
+        )?;
+        tx.commit()?;
+        log::info!(
+            "[Database] Applied migration {} ({})",
+            migration.version,
+            migration.name
+        );
+        version = migration.version;
+    }
+    Ok(version)
+}
+
 /// Durable archive fence for provider sessions that may not have a
 /// conversation row yet (fresh spawns and predictive standbys).
 fn setup_happy_provider_session_lifecycle_schema(conn: &Connection) -> Result<()> {
@@ -1509,6 +1707,186 @@ mod tests {
     use super::*;
     use rusqlite::params;
 
+    #[test]
+    fn migrations_upgrade_a_baseline_database_and_keep_rows() {
+        let conn = Connection::open_in_memory().unwrap();
+        // A v1 database: the pre-migration schema with existing history.
+        conn.execute(
+            "CREATE TABLE conversations (
+                id TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                selected_model TEXT,
+                selected_provider TEXT,
+                is_archived INTEGER DEFAULT 0
+            )",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "CREATE TABLE messages (
+                id TEXT PRIMARY KEY,
+                conversation_id TEXT,
+                role TEXT NOT NULL,
+                content TEXT NOT NULL,
+                model TEXT,
+                timestamp INTEGER NOT NULL
+            )",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO conversations (id, title, created_at) VALUES ('c1', 'Kept', 1000)",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO messages (id, conversation_id, role, content, timestamp)
+             VALUES ('m1', 'c1', 'user', 'hello', 1000)",
+            [],
+        )
+        .unwrap();
+        assert_eq!(
+            current_schema_version(&conn).unwrap(),
+            BASELINE_SCHEMA_VERSION
+        );
+
+        setup_schema(&conn).unwrap();
+        assert_eq!(
+            current_schema_version(&conn).unwrap(),
+            latest_schema_version()
+        );
+
+        let (title, tags): (String, String) = conn
+            .query_row(
+                "SELECT title, tags FROM conversations WHERE id = 'c1'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(title, "Kept");
+        assert_eq!(tags, "[]");
+        let content: String = conn
+            .query_row("SELECT content FROM messages WHERE id = 'm1'", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(content, "hello");
+
+        // Re-running on startup is a no-op.
+        setup_schema(&conn).unwrap();
+        let applied: i64 = conn
+            .query_row("SELECT COUNT(*) FROM schema_version", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(applied, MIGRATIONS.len() as i64);
+    }
+
+    #[test]
+    fn migrations_skip_columns_added_by_earlier_probes() {
+        let conn = Connection::open_in_memory().unwrap();
+        // A database where an earlier build already probed in `tags` and
+        // `trashed_at` before the migration runner existed.
+        conn.execute(
+            "CREATE TABLE conversations (
+                id TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                tags TEXT NOT NULL DEFAULT '[]',
+                trashed_at INTEGER
+            )",
+            [],
+        )
+        .unwrap();
+
+        assert_eq!(run_migrations(&conn).unwrap(), latest_schema_version());
+        let applied: i64 = conn
+            .query_row("SELECT COUNT(*) FROM schema_version", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(applied, MIGRATIONS.len() as i64);
+    }
+
+    #[test]
+    fn truncate_after_edit_drops_later_turns_and_their_events() {
+        let conn = Connection::open_in_memory().unwrap();
+        setup_schema(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO conversations (id, title, created_at) VALUES ('c1', 'Chat', 1000)",
+            [],
+        )
+        .unwrap();
+        for (id, role, timestamp) in [
+            ("m1", "user", 1000),
+            ("m2", "assistant", 2000),
+            ("m3", "user", 3000),
+            ("tool", "assistant", 4000),
+            ("m4", "assistant", 5000),
+        ] {
+            save_message_record(
+                &conn,
+                &PersistedMessage {
+                    id: id.to_string(),
+                    conversation_id: "c1".to_string(),
+                    role: role.to_string(),
+                    content: format!("{id} text"),
+                    model: None,
+                    timestamp,
+                    metadata: None,
+                    provider: None,
+                },
+            )
+            .unwrap();
+        }
+        conn.execute(
+            "INSERT INTO eval_signals (message_id, task_type, satisfaction, created_at)
+             VALUES ('m4', 'chat', 1, 5000)",
+            [],
+        )
+        .unwrap();
+
+        assert!(update_message_content(&conn, "m3", "fixed typo").unwrap());
+        assert!(!update_message_content(&conn, "missing", "x").unwrap());
+        let mut removed = truncate_messages_after(&conn, "m3").unwrap();
+        removed.sort();
+        assert_eq!(removed, vec!["m4", "tool"]);
+
+        let remaining: Vec<(String, String)> = conn
+            .prepare("SELECT id, content FROM messages ORDER BY timestamp")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(
+            remaining,
+            vec![
+                ("m1".to_string(), "m1 text".to_string()),
+                ("m2".to_string(), "m2 text".to_string()),
+                ("m3".to_string(), "fixed typo".to_string()),
+            ]
+        );
+        let orphans: i64 = conn
+            .query_row(
+                "SELECT (SELECT COUNT(*) FROM message_events
+                         WHERE message_id NOT IN (SELECT id FROM messages))
+                      + (SELECT COUNT(*) FROM eval_signals
+                         WHERE message_id NOT IN (SELECT id FROM messages))",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(orphans, 0);
+        let tombstones: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sync_outbox
+                 WHERE table_name = 'messages' AND op = 'tombstone'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(tombstones, 2);
+        assert!(truncate_messages_after(&conn, "missing").is_err());
+    }
+
     #[test]
     fn save_message_record_is_idempotent_and_audited() {
         let conn = Connection::open_in_memory().unwrap();
@@ -11,6 +11,10 @@ use std::path::Path;
 /// Maximum file size to index (10MB)
 const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
 
+/// Chunks sent per embedding request. The frontend indexer batches each
+/// file's chunks in groups of this size.
+pub const EMBEDDING_BATCH_SIZE: usize = 20;
+
 /// Files to ignore during discovery
 const IGNORE_PATTERNS: &[&str] = &[
     "node_modules",
@@ -69,6 +73,38 @@ pub struct FileChunk {
     pub symbol_name: Option<String>,
 }
 
+/// Embedding throughput and pricing used to turn work into time and cost.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct EmbeddingThroughput {
+    /// Average wall time of one embedding request (one batch), in seconds.
+    pub seconds_per_batch: f64,
+    /// Price per million input tokens, in USD.
+    pub usd_per_million_tokens: f64,
+}
+
+impl Default for EmbeddingThroughput {
+    /// text-embedding-3-small through the gateway.
+    fn default() -> Self {
+        Self {
+            seconds_per_batch: 0.6,
+            usd_per_million_tokens: 0.02,
+        }
+    }
+}
+
+/// Planning data for indexing a set of discovered files.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct IndexingEstimate {
+    pub total_files: usize,
+    pub total_bytes: u64,
+    pub estimated_chunks: usize,
+    pub estimated_tokens: usize,
+    /// Embedding requests needed, batching each file separately.
+    pub estimated_batches: usize,
+    pub estimated_seconds: f64,
+    pub estimated_cost_usd: f64,
+}
+
 /// Discover all indexable files in a project directory.
 pub fn discover_files(project_path: &Path) -> Vec<DiscoveredFile> {
     let mut files = Vec::new();
@@ -93,51 +129,66 @@ fn discover_files_recursive(root: &Path, current: &Path, files: &mut Vec<Discove
 
         if path.is_dir() {
             discover_files_recursive(root, &path, files);
-        } else if path.is_file() {
-            // Check if file is indexable
-            if !chunker::is_indexable_file(&path) {
-                continue;
-            }
+        } else if path.is_file()
+            && let Some(file) = describe_file(root, &path)
+        {
+            files.push(file);
+        }
+    }
+}
 
-            // Check file size
-            let metadata = match fs::metadata(&path) {
-                Ok(m) => m,
-                Err(_) => continue,
-            };
+/// Describe a single file under `root` for indexing, applying the same
+/// ignore, type, and size rules as `discover_files`. Returns `None` when the
+/// file would not have been discovered.
+pub fn discover_file(root: &Path, path: &Path) -> Option<DiscoveredFile> {
+    let relative_path = path.strip_prefix(root).ok()?;
+    if is_ignored_path(&relative_path.to_string_lossy()) || !path.is_file() {
+        return None;
+    }
+    describe_file(root, path)
+}
 
-            if metadata.len() > MAX_FILE_SIZE {
-                continue;
-            }
+fn describe_file(root: &Path, path: &Path) -> Option<DiscoveredFile> {
+    // Check if file is indexable
+    if !chunker::is_indexable_file(path) {
+        return None;
+    }
 
-            // Get language
-            let language = match chunker::detect_language(&path) {
-                Some(l) => l,
-                None => continue,
-            };
-
-            // Calculate content hash
-            let content = match fs::read_to_string(&path) {
-                Ok(c) => c,
-                Err(_) => continue, // Skip binary or unreadable files
-            };
-            let hash = compute_hash(&content);
-
-            // Get relative path
-            let relative_path = path
-                .strip_prefix(root)
-                .unwrap_or(&path)
-                .to_string_lossy()
-                .to_string();
-
-            files.push(DiscoveredFile {
-                path: path.to_string_lossy().to_string(),
-                relative_path,
-                language,
-                size: metadata.len(),
-                hash,
-            });
-        }
+    // Check file size
+    let metadata = fs::metadata(path).ok()?;
+    if metadata.len() > MAX_FILE_SIZE {
+        return None;
     }
+
+    // Get language
+    let language = chunker::detect_language(path)?;
+
+    // Calculate content hash (skips binary or unreadable files)
+    let content = fs::read_to_string(path).ok()?;
+    let hash = compute_hash(&content);
+
+    // Get relative path
+    let relative_path = path
+        .strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string();
+
+    Some(DiscoveredFile {
+        path: path.to_string_lossy().to_string(),
+        relative_path,
+        language,
+        size: metadata.len(),
+        hash,
+    })
+}
+
+/// Whether any component of a project-relative path is ignored, matching the
+/// directory pruning done by `discover_files`.
+fn is_ignored_path(relative_path: &str) -> bool {
+    Path::new(relative_path)
+        .components()
+        .any(|component| should_ignore(&component.as_os_str().to_string_lossy()))
 }
 
 fn should_ignore(name: &str) -> bool {
@@ -186,22 +237,42 @@ pub fn chunk_file(file: &DiscoveredFile) -> Result<ChunkedFile, String> {
     })
 }
 
-/// Get total chunk count and estimated tokens for discovered files.
-pub fn estimate_indexing_work(files: &[DiscoveredFile]) -> (usize, usize) {
-    let mut total_chunks = 0;
-    let mut total_tokens = 0;
+/// Estimate the work, time, and cost of indexing discovered files.
+///
+/// Chunk counts come from running the real chunker, so they match what
+/// indexing will store. Files that `discover_files` would have skipped
+/// (ignored paths, unsupported or oversized files) are left out.
+pub fn estimate_indexing_work(
+    files: &[DiscoveredFile],
+    throughput: &EmbeddingThroughput,
+) -> IndexingEstimate {
+    let mut estimate = IndexingEstimate::default();
 
     for file in files {
-        if let Ok(chunked) = chunk_file(file) {
-            total_chunks += chunked.chunks.len();
-            for chunk in &chunked.chunks {
-                // Rough estimate: 4 chars per token
-                total_tokens += chunk.content.len() / 4;
-            }
+        if is_ignored_path(&file.relative_path)
+            || file.size > MAX_FILE_SIZE
+            || !chunker::is_indexable_file(Path::new(&file.path))
+        {
+            continue;
+        }
+        let Ok(chunked) = chunk_file(file) else {
+            continue;
+        };
+
+        estimate.total_files += 1;
+        estimate.total_bytes += file.size;
+        estimate.estimated_chunks += chunked.chunks.len();
+        estimate.estimated_batches += chunked.chunks.len().div_ceil(EMBEDDING_BATCH_SIZE);
+        for chunk in &chunked.chunks {
+            // Rough estimate: 4 chars per token
+            estimate.estimated_tokens += chunk.content.len() / 4;
         }
     }
 
-    (total_chunks, total_tokens)
+    estimate.estimated_seconds = estimate.estimated_batches as f64 * throughput.seconds_per_batch;
+    estimate.estimated_cost_usd =
+        estimate.estimated_tokens as f64 / 1_000_000.0 * throughput.usd_per_million_tokens;
+    estimate
 }
 
 #[cfg(test)]
@@ -217,6 +288,81 @@ mod tests {
         assert!(!should_ignore("index.ts"));
     }
 
+    #[test]
+    fn test_is_ignored_path() {
+        assert!(is_ignored_path("node_modules/react/index.js"));
+        assert!(is_ignored_path("src/vendor.min.js"));
+        assert!(!is_ignored_path("src/main.rs"));
+    }
+
+    #[test]
+    fn discover_file_matches_project_discovery() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("main.rs"), "fn main() {}\n").unwrap();
+        fs::create_dir(dir.path().join("target")).unwrap();
+        fs::write(dir.path().join("target").join("gen.rs"), "fn gen() {}\n").unwrap();
+
+        let discovered = discover_files(dir.path());
+        let single = discover_file(dir.path(), &dir.path().join("main.rs")).unwrap();
+        assert_eq!(discovered.len(), 1);
+        assert_eq!(single.relative_path, discovered[0].relative_path);
+        assert_eq!(single.hash, discovered[0].hash);
+
+        assert!(discover_file(dir.path(), &dir.path().join("target").join("gen.rs")).is_none());
+        assert!(discover_file(dir.path(), &dir.path().join("missing.rs")).is_none());
+        assert!(discover_file(dir.path(), Path::new("/elsewhere/main.rs")).is_none());
+    }
+
+    #[test]
+    fn estimate_matches_chunker_and_skips_ignored_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let source: String = (0..30)
+            .map(|i| format!("fn f{i}() {{\n    let x = {i};\n    println!(\"{{}}\", x);\n}}\n\n"))
+            .collect();
+        fs::write(dir.path().join("lib.rs"), &source).unwrap();
+        fs::create_dir(dir.path().join("node_modules")).unwrap();
+        fs::write(
+            dir.path().join("node_modules").join("dep.js"),
+            "const a = 1;\n",
+        )
+        .unwrap();
+
+        let mut files = discover_files(dir.path());
+        assert_eq!(files.len(), 1);
+        let expected_chunks = chunk_file(&files[0]).unwrap().chunks.len();
+        assert!(expected_chunks > 0);
+
+        // A caller-supplied ignored file is skipped just like discovery would.
+        files.push(DiscoveredFile {
+            path: dir
+                .path()
+                .join("node_modules/dep.js")
+                .to_string_lossy()
+                .to_string(),
+            relative_path: "node_modules/dep.js".to_string(),
+            language: "javascript".to_string(),
+            size: 13,
+            hash: compute_hash("const a = 1;\n"),
+        });
+
+        let throughput = EmbeddingThroughput {
+            seconds_per_batch: 2.0,
+            usd_per_million_tokens: 1_000_000.0,
+        };
+        let estimate = estimate_indexing_work(&files, &throughput);
+        assert_eq!(estimate.total_files, 1);
+        assert_eq!(estimate.total_bytes, source.len() as u64);
+        assert_eq!(estimate.estimated_chunks, expected_chunks);
+        assert_eq!(
+            estimate.estimated_batches,
+            expected_chunks.div_ceil(EMBEDDING_BATCH_SIZE)
+        );
+        assert!(
+            (estimate.estimated_seconds - estimate.estimated_batches as f64 * 2.0).abs() < 1e-9
+        );
+        assert!((estimate.estimated_cost_usd - estimate.estimated_tokens as f64).abs() < 1e-6);
+    }
+
     #[test]
     fn test_compute_hash() {
         let hash1 = compute_hash("hello world");
@@ -11,6 +11,9 @@ use tauri::{AppHandle, Manager};
 /// Embedding dimension for text-embedding-3-small model.
 pub const EMBEDDING_DIM: usize = 1536;
 
+/// Embedding model the stored vectors were produced with.
+pub const EMBEDDING_MODEL: &str = "text-embedding-3-small";
+
 /// Ensure sqlite-vec is loaded only once.
 static INIT_VEC: Once = Once::new();
 
@@ -43,6 +46,164 @@ pub struct CodeChunk {
 pub struct SearchResult {
     pub chunk: CodeChunk,
     pub distance: f32,
+    /// The query-relevant part of the chunk, set when the search had query text.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<SearchSnippet>,
+}
+
+/// Lines of context kept above and below the best-matching line.
+pub const SNIPPET_CONTEXT_LINES: usize = 3;
+
+/// The lines of a chunk around its best match, with the query terms marked.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchSnippet {
+    pub path: String,
+    pub start_line: i32,
+    pub end_line: i32,
+    pub snippet: String,
+    /// `[start, end)` offsets into `snippet` in UTF-16 code units, so they
+    /// index JavaScript strings directly. Sorted and non-overlapping.
+    pub highlight_ranges: Vec<(usize, usize)>,
+}
+
+/// Lowercased query words worth highlighting (identifiers of 2+ chars).
+fn query_terms(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for term in query.split(|c: char| !(c.is_alphanumeric() || c == '_')) {
+        let term = term.to_lowercase();
+        if term.chars().count() >= 2 && !terms.contains(&term) {
+            terms.push(term);
+        }
+    }
+    terms
+}
+
+/// Byte ranges in `text` where any term occurs, case-insensitively, merged.
+fn term_ranges(text: &str, terms: &[String]) -> Vec<(usize, usize)> {
+    let lower = text.to_lowercase();
+    // Lowercasing can change byte lengths outside ASCII; skip highlighting
+    // rather than risk misaligned offsets.
+    if lower.len() != text.len() {
+        return Vec::new();
+    }
+    let mut ranges: Vec<(usize, usize)> = terms
+        .iter()
+        .flat_map(|term| {
+            lower
+                .match_indices(term.as_str())
+                .map(|(start, m)| (start, start + m.len()))
+        })
+        .collect();
+    ranges.sort_unstable();
+    let mut merged: Vec<(usize, usize)> = Vec::new();
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Shortest query term, and the shared prefix length, for a stem match.
+const STEM_CHARS: usize = 4;
+
+/// Lowercased identifier words in `line`, split at punctuation, `_` and
+/// camelCase humps.
+fn line_words(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut after_lower = false;
+    for c in line.chars() {
+        let boundary = !c.is_alphanumeric() || (c.is_uppercase() && after_lower);
+        if boundary && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        if c.is_alphanumeric() {
+            current.extend(c.to_lowercase());
+            after_lower = !c.is_uppercase();
+        } else {
+            after_lower = false;
+        }
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Query terms sharing their first `STEM_CHARS` characters with a word on
+/// `line`, so "authentication" still finds `authenticate_user`.
+fn stem_hits(line: &str, terms: &[String]) -> usize {
+    let words = line_words(line);
+    terms
+        .iter()
+        .filter_map(|term| {
+            let stem: String = term.chars().take(STEM_CHARS).collect();
+            (stem.chars().count() == STEM_CHARS).then_some(stem)
+        })
+        .filter(|stem| words.iter().any(|word| word.starts_with(stem.as_str())))
+        .count()
+}
+
+/// The line with the highest nonzero `score`, earliest on ties.
+fn best_scoring_line(lines: &[&str], score: impl Fn(&str) -> usize) -> Option<usize> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| (i, score(line)))
+        .filter(|(_, hits)| *hits > 0)
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+        .map(|(i, _)| i)
+}
+
+fn utf16_offset(text: &str, byte_offset: usize) -> usize {
+    text[..byte_offset].encode_utf16().count()
+}
+
+/// Cut `chunk` down to its most query-relevant line plus
+/// `SNIPPET_CONTEXT_LINES` on each side, and mark the query-term hits.
+///
+/// The line with the most query-term hits wins. A chunk that matched by
+/// meaning alone is centered on the line sharing the most word stems with
+/// the query, then on the line naming the chunk's symbol, and otherwise
+/// keeps its opening lines.
+pub fn build_snippet(chunk: &CodeChunk, query: &str) -> SearchSnippet {
+    let terms = query_terms(query);
+    let lines: Vec<&str> = chunk.content.lines().collect();
+    let best_line = best_scoring_line(&lines, |line| term_ranges(line, &terms).len())
+        .or_else(|| best_scoring_line(&lines, |line| stem_hits(line, &terms)))
+        .or_else(|| {
+            let symbol = chunk
+                .symbol_name
+                .as_deref()
+                .filter(|name| !name.is_empty())?;
+            lines.iter().position(|line| line.contains(symbol))
+        });
+
+    let (first, last) = match best_line {
+        Some(line) => (
+            line.saturating_sub(SNIPPET_CONTEXT_LINES),
+            (line + SNIPPET_CONTEXT_LINES).min(lines.len().saturating_sub(1)),
+        ),
+        None => (
+            0,
+            (2 * SNIPPET_CONTEXT_LINES).min(lines.len().saturating_sub(1)),
+        ),
+    };
+    let snippet = lines.get(first..=last).unwrap_or_default().join("\n");
+    let highlight_ranges = term_ranges(&snippet, &terms)
+        .into_iter()
+        .map(|(start, end)| (utf16_offset(&snippet, start), utf16_offset(&snippet, end)))
+        .collect();
+
+    SearchSnippet {
+        path: chunk.file_path.clone(),
+        start_line: chunk.start_line + first as i32,
+        end_line: chunk.start_line + last as i32,
+        snippet,
+        highlight_ranges,
+    }
 }
 
 /// Get the path to the vector database for a project.
@@ -120,6 +281,18 @@ pub fn init_vector_db(app: &AppHandle, project_path: &str) -> Result<Connection>
     )?;
 
     // Store project path for reference
+    create_metadata_table(&conn)?;
+    conn.execute(
+        "INSERT OR REPLACE INTO index_metadata (key, value) VALUES ('project_path', ?1)",
+        params![project_path],
+    )?;
+    write_index_header(&conn)?;
+    check_index_header(&conn)?;
+
+    Ok(conn)
+}
+
+fn create_metadata_table(conn: &Connection) -> Result<()> {
     conn.execute(
         "CREATE TABLE IF NOT EXISTS index_metadata (
             key TEXT PRIMARY KEY,
@@ -127,12 +300,59 @@ pub fn init_vector_db(app: &AppHandle, project_path: &str) -> Result<Connection>
         )",
         [],
     )?;
+    Ok(())
+}
+
+/// Record the embedding dimension and model on first creation. Existing
+/// values are kept so an index built by another model is caught on load.
+fn write_index_header(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "INSERT OR IGNORE INTO index_metadata (key, value) VALUES ('embedding_dim', ?1)",
+        params![EMBEDDING_DIM.to_string()],
+    )?;
     conn.execute(
-        "INSERT OR REPLACE INTO index_metadata (key, value) VALUES ('project_path', ?1)",
-        params![project_path],
+        "INSERT OR IGNORE INTO index_metadata (key, value) VALUES ('embedding_model', ?1)",
+        params![EMBEDDING_MODEL],
     )?;
+    Ok(())
+}
 
-    Ok(conn)
+/// Fail if the index was built with a different embedding dimension or model,
+/// since its vectors cannot be compared with new query embeddings. Indexes
+/// created before the header existed have no entries and are accepted.
+fn check_index_header(conn: &Connection) -> Result<()> {
+    let read = |key: &str| -> Result<Option<String>> {
+        match conn.query_row(
+            "SELECT value FROM index_metadata WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        ) {
+            Ok(value) => Ok(Some(value)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    };
+
+    let dim = read("embedding_dim")?;
+    let model = read("embedding_model")?;
+    let dim_matches = dim
+        .as_deref()
+        .is_none_or(|d| d == EMBEDDING_DIM.to_string());
+    let model_matches = model.as_deref().is_none_or(|m| m == EMBEDDING_MODEL);
+    if dim_matches && model_matches {
+        return Ok(());
+    }
+
+    Err(rusqlite::Error::SqliteFailure(
+        rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_MISMATCH),
+        Some(format!(
+            "Index was built with {} ({} dims) but {} ({} dims) is in use; rebuild the index",
+            model.as_deref().unwrap_or(EMBEDDING_MODEL),
+            dim.as_deref().unwrap_or("unknown"),
+            EMBEDDING_MODEL,
+            EMBEDDING_DIM
+        )),
+    ))
 }
 
 /// Open an existing vector database connection.
@@ -146,10 +366,28 @@ pub fn open_vector_db(app: &AppHandle, project_path: &str) -> Result<Connection>
     }
 
     let conn = Connection::open(&path)?;
+    if table_exists(&conn, "index_metadata")? {
+        check_index_header(&conn)?;
+    }
 
     Ok(conn)
 }
 
+fn table_exists(conn: &Connection, name: &str) -> Result<bool> {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1)",
+        params![name],
+        |row| row.get(0),
+    )
+}
+
+/// Flush pending writes to the database file. Each insert already commits on
+/// its own; this checkpoints any write-ahead log so the `.db` file is complete
+/// on its own before the app exits.
+pub fn flush_vector_db(conn: &Connection) -> Result<()> {
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+}
+
 /// Insert a code chunk with its embedding.
 pub fn insert_chunk(
     conn: &Connection,
@@ -249,6 +487,7 @@ pub fn search_similar(
                     indexed_at: row.get(9)?,
                 },
                 distance: row.get(10)?,
+                snippet: None,
             })
         })?
         .filter_map(|r| r.ok())
@@ -317,6 +556,134 @@ mod tests {
         assert_eq!(blob.len(), 12); // 3 floats * 4 bytes each
     }
 
+    fn open_metadata() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        create_metadata_table(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_index_header_round_trip() {
+        let conn = open_metadata();
+        // Legacy indexes without a header still load
+        assert!(check_index_header(&conn).is_ok());
+
+        write_index_header(&conn).unwrap();
+        write_index_header(&conn).unwrap();
+        assert!(check_index_header(&conn).is_ok());
+        assert!(flush_vector_db(&conn).is_ok());
+    }
+
+    #[test]
+    fn test_index_header_mismatch_is_rejected() {
+        let conn = open_metadata();
+        conn.execute(
+            "INSERT INTO index_metadata (key, value) VALUES ('embedding_dim', '768')",
+            [],
+        )
+        .unwrap();
+        write_index_header(&conn).unwrap();
+
+        let err = check_index_header(&conn).unwrap_err().to_string();
+        assert!(err.contains("768 dims"), "{err}");
+
+        conn.execute(
+            "UPDATE index_metadata SET value = ?1 WHERE key = 'embedding_dim'",
+            params![EMBEDDING_DIM.to_string()],
+        )
+        .unwrap();
+        conn.execute(
+            "UPDATE index_metadata SET value = 'other-model' WHERE key = 'embedding_model'",
+            [],
+        )
+        .unwrap();
+        assert!(check_index_header(&conn).is_err());
+    }
+
+    fn chunk(start_line: i32, content: &str) -> CodeChunk {
+        CodeChunk {
+            id: 1,
+            file_path: "src/auth.rs".to_string(),
+            start_line,
+            end_line: start_line + content.lines().count() as i32 - 1,
+            content: content.to_string(),
+            chunk_type: "function".to_string(),
+            symbol_name: None,
+            language: "rust".to_string(),
+            file_hash: "h".to_string(),
+            indexed_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_snippet_centers_on_best_match_with_context() {
+        let content = (1..=20)
+            .map(|i| match i {
+                12 => "    let token = refresh_token(&client);".to_string(),
+                _ => format!("    step_{}();", i),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let snippet = build_snippet(&chunk(100, &content), "Refresh TOKEN");
+
+        assert_eq!(snippet.path, "src/auth.rs");
+        assert_eq!(snippet.start_line, 108);
+        assert_eq!(snippet.end_line, 114);
+        assert_eq!(snippet.snippet.lines().count(), 7);
+        let marked: Vec<&str> = snippet
+            .highlight_ranges
+            .iter()
+            .map(|&(start, end)| &snippet.snippet[start..end])
+            .collect();
+        assert_eq!(marked, vec!["token", "refresh", "token"]);
+    }
+
+    #[test]
+    fn test_snippet_without_hits_keeps_opening_lines() {
+        let snippet = build_snippet(&chunk(1, "fn a() {}\nfn b() {}"), "login flow");
+        assert_eq!(snippet.start_line, 1);
+        assert_eq!(snippet.end_line, 2);
+        assert_eq!(snippet.snippet, "fn a() {}\nfn b() {}");
+        assert!(snippet.highlight_ranges.is_empty());
+    }
+
+    #[test]
+    fn test_snippet_without_hits_centers_on_shared_stems() {
+        let content = (1..=20)
+            .map(|i| match i {
+                15 => "    if !authenticateUser(&session) {".to_string(),
+                _ => format!("    step_{}();", i),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let snippet = build_snippet(&chunk(1, &content), "authentication users");
+        assert_eq!(snippet.start_line, 12);
+        assert_eq!(snippet.end_line, 18);
+        assert!(snippet.highlight_ranges.is_empty());
+    }
+
+    #[test]
+    fn test_snippet_without_hits_falls_back_to_symbol_line() {
+        let content = (1..=20)
+            .map(|i| match i {
+                10 => "fn rotate_keys() {".to_string(),
+                _ => format!("    step_{}();", i),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut symbol_chunk = chunk(1, &content);
+        symbol_chunk.symbol_name = Some("rotate_keys".to_string());
+        let snippet = build_snippet(&symbol_chunk, "credential renewal");
+        assert_eq!(snippet.start_line, 7);
+        assert_eq!(snippet.end_line, 13);
+    }
+
+    #[test]
+    fn test_snippet_ranges_are_utf16_offsets() {
+        let snippet = build_snippet(&chunk(1, "// café → cache"), "cache");
+        assert_eq!(snippet.highlight_ranges, vec![(10, 15)]);
+    }
+
     #[test]
     fn test_md5_hash() {
         let hash1 = md5_hash("/path/to/project");
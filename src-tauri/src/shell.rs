@@ -4,12 +4,13 @@
 use serde::Serialize;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex as StdMutex};
 use std::time::Duration;
-use tauri::{AppHandle, Emitter, Runtime};
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
 use tauri_plugin_store::StoreExt;
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command;
-use tokio::sync::mpsc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{ChildStdin, Command};
+use tokio::sync::{Mutex, mpsc};
 
 /// Tauri event channel for streaming Bash stdout/stderr while the command
 /// is still running (#2100). Payload: [`ShellProgressEvent`]. Subscribed
@@ -123,6 +124,43 @@ struct ShellProgressEvent {
     is_stderr: bool,
 }
 
+/// The stdin pipe of a running streaming command. Empty until the child is
+/// spawned, and again once its input is closed.
+type StdinSlot = Arc<Mutex<Option<ChildStdin>>>;
+
+/// Stdin pipes of in-flight streaming shell commands, keyed by tool call id,
+/// so `send_shell_input` can answer prompts while they run.
+#[derive(Default)]
+pub struct ShellInputs {
+    slots: StdMutex<HashMap<String, StdinSlot>>,
+}
+
+impl ShellInputs {
+    fn register(&self, tool_call_id: &str) -> StdinSlot {
+        let slot = StdinSlot::default();
+        self.slots
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(tool_call_id.to_string(), slot.clone());
+        slot
+    }
+
+    fn unregister(&self, tool_call_id: &str) {
+        self.slots
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(tool_call_id);
+    }
+
+    fn get(&self, tool_call_id: &str) -> Option<StdinSlot> {
+        self.slots
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(tool_call_id)
+            .cloned()
+    }
+}
+
 #[tauri::command]
 pub async fn execute_shell_command<R: Runtime>(
     app: AppHandle<R>,
@@ -130,13 +168,14 @@ pub async fn execute_shell_command<R: Runtime>(
     timeout_secs: Option<u64>,
     inject_seren_credentials: Option<bool>,
 ) -> Result<CommandResult, String> {
+    crate::shell_policy::policy_for(&app).check(&command)?;
     let api_key = if should_inject_seren_credentials(&command, inject_seren_credentials) {
         read_stored_seren_api_key(&app)?
     } else {
         None
     };
 
-    execute_shell_command_inner(command, timeout_secs, api_key.as_deref(), None).await
+    execute_shell_command_inner(command, timeout_secs, api_key.as_deref(), None, None).await
 }
 
 /// Streaming variant of [`execute_shell_command`] used by the frontend
@@ -145,6 +184,13 @@ pub async fn execute_shell_command<R: Runtime>(
 /// `tool_call_id`, then returns the same [`CommandResult`] as the
 /// buffered command. The final payload is still authoritative — chunks are
 /// for live display only and the receiver is free to drop them.
+///
+/// With `interactive`, the command's stdin is a pipe that `send_shell_input`
+/// writes to while it runs, so y/n confirmations and password prompts can be
+/// answered. Otherwise stdin is inherited as before, so commands that read it
+/// opportunistically do not sit waiting on an open pipe. Programs that insist
+/// on a real TTY may still misbehave; those belong in a terminal session
+/// rather than a tool call.
 #[tauri::command]
 pub async fn execute_shell_command_streaming<R: Runtime>(
     app: AppHandle<R>,
@@ -152,10 +198,12 @@ pub async fn execute_shell_command_streaming<R: Runtime>(
     timeout_secs: Option<u64>,
     inject_seren_credentials: Option<bool>,
     tool_call_id: String,
+    interactive: Option<bool>,
 ) -> Result<CommandResult, String> {
     if tool_call_id.trim().is_empty() {
         return Err("tool_call_id must not be empty".to_string());
     }
+    crate::shell_policy::policy_for(&app).check(&command)?;
 
     let api_key = if should_inject_seren_credentials(&command, inject_seren_credentials) {
         read_stored_seren_api_key(&app)?
@@ -183,9 +231,20 @@ pub async fn execute_shell_command_streaming<R: Runtime>(
         }
     });
 
-    let result =
-        execute_shell_command_inner(command, timeout_secs, api_key.as_deref(), Some(chunk_tx))
-            .await;
+    let inputs = app.state::<ShellInputs>();
+    let interactive = interactive.unwrap_or(false);
+    let stdin = interactive.then(|| inputs.register(&tool_call_id));
+    let result = execute_shell_command_inner(
+        command,
+        timeout_secs,
+        api_key.as_deref(),
+        Some(chunk_tx),
+        stdin,
+    )
+    .await;
+    if interactive {
+        inputs.unregister(&tool_call_id);
+    }
 
     // Wait for the forwarder to drain the rest of the channel so the
     // frontend has every chunk in hand before the result settles. The
@@ -194,6 +253,36 @@ pub async fn execute_shell_command_streaming<R: Runtime>(
     result
 }
 
+/// Write `data` to the stdin of the streaming command started with
+/// `tool_call_id`. Include the trailing newline a prompt expects. With
+/// `close`, stdin is closed afterwards so programs reading to EOF finish.
+#[tauri::command]
+pub async fn send_shell_input(
+    inputs: State<'_, ShellInputs>,
+    tool_call_id: String,
+    data: String,
+    close: Option<bool>,
+) -> Result<(), String> {
+    let slot = inputs
+        .get(&tool_call_id)
+        .ok_or_else(|| format!("No running command for {}", tool_call_id))?;
+    let mut stdin = slot.lock().await;
+    let pipe = stdin
+        .as_mut()
+        .ok_or_else(|| format!("Input for {} is closed", tool_call_id))?;
+    pipe.write_all(data.as_bytes())
+        .await
+        .map_err(|e| format!("Failed to write to command input: {}", e))?;
+    pipe.flush()
+        .await
+        .map_err(|e| format!("Failed to write to command input: {}", e))?;
+    if close.unwrap_or(false) {
+        // Dropping the pipe sends EOF.
+        stdin.take();
+    }
+    Ok(())
+}
+
 /// Execute an AI tool shell command with optional stored Seren auth injection.
 ///
 /// `inject_seren_credentials = None` uses the same narrow auto-detect policy as
@@ -205,20 +294,22 @@ pub async fn execute_shell_command_for_tool<R: Runtime>(
     timeout_secs: Option<u64>,
     inject_seren_credentials: Option<bool>,
 ) -> Result<CommandResult, String> {
+    crate::shell_policy::policy_for(app).check(&command)?;
     let api_key = if should_inject_seren_credentials(&command, inject_seren_credentials) {
         read_stored_seren_api_key(app)?
     } else {
         None
     };
 
-    execute_shell_command_inner(command, timeout_secs, api_key.as_deref(), None).await
+    execute_shell_command_inner(command, timeout_secs, api_key.as_deref(), None, None).await
 }
 
 pub async fn execute_shell_command_without_seren_credentials(
     command: String,
     timeout_secs: Option<u64>,
 ) -> Result<CommandResult, String> {
-    execute_shell_command_inner(command, timeout_secs, None, None).await
+    crate::shell_policy::default_policy().check(&command)?;
+    execute_shell_command_inner(command, timeout_secs, None, None, None).await
 }
 
 #[tauri::command]
@@ -233,6 +324,7 @@ pub async fn run_skill_script<R: Runtime>(
 ) -> Result<CommandResult, String> {
     let cwd = validate_skill_script_cwd(&skill_slug, &cwd)?;
     let (program, args) = validate_skill_script_argv(&argv)?;
+    crate::shell_policy::policy_for(&app).check_program(&program)?;
     let api_key = if inject_seren_credentials.unwrap_or(true) {
         read_stored_seren_api_key(&app)?
     } else {
@@ -256,6 +348,7 @@ async fn execute_shell_command_inner(
     timeout_secs: Option<u64>,
     seren_api_key: Option<&str>,
     progress: Option<mpsc::Sender<StreamChunk>>,
+    stdin: Option<StdinSlot>,
 ) -> Result<CommandResult, String> {
     if command.trim().is_empty() {
         return Err("Command must not be empty".to_string());
@@ -265,7 +358,14 @@ async fn execute_shell_command_inner(
         .unwrap_or(DEFAULT_TIMEOUT_SECS)
         .min(MAX_TIMEOUT_SECS);
 
-    let result = spawn_one_shot(&command, secs, seren_api_key, progress.clone()).await?;
+    let result = spawn_one_shot(
+        &command,
+        secs,
+        seren_api_key,
+        progress.clone(),
+        stdin.clone(),
+    )
+    .await?;
 
     // GH #1908: on Windows, when the user has no real Python on PATH but the
     // App Execution Alias for Python is still enabled, `python …` is routed
@@ -288,12 +388,12 @@ async fn execute_shell_command_inner(
         if looks_like_windows_apps_python_stub(&result.stderr) {
             if let Some(retry_command) = translate_python_to_py_launcher(&command) {
                 log::info!("[Shell] WindowsApps Python stub detected; retrying via `py` launcher");
-                return spawn_one_shot(&retry_command, secs, seren_api_key, progress).await;
+                return spawn_one_shot(&retry_command, secs, seren_api_key, progress, stdin).await;
             }
         }
     }
 
-    let _ = progress;
+    let _ = (progress, stdin);
     Ok(result)
 }
 
@@ -433,6 +533,7 @@ async fn spawn_one_shot(
     secs: u64,
     seren_api_key: Option<&str>,
     progress: Option<mpsc::Sender<StreamChunk>>,
+    stdin: Option<StdinSlot>,
 ) -> Result<CommandResult, String> {
     let timeout = Duration::from_secs(secs);
 
@@ -490,6 +591,9 @@ async fn spawn_one_shot(
     cmd.stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped())
         .kill_on_drop(true);
+    if stdin.is_some() {
+        cmd.stdin(std::process::Stdio::piped());
+    }
 
     // Log the exact CWD we inherit and the command we're about to run.
     // GH #1595: a Windows user reported tool-written files landing
@@ -511,6 +615,9 @@ async fn spawn_one_shot(
     let mut child = cmd
         .spawn()
         .map_err(|e| format!("Failed to spawn command: {}", e))?;
+    if let Some(slot) = &stdin {
+        *slot.lock().await = child.stdin.take();
+    }
 
     // No progress sink: keep the buffered fast path. Every existing caller
     // (Tauri command, skill scripts, non-streaming tool execution) hits
@@ -597,6 +704,9 @@ async fn spawn_one_shot(
     });
 
     let wait_result = tokio::time::timeout(timeout, child.wait()).await;
+    if let Some(slot) = &stdin {
+        slot.lock().await.take();
+    }
 
     // Whether we timed out or finished cleanly, both reader tasks have to
     // run to EOF before we can return the accumulated output. On timeout
@@ -1049,6 +1159,7 @@ mod tests {
             Some(5),
             None,
             Some(tx),
+            None,
         )
         .await
         .expect("streaming command succeeds");
@@ -1077,6 +1188,34 @@ mod tests {
         assert!(!result.timed_out);
     }
 
+    #[tokio::test]
+    #[cfg(not(target_os = "windows"))]
+    async fn streaming_path_feeds_stdin_to_a_prompting_command() {
+        let (tx, _rx) = mpsc::channel::<StreamChunk>(32);
+        let slot = StdinSlot::default();
+        let run = tokio::spawn(execute_shell_command_inner(
+            "printf 'Continue? '; read answer; echo \"got $answer\"".to_string(),
+            Some(5),
+            None,
+            Some(tx),
+            Some(slot.clone()),
+        ));
+
+        loop {
+            if let Some(pipe) = slot.lock().await.as_mut() {
+                pipe.write_all(b"yes\n").await.unwrap();
+                pipe.flush().await.unwrap();
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let result = run.await.unwrap().expect("prompting command succeeds");
+        assert_eq!(result.stdout, "Continue? got yes\n");
+        assert!(!result.timed_out);
+        assert!(slot.lock().await.is_none(), "stdin is released on exit");
+    }
+
     /// Critical-path regression for #2100: the non-streaming path (every
     /// existing caller — the Tauri command, skill scripts, the chat
     /// frontend tool when not opting into streaming) must keep returning
@@ -1085,10 +1224,15 @@ mod tests {
     #[tokio::test]
     #[cfg(not(target_os = "windows"))]
     async fn non_streaming_path_unchanged_buffered_output() {
-        let result =
-            execute_shell_command_inner("echo alpha; echo beta".to_string(), Some(5), None, None)
-                .await
-                .expect("buffered command succeeds");
+        let result = execute_shell_command_inner(
+            "echo alpha; echo beta".to_string(),
+            Some(5),
+            None,
+            None,
+            None,
+        )
+        .await
+        .expect("buffered command succeeds");
 
         assert_eq!(result.exit_code, Some(0));
         assert_eq!(result.stdout, "alpha\nbeta\n");
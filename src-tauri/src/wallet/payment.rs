@@ -7,7 +7,8 @@ use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use super::{
-    Eip712Domain, PrivateKeyWallet, build_authorization_message, sign_transfer_authorization,
+    Eip712Domain, PrivateKeyWallet, build_authorization_message,
+    recover_transfer_authorization_signer, sign_transfer_authorization,
 };
 
 /// Parsed payment requirements from a 402 response
@@ -291,6 +292,9 @@ pub enum PaymentError {
 
     #[error("Signing failed: {0}")]
     SigningFailed(String),
+
+    #[error("Invalid payment header: {0}")]
+    InvalidPayment(String),
 }
 
 /// Complete x402 payment payload ready for submission
@@ -313,13 +317,13 @@ pub struct X402PaymentPayloadV1 {
     pub payload: X402PayloadInner,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct X402PayloadInner {
     pub signature: String,
     pub authorization: X402Authorization,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct X402Authorization {
     pub from: String,
@@ -330,6 +334,24 @@ pub struct X402Authorization {
     pub nonce: String,
 }
 
+/// The parts of a signed payment header needed to check its signature
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignedX402Header {
+    x402_version: u8,
+    payload: X402PayloadInner,
+}
+
+/// A payment header whose signature was checked against the requirements it pays
+#[derive(Debug, Clone)]
+pub struct VerifiedX402Payment {
+    pub option: X402PaymentOption,
+    /// Address recovered from the signature
+    pub payer: String,
+    pub resource: Option<String>,
+    pub x402_version: u8,
+}
+
 #[derive(Debug, Clone)]
 pub enum BuiltX402PaymentPayload {
     V1(X402PaymentPayloadV1),
@@ -362,52 +384,37 @@ impl BuiltX402PaymentPayload {
     }
 }
 
+/// Networks the x402 signer knows by name, with their EIP-155 chain ids.
+const KNOWN_NETWORKS: &[(&str, u64)] = &[
+    ("base", 8453),
+    ("base-sepolia", 84532),
+    ("ethereum", 1),
+    ("ethereum-sepolia", 11155111),
+    ("avalanche", 43114),
+    ("avalanche-fuji", 43113),
+];
+
+/// Chain ids of the networks the wallet can sign payments for.
+pub fn supported_chain_ids() -> Vec<u64> {
+    KNOWN_NETWORKS
+        .iter()
+        .map(|(_, chain_id)| *chain_id)
+        .collect()
+}
+
 fn chain_id_from_network(network: &str) -> Option<u64> {
     if let Some(chain_id) = network.strip_prefix("eip155:") {
         return chain_id.parse().ok();
     }
 
-    match network {
-        "base" => Some(8453),
-        "base-sepolia" => Some(84532),
-        "ethereum" => Some(1),
-        "ethereum-sepolia" => Some(11155111),
-        "avalanche" => Some(43114),
-        "avalanche-fuji" => Some(43113),
-        _ => None,
-    }
-}
-
-/// Build a complete x402 payment payload
-pub async fn build_x402_payment_payload(
-    wallet: &PrivateKeyWallet,
-    requirements: &PaymentRequirements,
-    option: &X402PaymentOption,
-) -> Result<BuiltX402PaymentPayload, PaymentError> {
-    match requirements.x402_version {
-        Some(1) => Ok(BuiltX402PaymentPayload::V1(
-            build_x402_payment_payload_v1(wallet, option).await?,
-        )),
-        Some(2) => Ok(BuiltX402PaymentPayload::V2(
-            build_x402_payment_payload_v2(wallet, requirements, option).await?,
-        )),
-        other => Err(PaymentError::ParseFailed(format!(
-            "Unsupported x402 version in requirements: {:?}",
-            other
-        ))),
-    }
+    KNOWN_NETWORKS
+        .iter()
+        .find(|(name, _)| *name == network)
+        .map(|(_, chain_id)| *chain_id)
 }
 
-async fn build_x402_payment_payload_v2(
-    wallet: &PrivateKeyWallet,
-    requirements: &PaymentRequirements,
-    option: &X402PaymentOption,
-) -> Result<X402PaymentPayload, PaymentError> {
-    let from_address = wallet.address().to_string();
-    let resource = requirements.resource.clone().ok_or_else(|| {
-        PaymentError::ParseFailed("Missing x402 resource info in 402 response".to_string())
-    })?;
-
+/// EIP-712 domain of the asset contract an x402 option pays with.
+fn transfer_authorization_domain(option: &X402PaymentOption) -> Result<Eip712Domain, PaymentError> {
     let chain_id = chain_id_from_network(&option.network).ok_or_else(|| {
         PaymentError::SigningFailed(format!(
             "Unsupported network for EIP-3009 signing: {}",
@@ -462,6 +469,58 @@ async fn build_x402_payment_payload_v2(
         })
         .unwrap_or("2");
 
+    Ok(Eip712Domain {
+        name: Some(domain_name.to_string()),
+        version: Some(domain_version.to_string()),
+        chain_id: Some(U256::from(chain_id)),
+        verifying_contract: Some(verifying_contract),
+    })
+}
+
+/// Parse a 0x-prefixed 32-byte authorization nonce.
+fn parse_nonce(nonce: &str) -> Option<FixedBytes<32>> {
+    let hex_str = nonce.strip_prefix("0x").unwrap_or(nonce);
+    let bytes = hex::decode(hex_str).ok()?;
+    if bytes.len() != 32 {
+        return None;
+    }
+    let mut arr = [0u8; 32];
+    arr.copy_from_slice(&bytes);
+    Some(FixedBytes::from(arr))
+}
+
+/// Build a complete x402 payment payload
+pub async fn build_x402_payment_payload(
+    wallet: &PrivateKeyWallet,
+    requirements: &PaymentRequirements,
+    option: &X402PaymentOption,
+) -> Result<BuiltX402PaymentPayload, PaymentError> {
+    match requirements.x402_version {
+        Some(1) => Ok(BuiltX402PaymentPayload::V1(
+            build_x402_payment_payload_v1(wallet, option).await?,
+        )),
+        Some(2) => Ok(BuiltX402PaymentPayload::V2(
+            build_x402_payment_payload_v2(wallet, requirements, option).await?,
+        )),
+        other => Err(PaymentError::ParseFailed(format!(
+            "Unsupported x402 version in requirements: {:?}",
+            other
+        ))),
+    }
+}
+
+async fn build_x402_payment_payload_v2(
+    wallet: &PrivateKeyWallet,
+    requirements: &PaymentRequirements,
+    option: &X402PaymentOption,
+) -> Result<X402PaymentPayload, PaymentError> {
+    let from_address = wallet.address().to_string();
+    let resource = requirements.resource.clone().ok_or_else(|| {
+        PaymentError::ParseFailed("Missing x402 resource info in 402 response".to_string())
+    })?;
+
+    let domain = transfer_authorization_domain(option)?;
+
     // Calculate validity window
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -490,23 +549,8 @@ async fn build_x402_payment_payload_v2(
         .and_then(|v| v.get("message"))
         .and_then(|v| v.get("nonce"))
         .and_then(|v| v.as_str())
-        .and_then(|nonce| {
-            let hex_str = nonce.strip_prefix("0x").unwrap_or(nonce);
-            let bytes = hex::decode(hex_str).ok()?;
-            if bytes.len() != 32 {
-                return None;
-            }
-            let mut arr = [0u8; 32];
-            arr.copy_from_slice(&bytes);
-            Some(FixedBytes::from(arr))
-        });
+        .and_then(parse_nonce);
 
-    let domain = Eip712Domain {
-        name: Some(domain_name.to_string()),
-        version: Some(domain_version.to_string()),
-        chain_id: Some(U256::from(chain_id)),
-        verifying_contract: Some(verifying_contract),
-    };
     let message = build_authorization_message(
         &from_address,
         &option.pay_to,
@@ -546,59 +590,7 @@ async fn build_x402_payment_payload_v1(
 ) -> Result<X402PaymentPayloadV1, PaymentError> {
     let from_address = wallet.address().to_string();
 
-    let chain_id = chain_id_from_network(&option.network).ok_or_else(|| {
-        PaymentError::SigningFailed(format!(
-            "Unsupported network for EIP-3009 signing: {}",
-            option.network
-        ))
-    })?;
-
-    let typed_verifying_contract = option
-        .extra
-        .get("eip712TypedData")
-        .and_then(|v| v.get("domain"))
-        .and_then(|v| v.get("verifyingContract"))
-        .and_then(|v| v.as_str());
-    if let Some(vc) = typed_verifying_contract
-        && !vc.eq_ignore_ascii_case(&option.asset)
-    {
-        return Err(PaymentError::SigningFailed(format!(
-            "Mismatched verifyingContract ({}) for asset {}",
-            vc, option.asset
-        )));
-    }
-    let verifying_contract = typed_verifying_contract.unwrap_or(&option.asset);
-    let verifying_contract = verifying_contract.parse().map_err(|_| {
-        PaymentError::SigningFailed("Invalid verifyingContract address".to_string())
-    })?;
-
-    let domain_name = option
-        .extra
-        .get("name")
-        .and_then(|v| v.as_str())
-        .or_else(|| {
-            option
-                .extra
-                .get("eip712TypedData")
-                .and_then(|v| v.get("domain"))
-                .and_then(|v| v.get("name"))
-                .and_then(|v| v.as_str())
-        })
-        .unwrap_or("USD Coin");
-
-    let domain_version = option
-        .extra
-        .get("version")
-        .and_then(|v| v.as_str())
-        .or_else(|| {
-            option
-                .extra
-                .get("eip712TypedData")
-                .and_then(|v| v.get("domain"))
-                .and_then(|v| v.get("version"))
-                .and_then(|v| v.as_str())
-        })
-        .unwrap_or("2");
+    let domain = transfer_authorization_domain(option)?;
 
     // Calculate validity window
     let now = SystemTime::now()
@@ -628,23 +620,8 @@ async fn build_x402_payment_payload_v1(
         .and_then(|v| v.get("message"))
         .and_then(|v| v.get("nonce"))
         .and_then(|v| v.as_str())
-        .and_then(|nonce| {
-            let hex_str = nonce.strip_prefix("0x").unwrap_or(nonce);
-            let bytes = hex::decode(hex_str).ok()?;
-            if bytes.len() != 32 {
-                return None;
-            }
-            let mut arr = [0u8; 32];
-            arr.copy_from_slice(&bytes);
-            Some(FixedBytes::from(arr))
-        });
+        .and_then(parse_nonce);
 
-    let domain = Eip712Domain {
-        name: Some(domain_name.to_string()),
-        version: Some(domain_version.to_string()),
-        chain_id: Some(U256::from(chain_id)),
-        verifying_contract: Some(verifying_contract),
-    };
     let message = build_authorization_message(
         &from_address,
         &option.pay_to,
@@ -678,6 +655,74 @@ async fn build_x402_payment_payload_v1(
     })
 }
 
+/// Check that a base64 payment header carries an EIP-3009 authorization for
+/// the first x402 option in `requirements`, signed by the address it names.
+pub fn verify_x402_payment_header(
+    requirements: &PaymentRequirements,
+    header_b64: &str,
+) -> Result<VerifiedX402Payment, PaymentError> {
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(header_b64.trim())
+        .map_err(|e| PaymentError::InvalidPayment(e.to_string()))?;
+    let header: SignedX402Header = serde_json::from_slice(&decoded)
+        .map_err(|e| PaymentError::InvalidPayment(e.to_string()))?;
+    if requirements.x402_version != Some(header.x402_version) {
+        return Err(PaymentError::InvalidPayment(format!(
+            "x402 version {} does not match the requirements",
+            header.x402_version
+        )));
+    }
+
+    let option = requirements
+        .x402_option()
+        .ok_or_else(|| PaymentError::ParseFailed("No x402 payment option".to_string()))?;
+    let authorization = &header.payload.authorization;
+    if !authorization.to.eq_ignore_ascii_case(&option.pay_to)
+        || authorization.value != option.amount
+    {
+        return Err(PaymentError::InvalidPayment(
+            "Authorization does not match the requested payment".to_string(),
+        ));
+    }
+
+    let valid_after = authorization
+        .valid_after
+        .parse::<u64>()
+        .map_err(|_| PaymentError::InvalidPayment("Invalid validAfter".to_string()))?;
+    let valid_before = authorization
+        .valid_before
+        .parse::<u64>()
+        .map_err(|_| PaymentError::InvalidPayment("Invalid validBefore".to_string()))?;
+    let nonce = parse_nonce(&authorization.nonce)
+        .ok_or_else(|| PaymentError::InvalidPayment("Invalid nonce".to_string()))?;
+    let message = build_authorization_message(
+        &authorization.from,
+        &authorization.to,
+        &authorization.value,
+        valid_after,
+        valid_before,
+        Some(nonce),
+    )
+    .map_err(|e| PaymentError::InvalidPayment(e.to_string()))?;
+
+    let domain = transfer_authorization_domain(option)?;
+    let signer =
+        recover_transfer_authorization_signer(&domain, &message, &header.payload.signature)
+            .map_err(|e| PaymentError::InvalidPayment(e.to_string()))?;
+    if signer != message.from {
+        return Err(PaymentError::InvalidPayment(
+            "Signature was not made by the paying address".to_string(),
+        ));
+    }
+
+    Ok(VerifiedX402Payment {
+        option: option.clone(),
+        payer: signer.to_string(),
+        resource: requirements.resource.as_ref().map(|r| r.url.clone()),
+        x402_version: header.x402_version,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -814,4 +859,65 @@ mod tests {
         let method = select_payment_method(&requirements, &user_caps);
         assert!(method.is_none());
     }
+
+    #[tokio::test]
+    async fn verify_payment_header_recovers_the_signer() {
+        let wallet = PrivateKeyWallet::from_key(Some(
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".into(),
+        ))
+        .unwrap()
+        .unwrap();
+        let body = |amount: &str| {
+            format!(
+                r#"{{
+                    "x402Version": 2,
+                    "resource": {{
+                        "url": "/publishers/test-publisher/query",
+                        "description": "SQL query on Test Publisher",
+                        "mimeType": "application/json"
+                    }},
+                    "accepts": [{{
+                        "scheme": "exact",
+                        "network": "eip155:8453",
+                        "amount": "{amount}",
+                        "asset": "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913",
+                        "payTo": "0x1234567890123456789012345678901234567890",
+                        "maxTimeoutSeconds": 300,
+                        "extra": {{ "name": "USD Coin", "version": "2" }}
+                    }}]
+                }}"#
+            )
+        };
+        let requirements = PaymentRequirements::parse(&body("1000000")).unwrap();
+        let option = requirements.x402_option().unwrap();
+        let header = build_x402_payment_payload(&wallet, &requirements, option)
+            .await
+            .unwrap()
+            .encode_b64()
+            .unwrap();
+
+        let verified = verify_x402_payment_header(&requirements, &header).unwrap();
+        assert_eq!(verified.payer, wallet.address().to_string());
+        assert_eq!(verified.option.amount, "1000000");
+        assert_eq!(verified.x402_version, 2);
+        assert_eq!(
+            verified.resource.as_deref(),
+            Some("/publishers/test-publisher/query")
+        );
+
+        // The same header cannot be logged against a different payment.
+        let other = PaymentRequirements::parse(&body("5000000")).unwrap();
+        assert!(verify_x402_payment_header(&other, &header).is_err());
+
+        // A header naming a payer that did not sign it is rejected.
+        let decoded = base64::engine::general_purpose::STANDARD
+            .decode(&header)
+            .unwrap();
+        let mut forged: serde_json::Value = serde_json::from_slice(&decoded).unwrap();
+        forged["payload"]["authorization"]["from"] =
+            "0x70997970C51812dc3A010C7d01b50e0d17dc79C8".into();
+        let forged =
+            base64::engine::general_purpose::STANDARD.encode(serde_json::to_vec(&forged).unwrap());
+        assert!(verify_x402_payment_header(&requirements, &forged).is_err());
+    }
 }
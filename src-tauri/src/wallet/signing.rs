@@ -1,7 +1,7 @@
 // ABOUTME: EIP-712 signing for x402 payments.
 // ABOUTME: Implements signing for USDC transferWithAuthorization (EIP-3009).
 
-use alloy::primitives::{Address, FixedBytes, U256};
+use alloy::primitives::{Address, FixedBytes, Signature, U256};
 use alloy::signers::Signer;
 use alloy::sol;
 use alloy::sol_types::SolStruct;
@@ -163,6 +163,28 @@ pub async fn sign_transfer_authorization(
     Ok(format!("0x{}", hex::encode(signature.as_bytes())))
 }
 
+/// Recover the address that signed a TransferWithAuthorization message
+///
+/// # Arguments
+/// * `signature` - 65-byte signature as a hex string, with or without 0x
+pub fn recover_transfer_authorization_signer(
+    domain: &Eip712Domain,
+    message: &AuthorizationMessage,
+    signature: &str,
+) -> Result<Address, WalletError> {
+    let bytes = hex::decode(signature.strip_prefix("0x").unwrap_or(signature))
+        .map_err(|_| WalletError::SigningFailed("Invalid signature hex".into()))?;
+    let signature =
+        Signature::from_raw(&bytes).map_err(|e| WalletError::SigningFailed(e.to_string()))?;
+
+    let signing_hash = message
+        .to_sol_struct()
+        .eip712_signing_hash(&domain.to_alloy_domain());
+    signature
+        .recover_address_from_prehash(&signing_hash)
+        .map_err(|e| WalletError::SigningFailed(e.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
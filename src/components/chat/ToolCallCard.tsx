@@ -16,6 +16,7 @@ import {
   connectPublisher,
   resolveOAuthProviderForPublisher,
 } from "@/services/publisher-oauth";
+import { sendShellInput } from "@/services/shell-progress";
 
 interface ToolCallCardProps {
   toolCall: ToolCallEvent;
@@ -254,6 +255,23 @@ export const ToolCallCard: Component<ToolCallCardProps> = (props) => {
     return Boolean(partial && partial.length > 0 && isToolRunning());
   };
 
+  // Commands started with `interactive` read stdin from this input.
+  const [stdinText, setStdinText] = createSignal("");
+  const [stdinError, setStdinError] = createSignal<string | null>(null);
+  const acceptsInput = (): boolean =>
+    isToolRunning() && props.toolCall.parameters?.interactive === true;
+
+  const sendInput = async (close: boolean) => {
+    const data = close ? "" : `${stdinText()}\n`;
+    try {
+      await sendShellInput(props.toolCall.toolCallId, data, close);
+      setStdinText("");
+      setStdinError(null);
+    } catch (error) {
+      setStdinError(String(error));
+    }
+  };
+
   const partialByteCount = (): number => {
     const partial = props.toolCall.partialResult;
     if (!partial) return 0;
@@ -630,6 +648,41 @@ export const ToolCallCard: Component<ToolCallCardProps> = (props) => {
             </div>
           </Show>
 
+          {/* Answer prompts from a command started as interactive. */}
+          <Show when={acceptsInput()}>
+            <form
+              class="mb-3 flex items-center gap-2"
+              onSubmit={(event) => {
+                event.preventDefault();
+                void sendInput(false);
+              }}
+            >
+              <input
+                type="text"
+                class="flex-1 min-w-0 bg-background border border-surface-3 rounded px-2 py-1 font-mono text-foreground"
+                placeholder="Type a reply and press Enter"
+                autocomplete="off"
+                value={stdinText()}
+                onInput={(event) => setStdinText(event.currentTarget.value)}
+              />
+              <button
+                type="button"
+                class="text-xs text-muted-foreground hover:text-foreground shrink-0"
+                title="Close stdin (send EOF)"
+                onClick={() => void sendInput(true)}
+              >
+                EOF
+              </button>
+            </form>
+            <Show when={stdinError()}>
+              {(error) => (
+                <div class="mb-3 break-words text-[11px] text-destructive">
+                  {error()}
+                </div>
+              )}
+            </Show>
+          </Show>
+
           {/* Result */}
           <Show when={props.toolCall.result}>
             <div class="mb-3">
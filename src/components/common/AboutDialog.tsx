@@ -3,7 +3,7 @@
 
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
-import { createSignal, onCleanup, onMount, Show } from "solid-js";
+import { createSignal, For, onCleanup, onMount, Show } from "solid-js";
 import { updaterStore } from "@/stores/updater.store";
 
 interface BuildInfo {
@@ -16,6 +16,12 @@ interface BuildInfo {
   webview: string;
   rust_version: string;
   os: string;
+  features: string[];
+  sidecars: Record<string, string | null>;
+}
+
+function formatFeatures(features: string[]): string {
+  return features.length > 0 ? features.join(", ") : "none";
 }
 
 export function AboutDialog() {
@@ -66,6 +72,10 @@ export function AboutDialog() {
       `WebView: ${data.webview}`,
       `Rust: ${data.rust_version}`,
       `OS: ${data.os}`,
+      `Features: ${formatFeatures(data.features)}`,
+      ...Object.entries(data.sidecars).map(
+        ([name, version]) => `${name}: ${version ?? "not installed"}`,
+      ),
     ].join("\n");
 
     navigator.clipboard.writeText(text).then(() => {
@@ -116,6 +126,15 @@ export function AboutDialog() {
                   <Row label="WebView" value={data().webview} />
                   <Row label="Rust" value={data().rust_version} />
                   <Row label="OS" value={data().os} />
+                  <Row
+                    label="Features"
+                    value={formatFeatures(data().features)}
+                  />
+                  <For each={Object.entries(data().sidecars)}>
+                    {([name, version]) => (
+                      <Row label={name} value={version ?? "not installed"} />
+                    )}
+                  </For>
                 </div>
               )}
             </Show>
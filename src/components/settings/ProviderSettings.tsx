@@ -9,7 +9,6 @@ import {
   onMount,
   Show,
 } from "solid-js";
-import { validateProviderKey } from "@/lib/providers";
 import {
   CONFIGURABLE_PROVIDERS,
   PROVIDER_CONFIGS,
@@ -173,11 +172,7 @@ export const ProviderSettings: Component = () => {
 
     if (!provider || !apiKey) return;
 
-    const success = await providerStore.configureProvider(
-      provider,
-      apiKey,
-      validateProviderKey,
-    );
+    const success = await providerStore.configureProvider(provider, apiKey);
 
     if (success) {
       setSelectedProvider(null);
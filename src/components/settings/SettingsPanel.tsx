@@ -682,6 +682,31 @@ export const SettingsPanel: Component<SettingsPanelProps> = (props) => {
               </label>
             </div>
 
+            <div class="flex items-start justify-start gap-4 py-3 border-b border-border">
+              <label class="flex items-start gap-3 cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={settingsState.app.chatOrchestratorTrace}
+                  onChange={(e) =>
+                    handleBooleanChange(
+                      "chatOrchestratorTrace",
+                      e.currentTarget.checked,
+                    )
+                  }
+                  class="w-[18px] h-[18px] mt-0.5 accent-accent cursor-pointer"
+                />
+                <span class="flex flex-col gap-0.5">
+                  <span class="text-[0.95rem] font-medium text-foreground">
+                    Trace Turns
+                  </span>
+                  <span class="text-[0.8rem] text-muted-foreground">
+                    Record routing, tool dispatch, and retries for each turn
+                    to help diagnose unexpected behavior
+                  </span>
+                </span>
+              </label>
+            </div>
+
             <h4 class="mt-6 mb-3 text-base font-semibold text-muted-foreground border-t border-border-medium pt-5">
               Auto-Compact
             </h4>
@@ -1210,7 +1235,8 @@ export const SettingsPanel: Component<SettingsPanelProps> = (props) => {
                   Auto-Approve Limit
                 </span>
                 <span class="text-[0.8rem] text-muted-foreground">
-                  Auto-approve payments up to this amount (USD)
+                  Auto-approve payments until they total this much (USD) in
+                  24 hours
                 </span>
               </label>
               <input
@@ -2143,6 +2169,32 @@ export const SettingsPanel: Component<SettingsPanelProps> = (props) => {
               </label>
             </div>
 
+            <div class="flex items-start justify-between gap-4 py-3 border-b border-border">
+              <label class="flex flex-col gap-0.5 flex-1">
+                <span class="text-[0.95rem] font-medium text-foreground">
+                  Embedding Concurrency
+                </span>
+                <span class="text-[0.8rem] text-muted-foreground">
+                  Embedding requests sent at once while indexing. Higher values
+                  index large projects faster but may hit rate limits.
+                </span>
+              </label>
+              <input
+                type="number"
+                min="1"
+                max="16"
+                step="1"
+                value={settingsState.app.semanticIndexingConcurrency}
+                onInput={(e) =>
+                  handleNumberChange(
+                    "semanticIndexingConcurrency",
+                    e.currentTarget.value,
+                  )
+                }
+                class="w-[100px] px-3 py-2 bg-surface-3/80 border border-border-strong rounded-md text-foreground text-[0.9rem] text-right focus:outline-none focus:border-accent"
+              />
+            </div>
+
             <div class="mt-6 p-4 bg-primary/10 border border-primary/30 rounded">
               <h4 class="m-0 mb-2 text-sm font-semibold text-foreground">
                 How It Works
@@ -7,11 +7,11 @@ import {
   type ContextMenuItem,
 } from "@/components/common/ContextMenu";
 import {
-  deletePath,
   openFolder,
   readFile,
   renamePath,
   revealInFileManager,
+  trashPath,
 } from "@/lib/files/service";
 import { chatStore } from "@/stores/chat.store";
 import {
@@ -198,12 +198,15 @@ export const FileTree: Component<FileTreeProps> = (props) => {
   // Delete file or directory
   const handleDelete = async (node: FileNode) => {
     const confirmDelete = window.confirm(
-      `Delete "${node.name}"?${node.isDirectory ? " This will delete all contents." : ""}`,
+      `Move "${node.name}" to the Trash?${node.isDirectory ? " This includes all of its contents." : ""}`,
     );
     if (!confirmDelete) return;
 
     try {
-      await deletePath(node.path);
+      const result = await trashPath(node.path);
+      if (!result.trashed) {
+        alert(result.message ?? `"${node.name}" was deleted permanently.`);
+      }
       // Refresh the parent directory
       const dir = node.path.substring(0, node.path.lastIndexOf("/"));
       await refreshDirectory(dir);
@@ -175,6 +175,14 @@ export function IndexingStatus() {
         </div>
       </Show>
 
+      <Show when={indexingStore.chunksFailed > 0}>
+        <div class="mt-2 px-2 py-1.5 bg-surface-0 rounded text-[11px] text-muted-foreground">
+          {formatNumber(indexingStore.chunksFailed)}{" "}
+          {indexingStore.chunksFailed === 1 ? "chunk" : "chunks"} failed to
+          index. Their files will be retried on the next run.
+        </div>
+      </Show>
+
       <Show when={indexingStore.error}>
         <div class="flex items-start gap-2 p-2 bg-destructive/10 border border-destructive/30 rounded mt-2">
           <span class="text-destructive text-sm shrink-0">⚠</span>
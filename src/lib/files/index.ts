@@ -15,5 +15,6 @@ export {
   renamePath,
   saveFileDialog,
   saveTab,
+  trashPath,
   writeFile,
 } from "./service";
@@ -21,6 +21,8 @@ import {
   readFile as readFileBridge,
   renamePath as renamePathBridge,
   revealInFileManager as revealInFileManagerBridge,
+  type TrashResult,
+  trashPath as trashPathBridge,
   writeFile as writeFileBridge,
 } from "@/lib/tauri-bridge";
 import { type FileNode, setNodes, setRootPath } from "@/stores/fileTree";
@@ -73,13 +75,15 @@ export async function isDirectory(path: string): Promise<boolean> {
 }
 
 /**
- * Create a new file with optional content.
+ * Create a new file with optional content. With `exclusive`, rejects with an
+ * `AlreadyExists` CreateFileError instead of overwriting an existing file.
  */
 export async function createFile(
   path: string,
   content?: string,
+  options: { exclusive?: boolean } = {},
 ): Promise<void> {
-  return createFileBridge(path, content);
+  return createFileBridge(path, content, options);
 }
 
 /**
@@ -96,6 +100,13 @@ export async function deletePath(path: string): Promise<void> {
   return deletePathBridge(path);
 }
 
+/**
+ * Move a file or directory to the OS trash so the delete can be undone.
+ */
+export async function trashPath(path: string): Promise<TrashResult> {
+  return trashPathBridge(path);
+}
+
 /**
  * Rename/move a file or directory.
  */
@@ -6,7 +6,40 @@ import { settingsStore } from "@/stores/settings.store";
 import { type FileChangeEvent, syncStore } from "@/stores/sync.store";
 import { reindexFile } from "./orchestrator";
 
+/** Quiet period after the last save before a file is re-indexed. */
+const REINDEX_DEBOUNCE_MS = 750;
+
 let unsubscribeFn: (() => void) | null = null;
+const pendingReindex = new Map<string, ReturnType<typeof setTimeout>>();
+
+/**
+ * Re-index a file once saves to it have settled. Each new save restarts the
+ * timer, so a burst of writes triggers a single re-index.
+ */
+function scheduleReindex(projectPath: string, filePath: string): void {
+  const existing = pendingReindex.get(filePath);
+  if (existing) {
+    clearTimeout(existing);
+  }
+
+  const timer = setTimeout(async () => {
+    pendingReindex.delete(filePath);
+    try {
+      await reindexFile(projectPath, filePath);
+      console.log("[File Watcher] Re-indexed:", filePath);
+    } catch (error) {
+      console.error("[File Watcher] Failed to re-index:", filePath, error);
+    }
+  }, REINDEX_DEBOUNCE_MS);
+  pendingReindex.set(filePath, timer);
+}
+
+function cancelPendingReindex(): void {
+  for (const timer of pendingReindex.values()) {
+    clearTimeout(timer);
+  }
+  pendingReindex.clear();
+}
 
 /**
  * Start listening to file change events and trigger re-indexing.
@@ -18,9 +51,10 @@ export function startFileWatcherForIndexing(): void {
     unsubscribeFn();
     unsubscribeFn = null;
   }
+  cancelPendingReindex();
 
   // Subscribe to file changes via syncStore
-  unsubscribeFn = syncStore.onFileChange(async (event: FileChangeEvent) => {
+  unsubscribeFn = syncStore.onFileChange((event: FileChangeEvent) => {
     // Check if indexing is enabled
     const indexingEnabled = settingsStore.get("semanticIndexingEnabled");
     if (!indexingEnabled) {
@@ -36,12 +70,7 @@ export function startFileWatcherForIndexing(): void {
     for (const filePath of event.paths) {
       // Only reindex on modify events (not create/delete for now)
       if (event.kind.includes("Modify")) {
-        try {
-          await reindexFile(projectPath, filePath);
-          console.log("[File Watcher] Re-indexed:", filePath);
-        } catch (error) {
-          console.error("[File Watcher] Failed to re-index:", filePath, error);
-        }
+        scheduleReindex(projectPath, filePath);
       }
     }
   });
@@ -55,4 +84,5 @@ export function stopFileWatcherForIndexing(): void {
     unsubscribeFn();
     unsubscribeFn = null;
   }
+  cancelPendingReindex();
 }
@@ -6,20 +6,42 @@ import type {
   ChunkedFile,
   DiscoveredFile,
   FileChunk,
+  IndexingEstimate,
+} from "@/services/indexing";
+import {
+  estimateIndexingCost,
+  type IndexChunkResult,
+  indexChunks,
 } from "@/services/indexing";
-import { indexChunks } from "@/services/indexing";
-import { embedTexts } from "@/services/seren-embed";
 import { indexingStore } from "@/stores/indexing.store";
-
-const BATCH_SIZE = 20; // Process 20 chunks at a time
+import { settingsStore } from "@/stores/settings.store";
 
 interface IndexingResult {
   totalFiles: number;
   totalChunks: number;
-  totalTokens: number;
+  /** Chunks that failed; their files are retried on the next run. */
+  failedChunks: number;
+  /** Estimated from chunk text, not the embedding API's reported usage. */
+  estimatedTokens: number;
   duration: number;
 }
 
+/**
+ * Count and log a file's failed chunks. `indexChunks` leaves such files
+ * looking stale, so they are picked up again on the next run.
+ */
+function reportFailures(filePath: string, results: IndexChunkResult[]): number {
+  const failed = results.filter((result) => result.error);
+  if (failed.length > 0) {
+    console.warn(
+      `[Indexing] ${failed.length} chunk(s) of ${filePath} failed:`,
+      failed[0].error,
+    );
+    indexingStore.addFailedChunks(failed.length);
+  }
+  return failed.length;
+}
+
 /**
  * Run the full indexing workflow for a project.
  */
@@ -28,7 +50,8 @@ export async function runIndexing(
 ): Promise<IndexingResult> {
   const startTime = Date.now();
   let totalChunks = 0;
-  let totalTokens = 0;
+  let failedChunks = 0;
+  let estimatedTokens = 0;
 
   try {
     // Phase 1: Initialize index database
@@ -51,16 +74,13 @@ export async function runIndexing(
     });
 
     // Phase 3: Estimate work
-    const [estimatedChunks, estimatedTokens] = await invoke<[number, number]>(
-      "estimate_indexing",
-      {
-        files,
-      },
-    );
+    const estimate = await invoke<IndexingEstimate>("estimate_indexing", {
+      files,
+    });
 
     indexingStore.updateProgress({
-      chunksTotal: estimatedChunks,
-      estimatedTokens: estimatedTokens,
+      chunksTotal: estimate.estimated_chunks,
+      estimatedTokens: estimate.estimated_tokens,
     });
 
     // Phase 4: Process files in batches
@@ -97,39 +117,34 @@ export async function runIndexing(
         continue; // Skip files with no chunks
       }
 
-      // Phase 5: Generate embeddings in batches
+      // Phase 5/6: Embed (concurrently) and store the file's chunks
       indexingStore.setPhase("embedding");
 
-      for (let j = 0; j < chunked.chunks.length; j += BATCH_SIZE) {
-        const batch = chunked.chunks.slice(j, j + BATCH_SIZE);
-        const texts = batch.map((c: { content: string }) => c.content);
-
-        // Generate embeddings via the shared publisher client.
-        const embeddingResponse = await embedTexts(texts);
-        totalTokens += embeddingResponse.usage.total_tokens;
-
-        // Phase 6: Store chunks with embeddings
-        indexingStore.setPhase("storing");
-
-        const chunksToStore = batch.map((chunk: FileChunk, idx: number) => ({
-          file_path: file.relative_path,
-          start_line: chunk.start_line,
-          end_line: chunk.end_line,
-          content: chunk.content,
-          chunk_type: chunk.chunk_type,
-          symbol_name: chunk.symbol_name,
-          language: file.language,
-          file_hash: file.hash,
-          embedding: embeddingResponse.data[idx].embedding,
-        }));
-
-        await indexChunks(projectPath, chunksToStore);
-
-        totalChunks += batch.length;
-        indexingStore.updateProgress({
-          chunksProcessed: totalChunks,
-        });
-      }
+      const chunksToStore = chunked.chunks.map((chunk: FileChunk) => ({
+        file_path: file.relative_path,
+        start_line: chunk.start_line,
+        end_line: chunk.end_line,
+        content: chunk.content,
+        chunk_type: chunk.chunk_type,
+        symbol_name: chunk.symbol_name,
+        language: file.language,
+        file_hash: file.hash,
+      }));
+      estimatedTokens += estimateIndexingCost(
+        chunksToStore.map((chunk) => chunk.content),
+      );
+
+      const chunksBefore = totalChunks;
+      const results = await indexChunks(projectPath, chunksToStore, {
+        concurrency: settingsStore.get("semanticIndexingConcurrency"),
+        onProgress: (processed) => {
+          indexingStore.updateProgress({
+            chunksProcessed: chunksBefore + processed,
+          });
+        },
+      });
+      totalChunks += chunksToStore.length;
+      failedChunks += reportFailures(file.relative_path, results);
     }
 
     // Phase 7: Complete
@@ -146,7 +161,8 @@ export async function runIndexing(
     return {
       totalFiles: files.length,
       totalChunks,
-      totalTokens,
+      failedChunks,
+      estimatedTokens,
       duration,
     };
   } catch (error) {
@@ -164,66 +180,34 @@ export async function reindexFile(
   filePath: string,
 ): Promise<void> {
   try {
-    // Read file content
-    const content = await invoke<string>("read_file", { path: filePath });
-
-    // Compute hash
-    const hash = await invoke<string>("compute_file_hash", { content });
-
-    // Check if needs reindex
-    const needsIndex = await invoke<boolean>("file_needs_reindex", {
+    // Hash, compare, drop stale chunks, and re-chunk in one round trip
+    const chunked = await invoke<ChunkedFile | null>("reindex_file", {
       projectPath,
       filePath,
-      fileHash: hash,
     });
 
-    if (!needsIndex) {
-      return; // File hasn't changed
+    if (!chunked || chunked.chunks.length === 0) {
+      return; // Unchanged or not indexable
     }
 
-    // Get file info
-    const files = await invoke<DiscoveredFile[]>("discover_project_files", {
-      projectPath,
+    const { file } = chunked;
+
+    // Embed and store chunks
+    const chunksToStore = chunked.chunks.map((chunk: FileChunk) => ({
+      file_path: file.relative_path,
+      start_line: chunk.start_line,
+      end_line: chunk.end_line,
+      content: chunk.content,
+      chunk_type: chunk.chunk_type,
+      symbol_name: chunk.symbol_name,
+      language: file.language,
+      file_hash: file.hash,
+    }));
+
+    const results = await indexChunks(projectPath, chunksToStore, {
+      concurrency: settingsStore.get("semanticIndexingConcurrency"),
     });
-    const file = files.find((f) => f.path === filePath);
-
-    if (!file) {
-      return; // File not indexable
-    }
-
-    // Delete old chunks
-    await invoke("delete_file_index", {
-      projectPath,
-      filePath: file.relative_path,
-    });
-
-    // Chunk the file
-    const chunked = await invoke<ChunkedFile>("chunk_file", { file });
-
-    if (chunked.chunks.length === 0) {
-      return;
-    }
-
-    // Generate embeddings via the shared publisher client.
-    const texts = chunked.chunks.map((c: FileChunk) => c.content);
-    const embeddingResponse = await embedTexts(texts);
-
-    // Store chunks
-    const chunksToStore = chunked.chunks.map(
-      (chunk: FileChunk, idx: number) => ({
-        file_path: file.relative_path,
-        start_line: chunk.start_line,
-        end_line: chunk.end_line,
-        content: chunk.content,
-        chunk_type: chunk.chunk_type,
-        symbol_name: chunk.symbol_name,
-        language: file.language,
-        file_hash: file.hash,
-        embedding: embeddingResponse.data[idx].embedding,
-      }),
-    );
-
-    await indexChunks(projectPath, chunksToStore);
+    reportFailures(file.relative_path, results);
 
     // Refresh stats
     await indexingStore.refreshStats();
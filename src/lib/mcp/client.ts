@@ -8,6 +8,8 @@ import type {
   McpConnection,
   McpConnectionStatus,
   McpInitializeResult,
+  McpPrompt,
+  McpPromptResult,
   McpResource,
   McpTool,
   McpToolCall,
@@ -150,6 +152,8 @@ function createMcpClient() {
    */
   type CallToolOptions = {
     signal?: AbortSignal;
+    /** Backend bound on the call; the server default (120s) when omitted. */
+    timeoutMs?: number;
   };
 
   type RetryToolOptions = CallToolOptions & {
@@ -158,51 +162,88 @@ function createMcpClient() {
     onAttempt?: (attempt: number) => void;
   };
 
-  function withAbort<T>(promise: Promise<T>, signal?: AbortSignal): Promise<T> {
+  function withAbort<T>(
+    promise: Promise<T>,
+    signal?: AbortSignal,
+    onAbort?: () => void,
+  ): Promise<T> {
     if (!signal) {
       return promise;
     }
 
     if (signal.aborted) {
+      onAbort?.();
       return Promise.reject(
         new DOMException("Operation aborted", "AbortError"),
       );
     }
 
     return new Promise<T>((resolve, reject) => {
-      const onAbort = () => {
-        signal.removeEventListener("abort", onAbort);
+      const handleAbort = () => {
+        signal.removeEventListener("abort", handleAbort);
+        onAbort?.();
         reject(new DOMException("Operation aborted", "AbortError"));
       };
 
-      signal.addEventListener("abort", onAbort);
+      signal.addEventListener("abort", handleAbort);
 
       promise
         .then((value) => {
-          signal.removeEventListener("abort", onAbort);
+          signal.removeEventListener("abort", handleAbort);
           resolve(value);
         })
         .catch((error) => {
-          signal.removeEventListener("abort", onAbort);
+          signal.removeEventListener("abort", handleAbort);
           reject(error);
         });
     });
   }
 
+  /**
+   * Tell the backend to stop waiting on an in-flight tool call, so an
+   * aborted call does not keep its slot until the server times out.
+   */
+  function cancelToolCall(callId: string): void {
+    invoke("mcp_cancel_tool_call", { callId }).catch((error) => {
+      console.warn("[MCP] Failed to cancel tool call:", error);
+    });
+  }
+
+  /**
+   * The backend stops a stdio server whose tool call timed out or was
+   * cancelled, so it has to be reconnected before its next call.
+   */
+  function markServerStopped(serverName: string): void {
+    setConnectionStatus(
+      serverName,
+      "error",
+      "Server stopped after an abandoned tool call; reconnect to use it",
+    );
+  }
+
   async function callTool(
     serverName: string,
     call: McpToolCall,
     options?: CallToolOptions,
   ): Promise<McpToolResult> {
+    const callId = crypto.randomUUID();
     const invocation = invoke<McpToolResult>("mcp_call_tool", {
       serverName,
       toolName: call.name,
       arguments: call.arguments,
+      callId,
+      timeoutMs: options?.timeoutMs ?? null,
     }).catch((error) => {
+      if (String(error).includes("timed out after")) {
+        markServerStopped(serverName);
+      }
       throw parseMcpError(error, serverName);
     });
 
-    return withAbort(invocation, options?.signal);
+    return withAbort(invocation, options?.signal, () => {
+      cancelToolCall(callId);
+      markServerStopped(serverName);
+    });
   }
 
   async function retryToolCall(
@@ -279,6 +320,28 @@ function createMcpClient() {
     return invoke("mcp_read_resource", { serverName, uri });
   }
 
+  /**
+   * List prompt templates available on an MCP server.
+   */
+  async function listPrompts(serverName: string): Promise<McpPrompt[]> {
+    return invoke<McpPrompt[]>("mcp_list_prompts", { serverName });
+  }
+
+  /**
+   * Render a prompt template with the user's argument values.
+   */
+  async function getPrompt(
+    serverName: string,
+    name: string,
+    args?: Record<string, string>,
+  ): Promise<McpPromptResult> {
+    return invoke<McpPromptResult>("mcp_get_prompt", {
+      serverName,
+      name,
+      arguments: args ?? null,
+    });
+  }
+
   /**
    * Check if an MCP server is connected.
    */
@@ -435,15 +498,42 @@ function createMcpClient() {
     call: McpToolCall,
     options?: CallToolOptions,
   ): Promise<McpToolResult> {
+    const callId = crypto.randomUUID();
     const invocation = invoke<McpToolResult>("mcp_call_tool_http", {
       serverName,
       toolName: call.name,
       arguments: call.arguments,
+      callId,
+      timeoutMs: options?.timeoutMs ?? null,
     }).catch((error) => {
       throw parseMcpError(error, serverName);
     });
 
-    return withAbort(invocation, options?.signal);
+    return withAbort(invocation, options?.signal, () =>
+      cancelToolCall(callId),
+    );
+  }
+
+  /**
+   * List prompt templates from an HTTP MCP server.
+   */
+  async function listPromptsHttp(serverName: string): Promise<McpPrompt[]> {
+    return invoke<McpPrompt[]>("mcp_list_prompts_http", { serverName });
+  }
+
+  /**
+   * Render a prompt template on an HTTP MCP server.
+   */
+  async function getPromptHttp(
+    serverName: string,
+    name: string,
+    args?: Record<string, string>,
+  ): Promise<McpPromptResult> {
+    return invoke<McpPromptResult>("mcp_get_prompt_http", {
+      serverName,
+      name,
+      arguments: args ?? null,
+    });
   }
 
   /**
@@ -470,6 +560,8 @@ function createMcpClient() {
     callTool,
     retryToolCall,
     readResource,
+    listPrompts,
+    getPrompt,
     isConnected,
     listConnected,
     refreshTools,
@@ -481,6 +573,8 @@ function createMcpClient() {
     disconnectHttp,
     listToolsHttp,
     callToolHttp,
+    listPromptsHttp,
+    getPromptHttp,
     isConnectedHttp,
     listConnectedHttp,
   };
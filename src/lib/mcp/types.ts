@@ -40,6 +40,14 @@ export interface McpPromptArgument {
   required?: boolean;
 }
 
+export interface McpPromptResult {
+  description?: string;
+  messages: Array<{
+    role: "user" | "assistant";
+    content: unknown;
+  }>;
+}
+
 export interface McpServerCapabilities {
   tools?: { listChanged?: boolean };
   resources?: { subscribe?: boolean; listChanged?: boolean };
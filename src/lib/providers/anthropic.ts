@@ -307,30 +307,6 @@ export const anthropicProvider: ProviderAdapter = {
     yield* parseAnthropicSSE(response.body);
   },
 
-  async validateKey(apiKey: string): Promise<boolean> {
-    try {
-      // Make a minimal request to validate the key
-      const response = await appFetch(`${ANTHROPIC_API_URL}/messages`, {
-        method: "POST",
-        headers: {
-          "Content-Type": "application/json",
-          "x-api-key": apiKey,
-          "anthropic-version": ANTHROPIC_VERSION,
-        },
-        body: JSON.stringify({
-          model: "claude-3-haiku-20240307",
-          messages: [{ role: "user", content: "Hi" }],
-          max_tokens: 1,
-        }),
-      });
-
-      // 200 = valid, 400 = valid key but bad request, 401/403 = invalid key
-      return response.ok || response.status === 400;
-    } catch {
-      return false;
-    }
-  },
-
   async getModels(): Promise<ProviderModel[]> {
     // Anthropic doesn't have a public models list endpoint
     return DEFAULT_MODELS;
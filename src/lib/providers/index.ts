@@ -139,20 +139,6 @@ export async function* streamProviderMessage(
   yield* provider.streamMessage(request, { token, isOAuth });
 }
 
-/**
- * Validate an API key for a provider.
- */
-export async function validateProviderKey(
-  providerId: ProviderId,
-  apiKey: string,
-): Promise<boolean> {
-  const provider = providers[providerId];
-  if (!provider) {
-    throw new Error(`Unknown provider: ${providerId}`);
-  }
-  return provider.validateKey(apiKey);
-}
-
 /**
  * Get available models for a provider.
  * For non-Seren providers, requires a valid API key.
@@ -172,17 +172,6 @@ export const openaiProvider: ProviderAdapter = {
     yield* parseOpenAISSE(response.body);
   },
 
-  async validateKey(apiKey: string): Promise<boolean> {
-    try {
-      const response = await appFetch(`${OPENAI_API_URL}/models`, {
-        headers: { Authorization: `Bearer ${apiKey}` },
-      });
-      return response.ok;
-    } catch {
-      return false;
-    }
-  },
-
   async getModels(apiKey: string): Promise<ProviderModel[]> {
     try {
       const response = await appFetch(`${OPENAI_API_URL}/models`, {
@@ -75,10 +75,6 @@ export const serenPrivateProvider: ProviderAdapter = {
     }
   },
 
-  async validateKey(_apiKey: string): Promise<boolean> {
-    return true;
-  },
-
   async getModels(_apiKey: string): Promise<ProviderModel[]> {
     return privateModelsService.listAvailable();
   },
@@ -437,12 +437,6 @@ export const serenProvider: ProviderAdapter = {
     }
   },
 
-  async validateKey(_apiKey: string): Promise<boolean> {
-    // Seren uses Seren auth token, not API key - always valid if logged in
-    const token = await getToken();
-    return token !== null;
-  },
-
   async getModels(_apiKey: string): Promise<ProviderModel[]> {
     // Try to fetch from Seren's models endpoint
     try {
@@ -234,6 +234,8 @@ export interface ToolResult {
   tool_call_id: string;
   content: string;
   is_error: boolean;
+  /** Image output (e.g. a screenshot) forwarded to vision-capable models. */
+  image?: { mime_type: string; base64: string };
 }
 
 /**
@@ -273,12 +275,6 @@ export interface ProviderAdapter {
     auth: string | AuthOptions,
   ): AsyncGenerator<string, void, unknown>;
 
-  /**
-   * Validate an API key by making a minimal test request.
-   * Returns true if the key is valid.
-   */
-  validateKey(apiKey: string): Promise<boolean>;
-
   /**
    * Get available models for this provider.
    * For some providers this is a static list, others fetch dynamically.
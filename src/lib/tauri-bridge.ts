@@ -278,6 +278,62 @@ export async function clearDefaultOrganizationId(): Promise<void> {
   cachedDefaultOrganizationIdPromise = null;
 }
 
+// ============================================================================
+// Typed Settings
+// ============================================================================
+
+/**
+ * Read a setting as the JSON value it was stored with.
+ * Returns null if the key is unset.
+ */
+export async function getSettingJson<T = unknown>(
+  store: string,
+  key: string,
+): Promise<T | null> {
+  const invoke = await getInvoke();
+  if (!invoke) return null;
+  return invoke<T | null>("get_setting_json", { store, key });
+}
+
+/**
+ * Store any JSON value as a setting, keeping its type.
+ */
+export async function setSettingJson(
+  store: string,
+  key: string,
+  value: unknown,
+): Promise<void> {
+  const invoke = await getInvoke();
+  if (!invoke) return;
+  await invoke("set_setting_json", { store, key, value });
+}
+
+/**
+ * Read a boolean setting. Values stored as the strings "true"/"false" are
+ * read as booleans; anything else that is not a boolean returns null.
+ */
+export async function getSettingBool(
+  store: string,
+  key: string,
+): Promise<boolean | null> {
+  const invoke = await getInvoke();
+  if (!invoke) return null;
+  return invoke<boolean | null>("get_setting_bool", { store, key });
+}
+
+/**
+ * Read a numeric setting. Numeric strings are parsed; anything else that is
+ * not a number returns null.
+ */
+export async function getSettingNumber(
+  store: string,
+  key: string,
+): Promise<number | null> {
+  const invoke = await getInvoke();
+  if (!invoke) return null;
+  return invoke<number | null>("get_setting_number", { store, key });
+}
+
 // ============================================================================
 // File System Operations
 // ============================================================================
@@ -320,6 +376,27 @@ export async function readFile(path: string): Promise<string> {
   throw new Error("File system operations require a local runtime");
 }
 
+/**
+ * File contents plus the hash `writeFile` can check via `expectedHash`.
+ */
+export interface HashedFileContent {
+  content: string;
+  hash: string;
+}
+
+/**
+ * Read a file along with its content hash, for a conflict-checked write.
+ */
+export async function readFileWithHash(
+  path: string,
+): Promise<HashedFileContent> {
+  const invoke = await getInvoke();
+  if (invoke) {
+    return await invoke<HashedFileContent>("read_file_with_hash", { path });
+  }
+  throw new Error("File system operations require a local runtime");
+}
+
 /**
  * Read a file as base64.
  */
@@ -335,14 +412,23 @@ export async function readFileBase64(path: string): Promise<string> {
 }
 
 /**
- * Write content to a file.
+ * Write content to a file. With `expectedHash` (from `readFileWithHash`),
+ * the write is rejected if the file changed since it was read.
  */
-export async function writeFile(path: string, content: string): Promise<void> {
+export async function writeFile(
+  path: string,
+  content: string,
+  options?: { expectedHash?: string },
+): Promise<void> {
+  const expectedHash = options?.expectedHash ?? null;
   const invoke = await getInvoke();
   if (invoke) {
-    await invoke("write_file", { path, content });
+    await invoke("write_file", { path, content, expectedHash });
     return;
   }
+  if (expectedHash) {
+    throw new Error("Conflict-checked writes require the desktop app");
+  }
   if (isBrowserLocalRuntime()) {
     await runtimeInvoke("write_file", { path, content });
     return;
@@ -378,21 +464,65 @@ export async function isDirectory(path: string): Promise<boolean> {
   throw new Error("File system operations require a local runtime");
 }
 
+export type CreateFileErrorKind =
+  | "AlreadyExists"
+  | "PermissionDenied"
+  | "Other";
+
+/**
+ * Typed failure from createFile so callers can distinguish an existing file
+ * from a permission problem.
+ */
+export class CreateFileError extends Error {
+  constructor(
+    readonly kind: CreateFileErrorKind,
+    message: string,
+  ) {
+    super(message);
+    this.name = "CreateFileError";
+  }
+}
+
+function toCreateFileError(error: unknown): CreateFileError {
+  if (error && typeof error === "object" && "kind" in error) {
+    const { kind, message } = error as { kind: string; message?: string };
+    if (kind === "AlreadyExists" || kind === "PermissionDenied") {
+      return new CreateFileError(kind, message ?? kind);
+    }
+    return new CreateFileError("Other", message ?? String(error));
+  }
+  const message = error instanceof Error ? error.message : String(error);
+  if (/EEXIST|already exists/i.test(message)) {
+    return new CreateFileError("AlreadyExists", message);
+  }
+  if (/EACCES|EPERM|permission denied/i.test(message)) {
+    return new CreateFileError("PermissionDenied", message);
+  }
+  return new CreateFileError("Other", message);
+}
+
 /**
- * Create a new file with optional content.
+ * Create a new file with optional content. With `exclusive`, rejects with an
+ * `AlreadyExists` CreateFileError instead of overwriting an existing file.
  */
 export async function createFile(
   path: string,
   content?: string,
+  options: { exclusive?: boolean } = {},
 ): Promise<void> {
+  const args = { path, content, exclusive: options.exclusive ?? false };
   const invoke = await getInvoke();
-  if (invoke) {
-    await invoke("create_file", { path, content });
-    return;
-  }
-  if (isBrowserLocalRuntime()) {
-    await runtimeInvoke("create_file", { path, content });
-    return;
+  try {
+    if (invoke) {
+      await invoke("create_file", args);
+      return;
+    }
+    if (isBrowserLocalRuntime()) {
+      await runtimeInvoke("create_file", args);
+      return;
+    }
+  } catch (error) {
+    throw toCreateFileError(error);
   }
   throw new Error("File system operations require a local runtime");
 }
@@ -429,6 +559,30 @@ export async function deletePath(path: string): Promise<void> {
   throw new Error("File system operations require a local runtime");
 }
 
+export interface TrashResult {
+  /** False when no trash was available and the path was deleted for good. */
+  trashed: boolean;
+  message: string | null;
+}
+
+/**
+ * Move a file or directory to the OS trash.
+ */
+export async function trashPath(path: string): Promise<TrashResult> {
+  const invoke = await getInvoke();
+  if (invoke) {
+    return await invoke<TrashResult>("trash_path", { path });
+  }
+  if (isBrowserLocalRuntime()) {
+    await runtimeInvoke("delete_path", { path });
+    return {
+      trashed: false,
+      message: "The browser runtime has no trash; deleted permanently",
+    };
+  }
+  throw new Error("File system operations require a local runtime");
+}
+
 /**
  * Rename/move a file or directory.
  */
@@ -480,20 +634,90 @@ export async function openPathWithDefaultApp(path: string): Promise<void> {
   throw new Error("File system operations require a local runtime");
 }
 
+export interface FileChangedEvent {
+  path: string;
+  kind: "modified" | "created" | "deleted";
+  /** New text, or null for deletions and large or binary files. */
+  content: string | null;
+}
+
+/**
+ * Watch a single existing file. Rapid writes and editor rename-swaps are
+ * coalesced into one event. Several watchers of one file share a backend
+ * watch, which stops when the last cleanup runs.
+ * @returns Cleanup function that stops the watch
+ */
+export async function watchFile(
+  path: string,
+  onChange: (event: FileChangedEvent) => void,
+): Promise<() => Promise<void>> {
+  const invoke = await getInvoke();
+  if (!invoke) {
+    throw new Error("File watching requires the desktop app");
+  }
+  const { listen } = await import("@tauri-apps/api/event");
+  const watchedPath = await invoke<string>("watch_file", { path });
+  const unlisten = await listen<FileChangedEvent>(
+    "files://changed",
+    (event) => {
+      if (event.payload.path === watchedPath) {
+        onChange(event.payload);
+      }
+    },
+  );
+  // Watches are reference-counted, so release this one only once.
+  let released = false;
+  return async () => {
+    if (released) return;
+    released = true;
+    unlisten();
+    await invoke("unwatch_file", { path: watchedPath });
+  };
+}
+
 // ============================================================================
 // Provider API Key Management
 // ============================================================================
 
+/** What a provider said about an API key checked by `checkProviderKey`. */
+export interface ProviderKeyValidation {
+  status: "valid" | "invalid" | "rate_limited" | "provider_error" | "network";
+  detail?: string;
+}
+
 /**
- * Store an API key for a provider securely.
+ * Check an Anthropic or OpenAI API key with a cheap authenticated request.
+ */
+export async function checkProviderKey(
+  provider: string,
+  apiKey: string,
+): Promise<ProviderKeyValidation> {
+  const invoke = await getInvoke();
+  if (!invoke) {
+    throw new Error("Provider key validation requires the desktop runtime");
+  }
+  return await invoke<ProviderKeyValidation>("validate_provider_key", {
+    provider,
+    apiKey,
+  });
+}
+
+/**
+ * Store an API key for a provider securely. With `validate`, a key the
+ * provider rejects is refused instead of stored.
  */
 export async function storeProviderKey(
   provider: string,
   apiKey: string,
+  options: { validate?: boolean } = {},
 ): Promise<void> {
   const invoke = await getInvoke();
   if (invoke) {
-    await invoke("store_provider_key", { provider, apiKey });
+    await invoke("store_provider_key", {
+      provider,
+      apiKey,
+      validate: options.validate ?? false,
+    });
   } else {
     // Browser fallback for testing
     devStorage.setItem(`provider_key_${provider}`, apiKey);
@@ -595,6 +819,36 @@ export async function clearOAuthCredentials(provider: string): Promise<void> {
   }
 }
 
+/**
+ * Result of revoking a provider's OAuth credentials.
+ */
+export interface OAuthRevocation {
+  /** Whether the provider confirmed the token was revoked. */
+  revoked: boolean;
+  message: string;
+}
+
+/**
+ * Revoke a provider's OAuth token with the provider, then clear it locally.
+ * Local credentials are removed even when remote revocation is not possible.
+ */
+export async function revokeOAuthCredentials(
+  provider: string,
+): Promise<OAuthRevocation> {
+  const invoke = await getInvoke();
+  if (invoke) {
+    return await invoke<OAuthRevocation>("revoke_oauth_credentials", {
+      provider,
+    });
+  }
+  // Browser fallback for testing
+  devStorage.removeItem(`oauth_creds_${provider}`);
+  return {
+    revoked: false,
+    message: `${provider} credentials were only removed locally`,
+  };
+}
+
 /**
  * Get a list of providers that have OAuth credentials configured.
  */
@@ -614,6 +868,28 @@ export async function getOAuthProviders(): Promise<string[]> {
   return providers;
 }
 
+/**
+ * The public half of a PKCE pair generated in Rust.
+ */
+export interface PkceChallenge {
+  challenge: string;
+  /** Always "S256". */
+  method: string;
+}
+
+/**
+ * Register a PKCE pair for the browser flow identified by `state`.
+ * Only the challenge is returned; the verifier stays in Rust and comes back
+ * on the matching `start_oauth_browser_flow` callback result.
+ */
+export async function beginOAuthPkce(state: string): Promise<PkceChallenge> {
+  const invoke = await getInvoke();
+  if (!invoke) {
+    throw new Error("PKCE requires the Tauri runtime");
+  }
+  return await invoke<PkceChallenge>("begin_oauth_pkce", { state });
+}
+
 /**
  * Listen for OAuth callback events from deep links.
  * @param callback - Function to call with the callback URL
@@ -659,6 +935,51 @@ export async function listenForInterviewLaunch(
   return unlisten;
 }
 
+// ============================================================================
+// Web Requests
+// ============================================================================
+
+/**
+ * A JSON API response parsed in Rust.
+ */
+export interface WebFetchJsonResult {
+  status: number;
+  /** Final URL after redirects. */
+  url: string;
+  json: unknown;
+}
+
+export interface WebFetchJsonOptions {
+  /** "GET" (default), "POST", "PUT", or "DELETE". */
+  method?: string;
+  headers?: Record<string, string>;
+  /** JSON request body; not allowed with GET. */
+  body?: unknown;
+  /** Request timeout in milliseconds (default: 30000). */
+  timeoutMs?: number;
+}
+
+/**
+ * Call a JSON API from Rust and return its parsed response.
+ * Non-JSON and oversized responses are rejected.
+ */
+export async function webFetchJson(
+  url: string,
+  options: WebFetchJsonOptions = {},
+): Promise<WebFetchJsonResult> {
+  const invoke = await getInvoke();
+  if (!invoke) {
+    throw new Error("web_fetch_json requires the Tauri runtime");
+  }
+  return await invoke<WebFetchJsonResult>("web_fetch_json", {
+    url,
+    method: options.method,
+    headers: options.headers,
+    body: options.body,
+    timeoutMs: options.timeoutMs,
+  });
+}
+
 // ============================================================================
 // Chat Conversation Management
 // ============================================================================
@@ -730,6 +1051,9 @@ export interface UnifiedConversationRow {
   project_id: string | null;
   privileged: boolean;
   counsel_direction: string | null;
+  /** Epoch ms when moved to the trash; only set when listed with `includeTrashed`. */
+  trashed_at?: number | null;
+  tags?: string[];
 }
 
 /**
@@ -781,11 +1105,14 @@ export async function createConversation(
  * Pass `kind: undefined` to fetch both kinds; pass `"chat"` or
  * `"agent"` to filter. The `limit` is shared across kinds and is
  * unbounded when omitted (mirrors the prior unlimited chat read).
+ * `filterTags` keeps only conversations carrying at least one of the tags.
  */
 export async function listConversations(options?: {
   kind?: "chat" | "agent";
   projectRoot?: string;
   limit?: number;
+  includeTrashed?: boolean;
+  filterTags?: string[];
 }): Promise<UnifiedConversationRow[]> {
   const invoke = await getInvoke();
   if (!invoke) {
@@ -795,9 +1122,26 @@ export async function listConversations(options?: {
     kind: options?.kind ?? null,
     projectRoot: options?.projectRoot ?? null,
     limit: options?.limit ?? null,
+    includeTrashed: options?.includeTrashed ?? false,
+    filterTags: options?.filterTags ?? null,
   });
 }
 
+/**
+ * Replace a conversation's tags. Returns the stored (trimmed, deduplicated)
+ * tags.
+ */
+export async function setConversationTags(
+  id: string,
+  tags: string[],
+): Promise<string[]> {
+  const invoke = await getInvoke();
+  if (!invoke) {
+    throw new Error("Conversation operations require Tauri runtime");
+  }
+  return await invoke<string[]>("set_conversation_tags", { id, tags });
+}
+
 /**
  * Get a single conversation by ID.
  */
@@ -1077,7 +1421,8 @@ export async function archiveConversation(id: string): Promise<void> {
 }
 
 /**
- * Permanently delete a conversation and its messages.
+ * Move a conversation to the trash. Use `restoreConversation` to bring it
+ * back; `purgeTrashed` deletes it permanently.
  */
 export async function deleteConversation(id: string): Promise<void> {
   const invoke = await getInvoke();
@@ -1087,6 +1432,29 @@ export async function deleteConversation(id: string): Promise<void> {
   await invoke("delete_conversation", { id });
 }
 
+/**
+ * Restore a trashed conversation.
+ */
+export async function restoreConversation(id: string): Promise<void> {
+  const invoke = await getInvoke();
+  if (!invoke) {
+    throw new Error("Conversation operations require Tauri runtime");
+  }
+  await invoke("restore_conversation", { id });
+}
+
+/**
+ * Permanently delete conversations trashed at least `olderThanDays` days ago
+ * (0 empties the trash). Returns the number of conversations removed.
+ */
+export async function purgeTrashed(olderThanDays: number): Promise<number> {
+  const invoke = await getInvoke();
+  if (!invoke) {
+    throw new Error("Conversation operations require Tauri runtime");
+  }
+  return await invoke<number>("purge_trashed", { olderThanDays });
+}
+
 /**
  * Save a message to a conversation.
  */
@@ -1133,6 +1501,85 @@ export async function getMessages(
   });
 }
 
+export type MessageOrder = "newest_first" | "oldest_first";
+
+export interface MessagePage {
+  messages: StoredMessage[];
+  next_cursor: string | null;
+}
+
+/**
+ * Get one page of messages for a conversation. Pass the previous page's
+ * `next_cursor` as `cursor` to keep loading in the same order.
+ */
+export async function getMessagesPage(
+  conversationId: string,
+  limit: number,
+  cursor?: string | null,
+  order: MessageOrder = "newest_first",
+): Promise<MessagePage> {
+  const invoke = await getInvoke();
+  if (!invoke) {
+    throw new Error("Message operations require Tauri runtime");
+  }
+  return await invoke<MessagePage>("get_messages_page", {
+    conversationId,
+    cursor: cursor ?? null,
+    limit,
+    order,
+  });
+}
+
+/**
+ * Replace the text of an earlier message. Returns the conversation's messages
+ * in chronological order.
+ */
+export async function editMessage(
+  messageId: string,
+  newContent: string,
+): Promise<StoredMessage[]> {
+  const invoke = await getInvoke();
+  if (!invoke) {
+    throw new Error("Message operations require Tauri runtime");
+  }
+  return await invoke<StoredMessage[]>("edit_message", {
+    messageId,
+    newContent,
+  });
+}
+
+/**
+ * Delete every message after `messageId` so the conversation can be re-run
+ * from that point. Returns the remaining messages in chronological order.
+ */
+export async function truncateAfter(
+  messageId: string,
+): Promise<StoredMessage[]> {
+  const invoke = await getInvoke();
+  if (!invoke) {
+    throw new Error("Message operations require Tauri runtime");
+  }
+  return await invoke<StoredMessage[]>("truncate_after", { messageId });
+}
+
+/**
+ * Serialize a whole conversation to Markdown (for reading) or JSON (for
+ * re-import). Credentials are masked in both.
+ */
+export async function exportConversation(
+  conversationId: string,
+  format: "markdown" | "json",
+): Promise<string> {
+  const invoke = await getInvoke();
+  if (!invoke) {
+    throw new Error("Message operations require Tauri runtime");
+  }
+  return await invoke<string>("export_conversation", {
+    conversationId,
+    format,
+  });
+}
+
 /**
  * Clear all messages in a conversation.
  */
@@ -1390,3 +1837,99 @@ export async function switchThreadProvider(
     expectedUpdatedAt: expectedUpdatedAt ?? null,
   });
 }
+
+/** Result envelope returned by the Rust wallet commands. */
+interface WalletCommandResult<T> {
+  success: boolean;
+  data?: T;
+  error?: string;
+}
+
+/** Public details of the locally stored wallet key. Never key material. */
+export interface WalletPublicInfo {
+  address: string | null;
+  chainIdsSupported: number[];
+  hasKey: boolean;
+}
+
+/**
+ * Get the stored wallet address, supported chains and whether a key is
+ * stored, in one read-only call.
+ */
+export async function getWalletPublicInfo(): Promise<WalletPublicInfo | null> {
+  const invoke = await getInvoke();
+  if (!invoke) return null;
+  const result = await invoke<WalletCommandResult<WalletPublicInfo>>(
+    "get_wallet_public_info",
+  );
+  return result.success ? (result.data ?? null) : null;
+}
+
+/**
+ * Whether a private key is stored, without attempting to sign.
+ */
+export async function walletHasKey(): Promise<boolean> {
+  const invoke = await getInvoke();
+  if (!invoke) return false;
+  const result = await invoke<WalletCommandResult<boolean>>("wallet_has_key");
+  return result.success && result.data === true;
+}
+
+/** One signed x402 payment from the local spend log. */
+export interface PaymentRecord {
+  id: number;
+  createdAt: number;
+  amount: string;
+  asset: string;
+  network: string;
+  recipient: string;
+  payer: string;
+  resource: string | null;
+  x402Version: number;
+  payloadHash: string;
+}
+
+/** A payment signed by the connected wallet, for the spend log. */
+export interface X402PaymentRecordInput {
+  /** The 402 response body the payment answers */
+  requirementsJson: string;
+  /** The signed payment header sent with the retry */
+  headerValue: string;
+}
+
+/**
+ * Append a payment signed in the frontend to the local spend log.
+ * The backend checks the header's signature against the requirements and
+ * builds the row from it; only a hash of the header is stored.
+ */
+export async function recordX402Payment(
+  payment: X402PaymentRecordInput,
+): Promise<void> {
+  const invoke = await getInvoke();
+  if (!invoke) return;
+  const result = await invoke<WalletCommandResult<null>>(
+    "record_x402_payment",
+    { request: payment },
+  );
+  if (!result.success) {
+    throw new Error(result.error ?? "Failed to record payment");
+  }
+}
+
+/**
+ * Read signed x402 payments, newest first.
+ */
+export async function getPaymentHistory(
+  limit?: number,
+): Promise<PaymentRecord[]> {
+  const invoke = await getInvoke();
+  if (!invoke) return [];
+  const result = await invoke<WalletCommandResult<PaymentRecord[]>>(
+    "get_payment_history",
+    { limit: limit ?? null },
+  );
+  if (!result.success) {
+    throw new Error(result.error ?? "Failed to read payment history");
+  }
+  return result.data ?? [];
+}
@@ -193,7 +193,8 @@ export const FILE_TOOLS: ToolDefinition[] = [
     function: {
       name: "read_file",
       description:
-        "Read the contents of a file at the given path. Returns the file contents as text.",
+        "Read the contents of a file at the given path. Returns the file contents as text, " +
+        "followed by the file's hash to pass to write_file as expected_hash.",
       parameters: {
         type: "object",
         properties: {
@@ -253,7 +254,9 @@ export const FILE_TOOLS: ToolDefinition[] = [
     function: {
       name: "write_file",
       description:
-        "Write content to a file, creating it if it doesn't exist or overwriting if it does. Use with caution.",
+        "Write content to a file, creating it if it doesn't exist or overwriting if it does. Use with caution. " +
+        "When editing a file you read, pass the hash read_file returned as expected_hash so the write fails " +
+        "instead of overwriting changes made since the read.",
       parameters: {
         type: "object",
         properties: {
@@ -265,6 +268,11 @@ export const FILE_TOOLS: ToolDefinition[] = [
             type: "string",
             description: "The content to write to the file",
           },
+          expected_hash: {
+            type: "string",
+            description:
+              "Hash from read_file. The write is refused if the file no longer matches it",
+          },
         },
         required: ["path", "content"],
       },
@@ -332,6 +340,41 @@ export const FILE_TOOLS: ToolDefinition[] = [
             type: "number",
             description: "Request timeout in milliseconds (default: 30000)",
           },
+          headers: {
+            type: "object",
+            additionalProperties: { type: "string" },
+            description:
+              "Optional extra request headers, e.g. Accept or Accept-Language",
+          },
+          respect_robots: {
+            type: "boolean",
+            description:
+              "When true, check the site's robots.txt first and refuse disallowed paths (default: false)",
+          },
+          mode: {
+            type: "string",
+            enum: ["raw", "text", "readability"],
+            description:
+              "How HTML is reduced: 'raw' converts the page to markdown (default), " +
+              "'text' strips all tags, 'readability' returns only the main article " +
+              "with its title. Prefer 'readability' for articles and docs to save context.",
+          },
+          max_bytes: {
+            type: "number",
+            description:
+              "Stop reading the response after this many bytes (default: 10485760)",
+          },
+          allowed_content_types: {
+            type: "array",
+            items: { type: "string" },
+            description:
+              "Reject responses whose media type is not listed, e.g. ['text/*', 'application/json']",
+          },
+          max_redirects: {
+            type: "number",
+            description:
+              "Fail if the URL redirects more than this many times (default: 10)",
+          },
         },
         required: ["url"],
       },
@@ -368,6 +411,12 @@ export const FILE_TOOLS: ToolDefinition[] = [
               "Setting true does not grant anything: SEREN_API_KEY and API_KEY are injected only when the " +
               "command targets an installed Seren skill path, regardless of this flag.",
           },
+          interactive: {
+            type: "boolean",
+            description:
+              "Set true only when the command will prompt for input (y/n confirmations, passwords) " +
+              "that the user answers while it runs. Leave unset otherwise so commands that read stdin do not wait on it.",
+          },
         },
         required: ["command"],
       },
@@ -847,18 +847,40 @@ export async function executeTool(
 
         const url = args.url as string;
         const timeoutMs = args.timeout_ms as number | undefined;
+        const headers = args.headers as Record<string, string> | undefined;
+        const respectRobots = args.respect_robots as boolean | undefined;
+        const mode = args.mode as "raw" | "text" | "readability" | undefined;
+        const maxBytes = args.max_bytes as number | undefined;
+        const allowedContentTypes = args.allowed_content_types as
+          | string[]
+          | undefined;
+        const maxRedirects = args.max_redirects as number | undefined;
         const response = await invoke<{
           content: string;
           content_type: string;
           url: string;
           status: number;
           truncated: boolean;
-        }>("web_fetch", { url, timeoutMs });
+          redirects: string[];
+        }>("web_fetch", {
+          url,
+          timeoutMs,
+          headers,
+          respectRobots,
+          mode,
+          maxBytes,
+          allowedContentTypes,
+          maxRedirects,
+        });
 
+        // Surface the redirect chain so shorteners and auth walls are visible
+        const redirectNote = response.redirects?.length
+          ? `Redirected: ${[url, ...response.redirects].join(" -> ")}\n`
+          : "";
         if (response.status >= 400) {
-          result = `Error: HTTP ${response.status} for ${response.url}`;
+          result = `${redirectNote}Error: HTTP ${response.status} for ${response.url}`;
         } else {
-          result = response.content;
+          result = `${redirectNote}${response.content}`;
         }
         break;
       }
@@ -874,10 +896,14 @@ export async function executeTool(
           timeoutSecs: number;
           injectSerenCredentials?: boolean;
           toolCallId: string;
+          interactive?: boolean;
         } = { command, timeoutSecs, toolCallId: toolCall.id };
         if (typeof args.inject_seren_credentials === "boolean") {
           invokeArgs.injectSerenCredentials = args.inject_seren_credentials;
         }
+        if (args.interactive === true) {
+          invokeArgs.interactive = true;
+        }
 
         const auth = await authorizeSubprocess(
           "shell",
@@ -1047,13 +1073,16 @@ async function executeMcpTool(
       arguments: args,
     });
 
-    // Convert MCP result content to string
+    // Convert MCP result content to string; the first image rides along
+    // so vision models can see it.
     let content = "";
+    let image: ToolResult["image"];
     for (const item of result.content) {
       if (item.type === "text") {
         content += item.text;
       } else if (item.type === "image") {
         content += `[Image: ${item.mimeType}]`;
+        image ??= { mime_type: item.mimeType, base64: item.data };
       } else if (item.type === "resource") {
         content += item.resource.text || `[Resource: ${item.resource.uri}]`;
       }
@@ -1063,6 +1092,7 @@ async function executeMcpTool(
       tool_call_id: toolCallId,
       content: truncateToolResult(content || "Tool executed successfully"),
       is_error: result.isError ?? false,
+      ...(image ? { image } : {}),
     };
   } catch (error) {
     const message = error instanceof Error ? error.message : String(error);
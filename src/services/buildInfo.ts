@@ -14,6 +14,8 @@ export interface BuildInfo {
   webview: string;
   rust_version: string;
   os: string;
+  features: string[];
+  sidecars: Record<string, string | null>;
 }
 
 /**
@@ -31,6 +31,17 @@ export interface ConversationSearchFilters {
   afterMs?: number | null;
   beforeMs?: number | null;
   includeArchived?: boolean;
+  conversationId?: string | null;
+  role?: string | null;
+}
+
+export interface MessageSearchHit {
+  messageId: string;
+  conversationId: string;
+  role: string;
+  title: string | null;
+  snippet: string;
+  timestamp: number;
 }
 
 export interface ConversationSearchOptions {
@@ -58,6 +69,8 @@ function normalizeFilters(
     afterMs: filters?.afterMs ?? null,
     beforeMs: filters?.beforeMs ?? null,
     includeArchived: filters?.includeArchived ?? false,
+    conversationId: filters?.conversationId ?? null,
+    role: filters?.role ?? null,
   };
 }
 
@@ -173,6 +186,23 @@ export async function searchConversations(
   };
 }
 
+/**
+ * Exact search collapsed to one hit per message, with the matched terms
+ * bracketed in `snippet`.
+ */
+export async function searchMessages(
+  query: string,
+  options: ConversationSearchOptions = {},
+): Promise<MessageSearchHit[]> {
+  const trimmed = query.trim();
+  if (!trimmed) return [];
+  return invoke<MessageSearchHit[]>("search_messages", {
+    query: trimmed,
+    filters: normalizeFilters(options.filters),
+    limit: options.limit ?? 20,
+  });
+}
+
 export async function backfillConversationFts(): Promise<number> {
   return invoke<number>("backfill_conversation_fts").catch(() => 0);
 }
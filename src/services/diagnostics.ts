@@ -0,0 +1,57 @@
+// ABOUTME: Service wrapper for the native diagnostics Tauri command.
+// ABOUTME: Returns a structured health report support can paste into a ticket.
+
+import { invoke } from "@tauri-apps/api/core";
+import { isTauriRuntime } from "@/lib/tauri-bridge";
+import type { BuildInfo } from "@/services/buildInfo";
+
+export interface DiagnosticCheck {
+  name: string;
+  ok: boolean;
+  detail: string;
+}
+
+export interface DiagnosticsReport {
+  ok: boolean;
+  build: BuildInfo;
+  checks: DiagnosticCheck[];
+}
+
+export interface RuntimeBinaryCheck {
+  component: string;
+  path: string | null;
+  version: string | null;
+  arch: string | null;
+  ok: boolean;
+  error: string | null;
+}
+
+export interface RuntimeVerification {
+  ok: boolean;
+  platform: string;
+  checks: RuntimeBinaryCheck[];
+}
+
+/**
+ * Run the native health checks (embedded runtime, agent CLIs, Gateway).
+ * Returns null in the browser fallback runtime.
+ */
+export async function runDiagnostics(): Promise<DiagnosticsReport | null> {
+  if (!isTauriRuntime()) {
+    return null;
+  }
+  return await invoke<DiagnosticsReport>("diagnostics");
+}
+
+/**
+ * Execute the bundled node/git and confirm they run on this machine's arch.
+ * Returns null in the browser fallback runtime.
+ */
+export async function verifyEmbeddedRuntime(): Promise<
+  RuntimeVerification | null
+> {
+  if (!isTauriRuntime()) {
+    return null;
+  }
+  return await invoke<RuntimeVerification>("verify_embedded_runtime");
+}
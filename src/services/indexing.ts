@@ -30,10 +30,22 @@ export interface CodeChunk {
   indexed_at: number;
 }
 
+/** The lines of a result chunk around its best match */
+export interface SearchSnippet {
+  path: string;
+  start_line: number;
+  end_line: number;
+  snippet: string;
+  /** `[start, end)` offsets into `snippet` marking query terms */
+  highlight_ranges: [number, number][];
+}
+
 /** Search result with similarity distance */
 export interface SearchResult {
   chunk: CodeChunk;
   distance: number;
+  /** Present when the search was made with query text */
+  snippet?: SearchSnippet;
 }
 
 /** Discovered file from backend */
@@ -45,6 +57,17 @@ export interface DiscoveredFile {
   hash: string;
 }
 
+/** Indexing plan from `estimate_indexing` */
+export interface IndexingEstimate {
+  total_files: number;
+  total_bytes: number;
+  estimated_chunks: number;
+  estimated_tokens: number;
+  estimated_batches: number;
+  estimated_seconds: number;
+  estimated_cost_usd: number;
+}
+
 /** File chunk from backend */
 export interface FileChunk {
   start_line: number;
@@ -121,11 +144,12 @@ export async function searchCodebase(
   // Generate embedding for the query
   const queryEmbedding = await embedText(query);
 
-  // Search the vector store
+  // Search the vector store, passing the text so results carry snippets
   return invoke<SearchResult[]>("search_codebase", {
     projectPath,
     queryEmbedding,
     limit,
+    query,
   });
 }
 
@@ -169,48 +193,204 @@ export async function deleteFileIndex(
   return invoke<number>("delete_file_index", { projectPath, filePath });
 }
 
+/**
+ * Flush the project's index to disk (call before the app closes).
+ */
+export async function flushIndex(projectPath: string): Promise<void> {
+  return invoke<void>("flush_index", { projectPath });
+}
+
+/** Chunks sent per embedding request */
+const EMBEDDING_BATCH_SIZE = 20;
+
+/** Embedding requests allowed in flight at once by default */
+export const DEFAULT_EMBEDDING_CONCURRENCY = 4;
+
+/** First delay before retrying a failed batch chunk by chunk */
+const DEFAULT_RETRY_DELAY_MS = 100;
+
+/** Ceiling for the retry delay, which doubles while retries keep failing */
+const MAX_RETRY_DELAY_MS = 2000;
+
+/** Outcome for one input chunk, in input order */
+export interface IndexChunkResult {
+  /** Position of the chunk in the input array */
+  index: number;
+  /** Row id in the vector store, or null when the chunk failed */
+  id: number | null;
+  error?: string;
+}
+
+export interface IndexChunksOptions {
+  /** Maximum concurrent embedding requests */
+  concurrency?: number;
+  /** Called as chunks are embedded and stored */
+  onProgress?: (processed: number, total: number) => void;
+  /** First backoff delay when a failed batch is retried chunk by chunk */
+  retryDelayMs?: number;
+}
+
+function errorMessage(error: unknown): string {
+  return error instanceof Error ? error.message : String(error);
+}
+
+/**
+ * Embed one batch. If the batch request fails, each chunk is retried alone so
+ * a single bad chunk only fails itself. Retries run one at a time with
+ * backoff, so a failing batch never adds requests beyond the caller's pool.
+ */
+async function embedBatch(
+  texts: string[],
+  retryDelayMs: number,
+): Promise<({ embedding: number[] } | { error: string })[]> {
+  try {
+    const response = await embedTexts(texts);
+    return texts.map((_, j) => {
+      const embedding = response.data[j]?.embedding;
+      if (!embedding || embedding.length !== EMBEDDING_DIM) {
+        return {
+          error: `Embedding dimension mismatch: expected ${EMBEDDING_DIM}, got ${embedding?.length ?? 0}`,
+        };
+      }
+      return { embedding };
+    });
+  } catch (batchError) {
+    if (texts.length === 1) {
+      return [{ error: errorMessage(batchError) }];
+    }
+    const results: ({ embedding: number[] } | { error: string })[] = [];
+    let delay = retryDelayMs;
+    for (const text of texts) {
+      await new Promise((resolve) => setTimeout(resolve, delay));
+      const [result] = await embedBatch([text], retryDelayMs);
+      results.push(result);
+      delay =
+        "error" in result
+          ? Math.min(delay * 2, MAX_RETRY_DELAY_MS)
+          : retryDelayMs;
+    }
+    return results;
+  }
+}
+
 /**
  * Index a batch of code chunks.
- * Generates embeddings via the embedding publisher and stores in local vector db.
+ * Generates embeddings via the embedding publisher with bounded concurrency
+ * and stores them in the local vector db in input order. A failing chunk does
+ * not abort the rest; every chunk gets an entry in the returned list.
+ *
+ * A file with a chunk that failed is never left looking current: if a chunk
+ * fails to embed, the file's other chunks are stored without its hash, and
+ * if a store fails, the file's stored chunks are deleted. Either way
+ * `file_needs_reindex` reports the file as stale and the next run retries it.
  */
 export async function indexChunks(
   projectPath: string,
   chunks: Omit<ChunkInput, "embedding">[],
-  onProgress?: (processed: number, total: number) => void,
-): Promise<number[]> {
+  options: IndexChunksOptions = {},
+): Promise<IndexChunkResult[]> {
   if (chunks.length === 0) return [];
 
-  // Batch size for embedding API calls
-  const BATCH_SIZE = 20;
-  const allIds: number[] = [];
-
-  for (let i = 0; i < chunks.length; i += BATCH_SIZE) {
-    const batch = chunks.slice(i, i + BATCH_SIZE);
-    const texts = batch.map((c) => c.content);
+  const concurrency = Math.max(
+    1,
+    Math.floor(options.concurrency ?? DEFAULT_EMBEDDING_CONCURRENCY),
+  );
+  const results: IndexChunkResult[] = chunks.map((_, index) => ({
+    index,
+    id: null,
+  }));
+  const batchStarts: number[] = [];
+  for (let i = 0; i < chunks.length; i += EMBEDDING_BATCH_SIZE) {
+    batchStarts.push(i);
+  }
 
-    // Generate embeddings for the batch
-    const embeddingResponse = await embedTexts(texts);
+  // Embed batches with at most `concurrency` requests in flight
+  const embedded: ({ embedding: number[] } | { error: string })[][] = [];
+  let processed = 0;
+  let next = 0;
+  const worker = async () => {
+    while (next < batchStarts.length) {
+      const b = next++;
+      const batch = chunks.slice(
+        batchStarts[b],
+        batchStarts[b] + EMBEDDING_BATCH_SIZE,
+      );
+      embedded[b] = await embedBatch(
+        batch.map((c) => c.content),
+        options.retryDelayMs ?? DEFAULT_RETRY_DELAY_MS,
+      );
+      processed += batch.length;
+      options.onProgress?.(processed, chunks.length);
+    }
+  };
+  const workers = Math.min(concurrency, batchStarts.length);
+  await Promise.all(Array.from({ length: workers }, worker));
 
-    // Combine chunks with their embeddings
-    const chunksWithEmbeddings: ChunkInput[] = batch.map((chunk, j) => ({
-      ...chunk,
-      embedding: embeddingResponse.data[j].embedding,
-    }));
+  const incompleteFiles = new Set<string>();
+  embedded.forEach((batch, b) => {
+    batch.forEach((outcome, j) => {
+      if ("error" in outcome) {
+        incompleteFiles.add(chunks[batchStarts[b] + j].file_path);
+      }
+    });
+  });
 
-    // Store in vector database
-    const ids = await invoke<number[]>("index_chunks", {
-      projectPath,
-      chunks: chunksWithEmbeddings,
+  // Store in input order so row ids follow chunk order
+  const storeFailedFiles = new Set<string>();
+  for (let b = 0; b < batchStarts.length; b++) {
+    const ready: ChunkInput[] = [];
+    const readyIndexes: number[] = [];
+    embedded[b].forEach((outcome, j) => {
+      const index = batchStarts[b] + j;
+      if ("error" in outcome) {
+        results[index].error = outcome.error;
+      } else {
+        const chunk = chunks[index];
+        ready.push({
+          ...chunk,
+          file_hash: incompleteFiles.has(chunk.file_path)
+            ? ""
+            : chunk.file_hash,
+          embedding: outcome.embedding,
+        });
+        readyIndexes.push(index);
+      }
     });
+    if (ready.length === 0) continue;
 
-    allIds.push(...ids);
+    try {
+      const ids = await invoke<number[]>("index_chunks", {
+        projectPath,
+        chunks: ready,
+      });
+      readyIndexes.forEach((index, k) => {
+        results[index].id = ids[k] ?? null;
+      });
+    } catch (storeError) {
+      for (const index of readyIndexes) {
+        results[index].error = errorMessage(storeError);
+        storeFailedFiles.add(chunks[index].file_path);
+      }
+    }
+  }
 
-    if (onProgress) {
-      onProgress(Math.min(i + BATCH_SIZE, chunks.length), chunks.length);
+  // Chunks already stored for these files carry the current hash
+  for (const filePath of storeFailedFiles) {
+    if (incompleteFiles.has(filePath)) continue;
+    try {
+      await invoke("delete_file_index", { projectPath, filePath });
+      for (const result of results) {
+        if (result.id !== null && chunks[result.index].file_path === filePath) {
+          result.id = null;
+          result.error = "Removed because another chunk of the file failed";
+        }
+      }
+    } catch (error) {
+      console.warn(`[Indexing] Failed to clear ${filePath}:`, error);
     }
   }
 
-  return allIds;
+  return results;
 }
 
 /**
@@ -509,10 +509,17 @@ export function getCurrentOAuthState(): OAuthState | null {
 }
 
 /**
- * Clear current OAuth state (e.g., on cancel).
+ * Clear current OAuth state (e.g., on cancel). A browser flow still waiting
+ * for its callback is torn down so its loopback port is released.
  */
 export function clearOAuthState(): void {
+  const pending = currentOAuthState;
   currentOAuthState = null;
+  if (pending) {
+    invoke("cancel_oauth_flow", { state: pending.state }).catch((error) => {
+      console.warn("[MCP OAuth] Failed to cancel browser flow:", error);
+    });
+  }
 }
 
 /**
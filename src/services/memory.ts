@@ -3,6 +3,7 @@
 
 import { invoke } from "@tauri-apps/api/core";
 import { authStore } from "@/stores/auth.store";
+import { fileTreeState } from "@/stores/fileTree";
 import { privacyStore } from "@/stores/privacy.store";
 import { projectStore } from "@/stores/project.store";
 import { settingsStore } from "@/stores/settings.store";
@@ -101,6 +102,11 @@ export interface RememberMemoryOptions {
   sessionId?: string;
   skipConflictCheck?: boolean;
   skipEnrichment?: boolean;
+  /**
+   * Project path or conversation id; `null` stores it globally. Defaults to
+   * the open project, except for user-level types such as preferences.
+   */
+  scope?: string | null;
 }
 
 export interface AssistantMemoryContext {
@@ -140,6 +146,30 @@ function getProjectId(explicit?: string | null): string | null {
   return explicit ?? projectStore.activeProject?.id ?? null;
 }
 
+/**
+ * Memory scope for the open project. Recall and bootstrap see this scope plus
+ * the global one, so one project's memories stay out of another's context.
+ */
+function getMemoryScope(explicit?: string | null): string | null {
+  return explicit ?? fileTreeState.rootPath ?? null;
+}
+
+/** Memory types that describe the user rather than a project. */
+const USER_LEVEL_MEMORY_TYPES = new Set(["preference"]);
+
+/**
+ * Scope a new memory is stored under. An explicit scope wins, `null` included;
+ * user-level memories (preferences) stay global.
+ */
+function getRememberScope(
+  memoryType: string,
+  explicit?: string | null,
+): string | null {
+  if (explicit !== undefined) return explicit;
+  if (USER_LEVEL_MEMORY_TYPES.has(memoryType)) return null;
+  return getMemoryScope();
+}
+
 function isObject(value: unknown): value is Record<string, unknown> {
   return typeof value === "object" && value !== null && !Array.isArray(value);
 }
@@ -353,10 +383,12 @@ export async function rememberMemory(
       ? { memoryType: memoryTypeOrOptions }
       : memoryTypeOrOptions;
 
+  const memoryType = options.memoryType ?? "semantic";
   const args: Record<string, unknown> = {
     content,
-    memoryType: options.memoryType ?? "semantic",
+    memoryType,
     projectId: getProjectId(),
+    scope: getRememberScope(memoryType, options.scope),
   };
   if (options.metadata !== undefined) args.metadata = options.metadata;
   if (options.pin !== undefined) args.pin = options.pin;
@@ -385,6 +417,7 @@ export async function createMemory(input: {
 export async function recallMemories(
   query: string,
   limit = 5,
+  scope?: string | null,
 ): Promise<RecallResult[]> {
   if (!isMemoryAvailable()) {
     return [];
@@ -395,6 +428,7 @@ export async function recallMemories(
       query,
       projectId: getProjectId(),
       limit,
+      scope: getMemoryScope(scope),
     });
   } catch (error) {
     console.warn("[Memory] Failed to recall memories:", error);
@@ -581,6 +615,30 @@ export async function syncMemories(): Promise<SyncResult | null> {
   }
 }
 
+/** How `importMemories` treats memories that are already cached. */
+export type MemoryMergeStrategy = "overwrite" | "skip-existing" | "newer-wins";
+
+export interface MemoryImportResult {
+  added: number;
+  updated: number;
+  skipped: number;
+  /** Memories that could not be written; the others were still imported. */
+  errors: string[];
+}
+
+/** Snapshot every local memory, synced or not, as a JSON backup. */
+export async function exportMemories(): Promise<string> {
+  return invoke<string>("memory_export");
+}
+
+/** Restore a backup produced by `exportMemories`. */
+export async function importMemories(
+  json: string,
+  mergeStrategy: MemoryMergeStrategy,
+): Promise<MemoryImportResult> {
+  return invoke<MemoryImportResult>("memory_import", { json, mergeStrategy });
+}
+
 const MEMORY_SYNC_INTERVAL_MS = 15 * 60 * 1000;
 let syncTimer: number | null = null;
 let syncInFlight = false;
@@ -659,6 +717,7 @@ export async function bootstrapMemoryContextDetails(
     tokenBudget?: number;
     orgId?: string;
     projectId?: string | null;
+    scope?: string | null;
     deadlineMs?: number;
   } = {},
 ): Promise<MemorySessionBootstrapResult | null> {
@@ -674,6 +733,7 @@ export async function bootstrapMemoryContextDetails(
         projectId: getProjectId(input.projectId),
         orgId: input.orgId,
         tokenBudget: input.tokenBudget,
+        scope: getMemoryScope(input.scope),
       }),
       deadlineMs,
     );
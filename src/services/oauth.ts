@@ -2,6 +2,7 @@
 // ABOUTME: Handles PKCE-based OAuth 2.0 for OpenAI. Gemini OAuth was removed in favor of the Gemini Agent (gemini-cli).
 
 import { appFetch } from "@/lib/fetch";
+import { isWindowsPlatform } from "@/lib/platform";
 import type { OAuthCredentials, ProviderId } from "@/lib/providers/types";
 import { PROVIDER_CONFIGS, supportsOAuth } from "@/lib/providers/types";
 import { isTauriRuntime } from "@/lib/tauri-bridge";
@@ -45,14 +46,15 @@ async function generateCodeChallenge(verifier: string): Promise<string> {
 
 /**
  * Get the OAuth redirect URI for this app.
- * In dev mode, use localhost callback server to avoid launching the production app.
- * In production, use the seren:// deep link scheme.
+ * Dev, validation and Windows builds use the loopback callback server: dev and
+ * validation to avoid launching the production app, Windows because the
+ * seren:// scheme is not registered there (deep links are disabled).
+ * Elsewhere, production uses the seren:// deep link scheme.
  */
 async function getRedirectUri(): Promise<string> {
   if (isTauriRuntime()) {
     const runtime = await getValidationRuntimeInfo();
-    if (runtime.isValidation || import.meta.env.DEV) {
-      // Dev and validation modes use loopback to avoid launching the production app.
+    if (runtime.isValidation || import.meta.env.DEV || isWindowsPlatform()) {
       return getDesktopOAuthCallbackUrl("/oauth/callback");
     }
     // Production: use deep link scheme
@@ -98,6 +98,7 @@ type WorkerEvent =
       thinking: string | null;
       cost?: number;
       rlm_steps?: string | null;
+      truncated?: boolean;
     }
   | { type: "error"; message: string }
   | {
@@ -112,6 +113,11 @@ type WorkerEvent =
       index: number;
       total: number;
       summary: string;
+    }
+  | {
+      type: "context_usage";
+      estimated_tokens: number;
+      model_context_window: number;
     };
 
 /** Capabilities payload sent to the Rust orchestrator. */
@@ -127,9 +133,17 @@ interface UserCapabilities {
   tool_definitions: ToolDefinition[];
   installed_skills: SkillRef[];
   reasoning_effort: string | null;
+  /** Optional sampling overrides forwarded verbatim to the chat request. */
+  generation?: GenerationParams;
   /** Active project root, threaded through to RoutingDecision.project_root
    * so the Rust ChatModelWorker can inject live git/repo context. */
   project_root: string | null;
+  /** Tool the first round must call; later rounds let the model choose. */
+  forced_tool?: string;
+  /** Persona replacing the default chat system prompt; skills still append. */
+  system_prompt?: string;
+  /** Emit `orchestrator://trace` events for this turn. */
+  trace: boolean;
   /** Snapshot of the existing Settings -> Agent controls for backend enforcement. */
   effective_agent_policy: {
     sandbox_mode: "read-only" | "workspace-write" | "full-access";
@@ -137,6 +151,8 @@ interface UserCapabilities {
     auto_approve_reads: boolean;
     network_enabled: boolean;
   };
+  /** Identical failing tool calls before the model is nudged; 0 disables. */
+  tool_failure_repeat_threshold?: number;
 }
 
 interface SkillRef {
@@ -147,12 +163,65 @@ interface SkillRef {
   path: string;
 }
 
+/** Sampling overrides; omitted fields keep the provider defaults. */
+export interface GenerationParams {
+  temperature?: number;
+  top_p?: number;
+  stop?: string[];
+}
+
+/** Routing decision returned by the Rust classifier/router. */
+export interface RoutingDecision {
+  worker_type: WorkerType;
+  model_id: string;
+  delegation: "in_loop" | "full_handoff";
+  reason: string;
+  selected_skills: SkillRef[];
+  publisher_slug?: string;
+  reasoning_effort?: string;
+  generation?: GenerationParams;
+  project_root?: string;
+  forced_tool?: string;
+  system_prompt?: string;
+}
+
+/** What a routing rule looks for in the prompt (case-insensitive). */
+export type RoutingRuleMatch =
+  | { type: "regex"; pattern: string }
+  | { type: "keyword"; keyword: string };
+
+/** The parts of a route a rule pins; unset fields keep the router's choice. */
+export interface RoutingRuleRoute {
+  worker_type?: WorkerType;
+  model_id?: string;
+  publisher_slug?: string;
+}
+
+export interface RoutingRule {
+  id: string;
+  match: RoutingRuleMatch;
+  route: RoutingRuleRoute;
+}
+
+/** One step of a traced turn, emitted on `orchestrator://trace`. */
+export interface OrchestratorTraceEvent {
+  conversation_id: string;
+  /** routing, round_start, tool_dispatch, truncation, retry, complete,
+   * turn_end, or rlm. */
+  stage: string;
+  /** Unix epoch milliseconds. */
+  timestamp: number;
+  fields: Record<string, unknown>;
+}
+
 /** Tool execution request emitted by the Rust ChatModelWorker for non-local tools. */
 interface ToolExecutionRequest {
   conversation_id: string;
   tool_call_id: string;
   name: string;
   arguments: string;
+  /** Seconds the Rust worker waits for the result before failing the call. */
+  timeout_secs?: number;
 }
 
 // =============================================================================
@@ -177,6 +246,7 @@ let lastOrchestrationParams: {
   conversationId: string;
   prompt: string;
   images?: Attachment[];
+  routingOverride?: RoutingDecision;
 } | null = null;
 
 // =============================================================================
@@ -187,12 +257,14 @@ let lastOrchestrationParams: {
  * Send a prompt through the orchestrator pipeline.
  *
  * Sets up event listeners, invokes the Rust command, and updates
- * the conversation store as events arrive.
+ * the conversation store as events arrive. Pass `routingOverride` (usually a
+ * `classifyOnly` result the user confirmed or edited) to skip the classifier.
  */
 export async function orchestrate(
   conversationId: string,
   prompt: string,
   images?: Attachment[],
+  routingOverride?: RoutingDecision,
 ): Promise<void> {
   const conv = conversationStore.conversations.find(
     (c) => c.id === conversationId,
@@ -229,7 +301,12 @@ export async function orchestrate(
   threadStore.noteThreadActivity(conversationId);
 
   // Save params for retry support
-  lastOrchestrationParams = { conversationId, prompt, images };
+  lastOrchestrationParams = {
+    conversationId,
+    prompt,
+    images,
+    routingOverride,
+  };
 
   // Employee-linked threads bypass the seren-models orchestrator and run
   // against the deployed agent's runtime via seren-cloud. The deployed
@@ -377,11 +454,7 @@ export async function orchestrate(
     );
 
     // 5. Invoke the Rust orchestrator (auth token read from store on Rust side)
-    const imagePayload = (images ?? []).map((img) => ({
-      name: img.name,
-      mime_type: img.mimeType,
-      base64: img.base64,
-    }));
+    const imagePayload = toImagePayload(images);
     await Promise.race([
       invoke("orchestrate", {
         conversationId,
@@ -390,6 +463,8 @@ export async function orchestrate(
         history,
         capabilities,
         images: imagePayload,
+        routingOverride: routingOverride ?? null,
+        turnTimeoutSecs: settingsStore.get("chatTurnTimeoutSecs") || null,
       }),
       watchdog.waitForTimeout(),
     ]);
@@ -420,6 +495,160 @@ export async function orchestrate(
   }
 }
 
+/**
+ * Ask the Rust classifier how a prompt would be routed in this thread,
+ * without executing it. Uses the same capabilities `orchestrate` would send.
+ */
+export async function classifyOnly(
+  conversationId: string,
+  prompt: string,
+): Promise<RoutingDecision> {
+  const conv = conversationStore.conversations.find(
+    (c) => c.id === conversationId,
+  );
+  const provider = ((conv?.selectedProvider as string | undefined) ??
+    providerStore.activeProvider) as ProviderId;
+  const model = conv?.selectedModel ?? providerStore.activeModel;
+  await skillsStore.ensureContextLoaded(fileTreeState.rootPath, conversationId);
+  return invoke<RoutingDecision>("classify_only", {
+    prompt,
+    capabilities: buildCapabilities(conversationId, provider, model),
+  });
+}
+
+/** Attachments in the shape the Rust `ImageAttachment` expects. */
+function toImagePayload(images: Attachment[] | undefined) {
+  return (images ?? []).map((img) => ({
+    name: img.name,
+    mime_type: img.mimeType,
+    base64: img.base64,
+  }));
+}
+
+/**
+ * Drop the thread's last assistant reply and re-run the prior user turn,
+ * optionally on `overrideModel`. Resolves with the routing decision used
+ * once the new reply has finished streaming as `assistantMessageId`.
+ * The model sees the thread rebuilt from its user, assistant and system
+ * messages only; earlier tool calls and results are not replayed.
+ */
+export async function regenerateLast(
+  conversationId: string,
+  assistantMessageId: string,
+  overrideModel?: string,
+): Promise<RoutingDecision> {
+  const conv = conversationStore.conversations.find(
+    (c) => c.id === conversationId,
+  );
+  const provider = ((conv?.selectedProvider as string | undefined) ??
+    providerStore.activeProvider) as ProviderId;
+  const model = conv?.selectedModel ?? providerStore.activeModel;
+  await skillsStore.ensureContextLoaded(fileTreeState.rootPath, conversationId);
+  // Attachments are not persisted, so resend the user turn's originals.
+  const messages = conversationStore.getMessagesFor(conversationId);
+  const lastUserIndex = messages
+    .map((message) => message.role)
+    .lastIndexOf("user");
+  const lastUserMessage = messages[lastUserIndex];
+  // The backend deletes everything after the user turn; mirror that here so
+  // the replaced reply and its tool messages leave the thread.
+  if (lastUserIndex >= 0) {
+    conversationStore.setMessages(
+      conversationId,
+      messages.slice(0, lastUserIndex + 1),
+    );
+  }
+  return invoke<RoutingDecision>("regenerate_last", {
+    conversationId,
+    overrideModel: overrideModel ?? null,
+    assistantMessageId,
+    capabilities: buildCapabilities(conversationId, provider, model),
+    images: toImagePayload(lastUserMessage?.images),
+  });
+}
+
+/**
+ * Continue an interrupted turn from its last completed tool round.
+ * `orchestrationId` is the assistant message id the turn was started with;
+ * rejects once the checkpoint has been cleared or has expired.
+ */
+export async function resumeOrchestration(
+  orchestrationId: string,
+): Promise<void> {
+  await invoke("resume_orchestration", { orchestrationId });
+}
+
+/** Drop cached routing decisions so the next `classifyOnly` re-routes. */
+export async function clearRoutingCache(): Promise<void> {
+  await invoke("clear_routing_cache");
+}
+
+/** User routing rules, in the order they are tried. */
+export async function listRoutingRules(): Promise<RoutingRule[]> {
+  return invoke<RoutingRule[]>("list_routing_rules");
+}
+
+/** Append a routing rule; earlier rules win when several match. */
+export async function addRoutingRule(
+  matcher: RoutingRuleMatch,
+  route: RoutingRuleRoute,
+): Promise<RoutingRule> {
+  return invoke<RoutingRule>("add_routing_rule", { matcher, route });
+}
+
+/** Remove a routing rule. Resolves false when no rule had that id. */
+export async function removeRoutingRule(id: string): Promise<boolean> {
+  return invoke<boolean>("remove_routing_rule", { id });
+}
+
+/**
+ * Subscribe to trace events for turns run with `chatOrchestratorTrace` on.
+ * Pass a conversation id to receive only that conversation's timeline.
+ */
+export async function listenOrchestratorTrace(
+  handler: (event: OrchestratorTraceEvent) => void,
+  conversationId?: string,
+): Promise<UnlistenFn> {
+  return listen<OrchestratorTraceEvent>("orchestrator://trace", (event) => {
+    if (!conversationId || event.payload.conversation_id === conversationId) {
+      handler(event.payload);
+    }
+  });
+}
+
+/** Orchestrations running right now, across all conversations. */
+export async function getActiveOrchestrationCount(): Promise<number> {
+  return invoke<number>("get_active_orchestration_count");
+}
+
+/** Eval signals accumulated for a conversation's orchestration. */
+export interface EvalSnapshot {
+  conversation_id: string;
+  signals: Array<{
+    task_type: string;
+    model_id: string | null;
+    satisfaction: number;
+    worker_type: string | null;
+    created_at: number;
+  }>;
+  positive: number;
+  negative: number;
+  score: number | null;
+  escalation_pending: boolean;
+  escalated_to: string | null;
+}
+
+/**
+ * Read how the current conversation is being evaluated. A thumbs-down while
+ * a turn is still running escalates its next tool round to a stronger model;
+ * `escalated_to` reports when that happened.
+ */
+export async function getEvalState(
+  conversationId: string,
+): Promise<EvalSnapshot> {
+  return invoke<EvalSnapshot>("get_eval_state", { conversationId });
+}
+
 /**
  * Cancel an active orchestration. For employee-linked threads this aborts
  * the local stream/poll and asks the cloud runtime to stop the run; for
@@ -456,8 +685,9 @@ export async function retryOrchestration(): Promise<void> {
     return;
   }
 
-  const { conversationId, prompt, images } = lastOrchestrationParams;
-  await orchestrate(conversationId, prompt, images);
+  const { conversationId, prompt, images, routingOverride } =
+    lastOrchestrationParams;
+  await orchestrate(conversationId, prompt, images, routingOverride);
 }
 
 // =============================================================================
@@ -507,6 +737,7 @@ function handleWorkerEvent(event: OrchestratorEvent): void {
         workerEvent.thinking,
         workerEvent.cost,
         workerEvent.rlm_steps ?? null,
+        workerEvent.truncated === true,
       );
       break;
     case "error":
@@ -521,6 +752,15 @@ function handleWorkerEvent(event: OrchestratorEvent): void {
     case "rlm_chunk_complete":
       // Steps are collected in the Complete event payload; nothing to do here.
       break;
+    case "context_usage":
+      conversationStore.setContextUsage(
+        {
+          estimatedTokens: workerEvent.estimated_tokens,
+          contextWindow: workerEvent.model_context_window,
+        },
+        event.conversation_id,
+      );
+      break;
   }
 }
 
@@ -532,6 +772,19 @@ function handleThinking(conversationId: string, text: string): void {
   conversationStore.appendStreamingThinking(text, conversationId);
 }
 
+/**
+ * Persisted id for a tool call or tool result row. Mirrors
+ * `tool_message_id` in orchestrator/service.rs: the backend saves these rows
+ * as the events arrive, and reusing the id lets this save upsert the same row.
+ */
+function toolMessageId(
+  conversationId: string,
+  messageType: "tool_call" | "tool_result",
+  toolCallId: string,
+): string {
+  return `${conversationId}:${messageType}:${toolCallId}`;
+}
+
 function handleToolCall(
   conversationId: string,
   event: {
@@ -558,7 +811,7 @@ function handleToolCall(
   }
 
   const toolMessage: UnifiedMessage = {
-    id: crypto.randomUUID(),
+    id: toolMessageId(conversationId, "tool_call", event.tool_call_id),
     type: "tool_call",
     role: "assistant",
     content: event.title || event.name,
@@ -617,7 +870,7 @@ function handleToolResult(
   }
 
   const resultMessage: UnifiedMessage = {
-    id: crypto.randomUUID(),
+    id: toolMessageId(conversationId, "tool_result", event.tool_call_id),
     type: "tool_result",
     role: "assistant",
     content: event.content,
@@ -675,6 +928,7 @@ function handleComplete(
   thinking: string | null,
   cost?: number,
   rlmStepsJson?: string | null,
+  truncated = false,
 ): void {
   const stream = activeStreams.get(conversationId);
   if (!stream) return;
@@ -725,6 +979,7 @@ function handleComplete(
     modelId: stream.modelId ?? undefined,
     duration,
     cost,
+    truncated: truncated || undefined,
     finalOutputValidation,
     memory: stream.memory,
     rlmSteps,
@@ -1203,6 +1458,7 @@ async function handleToolRequest(request: ToolExecutionRequest): Promise<void> {
       toolCallId: result.tool_call_id,
       content: result.content,
       isError: result.is_error,
+      image: result.image ?? null,
     });
   } catch (error) {
     const message = error instanceof Error ? error.message : String(error);
@@ -1394,11 +1650,14 @@ function buildCapabilities(
     })),
     reasoning_effort: chatStore.reasoningEffort ?? null,
     project_root: fileTreeState.rootPath ?? null,
+    trace: settingsStore.settings.chatOrchestratorTrace,
     effective_agent_policy: {
       sandbox_mode: settingsStore.settings.agentSandboxMode,
       approval_policy: settingsStore.settings.agentApprovalPolicy,
       auto_approve_reads: settingsStore.settings.agentAutoApproveReads,
       network_enabled: settingsStore.settings.agentNetworkEnabled,
     },
+    tool_failure_repeat_threshold:
+      settingsStore.settings.chatToolFailureRepeatThreshold,
   };
 }
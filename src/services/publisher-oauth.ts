@@ -15,6 +15,7 @@ import {
   getKnownOAuthProviderForPublisher,
   humanizeOAuthProviderSlug,
 } from "@/lib/oauth-provider-resolution";
+import { isWindowsPlatform } from "@/lib/platform";
 import { getToken } from "@/lib/tauri-bridge";
 import {
   getDesktopOAuthCallbackUrl,
@@ -231,10 +232,9 @@ export async function connectPublisher(
   // Fall back to the app-wide loopback server on Windows and validation
   // builds, where sharing the production deep-link scheme would route the
   // callback to the wrong app instance.
-  const isWindows = navigator.userAgent.includes("Windows");
   const runtime = await getValidationRuntimeInfo();
   const redirectUri =
-    isWindows || runtime.isValidation
+    isWindowsPlatform() || runtime.isValidation
       ? await getDesktopOAuthCallbackUrl("/oauth/callback")
       : "seren://oauth/callback";
 
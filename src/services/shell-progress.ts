@@ -1,6 +1,7 @@
 // ABOUTME: Bridges `shell://progress` Tauri events into the conversation store.
 // ABOUTME: Backs the Tail / LIVE pane for the in-process Bash tool (#2100).
 
+import { invoke } from "@tauri-apps/api/core";
 import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import { conversationStore } from "@/stores/conversation.store";
 
@@ -47,3 +48,17 @@ export async function stopShellProgressListener(): Promise<void> {
     unlisten = null;
   }
 }
+
+/**
+ * Answer a prompt from a running streaming command started with
+ * `interactive` by writing `data` to its stdin (include the trailing
+ * newline). Pass `close` to send EOF afterwards.
+ * Commands that need a real TTY may still misbehave.
+ */
+export async function sendShellInput(
+  toolCallId: string,
+  data: string,
+  close = false,
+): Promise<void> {
+  await invoke("send_shell_input", { toolCallId, data, close });
+}
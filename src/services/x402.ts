@@ -10,6 +10,7 @@ import {
   type PaymentRequirements,
   parsePaymentRequirements,
 } from "@/lib/x402";
+import { getPaymentHistory, recordX402Payment } from "@/lib/tauri-bridge";
 import { buildSignedPayload } from "@/lib/x402/payload";
 import { cryptoWalletStore } from "@/stores/crypto-wallet.store";
 import { settingsState } from "@/stores/settings.store";
@@ -45,6 +46,20 @@ export interface X402PaymentResult {
   error?: string;
 }
 
+/** Auto-approved crypto spend is capped over this rolling window. */
+const AUTO_APPROVE_WINDOW_MS = 24 * 60 * 60 * 1000;
+
+/** Payments read from the spend log to seed the window after a reload. */
+const SPEND_HISTORY_LIMIT = 1000;
+
+/** An unanswered payment prompt is declined after this long. */
+const APPROVAL_TIMEOUT_MS = 5 * 60 * 1000;
+
+interface SpendEntry {
+  at: number;
+  amountUsd: number;
+}
+
 interface ExtractedRequirements {
   requirements: PaymentRequirements;
   requirementsJson: string;
@@ -68,6 +83,10 @@ function createX402Service() {
   const [isProcessing, setIsProcessing] = createSignal(false);
   const [selectedMethod, setSelectedMethod] =
     createSignal<PaymentMethod | null>(null);
+  // Crypto payments signed (or being signed) in the window, for the
+  // cumulative auto-approve limit. Seeded from the persisted spend log.
+  let spent: SpendEntry[] = [];
+  let spendHistoryLoaded = false;
 
   /**
    * Check if an error is an x402 payment required error.
@@ -144,17 +163,74 @@ function createX402Service() {
     return null;
   }
 
+  function usdcToUsd(amountUsdc: string): number {
+    return Number.parseFloat(amountUsdc) / 1_000_000; // USDC has 6 decimals
+  }
+
+  /**
+   * Total signed within the window, dropping older entries.
+   */
+  function spentInWindowUsd(now: number): number {
+    spent = spent.filter((entry) => now - entry.at < AUTO_APPROVE_WINDOW_MS);
+    return spent.reduce((sum, entry) => sum + entry.amountUsd, 0);
+  }
+
+  /**
+   * Seed the window from the persisted spend log once per session, so a
+   * reload does not reset the limit. Returns false if the log could not be
+   * read; callers then ask instead of auto-approving.
+   */
+  async function loadSpendHistory(): Promise<boolean> {
+    if (spendHistoryLoaded) return true;
+    try {
+      const records = await getPaymentHistory(SPEND_HISTORY_LIMIT);
+      if (!spendHistoryLoaded) {
+        const now = Date.now();
+        const persisted = records
+          .filter((record) => now - record.createdAt < AUTO_APPROVE_WINDOW_MS)
+          .map((record) => ({
+            at: record.createdAt,
+            amountUsd: usdcToUsd(record.amount),
+          }));
+        spent = [...persisted, ...spent];
+        spendHistoryLoaded = true;
+      }
+      return true;
+    } catch (error) {
+      console.warn("[x402] Failed to read payment history:", error);
+      return false;
+    }
+  }
+
   /**
-   * Check if a payment amount is below the auto-approve threshold.
+   * Check if a payment fits under the auto-approve limit, counting what was
+   * already signed in the last 24 hours.
    */
   function shouldAutoApprove(amountUsdc: string): boolean {
     const threshold = settingsState.app.cryptoAutoApproveLimit;
-    const amountUsd = Number.parseFloat(amountUsdc) / 1_000_000; // USDC has 6 decimals
-    return amountUsd <= threshold;
+    const amountUsd = usdcToUsd(amountUsdc);
+    if (!Number.isFinite(amountUsd) || amountUsd < 0) return false;
+    return spentInWindowUsd(Date.now()) + amountUsd <= threshold;
   }
 
   /**
-   * Request user approval for a payment.
+   * Count a payment against the window. Auto-approved payments reserve their
+   * amount before signing, in the same tick as the limit check, so concurrent
+   * payments cannot all pass the limit before any of them is counted.
+   */
+  function reserveSpend(amountUsdc: string): SpendEntry {
+    const entry = { at: Date.now(), amountUsd: usdcToUsd(amountUsdc) };
+    spent.push(entry);
+    return entry;
+  }
+
+  function releaseSpend(entry: SpendEntry): void {
+    spent = spent.filter((other) => other !== entry);
+  }
+
+  /**
+   * Request user approval for a payment. An unanswered prompt is declined
+   * after `APPROVAL_TIMEOUT_MS`.
    */
   async function requestApproval(
     serverName: string,
@@ -170,6 +246,12 @@ function createX402Service() {
 
     return new Promise((resolve) => {
       const id = `payment-${Date.now()}-${Math.random().toString(36).slice(2)}`;
+      const timeout = setTimeout(() => {
+        const pending = pendingPayment();
+        if (pending?.id === id) {
+          pending.resolve({ approved: false });
+        }
+      }, APPROVAL_TIMEOUT_MS);
 
       setPendingPayment({
         id,
@@ -182,6 +264,7 @@ function createX402Service() {
         chainName: getChainName(network),
         requirements,
         resolve: (result) => {
+          clearTimeout(timeout);
           setPendingPayment(null);
           setSelectedMethod(result.method ?? null);
           resolve(result);
@@ -191,7 +274,8 @@ function createX402Service() {
   }
 
   /**
-   * Sign an x402 payment and get the payment header.
+   * Sign an x402 payment and get the payment header. Only reached through
+   * `handlePaymentRequired`, which applies the approval gate.
    */
   async function signPayment(
     requirementsJson: string,
@@ -222,6 +306,14 @@ function createX402Service() {
       // Sign via the connected wallet (EIP-712)
       const result = await buildSignedPayload(account, requirements, option);
 
+      // Signing succeeded; a failed history write must not block the payment.
+      recordX402Payment({
+        requirementsJson,
+        headerValue: result.headerValue,
+      }).catch((error) =>
+        console.warn("[x402] Failed to record payment history:", error),
+      );
+
       return {
         success: true,
         paymentHeader: result.headerValue,
@@ -294,8 +386,14 @@ function createX402Service() {
       settingsState.app.preferredPaymentMethod === "crypto"
     ) {
       const amount = x402Option.amount;
-      if (shouldAutoApprove(amount)) {
-        return await signPayment(requirementsJson);
+      // No await between the limit check and the reservation.
+      if ((await loadSpendHistory()) && shouldAutoApprove(amount)) {
+        const reservation = reserveSpend(amount);
+        const result = await signPayment(requirementsJson);
+        if (!result.success) {
+          releaseSpend(reservation);
+        }
+        return result;
       }
     }
 
@@ -307,7 +405,11 @@ function createX402Service() {
 
     // Process payment based on selected method
     if (result.method === "crypto") {
-      return await signPayment(requirementsJson);
+      const signed = await signPayment(requirementsJson);
+      if (signed.success && x402Option) {
+        reserveSpend(x402Option.amount);
+      }
+      return signed;
     } else if (result.method === "serenbucks") {
       return await handleSerenBucksPayment();
     }
@@ -356,7 +458,6 @@ function createX402Service() {
     extractRequirements,
     shouldAutoApprove,
     handlePaymentRequired,
-    signPayment,
     handleSerenBucksPayment,
     approvePendingPayment,
     approveWithMethod,
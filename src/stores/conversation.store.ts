@@ -35,6 +35,13 @@ export interface Conversation {
   employeeId: string | null;
   privileged?: boolean;
   counselDirection?: string | null;
+  tags?: string[];
+}
+
+/** Latest prompt-size estimate reported by the orchestrator. */
+export interface ContextUsage {
+  estimatedTokens: number;
+  contextWindow: number;
 }
 
 interface ConversationState {
@@ -43,6 +50,7 @@ interface ConversationState {
   messages: Record<string, UnifiedMessage[]>;
   loading: Record<string, boolean>;
   rlmProcessing: Record<string, boolean>;
+  contextUsage: Record<string, ContextUsage>;
   error: string | null;
   streamingContent: Record<string, string>;
   streamingThinking: Record<string, string>;
@@ -55,6 +63,7 @@ const [state, setState] = createStore<ConversationState>({
   messages: {},
   loading: {},
   rlmProcessing: {},
+  contextUsage: {},
   error: null,
   streamingContent: {},
   streamingThinking: {},
@@ -118,6 +127,7 @@ function unifiedRowToConversation(row: UnifiedConversationRow): Conversation {
     employeeId: row.employee_id,
     privileged: row.privileged,
     counselDirection: row.counsel_direction,
+    tags: row.tags,
   };
 }
 
@@ -201,6 +211,18 @@ export const conversationStore = {
     setState("rlmProcessing", conversationId, value);
   },
 
+  getContextUsageFor(conversationId: string): ContextUsage | null {
+    return state.contextUsage[conversationId] ?? null;
+  },
+
+  setContextUsage(
+    usage: ContextUsage,
+    conversationId = state.activeConversationId,
+  ) {
+    if (!conversationId) return;
+    setState("contextUsage", conversationId, usage);
+  },
+
   get error(): string | null {
     return state.error;
   },
@@ -313,6 +335,7 @@ export const conversationStore = {
       "messages",
       "loading",
       "rlmProcessing",
+      "contextUsage",
       "streamingContent",
       "streamingThinking",
       "streamingStalled",
@@ -440,6 +463,7 @@ export const conversationStore = {
       "messages",
       "loading",
       "rlmProcessing",
+      "contextUsage",
       "streamingContent",
       "streamingThinking",
       "streamingStalled",
@@ -624,6 +648,7 @@ export const conversationStore = {
       messages: {},
       loading: {},
       rlmProcessing: {},
+      contextUsage: {},
       error: null,
       streamingContent: {},
       streamingThinking: {},
@@ -718,6 +743,7 @@ export const conversationStore = {
     setState("streamingThinking", conversationId, "");
     setState("loading", conversationId, false);
     setState("rlmProcessing", conversationId, false);
+    setState("contextUsage", conversationId, undefined as never);
   },
 
   async clearAllHistory() {
@@ -733,6 +759,7 @@ export const conversationStore = {
     setState("streamingThinking", {});
     setState("loading", {});
     setState("rlmProcessing", {});
+    setState("contextUsage", {});
     setState("activeConversationId", null);
 
     await this.createConversation();
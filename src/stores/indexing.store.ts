@@ -27,6 +27,8 @@ interface IndexingState {
   chunksProcessed: number;
   currentFile: string | null;
   estimatedTokens: number;
+  /** Chunks that could not be embedded or stored since the last reset. */
+  chunksFailed: number;
   error: string | null;
   stats: IndexStats | null;
   hasIndex: boolean;
@@ -40,6 +42,7 @@ const [state, setState] = createStore<IndexingState>({
   chunksProcessed: 0,
   currentFile: null,
   estimatedTokens: 0,
+  chunksFailed: 0,
   error: null,
   stats: null,
   hasIndex: false,
@@ -103,6 +106,13 @@ export const indexingStore = {
     return state.estimatedTokens;
   },
 
+  /**
+   * Get the number of chunks that failed to index.
+   */
+  get chunksFailed(): number {
+    return state.chunksFailed;
+  },
+
   /**
    * Set indexing phase.
    */
@@ -124,6 +134,13 @@ export const indexingStore = {
     setState(update);
   },
 
+  /**
+   * Count chunks that failed to index; their files are retried next run.
+   */
+  addFailedChunks(count: number): void {
+    setState("chunksFailed", (failed) => failed + count);
+  },
+
   /**
    * Set error state.
    */
@@ -153,6 +170,7 @@ export const indexingStore = {
       chunksProcessed: 0,
       currentFile: null,
       estimatedTokens: 0,
+      chunksFailed: 0,
       error: null,
       stats: null,
       hasIndex: false,
@@ -266,16 +266,15 @@ async function loadProviderSettings(): Promise<void> {
 
 /**
  * Configure a provider with an API key.
- * Validates the key before storing.
+ * The backend checks the key with the provider and refuses one the provider
+ * rejects; a check that cannot reach a verdict still stores the key.
  * @param providerId - The provider to configure
  * @param apiKey - The API key to store
- * @param validateFn - Function to validate the key (injected to avoid circular deps)
  * @returns true if configuration succeeded
  */
 async function configureProvider(
   providerId: ProviderId,
   apiKey: string,
-  validateFn?: (providerId: ProviderId, apiKey: string) => Promise<boolean>,
 ): Promise<boolean> {
   if (providerId === "seren" || providerId === "seren-private") {
     return false; // Can't configure Seren with API key
@@ -285,20 +284,8 @@ async function configureProvider(
   setState("validationError", null);
 
   try {
-    // Validate the key if validation function provided
-    if (validateFn) {
-      const isValid = await validateFn(providerId, apiKey);
-      if (!isValid) {
-        setState(
-          "validationError",
-          `Invalid API key for ${PROVIDER_CONFIGS[providerId].name}. Please check and try again.`,
-        );
-        return false;
-      }
-    }
-
-    // Store the key securely
-    await storeProviderKey(providerId, apiKey);
+    // Validate and store the key securely
+    await storeProviderKey(providerId, apiKey.trim(), { validate: true });
 
     // Update configured providers list
     if (!state.configuredProviders.includes(providerId)) {
@@ -310,7 +297,8 @@ async function configureProvider(
 
     return true;
   } catch (error) {
-    const message = error instanceof Error ? error.message : "Unknown error";
+    // Tauri rejects with the command's error string
+    const message = error instanceof Error ? error.message : String(error);
     setState(
       "validationError",
       `Failed to configure ${PROVIDER_CONFIGS[providerId].name}: ${message}`,
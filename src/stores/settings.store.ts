@@ -29,6 +29,16 @@ async function getInvoke(): Promise<
   return invoke;
 }
 
+/**
+ * User additions to the shell command policy enforced by the backend.
+ */
+export interface ShellCommandPolicy {
+  allow: string[];
+  deny: string[];
+  allowPatterns: string[];
+  denyPatterns: string[];
+}
+
 /**
  * Application settings.
  */
@@ -50,6 +60,23 @@ export interface Settings {
    * Default: 10. Range: 0-50.
    */
   chatMaxToolIterations: number;
+  /**
+   * Wall-clock limit in seconds for a whole orchestrated turn, across all
+   * tool rounds. The turn is stopped with a timeout note when exceeded.
+   * Default: 0 (no limit).
+   */
+  chatTurnTimeoutSecs: number;
+  /**
+   * Identical failing tool calls in a row (same tool, arguments, and error)
+   * before the model is told to try something else. 0 disables. Default: 2.
+   * The turn aborts one repeat after the nudge, and never before the third.
+   */
+  chatToolFailureRepeatThreshold: number;
+  /**
+   * Emit an `orchestrator://trace` timeline (routing, rounds, tool dispatch,
+   * truncations, retries, completion) for each chat turn. Default: off.
+   */
+  chatOrchestratorTrace: boolean;
 
   // Auto-compact settings
   autoCompactEnabled: boolean;
@@ -94,6 +121,10 @@ export interface Settings {
   autoTopUpAmount: number;
 
   // Crypto wallet settings
+  /**
+   * USD that crypto payments may total over a rolling 24 hours without
+   * asking. Payments past the running total need explicit approval.
+   */
   cryptoAutoApproveLimit: number;
 
   // Payment method settings
@@ -102,6 +133,8 @@ export interface Settings {
 
   // Semantic indexing settings
   semanticIndexingEnabled: boolean;
+  /** Embedding requests allowed in flight at once while indexing. */
+  semanticIndexingConcurrency: number;
 
   // Memory settings
   memoryEnabled: boolean;
@@ -171,6 +204,16 @@ export interface Settings {
    * running sessions; they apply when the next session starts.
    */
   claudeReasoningEffort: string;
+  /** User agent for web fetches. Empty uses the default Seren-Desktop UA. */
+  webFetchUserAgent: string;
+  /**
+   * Additions to the built-in shell command policy. `allow` and `deny` list
+   * program names; patterns are regexes. An allow pattern must match one
+   * whole simple command, and each part of a chained line is checked.
+   */
+  shellCommandPolicy: ShellCommandPolicy;
+  /** Orchestrations allowed to run at once across all conversations. */
+  maxConcurrentOrchestrations: number;
 
   // Voice settings
   voiceAutoSubmit: boolean;
@@ -198,6 +241,13 @@ export interface Settings {
 
   // General settings
   telemetryEnabled: boolean;
+  /**
+   * Pin the OAuth callback server to this port range, e.g. for firewalls
+   * that only allow known ports. Null picks a free port; an unset max uses
+   * the min alone.
+   */
+  oauthCallbackPortMin: number | null;
+  oauthCallbackPortMax: number | null;
 }
 
 /**
@@ -239,6 +289,9 @@ const DEFAULT_SETTINGS: Settings = {
   chatEnterToSend: true,
   chatThinkingExpanded: false,
   chatMaxToolIterations: 0,
+  chatTurnTimeoutSecs: 0,
+  chatToolFailureRepeatThreshold: 2,
+  chatOrchestratorTrace: false,
   // Auto-compact
   autoCompactEnabled: true,
   autoCompactThreshold: 85,
@@ -268,6 +321,7 @@ const DEFAULT_SETTINGS: Settings = {
   enablePaymentFallback: true,
   // Semantic indexing
   semanticIndexingEnabled: false,
+  semanticIndexingConcurrency: 4,
   // Memory
   memoryEnabled: true,
   sourceRetentionEnabled: false,
@@ -294,6 +348,14 @@ const DEFAULT_SETTINGS: Settings = {
   lmStudioBaseUrl: "http://localhost:1234",
   lmStudioApiKey: "",
   claudeReasoningEffort: "medium",
+  webFetchUserAgent: "",
+  shellCommandPolicy: {
+    allow: [],
+    deny: [],
+    allowPatterns: [],
+    denyPatterns: [],
+  },
+  maxConcurrentOrchestrations: 4,
   // Voice
   voiceAutoSubmit: true,
   voiceCleanupEnabled: true,
@@ -307,6 +369,8 @@ const DEFAULT_SETTINGS: Settings = {
   meetingAudioPrimed: false,
   // General
   telemetryEnabled: true,
+  oauthCallbackPortMin: null,
+  oauthCallbackPortMax: null,
 };
 
 function buildPlaywrightServer(scriptPath: string): McpServerConfig {
@@ -65,6 +65,8 @@ export interface UnifiedMessage {
   duration?: number;
   /** Total cost in SerenBucks for this message's query, reported by Gateway. */
   cost?: number;
+  /** The model hit its output length cap, so the answer is cut off. */
+  truncated?: boolean;
   /** Verified Agent Output report for final assistant messages. */
   finalOutputValidation?: FinalOutputValidationReport;
   /** Contextual memory provenance and post-answer capture state. */
@@ -0,0 +1,87 @@
+// ABOUTME: Tests that the indexing file watcher debounces rapid saves per file.
+// ABOUTME: Mocks the sync store and orchestrator to count re-index calls.
+
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+
+const { reindexFileMock, handlers } = vi.hoisted(() => ({
+  reindexFileMock: vi.fn(),
+  handlers: [] as Array<(event: { kind: string; paths: string[] }) => void>,
+}));
+
+vi.mock("@/lib/indexing/orchestrator", () => ({
+  reindexFile: reindexFileMock,
+}));
+
+vi.mock("@/stores/fileTree", () => ({
+  fileTreeState: { rootPath: "/project" },
+}));
+
+vi.mock("@/stores/settings.store", () => ({
+  settingsStore: { get: () => true },
+}));
+
+vi.mock("@/stores/sync.store", () => ({
+  syncStore: {
+    onFileChange: (handler: (typeof handlers)[number]) => {
+      handlers.push(handler);
+      return () => {
+        handlers.splice(handlers.indexOf(handler), 1);
+      };
+    },
+  },
+}));
+
+import {
+  startFileWatcherForIndexing,
+  stopFileWatcherForIndexing,
+} from "@/lib/indexing/file-watcher";
+
+function save(path: string) {
+  for (const handler of handlers) {
+    handler({ kind: "Modify(Data(Content))", paths: [path] });
+  }
+}
+
+describe("file watcher re-indexing", () => {
+  beforeEach(() => {
+    vi.useFakeTimers();
+    reindexFileMock.mockReset();
+    reindexFileMock.mockResolvedValue(undefined);
+    startFileWatcherForIndexing();
+  });
+
+  afterEach(() => {
+    stopFileWatcherForIndexing();
+    vi.useRealTimers();
+  });
+
+  it("re-indexes a file once after a burst of saves", async () => {
+    save("/project/src/main.rs");
+    await vi.advanceTimersByTimeAsync(300);
+    save("/project/src/main.rs");
+    await vi.advanceTimersByTimeAsync(300);
+    save("/project/src/main.rs");
+    expect(reindexFileMock).not.toHaveBeenCalled();
+
+    await vi.advanceTimersByTimeAsync(1000);
+    expect(reindexFileMock).toHaveBeenCalledTimes(1);
+    expect(reindexFileMock).toHaveBeenCalledWith(
+      "/project",
+      "/project/src/main.rs",
+    );
+  });
+
+  it("debounces each file independently", async () => {
+    save("/project/a.rs");
+    save("/project/b.rs");
+    await vi.advanceTimersByTimeAsync(1000);
+    expect(reindexFileMock).toHaveBeenCalledTimes(2);
+  });
+
+  it("drops pending re-indexes when stopped", async () => {
+    save("/project/a.rs");
+    stopFileWatcherForIndexing();
+    await vi.advanceTimersByTimeAsync(1000);
+    expect(reindexFileMock).not.toHaveBeenCalled();
+  });
+});
@@ -0,0 +1,171 @@
+// ABOUTME: Tests for concurrent chunk embedding in the indexing service.
+// ABOUTME: Mocks IPC and embeddings to check ordering, concurrency, and partial failures.
+
+import { beforeEach, describe, expect, it, vi } from "vitest";
+
+const { invokeMock, embedTextsMock } = vi.hoisted(() => ({
+  invokeMock: vi.fn(),
+  embedTextsMock: vi.fn(),
+}));
+
+vi.mock("@tauri-apps/api/core", () => ({
+  invoke: invokeMock,
+}));
+
+vi.mock("@/services/seren-embed", () => ({
+  EMBEDDING_DIM: 3,
+  embedText: vi.fn(),
+  embedTexts: embedTextsMock,
+  estimateBatchTokens: vi.fn(() => 0),
+}));
+
+import { indexChunks } from "@/services/indexing";
+
+function chunk(content: string) {
+  return {
+    file_path: "src/main.rs",
+    start_line: 1,
+    end_line: 2,
+    content,
+    chunk_type: "function",
+    symbol_name: null,
+    language: "rust",
+    file_hash: "abc",
+  };
+}
+
+function embeddingResponse(texts: string[]) {
+  return {
+    object: "list",
+    model: "text-embedding-3-small",
+    usage: { prompt_tokens: 0, total_tokens: 0 },
+    data: texts.map((_, index) => ({
+      object: "embedding",
+      embedding: [index, 0, 0],
+      index,
+    })),
+  };
+}
+
+describe("indexChunks", () => {
+  let nextId: number;
+
+  beforeEach(() => {
+    invokeMock.mockReset();
+    embedTextsMock.mockReset();
+    nextId = 1;
+    invokeMock.mockImplementation(
+      (_command: string, args: { chunks: unknown[] }) =>
+        Promise.resolve(args.chunks.map(() => nextId++)),
+    );
+  });
+
+  it("indexes the rest of a batch when one chunk fails to embed", async () => {
+    embedTextsMock.mockImplementation((texts: string[]) =>
+      texts.includes("bad")
+        ? Promise.reject(new Error("payload rejected"))
+        : Promise.resolve(embeddingResponse(texts)),
+    );
+
+    const results = await indexChunks(
+      "/project",
+      [chunk("first"), chunk("bad"), chunk("third")],
+      { retryDelayMs: 0 },
+    );
+
+    expect(results.map((result) => result.index)).toEqual([0, 1, 2]);
+    expect(results[0]).toMatchObject({ id: 1 });
+    expect(results[1]).toMatchObject({ id: null, error: "payload rejected" });
+    expect(results[2]).toMatchObject({ id: 2 });
+    const stored = invokeMock.mock.calls[0][1].chunks;
+    expect(stored.map((c: { content: string }) => c.content)).toEqual([
+      "first",
+      "third",
+    ]);
+    // Stored without the hash so the file is retried on the next run
+    expect(stored.map((c: { file_hash: string }) => c.file_hash)).toEqual([
+      "",
+      "",
+    ]);
+  });
+
+  it("clears a file's stored chunks when a later store fails", async () => {
+    embedTextsMock.mockImplementation((texts: string[]) =>
+      Promise.resolve(embeddingResponse(texts)),
+    );
+    let stores = 0;
+    invokeMock.mockImplementation(
+      (command: string, args: { chunks?: unknown[] }) => {
+        if (command === "delete_file_index") return Promise.resolve(1);
+        stores++;
+        return stores === 2
+          ? Promise.reject(new Error("disk full"))
+          : Promise.resolve(args.chunks?.map(() => nextId++));
+      },
+    );
+
+    const chunks = Array.from({ length: 40 }, (_, i) => chunk(`c${i}`));
+    const results = await indexChunks("/project", chunks);
+
+    expect(invokeMock).toHaveBeenCalledWith("delete_file_index", {
+      projectPath: "/project",
+      filePath: "src/main.rs",
+    });
+    expect(results.every((result) => result.id === null)).toBe(true);
+    expect(results[39].error).toBe("disk full");
+  });
+
+  it("caps concurrent embedding requests and stores in input order", async () => {
+    let inFlight = 0;
+    let peak = 0;
+    embedTextsMock.mockImplementation(async (texts: string[]) => {
+      inFlight++;
+      peak = Math.max(peak, inFlight);
+      // Later batches finish first to prove storage order is preserved
+      await new Promise((resolve) =>
+        setTimeout(resolve, texts[0] === "c0" ? 20 : 1),
+      );
+      inFlight--;
+      return embeddingResponse(texts);
+    });
+
+    const chunks = Array.from({ length: 100 }, (_, i) => chunk(`c${i}`));
+    const progress: number[] = [];
+    const results = await indexChunks("/project", chunks, {
+      concurrency: 2,
+      onProgress: (processed) => progress.push(processed),
+    });
+
+    expect(peak).toBe(2);
+    expect(results.every((result) => result.error === undefined)).toBe(true);
+    expect(results.map((result) => result.id)).toEqual(
+      Array.from({ length: 100 }, (_, i) => i + 1),
+    );
+    expect(invokeMock.mock.calls[0][1].chunks[0].content).toBe("c0");
+    expect(progress.at(-1)).toBe(100);
+  });
+
+  it("retries a failed batch one chunk at a time", async () => {
+    let inFlight = 0;
+    let peak = 0;
+    embedTextsMock.mockImplementation(async (texts: string[]) => {
+      inFlight++;
+      peak = Math.max(peak, inFlight);
+      await new Promise((resolve) => setTimeout(resolve, 1));
+      inFlight--;
+      if (texts.length > 1) throw new Error("batch rejected");
+      return embeddingResponse(texts);
+    });
+
+    const chunks = Array.from({ length: 40 }, (_, i) => chunk(`c${i}`));
+    const results = await indexChunks("/project", chunks, {
+      concurrency: 2,
+      retryDelayMs: 0,
+    });
+
+    expect(peak).toBe(2);
+    expect(results.every((result) => result.id !== null)).toBe(true);
+    // Two failed batches, then one request per chunk
+    expect(embedTextsMock).toHaveBeenCalledTimes(42);
+  });
+});
@@ -224,6 +224,21 @@ describe("publisher OAuth reconnect", () => {
     });
   });
 
+  it("uses loopback callback URL on Windows where deep links are disabled", async () => {
+    Object.defineProperty(globalThis, "navigator", {
+      configurable: true,
+      value: { platform: "Win32", userAgent: "Windows NT 10.0" },
+    });
+    const { connectPublisher } = await import("@/services/publisher-oauth");
+
+    await connectPublisher("google");
+
+    expect(mocks.invoke).toHaveBeenCalledWith("get_oauth_redirect_url", {
+      bearerToken: "access-token",
+      url: "https://api.serendb.com/oauth/google/authorize?redirect_uri=http%3A%2F%2F127.0.0.1%3A49152%2Foauth%2Fcallback",
+    });
+  });
+
   it("disconnects a single OAuth account row by connection id", async () => {
     const { disconnectOAuthConnection } = await import(
       "@/services/publisher-oauth"
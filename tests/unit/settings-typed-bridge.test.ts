@@ -0,0 +1,62 @@
+// ABOUTME: Verifies the typed settings helpers call the JSON and typed Rust commands.
+// ABOUTME: Values must reach the command boundary unstringified.
+
+import { beforeEach, describe, expect, it, vi } from "vitest";
+
+const { invokeMock } = vi.hoisted(() => ({
+  invokeMock: vi.fn(),
+}));
+
+vi.mock("@tauri-apps/api/core", () => ({
+  invoke: invokeMock,
+}));
+
+import {
+  getSettingBool,
+  getSettingJson,
+  getSettingNumber,
+  setSettingJson,
+} from "@/lib/tauri-bridge";
+
+describe("typed settings bridge", () => {
+  beforeEach(() => {
+    vi.clearAllMocks();
+    vi.stubGlobal("window", { __TAURI_INTERNALS__: {} });
+  });
+
+  it("stores and reads values without stringifying them", async () => {
+    invokeMock.mockResolvedValueOnce(undefined);
+    invokeMock.mockResolvedValueOnce({ enabled: false, limit: 3 });
+    invokeMock.mockResolvedValueOnce(false);
+    invokeMock.mockResolvedValueOnce(0.25);
+
+    await setSettingJson("settings.json", "prefs", {
+      enabled: false,
+      limit: 3,
+    });
+    expect(await getSettingJson("settings.json", "prefs")).toEqual({
+      enabled: false,
+      limit: 3,
+    });
+    expect(await getSettingBool("settings.json", "enabled")).toBe(false);
+    expect(await getSettingNumber("settings.json", "limit")).toBe(0.25);
+
+    expect(invokeMock).toHaveBeenNthCalledWith(1, "set_setting_json", {
+      store: "settings.json",
+      key: "prefs",
+      value: { enabled: false, limit: 3 },
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(2, "get_setting_json", {
+      store: "settings.json",
+      key: "prefs",
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(3, "get_setting_bool", {
+      store: "settings.json",
+      key: "enabled",
+    });
+    expect(invokeMock).toHaveBeenNthCalledWith(4, "get_setting_number", {
+      store: "settings.json",
+      key: "limit",
+    });
+  });
+});
@@ -0,0 +1,152 @@
+// ABOUTME: Tests for the cumulative crypto auto-approve limit in the x402 service.
+// ABOUTME: Auto-signing stops once the running total would pass the limit.
+
+import { beforeEach, describe, expect, it, vi } from "vitest";
+
+const mocks = vi.hoisted(() => ({
+  buildSignedPayload: vi.fn(),
+  recordX402Payment: vi.fn(),
+  getPaymentHistory: vi.fn(),
+  amount: "60000",
+}));
+
+vi.mock("@/lib/x402", () => ({
+  formatUsdcAmount: (amount: string) => amount,
+  getChainName: () => "Base",
+  getX402Option: () => ({
+    amount: mocks.amount,
+    payTo: "0xrecipient",
+    network: "base",
+  }),
+  hasX402Option: () => true,
+  parsePaymentRequirements: () => ({ accepts: [{ type: "x402" }] }),
+}));
+
+vi.mock("@/lib/x402/payload", () => ({
+  buildSignedPayload: mocks.buildSignedPayload,
+}));
+
+vi.mock("@/lib/tauri-bridge", () => ({
+  recordX402Payment: mocks.recordX402Payment,
+  getPaymentHistory: mocks.getPaymentHistory,
+}));
+
+vi.mock("@/stores/crypto-wallet.store", () => ({
+  cryptoWalletStore: { getAccount: () => ({ address: "0xabc" }) },
+}));
+
+vi.mock("@/stores/settings.store", () => ({
+  settingsState: {
+    app: { cryptoAutoApproveLimit: 0.1, preferredPaymentMethod: "crypto" },
+  },
+}));
+
+const paymentError = () => new Error('402 Payment Required {"accepts":[]}');
+
+describe("x402 cumulative auto-approve", () => {
+  beforeEach(() => {
+    vi.clearAllMocks();
+    vi.resetModules();
+    mocks.getPaymentHistory.mockResolvedValue([]);
+    mocks.buildSignedPayload.mockResolvedValue({
+      headerValue: "signed",
+      x402Version: 2,
+    });
+    mocks.recordX402Payment.mockResolvedValue(undefined);
+  });
+
+  it("asks once auto-approved payments would pass the limit", async () => {
+    const { x402Service } = await import("@/services/x402");
+
+    // 0.06 USDC fits under the 0.10 limit and is signed without asking.
+    const first = await x402Service.handlePaymentRequired(
+      "server",
+      "tool",
+      paymentError(),
+    );
+    expect(first?.paymentHeader).toBe("signed");
+    expect(x402Service.pendingPayment()).toBeNull();
+
+    // Another 0.06 would bring the total to 0.12, so the user is asked.
+    const second = x402Service.handlePaymentRequired(
+      "server",
+      "tool",
+      paymentError(),
+    );
+    await vi.waitFor(() =>
+      expect(x402Service.pendingPayment()).not.toBeNull(),
+    );
+    x402Service.declinePendingPayment();
+    expect(await second).toBeNull();
+    expect(mocks.buildSignedPayload).toHaveBeenCalledTimes(1);
+    expect(mocks.recordX402Payment).toHaveBeenCalledTimes(1);
+    expect(mocks.recordX402Payment).toHaveBeenCalledWith(
+      expect.objectContaining({
+        requirementsJson: expect.any(String),
+        headerValue: "signed",
+      }),
+    );
+  });
+
+  it("reserves spend before signing concurrent payments", async () => {
+    const { x402Service } = await import("@/services/x402");
+
+    // Both 0.06 payments start before either finishes signing; only one may
+    // be auto-approved.
+    const first = x402Service.handlePaymentRequired(
+      "server",
+      "tool",
+      paymentError(),
+    );
+    const second = x402Service.handlePaymentRequired(
+      "server",
+      "tool",
+      paymentError(),
+    );
+    await vi.waitFor(() =>
+      expect(x402Service.pendingPayment()).not.toBeNull(),
+    );
+    x402Service.declinePendingPayment();
+    expect((await first)?.paymentHeader).toBe("signed");
+    expect(await second).toBeNull();
+    expect(mocks.buildSignedPayload).toHaveBeenCalledTimes(1);
+  });
+
+  it("counts payments from the persisted spend log", async () => {
+    mocks.getPaymentHistory.mockResolvedValue([
+      { createdAt: Date.now() - 60_000, amount: "60000" },
+    ]);
+    const { x402Service } = await import("@/services/x402");
+
+    const pending = x402Service.handlePaymentRequired(
+      "server",
+      "tool",
+      paymentError(),
+    );
+    await vi.waitFor(() =>
+      expect(x402Service.pendingPayment()).not.toBeNull(),
+    );
+    x402Service.declinePendingPayment();
+    expect(await pending).toBeNull();
+    expect(mocks.buildSignedPayload).not.toHaveBeenCalled();
+  });
+
+  it("releases the reservation when signing fails", async () => {
+    mocks.buildSignedPayload.mockRejectedValueOnce(new Error("rejected"));
+    const { x402Service } = await import("@/services/x402");
+
+    const failed = await x402Service.handlePaymentRequired(
+      "server",
+      "tool",
+      paymentError(),
+    );
+    expect(failed?.success).toBe(false);
+
+    const retried = await x402Service.handlePaymentRequired(
+      "server",
+      "tool",
+      paymentError(),
+    );
+    expect(retried?.paymentHeader).toBe("signed");
+  });
+});